@@ -1,32 +1,470 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::{parse_macro_input, Field, GenericArgument, Ident, ItemStruct, PathSegment, Type};
+use syn::{
+    parenthesized, parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Expr, Field, Fields,
+    FieldsUnnamed, GenericArgument, Generics, Ident, LitStr, PathSegment, Type, Visibility,
+};
 
 pub fn derive_api(item: TokenStream) -> TokenStream {
-    let ItemStruct {
-        attrs: _,
-        vis: _,
-        struct_token: _,
+    let input = parse_macro_input!(item as DeriveInput);
+
+    match derive_input(input) {
+        Ok(token_stream) => token_stream,
+        Err(err) => err.to_compile_error(),
+    }
+    .into()
+}
+
+fn derive_input(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis,
         ident,
         generics,
-        fields,
-        semi_token: _,
-    } = parse_macro_input!(item as ItemStruct);
+        data,
+    } = input;
+
+    let mut builder = false;
+
+    let mut map_collect = false;
+
+    for attr in &attrs {
+        if !attr.path().is_ident("dsl") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("builder") {
+                builder = true;
+                Ok(())
+            } else if meta.path.is_ident("map_collect") {
+                map_collect = true;
+                Ok(())
+            } else {
+                Err(meta.error("expect `builder` or `map_collect`"))
+            }
+        })?;
+    }
+
+    let named = matches!(&data, Data::Struct(data) if matches!(data.fields, Fields::Named(_)));
+
+    if builder && !named {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`builder` is only supported by structs with named fields",
+        ));
+    }
+
+    let newtype = matches!(&data, Data::Struct(data) if matches!(&data.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1));
+
+    if map_collect && !newtype {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`map_collect` is only supported by newtypes, e.g. `struct Href(String)`",
+        ));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let mut apis = vec![];
 
-    for field in fields {
-        DeriveFiled::new(field).derive(&mut apis);
+    let mut impls = vec![];
+
+    match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => {
+                let mut derive_fields = vec![];
+
+                for field in fields.named {
+                    let field = DeriveFiled::new(field)?;
+                    field.derive(&mut apis, &Receiver::Value);
+                    derive_fields.push(field);
+                }
+
+                let required = derive_fields
+                    .iter()
+                    .filter(|field| field.required)
+                    .collect::<Vec<_>>();
+
+                if !required.is_empty() {
+                    apis.push(derive_required_constructor(&required));
+                }
+
+                if builder {
+                    impls.push(derive_builder(&vis, &ident, &generics, &derive_fields));
+                }
+            }
+            Fields::Unnamed(fields) => {
+                if map_collect {
+                    let inner = &fields.unnamed.first().unwrap().ty;
+
+                    impls.push(derive_map_collect(&ident, inner));
+                }
+
+                derive_tuple_struct(fields, &mut apis);
+            }
+            Fields::Unit => {}
+        },
+        Data::Enum(data) => {
+            let mut variants = vec![];
+
+            derive_enum(data, &mut apis, &mut variants);
+
+            impls.extend(variants.into_iter().map(|(ty, variant)| {
+                quote! {
+                    impl #impl_generics From<#ty> for #ident #ty_generics #where_clause {
+                        fn from(value: #ty) -> Self {
+                            Self::#variant(value)
+                        }
+                    }
+                }
+            }));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "the Dsl derive supports structs and enums, but not unions",
+            ));
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#apis)*
+        }
+
+        #(#impls)*
+    })
+}
+
+/// Derive `is_xxx` and constructor fns for each variant, and `From` impls for single-field variants.
+///
+/// `From` impls are skipped if more than one single-field variant has the same field type.
+fn derive_enum(
+    data: DataEnum,
+    apis: &mut Vec<proc_macro2::TokenStream>,
+    impls: &mut Vec<(Type, Ident)>,
+) {
+    let mut single_field_types = vec![];
+
+    for variant in &data.variants {
+        if variant.fields.len() == 1 {
+            let ty = variant
+                .fields
+                .iter()
+                .next()
+                .unwrap()
+                .ty
+                .to_token_stream()
+                .to_string();
+            single_field_types.push(ty);
+        }
     }
 
+    for variant in data.variants {
+        let variant_ident = variant.ident;
+
+        let docs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+
+        let snake_name = to_snake_case(&variant_ident.to_string());
+
+        let fn_is = format_ident!("is_{}", snake_name);
+
+        let is_doc = format!(
+            "Returns true if this value is the [`{}`](Self::{}) variant.",
+            variant_ident, variant_ident
+        );
+
+        let fn_new = if syn::parse_str::<Ident>(&snake_name).is_ok() {
+            format_ident!("{}", snake_name)
+        } else {
+            // keywords, e.g. `None` => `r#none`.
+            Ident::new_raw(&snake_name, variant_ident.span())
+        };
+
+        match variant.fields {
+            Fields::Unit => {
+                apis.push(quote! {
+                    #[doc = #is_doc]
+                    pub fn #fn_is(&self) -> bool {
+                        matches!(self, Self::#variant_ident)
+                    }
+
+                    #(#docs)*
+                    pub fn #fn_new() -> Self {
+                        Self::#variant_ident
+                    }
+                });
+            }
+            Fields::Unnamed(fields) => {
+                let types = fields
+                    .unnamed
+                    .iter()
+                    .map(|field| &field.ty)
+                    .collect::<Vec<_>>();
+
+                let args = (0..types.len())
+                    .map(|index| format_ident!("v{}", index))
+                    .collect::<Vec<_>>();
+
+                let generics = (0..types.len())
+                    .map(|index| format_ident!("V{}", index))
+                    .collect::<Vec<_>>();
+
+                apis.push(quote! {
+                    #[doc = #is_doc]
+                    pub fn #fn_is(&self) -> bool {
+                        matches!(self, Self::#variant_ident(..))
+                    }
+
+                    #(#docs)*
+                    pub fn #fn_new<#(#generics),*>(#(#args: #generics),*) -> Self
+                    where
+                        #(#types: From<#generics>),*
+                    {
+                        Self::#variant_ident(#(#args.into()),*)
+                    }
+                });
+
+                if types.len() == 1 {
+                    let ty = types[0].to_token_stream().to_string();
+
+                    if single_field_types.iter().filter(|v| **v == ty).count() == 1 {
+                        impls.push((types[0].clone(), variant_ident));
+                    }
+                }
+            }
+            Fields::Named(fields) => {
+                let types = fields
+                    .named
+                    .iter()
+                    .map(|field| &field.ty)
+                    .collect::<Vec<_>>();
+
+                let args = fields
+                    .named
+                    .iter()
+                    .map(|field| field.ident.as_ref().unwrap())
+                    .collect::<Vec<_>>();
+
+                let generics = (0..types.len())
+                    .map(|index| format_ident!("V{}", index))
+                    .collect::<Vec<_>>();
+
+                apis.push(quote! {
+                    #[doc = #is_doc]
+                    pub fn #fn_is(&self) -> bool {
+                        matches!(self, Self::#variant_ident { .. })
+                    }
+
+                    #(#docs)*
+                    pub fn #fn_new<#(#generics),*>(#(#args: #generics),*) -> Self
+                    where
+                        #(#types: From<#generics>),*
+                    {
+                        Self::#variant_ident { #(#args: #args.into()),* }
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Derive the `new` constructor taking the `#[dsl(required)]` fields, other fields are filled with defaults.
+fn derive_required_constructor(fields: &[&DeriveFiled]) -> proc_macro2::TokenStream {
+    let args = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+
+    let fn_names = fields.iter().map(|field| &field.fn_name);
+
+    let generics = (0..fields.len())
+        .map(|index| format_ident!("V{}", index))
+        .collect::<Vec<_>>();
+
+    let bounds = fields
+        .iter()
+        .zip(generics.iter())
+        .map(|(field, generic)| field.bound(generic));
+
+    quote! {
+        /// Create a new value from the required fields, other fields are set to their defaults.
+        pub fn new<#(#generics),*>(#(#args: #generics),*) -> Self
+        where
+            #(#bounds),*
+        {
+            Self::default()#(.#fn_names(#args))*
+        }
+    }
+}
+
+/// Derive the `XxxBuilder` type with `&mut self` setters, see `#[dsl(builder)]`.
+fn derive_builder(
+    vis: &Visibility,
+    ident: &Ident,
+    generics: &Generics,
+    fields: &[DeriveFiled],
+) -> proc_macro2::TokenStream {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let builder = format_ident!("{}Builder", ident);
+
+    let mut apis = vec![];
+
+    let mut required = vec![];
+
+    for field in fields {
+        let index = if field.required {
+            required.push(field.ident.to_string());
+            Some(required.len() - 1)
+        } else {
+            None
+        };
+
+        field.derive(&mut apis, &Receiver::Builder { required: index });
+    }
+
+    let count = required.len();
+
+    let builder_doc = format!("The builder of [`{}`].", ident);
+
+    let fn_builder_doc = format!("Create a new [`{}`].", builder);
+
+    let ty_name = ident.to_string();
+
     quote! {
+        #[doc = #builder_doc]
+        #[derive(Debug, Clone)]
+        #vis struct #builder #impl_generics #where_clause {
+            value: #ident #ty_generics,
+            assigned: [bool; #count],
+        }
+
         impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #fn_builder_doc]
+            pub fn builder() -> #builder #ty_generics {
+                #builder {
+                    value: Default::default(),
+                    assigned: [false; #count],
+                }
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
             #(#apis)*
+
+            /// Create the value, returns [`MissingField`](crate::Error::MissingField)
+            /// if any required field is not set.
+            pub fn build(&self) -> crate::Result<#ident #ty_generics> {
+                const REQUIRED: [&str; #count] = [#(#required),*];
+
+                if let Some(index) = self.assigned.iter().position(|assigned| !assigned) {
+                    return Err(crate::Error::MissingField {
+                        ty: #ty_name,
+                        field: REQUIRED[index],
+                    });
+                }
+
+                Ok(self.value.clone())
+            }
         }
     }
-    .into()
+}
+
+/// The max tuple length accepted by the derived `MapCollect` impls.
+const MAP_COLLECT_TUPLE_LEN: usize = 16;
+
+/// Derive `From<Inner>` and `MapCollect` impls for newtype `ident`, see `#[dsl(map_collect)]`.
+///
+/// Lists and tuples of any values convertible to the `inner` type can be collected into a list of `ident`.
+fn derive_map_collect(ident: &Ident, inner: &Type) -> proc_macro2::TokenStream {
+    let mut impls = vec![quote! {
+        impl From<#inner> for #ident {
+            fn from(value: #inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl<V> crate::MapCollect<#ident> for ::alloc::vec::Vec<V>
+        where
+            #inner: From<V>,
+        {
+            fn map_collect(self) -> ::alloc::vec::Vec<#ident> {
+                self.into_iter().map(|v| #ident(v.into())).collect()
+            }
+        }
+    }];
+
+    for len in 2..=MAP_COLLECT_TUPLE_LEN {
+        let generics = (0..len)
+            .map(|index| format_ident!("A{}", index))
+            .collect::<Vec<_>>();
+
+        let args = (0..len)
+            .map(|index| format_ident!("a{}", index))
+            .collect::<Vec<_>>();
+
+        impls.push(quote! {
+            impl<#(#generics),*> crate::MapCollect<#ident> for (#(#generics),*)
+            where
+                #(#inner: From<#generics>),*
+            {
+                fn map_collect(self) -> ::alloc::vec::Vec<#ident> {
+                    let (#(#args),*) = self;
+                    ::alloc::vec![#(#ident(#args.into())),*]
+                }
+            }
+        });
+    }
+
+    quote!(#(#impls)*)
+}
+
+/// Derive the positional `new` constructor for tuple structs.
+fn derive_tuple_struct(fields: FieldsUnnamed, apis: &mut Vec<proc_macro2::TokenStream>) {
+    let types = fields
+        .unnamed
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+
+    let args = (0..types.len())
+        .map(|index| format_ident!("v{}", index))
+        .collect::<Vec<_>>();
+
+    let generics = (0..types.len())
+        .map(|index| format_ident!("V{}", index))
+        .collect::<Vec<_>>();
+
+    apis.push(quote! {
+        pub fn new<#(#generics),*>(#(#args: #generics),*) -> Self
+        where
+            #(#types: From<#generics>),*
+        {
+            Self(#(#args.into()),*)
+        }
+    });
+}
+
+/// Convert a `CamelCase` variant name to `snake_case`, e.g. `SmallCaps` => `small_caps`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if index > 0 {
+                snake.push('_');
+            }
+
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
 }
 
 #[derive(PartialEq, Debug)]
@@ -37,266 +475,431 @@ enum DeriveType {
     Unknown(String),
 }
 
+/// The receiver of the generated setters.
+enum Receiver {
+    /// Consuming setters on the derived type itself.
+    Value,
+    /// `&mut self` setters on the generated builder, `required` is the index of the field in the
+    /// builder's assigned flags if it's a required field.
+    Builder { required: Option<usize> },
+}
+
 struct DeriveFiled {
     ident: Ident,
+    /// The generated setter name, set by `#[dsl(rename = "...")]`.
+    fn_name: Ident,
+    /// Set by `#[dsl(skip)]`.
+    skip: bool,
+    /// Set by `#[dsl(required)]`.
+    required: bool,
+    /// Set by `#[dsl(range(..))]`, the range of the numeric values.
+    range: Option<Expr>,
+    /// Set by `#[dsl(non_empty)]`.
+    non_empty: bool,
+    /// The doc comments of this field.
+    docs: Vec<Attribute>,
     root_type: Type,
     type_stack: Vec<DeriveType>,
 }
 
 impl DeriveFiled {
-    fn new(field: Field) -> Self {
-        DeriveFiled {
-            ident: field.ident.expect("Unsupport tuple structure."),
+    fn new(field: Field) -> syn::Result<Self> {
+        let ident = field.ident.expect("named field");
+
+        let mut fn_name = ident.clone();
+
+        let mut skip = false;
+
+        let mut required = false;
+
+        let mut range = None;
+
+        let mut non_empty = false;
+
+        let docs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .cloned()
+            .collect();
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("dsl") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("range") {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    range = Some(content.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("non_empty") {
+                    non_empty = true;
+                    Ok(())
+                } else if meta.path.is_ident("required") {
+                    required = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    fn_name = name.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "expect `skip`, `required`, `non_empty`, `range(..)` or `rename = \"...\"`",
+                    ))
+                }
+            })?;
+        }
+
+        if skip && required {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`skip` and `required` are exclusive",
+            ));
+        }
+
+        let mut field = DeriveFiled {
+            ident,
+            fn_name,
+            skip,
+            required,
+            range,
+            non_empty,
+            docs,
             root_type: field.ty,
             type_stack: Default::default(),
-        }
+        };
+
+        field.parse_field_type()?;
+
+        Ok(field)
     }
 
-    fn parse_generic_type(seg: &PathSegment) -> &Type {
-        match &seg.arguments {
-            syn::PathArguments::AngleBracketed(args) => {
-                match args.args.first().expect("DSL derive inner error.") {
-                    GenericArgument::Type(t) => {
-                        return t;
-                    }
-                    _ => {
-                        panic!("DSL derive inner error.")
-                    }
-                }
+    fn parse_generic_type(seg: &PathSegment) -> syn::Result<&Type> {
+        if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
+            if let Some(GenericArgument::Type(t)) = args.args.first() {
+                return Ok(t);
             }
-            _ => panic!("DSL derive inner error."),
         }
+
+        Err(syn::Error::new_spanned(
+            seg,
+            format!("expect a type argument, e.g. `{}<T>`", seg.ident),
+        ))
     }
 
-    fn parse_field_type(&mut self) {
+    fn parse_field_type(&mut self) -> syn::Result<()> {
         let mut current_type = &self.root_type;
 
         loop {
-            match current_type {
-                Type::Path(path) => {
-                    if path.path.segments.len() != 1 {
-                        self.type_stack.push(DeriveType::Unknown(
-                            current_type.to_token_stream().to_string(),
-                        ));
-
-                        break;
+            if let Type::Path(path) = current_type {
+                // match the last segment, so qualified paths like `std::vec::Vec<T>` are handled too.
+                if let Some(seg) = path.path.segments.last() {
+                    let wrapper = match seg.ident.to_string().as_str() {
+                        "Option" => Some(DeriveType::Option),
+                        "Animatable" => Some(DeriveType::Animatable),
+                        // nested lists are treated as opaque item types.
+                        "Vec" if !self.type_stack.contains(&DeriveType::Vec) => {
+                            Some(DeriveType::Vec)
+                        }
+                        _ => None,
+                    };
+
+                    if let Some(wrapper) = wrapper {
+                        self.type_stack.push(wrapper);
+                        current_type = Self::parse_generic_type(seg)?;
+                        continue;
                     }
+                }
+            }
 
-                    let seg = path.path.segments.first().unwrap();
+            self.type_stack.push(DeriveType::Unknown(
+                current_type.to_token_stream().to_string(),
+            ));
 
-                    match seg.ident.to_string().as_str() {
-                        "Option" => {
-                            // only parse top level `Option` type.
-                            if self.type_stack.is_empty() {
-                                self.type_stack.push(DeriveType::Option);
+            break;
+        }
 
-                                current_type = Self::parse_generic_type(seg);
+        Ok(())
+    }
 
-                                continue;
-                            } else {
-                                self.type_stack.push(DeriveType::Unknown(
-                                    current_type.to_token_stream().to_string(),
-                                ));
+    /// Returns the innermost type of this field.
+    fn content_type(&self) -> proc_macro2::TokenStream {
+        match self.type_stack.last() {
+            Some(DeriveType::Unknown(token_stream)) => token_stream.parse().unwrap(),
+            _ => unreachable!("the type stack always ends with the content type"),
+        }
+    }
 
-                                break;
-                            }
-                        }
-                        "Vec" => {
-                            // only parse: Vec<T> or Option<Vec<T>>.
-                            if self.type_stack.is_empty()
-                                || (self.type_stack.len() == 1
-                                    && *self.type_stack.first().unwrap() == DeriveType::Option)
-                                || (self.type_stack.len() == 1
-                                    && *self.type_stack.first().unwrap() == DeriveType::Animatable)
-                            {
-                                self.type_stack.push(DeriveType::Vec);
-
-                                current_type = Self::parse_generic_type(seg);
-                            } else {
-                                self.type_stack.push(DeriveType::Unknown(
-                                    current_type.to_token_stream().to_string(),
-                                ));
-
-                                break;
-                            }
-
-                            continue;
-                        }
-                        "Animatable" => {
-                            // only parse Animatable<T>, Vec<Animatable<T>> or Option<Animatable<T>>,
-                            if self.type_stack.is_empty()
-                                || (self.type_stack.len() == 1
-                                    && *self.type_stack.first().unwrap() == DeriveType::Option)
-                            {
-                                self.type_stack.push(DeriveType::Animatable);
-
-                                current_type = Self::parse_generic_type(seg);
-
-                                continue;
-                            } else {
-                                self.type_stack.push(DeriveType::Unknown(
-                                    current_type.to_token_stream().to_string(),
-                                ));
-
-                                break;
-                            }
-                        }
-                        _ => {
-                            self.type_stack.push(DeriveType::Unknown(
-                                current_type.to_token_stream().to_string(),
-                            ));
+    /// Returns the wrappers of the content type, from outermost to innermost.
+    fn wrappers(&self) -> &[DeriveType] {
+        &self.type_stack[..self.type_stack.len() - 1]
+    }
 
-                            break;
-                        }
-                    }
-                }
-                _ => {
-                    self.type_stack.push(DeriveType::Unknown(
-                        current_type.to_token_stream().to_string(),
-                    ));
+    /// Returns the bound of the generated setter parameter type `generic`.
+    fn bound(&self, generic: &Ident) -> proc_macro2::TokenStream {
+        let content_type = self.content_type();
 
-                    break;
-                }
+        if self.type_stack.contains(&DeriveType::Vec) {
+            quote!(#generic: crate::MapCollect<#content_type>)
+        } else {
+            quote!(#content_type: From<#generic>)
+        }
+    }
+
+    /// Returns the debug assertions of `#[dsl(range(..))]` and `#[dsl(non_empty)]` on the converted value `v`.
+    fn checks(&self, list: bool) -> proc_macro2::TokenStream {
+        let mut checks = vec![];
+
+        if let Some(range) = &self.range {
+            let message = format!(
+                "`{}` is out of range `{}`: {{:?}}",
+                self.ident,
+                range.to_token_stream()
+            );
+
+            let check = quote! {
+                debug_assert!(
+                    (#range).contains(&crate::DslNumber::dsl_number(item)),
+                    #message,
+                    item
+                );
+            };
+
+            if list {
+                checks.push(quote! {
+                    for item in &v {
+                        #check
+                    }
+                });
+            } else {
+                checks.push(quote! {
+                    let item = &v;
+                    #check
+                });
             }
         }
+
+        if self.non_empty {
+            let message = format!("`{}` is empty", self.ident);
+
+            checks.push(quote! {
+                debug_assert!(!v.is_empty(), #message);
+            });
+        }
+
+        quote!(#(#checks)*)
     }
 
-    fn content_type(&self) -> proc_macro2::TokenStream {
-        assert!(self.type_stack.len() > 0);
-        assert!(self.type_stack.len() < 4);
-
-        let content_type_index = match self.type_stack.len() {
-            1 => 0,
-            2 => 1,
-            3 => 2,
-            _ => panic!("DSL derive inner error."),
+    /// Wrap `inner` with `wrappers`, e.g. `Option<Animatable<T>>` => `Some(Animatable::Constant(inner))`.
+    fn wrap(wrappers: &[DeriveType], inner: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        wrappers
+            .iter()
+            .rev()
+            .fold(inner, |inner, wrapper| match wrapper {
+                DeriveType::Option => quote!(Some(#inner)),
+                DeriveType::Animatable => quote!(crate::Animatable::Constant(#inner)),
+                _ => panic!("DSL derive inner error."),
+            })
+    }
+
+    /// Returns the position of the first [`DeriveType::Animatable`] in `wrappers`,
+    /// if it's only wrapped by `Option`s.
+    fn animatable(wrappers: &[DeriveType]) -> Option<usize> {
+        wrappers
+            .iter()
+            .position(|wrapper| *wrapper != DeriveType::Option)
+            .filter(|index| wrappers[*index] == DeriveType::Animatable)
+    }
+
+    fn derive(&self, apis: &mut Vec<proc_macro2::TokenStream>, receiver: &Receiver) {
+        if self.skip {
+            return;
+        }
+
+        let field = &self.ident;
+
+        let (recv, ret, target, mark, unmark) = match receiver {
+            Receiver::Value => (
+                quote!(mut self),
+                quote!(Self),
+                quote!(self.#field),
+                quote!(),
+                quote!(),
+            ),
+            Receiver::Builder { required } => {
+                let (mark, unmark) = match required {
+                    Some(index) => (
+                        quote!(self.assigned[#index] = true;),
+                        quote!(self.assigned[#index] = false;),
+                    ),
+                    None => (quote!(), quote!()),
+                };
+
+                (
+                    quote!(&mut self),
+                    quote!(&mut Self),
+                    quote!(self.value.#field),
+                    mark,
+                    unmark,
+                )
+            }
         };
 
-        match &self.type_stack[content_type_index] {
-            DeriveType::Unknown(token_stream) => {
-                return token_stream.parse().unwrap();
+        let fn_name = &self.fn_name;
+
+        let content_type = self.content_type();
+
+        let docs = &self.docs;
+
+        let wrappers = self.wrappers();
+
+        let vec = wrappers.iter().position(|v| *v == DeriveType::Vec);
+
+        let accepts = if vec.is_some() {
+            format!(
+                "Accepts anything that can be collected into a list of `{}`.",
+                content_type
+            )
+        } else {
+            format!("Accepts anything convertible to `{}`.", content_type)
+        };
+
+        let bound = self.bound(&format_ident!("V"));
+
+        let (convert, value) = match vec {
+            Some(index) => {
+                let items = &wrappers[index + 1..];
+
+                let collect = if items.is_empty() {
+                    quote!(v)
+                } else {
+                    let item = Self::wrap(items, quote!(v));
+                    quote!(v.into_iter().map(|v| #item).collect())
+                };
+
+                (
+                    quote!(let v: ::alloc::vec::Vec<#content_type> = v.map_collect();),
+                    Self::wrap(&wrappers[..index], collect),
+                )
             }
-            _ => {
-                panic!("DSL derive inner error.");
+            None => (
+                quote!(let v: #content_type = v.into();),
+                Self::wrap(wrappers, quote!(v)),
+            ),
+        };
+
+        let checks = self.checks(vec.is_some());
+
+        apis.push(quote! {
+            #(#docs)*
+            #[doc = ""]
+            #[doc = #accepts]
+            pub fn #fn_name<V>(#recv, v: V) -> #ret
+            where
+                #bound,
+            {
+                #convert
+                #checks
+                #target = #value;
+                #mark
+                self
             }
+        });
+
+        // binds the whole value to an animated register.
+        if let Some(index) = Self::animatable(&wrappers[..vec.unwrap_or(wrappers.len())]) {
+            let fn_name_animated = format_ident!("{}_animated", fn_name);
+
+            let value = Self::wrap(
+                &wrappers[..index],
+                quote!(crate::Animatable::Animated(v.into())),
+            );
+
+            apis.push(quote! {
+                #(#docs)*
+                #[doc = ""]
+                #[doc = "Binds this field to the animated register named `v`."]
+                pub fn #fn_name_animated<S>(#recv, v: S) -> #ret
+                where
+                    S: Into<::alloc::string::String>,
+                {
+                    #target = #value;
+                    #mark
+                    self
+                }
+            });
         }
-    }
 
-    fn derive(&mut self, apis: &mut Vec<proc_macro2::TokenStream>) {
-        self.parse_field_type();
+        // binds each list item to an animated register.
+        if let Some(vec) = vec {
+            let items = &wrappers[vec + 1..];
 
-        let fn_name = &self.ident;
+            if let Some(index) = Self::animatable(items) {
+                let fn_name_animated_items = format_ident!("{}_animated_items", fn_name);
 
-        let fn_name_animated = format_ident!("{}_animated", fn_name);
+                let item = Self::wrap(
+                    &items[..index],
+                    quote!(crate::Animatable::Animated(v.into())),
+                );
 
-        let content_type = self.content_type();
+                let value = Self::wrap(
+                    &wrappers[..vec],
+                    quote!(v.into_iter().map(|v| #item).collect()),
+                );
 
-        match self.type_stack.first().unwrap() {
-            DeriveType::Vec => {
-                assert_eq!(self.type_stack.len(), 2);
                 apis.push(quote! {
-                    pub fn #fn_name<V>(mut self, v: V) -> Self
+                    #(#docs)*
+                    #[doc = ""]
+                    #[doc = "Binds each item of this list to the animated register named by `v`."]
+                    pub fn #fn_name_animated_items<I, S>(#recv, v: I) -> #ret
                     where
-                        V: crate::MapCollect<#content_type>,
+                        I: IntoIterator<Item = S>,
+                        S: Into<::alloc::string::String>,
                     {
-                        self.#fn_name = v.map_collect();
+                        #target = #value;
+                        #mark
                         self
                     }
                 });
             }
-            DeriveType::Animatable => {
-                if self.type_stack.len() == 3 {
-                    assert_eq!(self.type_stack[1], DeriveType::Vec);
-
-                    apis.push(quote! {
-                        pub fn #fn_name<V>(mut self, v: V) -> Self
-                        where
-                            V: crate::MapCollect<#content_type>,
-                        {
-                            self.#fn_name = Animatable::Constant(v.map_collect());
-                            self
-                        }
-                    });
-                } else {
-                    apis.push(quote! {
-                        pub fn #fn_name<V>(mut self, v: V) -> Self
-                        where
-                            #content_type: From<V>,
-                        {
-                            self.#fn_name = Animatable::Constant(v.into());
-                            self
-                        }
-                    });
+        }
+
+        if wrappers.first() == Some(&DeriveType::Option) {
+            let fn_name_clear = format_ident!("clear_{}", fn_name);
+            let fn_name_maybe = format_ident!("maybe_{}", fn_name);
+            let maybe_recv = match receiver {
+                Receiver::Value => quote!(self),
+                Receiver::Builder { .. } => quote!(&mut self),
+            };
+
+            apis.push(quote! {
+                #(#docs)*
+                #[doc = ""]
+                #[doc = "Unsets this field."]
+                pub fn #fn_name_clear(#recv) -> #ret {
+                    #target = None;
+                    #unmark
+                    self
                 }
 
-                apis.push(quote! {
-                    pub fn #fn_name_animated<S>(mut self, v: S) -> Self
-                    where
-                        S: ToOwned<Owned = String>
-                    {
-                        self.#fn_name = Animatable::Animated(v.to_owned());
-                        self
+                #(#docs)*
+                #[doc = ""]
+                #[doc = "Sets this field if `v` is `Some`, or unsets it otherwise."]
+                pub fn #fn_name_maybe<V>(#maybe_recv, v: Option<V>) -> #ret
+                where
+                    #bound,
+                {
+                    match v {
+                        Some(v) => self.#fn_name(v),
+                        None => self.#fn_name_clear(),
                     }
-                });
-            }
-            DeriveType::Option => {
-                if self.type_stack.len() == 3 {
-                    match self.type_stack[1] {
-                        DeriveType::Vec => {
-                            apis.push(quote! {
-                                pub fn #fn_name<V>(mut self, v: V) -> Self
-                                where
-                                    V: crate::MapCollect<#content_type>,
-                                {
-                                    self.#fn_name = Some(v.map_collect());
-                                    self
-                                }
-                            });
-                        }
-                        DeriveType::Animatable => {
-                            apis.push(quote! {
-                                pub fn #fn_name<V>(mut self, v: V) -> Self
-                                where
-                                    #content_type: From<V>,
-                                {
-                                    self.#fn_name = Some(Animatable::Constant(v.into()));
-                                    self
-                                }
-
-                                pub fn #fn_name_animated<S>(mut self, v: S) -> Self
-                                where
-                                    S: ToOwned<Owned = String>
-                                {
-                                    self.#fn_name = Some(Animatable::Animated(v.to_owned()));
-                                    self
-                                }
-                            });
-                        }
-                        _ => {}
-                    }
-                } else {
-                    apis.push(quote! {
-                        pub fn #fn_name<V>(mut self, v: V) -> Self
-                        where
-                            #content_type: From<V>,
-                        {
-                            self.#fn_name = Some(v.into());
-                            self
-                        }
-                    });
                 }
-            }
-            DeriveType::Unknown(_) => {
-                apis.push(quote! {
-                    pub fn #fn_name<V>(mut self, v: V) -> Self
-                    where
-                        #content_type: From<V>,
-                    {
-                        self.#fn_name = v.into();
-                        self
-                    }
-                });
-            }
+            });
         }
     }
 }
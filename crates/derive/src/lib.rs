@@ -4,7 +4,7 @@ mod dsl;
 use proc_macro::TokenStream;
 
 #[cfg(feature = "dsl")]
-#[proc_macro_derive(Dsl)]
+#[proc_macro_derive(Dsl, attributes(dsl))]
 pub fn derive_dsl_api(item: TokenStream) -> TokenStream {
     dsl::derive_api(item)
 }
@@ -1,9 +1,22 @@
 //! A device is an abstract of VGL language rendering target.
 
-use std::{collections::HashMap, future::Future};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use vglang_ir::{AnimatableValue, IR};
 
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+
 /// All `VGL language` rendering target must implement this trait.
 pub trait Device {
     /// A memory representation of one `VGL` program that is directly compiled from ir codes.
@@ -41,3 +54,111 @@ pub trait VGLProgram {
     fn execute<'a>(&'a self, animatable: &'a HashMap<String, AnimatableValue>)
         -> Self::Execute<'a>;
 }
+
+/// A rendering target that compiles ir codes incrementally, while they are being generated, instead of
+/// receiving the whole codes stream at once.
+pub trait StreamingDevice: Device {
+    /// The incremental compiler returned by [`stream`](StreamingDevice::stream).
+    type Stream: IRStream<Program = Self::Program, Error = Self::Error>;
+
+    /// Start compiling one `VGL` program.
+    fn stream(&self) -> Self::Stream;
+}
+
+/// An incremental compiler of one `VGL` program, see [`StreamingDevice`].
+pub trait IRStream {
+    /// The compiled program.
+    type Program;
+
+    type Error;
+
+    /// Feed the next ir code.
+    fn push(&mut self, ir: IR) -> Result<(), Self::Error>;
+
+    /// Finish compiling and returns the compiled program.
+    fn finish(self) -> Result<Self::Program, Self::Error>;
+}
+
+/// A cooperative cancellation flag, shared by the caller and the devices compiling or executing a program.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Request the cancellation, the devices stop at the next element boundary.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if [`cancel`](CancelToken::cancel) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The reason to abort a compile or execution, see [`Interrupt::check`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Interrupted {
+    /// The [`CancelToken`] was cancelled.
+    Cancelled,
+    /// The deadline has passed.
+    Timeout,
+}
+
+/// An optional [`CancelToken`], deadline and timeout, which the devices check at element boundaries.
+#[derive(Debug, Clone, Default)]
+pub struct Interrupt {
+    token: Option<CancelToken>,
+    deadline: Option<Instant>,
+    timeout: Option<Duration>,
+}
+
+impl Interrupt {
+    /// Abort when `token` is cancelled.
+    pub fn token(mut self, token: CancelToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Abort after `deadline`.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Abort each compile or execution after running for `timeout`, see [`begin`](Interrupt::begin).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Called by the devices when a compile or execution begins, returns a copy with the timeout
+    /// converted into a deadline from now.
+    pub fn begin(&self) -> Self {
+        let timeout = self.timeout.map(|timeout| Instant::now() + timeout);
+
+        Self {
+            token: self.token.clone(),
+            deadline: match (self.deadline, timeout) {
+                (Some(deadline), Some(timeout)) => Some(deadline.min(timeout)),
+                (deadline, timeout) => deadline.or(timeout),
+            },
+            timeout: None,
+        }
+    }
+
+    /// Returns an error if the token is cancelled or the deadline has passed, the cancellation is checked first.
+    pub fn check(&self) -> Result<(), Interrupted> {
+        if self.token.as_ref().is_some_and(CancelToken::is_cancelled) {
+            return Err(Interrupted::Cancelled);
+        }
+
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(Interrupted::Timeout);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+//! Renders the frames of one `VGL` program across the rayon thread pool.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::mpsc,
+    thread,
+};
+
+use futures::executor::block_on;
+use rayon::prelude::*;
+use vglang_ir::{AnimatableValue, IR};
+
+use crate::{Device, VGLProgram};
+
+/// The execution output of the programs compiled by `D`.
+pub type FrameOutput<D> = <<D as Device>::Program as VGLProgram>::Output;
+
+/// Renders `frames` frames of the program `codes` in parallel, and passes the outputs to `sink` in frame order.
+///
+/// * `new_device` creates the device of each rayon job, the program is compiled once per device.
+/// * `sample` returns the animatable registers of the frame at the given index.
+/// * `sink` is called on the current thread with `(index, output)` for the frames `0..frames` in this order,
+///   the outputs rendered ahead are held back until the frames before them are delivered.
+///
+/// Rendering stops at the first failed frame, and its error is returned after the frames before it that have
+/// been rendered are delivered.
+pub fn render_frames_parallel<D, N, R, S>(
+    new_device: N,
+    codes: &[IR],
+    frames: usize,
+    sample: R,
+    mut sink: S,
+) -> Result<(), D::Error>
+where
+    D: Device,
+    D::Error: Send,
+    FrameOutput<D>: Send,
+    N: Fn() -> D + Sync,
+    R: Fn(usize) -> HashMap<String, AnimatableValue> + Sync,
+    S: FnMut(usize, FrameOutput<D>),
+{
+    let (sender, receiver) = mpsc::channel();
+
+    let (new_device, sample) = (&new_device, &sample);
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            // returns `Err` to cancel the remaining frames, after an error or once the receiver is dropped.
+            let _ = (0..frames).into_par_iter().try_for_each_init(
+                || block_on(new_device().compile(codes.to_vec())).map_err(Some),
+                |program, index| {
+                    let output = match program {
+                        Ok(program) => block_on(program.execute(&sample(index))),
+                        Err(err) => match err.take() {
+                            Some(err) => Err(err),
+                            None => return Err(()),
+                        },
+                    };
+
+                    let failed = output.is_err();
+
+                    sender.send((index, output)).map_err(|_| ())?;
+
+                    if failed {
+                        Err(())
+                    } else {
+                        Ok(())
+                    }
+                },
+            );
+        });
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+
+        // the sender is dropped when all frames are rendered or cancelled.
+        while let Ok((index, output)) = receiver.recv() {
+            pending.insert(index, output);
+
+            while let Some(output) = pending.remove(&next) {
+                sink(next, output?);
+                next += 1;
+            }
+        }
+
+        // the frames before the failed one may be cancelled.
+        match pending.into_values().find_map(|output| output.err()) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    })
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use futures::future::{ready, Ready};
+use vglang_device::{render_frames_parallel, Device, VGLProgram};
+use vglang_ir::{AnimatableValue, IR};
+
+struct CountDevice;
+
+struct CountProgram(usize);
+
+impl Device for CountDevice {
+    type Program = CountProgram;
+
+    type Error = String;
+
+    type Compile<'a> = Ready<Result<CountProgram, String>>;
+
+    fn compile(&self, codes: Vec<IR>) -> Self::Compile<'_> {
+        if codes.is_empty() {
+            ready(Err("empty program".to_owned()))
+        } else {
+            ready(Ok(CountProgram(codes.len())))
+        }
+    }
+}
+
+impl VGLProgram for CountProgram {
+    type Output = usize;
+
+    type Error = String;
+
+    type Execute<'a> = Ready<Result<usize, String>>;
+
+    fn execute<'a>(&'a self, _: &'a HashMap<String, AnimatableValue>) -> Self::Execute<'a> {
+        ready(Ok(self.0))
+    }
+}
+
+#[test]
+fn test_render_frames_parallel() {
+    let codes = vec![IR::Pop(1), IR::Pop(1)];
+
+    let mut frames = vec![];
+
+    render_frames_parallel(
+        || CountDevice,
+        &codes,
+        100,
+        |_| HashMap::new(),
+        |index, output| frames.push((index, output)),
+    )
+    .unwrap();
+
+    assert_eq!(frames, (0..100).map(|index| (index, 2)).collect::<Vec<_>>());
+
+    assert_eq!(
+        render_frames_parallel(|| CountDevice, &[], 100, |_| HashMap::new(), |_, _| {}),
+        Err("empty program".to_owned())
+    );
+}
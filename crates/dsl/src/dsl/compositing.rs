@@ -0,0 +1,7 @@
+use vglang_ir::{Mask, MaskRef};
+
+use super::{scoped_appliable, scoped_with_content};
+
+scoped_with_content!(Mask);
+
+scoped_appliable!(MaskRef);
@@ -1,3 +1,5 @@
+use std::panic::Location;
+
 use vglang_ir::IR;
 
 use crate::generator::Generator;
@@ -118,14 +120,47 @@ tuple_appliable!(
     A21, A22, A23, A24, A25, A26, A27, A28, A29, A30, A31, A32, A33, A34, A35, A36, A37, A38, A39
 );
 
+/// Implements [`Appliable`] for scoped ir attributes, which push the attribute, draw the graphic
+/// and pop the attribute.
+macro_rules! scoped_appliable {
+    ($($ty: ty),+ $(,)?) => {
+        $(
+            impl $crate::dsl::Appliable for $ty {
+                fn apply<G, C>(self, graphic: C) -> impl $crate::dsl::Graphic<G>
+                where
+                    C: $crate::dsl::Graphic<G>,
+                    G: $crate::generator::Generator,
+                {
+                    move |g: &mut G| {
+                        g.push_from(self);
+                        graphic.draw(g);
+                        g.pop(1);
+                    }
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use scoped_appliable;
+
 /// Apply scope attributes to a `target` element.
+///
+/// The caller location is recorded as the call site of the generated instructions, see [`Generator::enter`].
+#[track_caller]
 pub fn apply<A, C, G>(attrs: A, target: C) -> impl Graphic<G>
 where
     A: Appliable,
     G: Generator,
     C: Graphic<G>,
 {
-    attrs.apply(target)
+    let location = Location::caller();
+
+    move |g: &mut G| {
+        g.enter(location);
+        attrs.apply(target).draw(g);
+        g.leave();
+    }
 }
 
 /// This trait defines a graphic element that may have one/more children elements.
@@ -136,12 +171,45 @@ pub trait WithContent {
         G: Generator;
 }
 
+/// Implements [`WithContent`] for scoped ir elements, which push the element, draw the content
+/// and pop the element.
+macro_rules! scoped_with_content {
+    ($($ty: ty),+ $(,)?) => {
+        $(
+            impl $crate::dsl::WithContent for $ty {
+                fn with_content<G, C>(self, graphic: C) -> impl $crate::dsl::Graphic<G>
+                where
+                    C: $crate::dsl::Graphic<G>,
+                    G: $crate::generator::Generator,
+                {
+                    move |g: &mut G| {
+                        g.push_from(self);
+                        graphic.draw(g);
+                        g.pop(1);
+                    }
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use scoped_with_content;
+
 /// apply graphic's content element.
+///
+/// The caller location is recorded as the call site of the generated instructions, see [`Generator::enter`].
+#[track_caller]
 pub fn with<P, C, G>(parent: P, content: C) -> impl Graphic<G>
 where
     P: WithContent,
     G: Generator,
     C: Graphic<G>,
 {
-    parent.with_content(content)
+    let location = Location::caller();
+
+    move |g: &mut G| {
+        g.enter(location);
+        parent.with_content(content).draw(g);
+        g.leave();
+    }
 }
@@ -0,0 +1,75 @@
+use vglang_ir::{
+    Fe, FeComposite, FeCompositeOperator, FeFlood, FeGaussianBlur, FeIn, FeMerge, FeMergeItem,
+    FeOffset, FilterRef, NumberOptNumber, Rgba,
+};
+
+use crate::generator::Generator;
+
+use super::{scoped_appliable, scoped_with_content, Graphic, WithContent};
+
+scoped_with_content!(Fe, FeMerge);
+
+scoped_appliable!(FilterRef);
+
+/// Implements [`Graphic`] for filter primitives, which are pushed as is.
+macro_rules! primitive_graphic {
+    ($($ty: ty),+ $(,)?) => {
+        $(
+            impl<G> Graphic<G> for $ty
+            where
+                G: Generator,
+            {
+                fn draw(self, g: &mut G) {
+                    g.push_from(self);
+                }
+            }
+        )+
+    };
+}
+
+primitive_graphic!(FeGaussianBlur, FeOffset, FeFlood, FeComposite, FeMergeItem);
+
+/// Create a drop shadow filter `id`, which draws the elements over their shadow blurred by `std_deviation`,
+/// offset by (`dx`, `dy`) and painted with `color`.
+///
+/// Apply it to the elements with [`FilterRef`].
+pub fn drop_shadow<G, C>(
+    id: &str,
+    dx: f32,
+    dy: f32,
+    std_deviation: f32,
+    color: C,
+) -> impl Graphic<G>
+where
+    G: Generator,
+    Rgba: From<C>,
+{
+    Fe::new(id).with_content((
+        FeGaussianBlur::default()
+            .r#in(FeIn::SourceAlpha)
+            .std_deviation(NumberOptNumber {
+                dx: std_deviation,
+                dy: None,
+            })
+            .result("blur"),
+        FeOffset::default()
+            .r#in("blur")
+            .dx(dx)
+            .dy(dy)
+            .result("offset"),
+        FeFlood::default().color(color).result("color"),
+        FeComposite::default()
+            .a("color")
+            .b("offset")
+            .operator(FeCompositeOperator::In)
+            .result("shadow"),
+        move |g: &mut G| {
+            FeMerge::default()
+                .with_content((
+                    FeMergeItem::new(FeIn::from("shadow")),
+                    FeMergeItem::new(FeIn::SourceGraphic),
+                ))
+                .draw(g)
+        },
+    ))
+}
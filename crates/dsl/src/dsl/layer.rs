@@ -1,3 +1,5 @@
+use std::panic::Location;
+
 use vglang_ir::{Layer, Measurement, PreserveAspectRatio, ViewBox};
 
 use crate::generator::Generator;
@@ -5,15 +7,22 @@ use crate::generator::Generator;
 use super::Graphic;
 
 /// Create a layer graphic element.
+///
+/// The caller location is recorded as the call site of the generated instructions, see [`Generator::enter`].
+#[track_caller]
 pub fn layer<G, C>(attrs: Layer, content: C) -> impl Graphic<G>
 where
     G: Generator,
     C: Graphic<G>,
 {
-    |g: &mut G| {
+    let location = Location::caller();
+
+    move |g: &mut G| {
+        g.enter(location);
         g.push_from(attrs);
         content.draw(g);
         g.pop(1);
+        g.leave();
     }
 }
 
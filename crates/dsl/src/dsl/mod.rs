@@ -11,6 +11,16 @@ pub use layer::*;
 mod shapes;
 
 mod painting;
+pub use painting::*;
+
+mod compositing;
+
+mod filter;
+pub use filter::*;
+
+mod symbol;
+
+mod transform;
 
 mod dimension;
 pub use dimension::*;
@@ -1,33 +1,22 @@
-use vglang_ir::{Fill, Stroke};
+use vglang_ir::{Fill, Label, Marker, Markers, Stroke};
 
-use crate::generator::Generator;
+use super::{scoped_appliable, scoped_with_content};
 
-use super::{Appliable, Graphic};
+scoped_appliable!(Fill, Stroke, Label, Markers);
 
-impl Appliable for Fill {
-    fn apply<G, C>(self, graphic: C) -> impl Graphic<G>
-    where
-        C: Graphic<G>,
-        G: Generator,
-    {
-        |g: &mut G| {
-            g.push_from(self);
-            graphic.draw(g);
-            g.pop(1);
-        }
-    }
+scoped_with_content!(Marker);
+
+/// Create a [`Markers`] attribute that draws the marker `id` at the first vertex.
+pub fn marker_start<S: Into<String>>(id: S) -> Markers {
+    Markers::default().start(id.into())
+}
+
+/// Create a [`Markers`] attribute that draws the marker `id` at the middle vertices.
+pub fn marker_mid<S: Into<String>>(id: S) -> Markers {
+    Markers::default().mid(id.into())
 }
 
-impl Appliable for Stroke {
-    fn apply<G, C>(self, graphic: C) -> impl Graphic<G>
-    where
-        C: Graphic<G>,
-        G: Generator,
-    {
-        |g: &mut G| {
-            g.push_from(self);
-            graphic.draw(g);
-            g.pop(1);
-        }
-    }
+/// Create a [`Markers`] attribute that draws the marker `id` at the last vertex.
+pub fn marker_end<S: Into<String>>(id: S) -> Markers {
+    Markers::default().end(id.into())
 }
@@ -1,4 +1,4 @@
-use vglang_ir::Rect;
+use vglang_ir::{Image, Path, Rect};
 
 use crate::generator::Generator;
 
@@ -12,3 +12,21 @@ where
         g.push_from(self);
     }
 }
+
+impl<G> Graphic<G> for Path
+where
+    G: Generator,
+{
+    fn draw(self, g: &mut G) {
+        g.push_from(self);
+    }
+}
+
+impl<G> Graphic<G> for Image
+where
+    G: Generator,
+{
+    fn draw(self, g: &mut G) {
+        g.push_from(self);
+    }
+}
@@ -0,0 +1,32 @@
+use vglang_ir::{SymbolDef, Use};
+
+use crate::generator::Generator;
+
+use super::{Graphic, WithContent};
+
+/// The symbol contents are only generated the first time the symbol `id` is defined,
+/// see [`Generator::define`].
+impl WithContent for SymbolDef {
+    fn with_content<G, C>(self, graphic: C) -> impl Graphic<G>
+    where
+        C: Graphic<G>,
+        G: Generator,
+    {
+        move |g: &mut G| {
+            if g.define(&self.id) {
+                g.push_from(self);
+                graphic.draw(g);
+                g.pop(1);
+            }
+        }
+    }
+}
+
+impl<G> Graphic<G> for Use
+where
+    G: Generator,
+{
+    fn draw(self, g: &mut G) {
+        g.push_from(self);
+    }
+}
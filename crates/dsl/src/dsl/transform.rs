@@ -0,0 +1,5 @@
+use vglang_ir::{Animatable, Transform};
+
+use super::scoped_appliable;
+
+scoped_appliable!(Transform, Animatable<Transform>);
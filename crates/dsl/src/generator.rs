@@ -1,5 +1,10 @@
-use vglang_device::Device;
-use vglang_ir::IR;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    panic::Location,
+};
+
+use vglang_device::{Device, IRStream, StreamingDevice};
+use vglang_ir::{verify, Violation, IR};
 
 /// This trait defines the compile target generator of `embed VGL language`.
 pub trait Generator {
@@ -18,26 +23,187 @@ pub trait Generator {
     {
         self.push(value.into());
     }
+
+    /// Enter a dsl call site, the instructions pushed before the paired [`leave`](Generator::leave)
+    /// are created by the code at the given location.
+    ///
+    /// The default implementation does nothing.
+    fn enter(&mut self, _location: &'static Location<'static>) {}
+
+    /// Leave the dsl call site entered by [`enter`](Generator::enter).
+    fn leave(&mut self) {}
+
+    /// Define the symbol `id`, returns false if it is already defined by this generator, in which case the
+    /// symbol contents are not generated again.
+    ///
+    /// The default implementation always returns true.
+    fn define(&mut self, _id: &str) -> bool {
+        true
+    }
 }
 
 /// A generator that output compile result as in-memory ir codes stream.
+///
+/// The dsl call site of each instruction is recorded as well, see [`location`](IRGenerator::location).
+/// The defined symbols are tracked too, see [`definition`](IRGenerator::definition).
 #[derive(Default)]
 pub struct IRGenerator {
     codes: Vec<IR>,
+    locations: Vec<Option<&'static Location<'static>>>,
+    call_sites: Vec<&'static Location<'static>>,
+    definitions: BTreeMap<String, usize>,
 }
 
 impl Generator for IRGenerator {
     fn push(&mut self, ir: IR) {
         self.codes.push(ir);
+        self.locations.push(self.call_sites.last().copied());
+    }
+
+    fn enter(&mut self, location: &'static Location<'static>) {
+        self.call_sites.push(location);
+    }
+
+    fn leave(&mut self) {
+        self.call_sites.pop();
+    }
+
+    fn define(&mut self, id: &str) -> bool {
+        if self.definitions.contains_key(id) {
+            return false;
+        }
+
+        self.definitions.insert(id.to_owned(), self.codes.len());
+
+        true
     }
 }
 
 impl IRGenerator {
+    /// Returns the generated ir codes.
+    pub fn codes(&self) -> &[IR] {
+        &self.codes
+    }
+
+    /// Consume self and returns the generated ir codes.
+    pub fn into_codes(self) -> Vec<IR> {
+        self.codes
+    }
+
+    /// Returns the index of the `symbol` instruction that defines the symbol `id`.
+    pub fn definition(&self, id: &str) -> Option<usize> {
+        self.definitions.get(id).copied()
+    }
+
+    /// Returns the dsl call site that created the instruction at `index`.
+    pub fn location(&self, index: usize) -> Option<&'static Location<'static>> {
+        self.locations.get(index).copied().flatten()
+    }
+
+    /// [`verify`] the generated ir codes, the violations are paired with the dsl call sites
+    /// of the offending instructions.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn verify(&self) -> Result<(), Vec<(Violation, Option<&'static Location<'static>>)>> {
+        verify(&self.codes).map_err(|violations| {
+            violations
+                .into_iter()
+                .map(|violation| {
+                    let location = self.location(violation.index);
+                    (violation, location)
+                })
+                .collect()
+        })
+    }
+
     /// Consume self and use provides [`Device`] to compile output as a `VGL` program.
     pub async fn compile<D>(self, device: &mut D) -> Result<D::Program, D::Error>
     where
         D: Device,
     {
-        device.compile(self.codes).await
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("compile", codes = self.codes.len());
+
+        let compile = device.compile(self.codes);
+
+        #[cfg(feature = "tracing")]
+        let compile = tracing::Instrument::instrument(compile, span);
+
+        compile.await
+    }
+}
+
+/// A generator that forwards the ir codes to a [`StreamingDevice`] as they are pushed, so the whole codes stream
+/// is never buffered by the generator.
+///
+/// This generator doesn't bound the memory of a program. The codes are buffered by the device instead, e.g. the svg
+/// device keeps all of them to regenerate the document on each execution, and no device of this workspace compiles
+/// them incrementally yet. It only saves the generator's copy of the codes stream.
+///
+/// Only the last pushed code is held back, so consecutive `pop` instructions can be merged into one, the same
+/// way as [`hoist_scopes`](vglang_ir::hoist_scopes) does.
+pub struct StreamGenerator<D>
+where
+    D: StreamingDevice,
+{
+    stream: D::Stream,
+    last: Option<IR>,
+    error: Option<D::Error>,
+    definitions: BTreeSet<String>,
+}
+
+impl<D> Generator for StreamGenerator<D>
+where
+    D: StreamingDevice,
+{
+    fn push(&mut self, ir: IR) {
+        match (&mut self.last, ir) {
+            (Some(IR::Pop(n)), IR::Pop(m)) => *n += m,
+            (_, ir) => {
+                if let Some(last) = self.last.replace(ir) {
+                    self.forward(last);
+                }
+            }
+        }
+    }
+
+    fn define(&mut self, id: &str) -> bool {
+        self.definitions.insert(id.to_owned())
+    }
+}
+
+impl<D> StreamGenerator<D>
+where
+    D: StreamingDevice,
+{
+    /// Start compiling one program with `device`.
+    pub fn new(device: &D) -> Self {
+        Self {
+            stream: device.stream(),
+            last: None,
+            error: None,
+            definitions: BTreeSet::new(),
+        }
+    }
+
+    fn forward(&mut self, ir: IR) {
+        // the codes after the first error are dropped, the error is returned by `finish`.
+        if self.error.is_none() {
+            if let Err(err) = self.stream.push(ir) {
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Consume self and returns the compiled program, or the first error raised by the device.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn finish(mut self) -> Result<D::Program, D::Error> {
+        if let Some(last) = self.last.take() {
+            self.forward(last);
+        }
+
+        match self.error {
+            Some(err) => Err(err),
+            None => self.stream.finish(),
+        }
     }
 }
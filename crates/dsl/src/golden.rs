@@ -0,0 +1,153 @@
+//! Golden-image regression tests, which compare the rendering output of graphics with blessed references.
+//!
+//! ```ignore
+//! let golden = Golden::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"));
+//!
+//! golden
+//!     .assert(&mut SvgDevice::default(), "rect.svg", Rect::from((0, 0, 10, 10)))
+//!     .await;
+//! ```
+//!
+//! A missing reference fails the test. Run the tests with `VGLANG_BLESS=1` to create or overwrite the references
+//! with the current outputs.
+
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
+
+use vglang_device::{Device, VGLProgram};
+
+use crate::{dsl::Graphic, generator::IRGenerator};
+
+/// Set this environment variable to `1` to bless the current outputs as the new references.
+pub const BLESS_ENV: &str = "VGLANG_BLESS";
+
+/// A directory of reference outputs.
+pub struct Golden {
+    dir: PathBuf,
+    bless: bool,
+}
+
+impl Golden {
+    /// Use the references stored in `dir`, the outputs are blessed if [`BLESS_ENV`] is `1`.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            bless: std::env::var(BLESS_ENV).is_ok_and(|value| value == "1"),
+        }
+    }
+
+    /// Set whether the outputs are blessed as the new references, regardless of [`BLESS_ENV`].
+    pub fn bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Returns the path of the reference `file_name`.
+    pub fn path(&self, file_name: &str) -> PathBuf {
+        self.dir.join(file_name)
+    }
+
+    /// Render `graphic` by `device` without registers, and [`assert_output`](Golden::assert_output) the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rendering fails or the output doesn't match the reference.
+    pub async fn assert<D, G>(&self, device: &mut D, file_name: &str, graphic: G)
+    where
+        D: Device,
+        D::Error: Debug,
+        <D::Program as VGLProgram>::Output: AsRef<[u8]>,
+        G: Graphic<IRGenerator>,
+    {
+        let mut generator = IRGenerator::default();
+
+        graphic.draw(&mut generator);
+
+        let program = generator
+            .compile(device)
+            .await
+            .unwrap_or_else(|err| panic!("compile `{}`: {:?}", file_name, err));
+
+        let output = program
+            .execute(&Default::default())
+            .await
+            .unwrap_or_else(|err| panic!("execute `{}`: {:?}", file_name, err));
+
+        self.assert_output(file_name, output.as_ref());
+    }
+
+    /// Compare `output` with the reference `file_name` exactly.
+    ///
+    /// If the outputs are [blessed](Golden::bless), the reference is created or overwritten from `output` instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reference is missing, or `output` doesn't match the reference, with the first differing line
+    /// of the text outputs.
+    pub fn assert_output(&self, file_name: &str, output: &[u8]) {
+        let path = self.path(file_name);
+
+        if self.bless {
+            write(&path, output);
+            return;
+        }
+
+        if !path.exists() {
+            panic!(
+                "reference {:?} of `{}` is missing, run with `{}=1` to create it.",
+                path, file_name, BLESS_ENV
+            );
+        }
+
+        let expected =
+            std::fs::read(&path).unwrap_or_else(|err| panic!("read reference {:?}: {}", path, err));
+
+        if expected == output {
+            return;
+        }
+
+        let differing_line = match (std::str::from_utf8(&expected), std::str::from_utf8(output)) {
+            (Ok(expected), Ok(output)) => {
+                let len = expected.lines().count().max(output.lines().count());
+
+                expected
+                    .lines()
+                    .chain(std::iter::repeat(""))
+                    .zip(output.lines().chain(std::iter::repeat("")))
+                    .take(len)
+                    .enumerate()
+                    .find(|(_, (expected, output))| expected != output)
+            }
+            // binary outputs.
+            _ => None,
+        };
+
+        match differing_line {
+            Some((line, (expected, output))) => panic!(
+                "output of `{}` differs from {:?} at line {}:\n  expected: {}\n    actual: {}\nrun with `{}=1` to bless the output.",
+                file_name,
+                path,
+                line + 1,
+                expected,
+                output,
+                BLESS_ENV
+            ),
+            None => panic!(
+                "output of `{}` differs from {:?}, run with `{}=1` to bless the output.",
+                file_name, path, BLESS_ENV
+            ),
+        }
+    }
+}
+
+fn write(path: &Path, output: &[u8]) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|err| panic!("create directory {:?}: {}", dir, err));
+    }
+
+    std::fs::write(path, output)
+        .unwrap_or_else(|err| panic!("write reference {:?}: {}", path, err));
+}
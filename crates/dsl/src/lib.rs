@@ -2,6 +2,8 @@
 
 pub mod dsl;
 pub mod generator;
+pub mod golden;
+pub mod profile;
 
 /// The attributes used by graphic elements.
 pub mod attrs {
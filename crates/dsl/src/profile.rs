@@ -0,0 +1,236 @@
+//! An opt-in profiler for the generation, validation, optimization, compile and execute phases.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Write},
+    panic::Location,
+    time::{Duration, Instant},
+};
+
+use vglang_device::{Device, VGLProgram};
+use vglang_ir::{AnimatableValue, Violation, IR};
+
+use crate::{dsl::Graphic, generator::IRGenerator};
+
+/// The phases recorded by [`Profiler`].
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Phase {
+    /// Generating ir codes from dsl graphics.
+    Generation,
+    /// Verifying the generated ir codes.
+    Validation,
+    /// Running optimization passes over the ir codes.
+    Optimization,
+    /// Compiling the ir codes by a device.
+    Compile,
+    /// Executing the compiled program.
+    Execute,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Generation => write!(f, "generation"),
+            Phase::Validation => write!(f, "validation"),
+            Phase::Optimization => write!(f, "optimization"),
+            Phase::Compile => write!(f, "compile"),
+            Phase::Execute => write!(f, "execute"),
+        }
+    }
+}
+
+/// One recorded phase.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct PhaseTiming {
+    /// The recorded phase.
+    pub phase: Phase,
+    /// The start time, relative to the creation of the profiler.
+    pub start: Duration,
+    /// The elapsed time of this phase.
+    pub duration: Duration,
+}
+
+/// The profiling result, see [`Profiler`].
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Profile {
+    /// The recorded phases in recording order.
+    pub phases: Vec<PhaseTiming>,
+    /// The number of generated instructions by instruction name, see [`IR::name`].
+    pub elements: BTreeMap<&'static str, usize>,
+}
+
+impl Profile {
+    /// Returns the total elapsed time of `phase`.
+    pub fn total(&self, phase: Phase) -> Duration {
+        self.phases
+            .iter()
+            .filter(|timing| timing.phase == phase)
+            .map(|timing| timing.duration)
+            .sum()
+    }
+
+    /// Returns this profile as a json array of trace events, which can be loaded by
+    /// `chrome://tracing`, `perfetto` or flamegraph tools.
+    ///
+    /// The element counts are written as the `args` of the generation events.
+    pub fn to_trace_json(&self) -> String {
+        let mut json = String::from("[");
+
+        for (index, timing) in self.phases.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+
+            _ = write!(
+                json,
+                r#"{{"name":"{}","cat":"vglang","ph":"X","pid":0,"tid":0,"ts":{},"dur":{}"#,
+                timing.phase,
+                timing.start.as_micros(),
+                timing.duration.as_micros()
+            );
+
+            if timing.phase == Phase::Generation {
+                json.push_str(r#","args":{"#);
+
+                for (index, (name, count)) in self.elements.iter().enumerate() {
+                    if index > 0 {
+                        json.push(',');
+                    }
+
+                    _ = write!(json, r#""{}":{}"#, name, count);
+                }
+
+                json.push('}');
+            }
+
+            json.push('}');
+        }
+
+        json.push(']');
+
+        json
+    }
+}
+
+/// Records the timings of each phase, from dsl graphics to the program output.
+///
+/// Profiling is opt-in: drive the phases through the profiler instead of calling the generator,
+/// device and program directly.
+pub struct Profiler {
+    origin: Instant,
+    profile: Profile,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            profile: Default::default(),
+        }
+    }
+}
+
+impl Profiler {
+    fn record<R, F>(&mut self, phase: Phase, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("phase", %phase).entered();
+
+        let start = Instant::now();
+
+        let r = f();
+
+        self.push(phase, start);
+
+        r
+    }
+
+    fn push(&mut self, phase: Phase, start: Instant) {
+        self.profile.phases.push(PhaseTiming {
+            phase,
+            start: start.duration_since(self.origin),
+            duration: start.elapsed(),
+        });
+    }
+
+    /// Generate ir codes from `graphic` and count the generated instructions.
+    pub fn generate<C>(&mut self, graphic: C) -> IRGenerator
+    where
+        C: Graphic<IRGenerator>,
+    {
+        let generator = self.record(Phase::Generation, || {
+            let mut generator = IRGenerator::default();
+            graphic.draw(&mut generator);
+            generator
+        });
+
+        for ir in generator.codes() {
+            *self.profile.elements.entry(ir.name()).or_default() += 1;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(codes = generator.codes().len(), elements = ?self.profile.elements, "generated");
+
+        generator
+    }
+
+    /// Verify the generated ir codes, see [`IRGenerator::verify`].
+    pub fn verify(
+        &mut self,
+        generator: &IRGenerator,
+    ) -> Result<(), Vec<(Violation, Option<&'static Location<'static>>)>> {
+        self.record(Phase::Validation, || generator.verify())
+    }
+
+    /// Run an optimization `pass` over the ir codes, e.g. [`hoist_scopes`](vglang_ir::hoist_scopes).
+    pub fn optimize<F>(&mut self, codes: Vec<IR>, pass: F) -> Vec<IR>
+    where
+        F: FnOnce(Vec<IR>) -> Vec<IR>,
+    {
+        self.record(Phase::Optimization, || pass(codes))
+    }
+
+    /// Compile the ir codes by `device`.
+    pub async fn compile<D>(&mut self, device: &D, codes: Vec<IR>) -> Result<D::Program, D::Error>
+    where
+        D: Device,
+    {
+        let start = Instant::now();
+
+        let r = device.compile(codes).await;
+
+        self.push(Phase::Compile, start);
+
+        r
+    }
+
+    /// Execute the compiled `program`.
+    pub async fn execute<P>(
+        &mut self,
+        program: &P,
+        animatable: &HashMap<String, AnimatableValue>,
+    ) -> Result<P::Output, P::Error>
+    where
+        P: VGLProgram,
+    {
+        let start = Instant::now();
+
+        let r = program.execute(animatable).await;
+
+        self.push(Phase::Execute, start);
+
+        r
+    }
+
+    /// Returns the profiling result recorded so far.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Consume self and returns the profiling result.
+    pub fn into_profile(self) -> Profile {
+        self.profile
+    }
+}
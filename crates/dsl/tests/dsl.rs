@@ -1,31 +1,90 @@
-use std::path::Path;
-
 use heck::ToLowerCamelCase;
-use vglang_dsl::{dsl::Graphic, generator::IRGenerator};
+use quick_xml::{events::Event, Reader};
+use vglang_dsl::{dsl::Graphic, generator::IRGenerator, golden::Golden};
 use vglang_svg::{SvgDevice, VGLProgram};
 
+/// Render `test` as svg, and compare its [canonical form](canonicalize) with the reference `tests/golden/<name>.svg`.
 pub async fn svg(name: &str, test: impl Graphic<IRGenerator>) {
-    println!("{}", name);
+    let golden = Golden::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"));
+
+    let file_name = format!("{}.svg", name.to_lower_camel_case());
 
     let mut generator = IRGenerator::default();
 
     test.draw(&mut generator);
 
-    let mut svg_device = SvgDevice::default();
+    let program = generator.compile(&mut SvgDevice::default()).await.unwrap();
 
-    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("dsl");
+    let output = program.execute(&Default::default()).await.unwrap();
 
-    if !dir.exists() {
-        std::fs::create_dir(dir.as_path()).unwrap();
-    }
+    golden.assert_output(&file_name, canonicalize(&output).as_bytes());
+}
+
+/// Returns the canonical form of the svg `document`: one node per line indented by its depth, attributes sorted
+/// by name, empty elements self-closed and without the xml declaration.
+///
+/// So the references don't depend on how the xml serializer orders attributes, and a mismatch points to one node.
+fn canonicalize(document: &str) -> String {
+    let mut reader = Reader::from_str(document);
+
+    // `<a/>` is read as `<a></a>`, so both forms are canonicalized the same.
+    reader.config_mut().expand_empty_elements = true;
+
+    let mut lines: Vec<String> = vec![];
+
+    // the lines of the open elements.
+    let mut open = vec![];
 
-    let program = generator.compile(&mut svg_device).await.unwrap();
+    loop {
+        let indent = "  ".repeat(open.len());
 
-    let output = program.execute(&mut Default::default()).await.unwrap();
+        match reader.read_event().unwrap() {
+            Event::Start(el) => {
+                let name = String::from_utf8_lossy(el.name().as_ref()).into_owned();
 
-    let output_file_path = dir.join(format!("{}.svg", name.to_lower_camel_case()));
+                let mut attrs = el
+                    .attributes()
+                    .map(|attr| {
+                        let attr = attr.unwrap();
+
+                        (
+                            String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+                            String::from_utf8_lossy(&attr.value).into_owned(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                attrs.sort();
+
+                let attrs = attrs
+                    .into_iter()
+                    .map(|(key, value)| format!(" {}=\"{}\"", key, value))
+                    .collect::<String>();
+
+                lines.push(format!("{}<{}{}>", indent, name, attrs));
+
+                open.push((lines.len() - 1, name));
+            }
+            Event::End(_) => {
+                let (line, name) = open.pop().unwrap();
+
+                if line == lines.len() - 1 {
+                    let start = &mut lines[line];
+                    start.insert(start.len() - 1, '/');
+                } else {
+                    lines.push(format!("{}</{}>", &indent[2..], name));
+                }
+            }
+            Event::Text(text) if !text.is_empty() => {
+                lines.push(format!("{}{}", indent, String::from_utf8_lossy(&text)));
+            }
+            Event::Eof => break,
+            // the xml declaration, comments, etc.
+            _ => {}
+        }
+    }
 
-    println!("    write output: {:?}", output_file_path);
+    lines.push(String::new());
 
-    std::fs::write(output_file_path, output).unwrap();
+    lines.join("\n")
 }
@@ -0,0 +1,82 @@
+use vglang_dsl::{
+    attrs::*,
+    dsl::{apply, drop_shadow, layer, with, Graphic},
+    generator::IRGenerator,
+};
+use vglang_svg::{SvgDevice, VGLProgram};
+
+#[futures_test::test]
+async fn test_drop_shadow() {
+    let scene = layer(
+        Layer::from((100, 100)),
+        (
+            drop_shadow("shadow", 2.0, 2.0, 1.5, Color::black),
+            apply(FilterRef::from("shadow"), Rect::from((10, 10, 50, 50))),
+        ),
+    );
+
+    let mut generator = IRGenerator::default();
+
+    scene.draw(&mut generator);
+
+    assert!(generator.verify().is_ok());
+
+    let svg = generator
+        .compile(&mut SvgDevice::default())
+        .await
+        .unwrap()
+        .execute(&Default::default())
+        .await
+        .unwrap();
+
+    assert!(svg.contains(r#"<defs><filter id="shadow""#), "{}", svg);
+    assert!(
+        svg.contains(r#"<feGaussianBlur result="blur" in="SourceAlpha" stdDeviation="1.5"/><feOffset result="offset" in="blur" dx="2" dy="2"/><feFlood result="color" flood-color="rgb(0,0,0)"/><feComposite result="shadow" in="color" in2="offset" operator="in"/>"#),
+        "{}",
+        svg
+    );
+    assert!(
+        svg.contains(
+            r#"<feMerge><feMergeNode in="shadow"/><feMergeNode in="SourceGraphic"/></feMerge>"#
+        ),
+        "{}",
+        svg
+    );
+    assert!(svg.contains(r#"<g filter="url(#shadow)"><rect"#), "{}", svg);
+}
+
+#[test]
+fn test_filter_wiring() {
+    let scene = layer(
+        Layer::from((100, 100)),
+        (
+            with(
+                Fe::new("blur"),
+                (
+                    FeOffset::default().r#in("missing").dx(1.0),
+                    FeGaussianBlur::default().result("blurred"),
+                ),
+            ),
+            FeFlood::default(),
+        ),
+    );
+
+    let mut generator = IRGenerator::default();
+
+    scene.draw(&mut generator);
+
+    let messages = generator
+        .verify()
+        .unwrap_err()
+        .into_iter()
+        .map(|(violation, _)| violation.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        [
+            "[2].in: undefined result `missing`",
+            "[5]: feFlood is only allowed in filter elements",
+        ]
+    );
+}
@@ -0,0 +1,39 @@
+use vglang_dsl::golden::Golden;
+
+fn golden() -> Golden {
+    Golden::new(std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden")).bless(false)
+}
+
+#[test]
+fn test_golden_blessed() {
+    let golden = golden().bless(true);
+
+    let _ = std::fs::remove_file(golden.path("blessed.txt"));
+
+    golden.assert_output("blessed.txt", b"a\nb");
+
+    assert_eq!(std::fs::read(golden.path("blessed.txt")).unwrap(), b"a\nb");
+
+    golden.bless(false).assert_output("blessed.txt", b"a\nb");
+}
+
+#[test]
+#[should_panic(expected = "is missing")]
+fn test_golden_missing() {
+    let golden = golden();
+
+    let _ = std::fs::remove_file(golden.path("missing.txt"));
+
+    golden.assert_output("missing.txt", b"a\nb");
+}
+
+#[test]
+#[should_panic(expected = "at line 2")]
+fn test_golden_mismatch() {
+    let golden = golden();
+
+    std::fs::create_dir_all(golden.path("")).unwrap();
+    std::fs::write(golden.path("mismatch.txt"), "a\nb").unwrap();
+
+    golden.assert_output("mismatch.txt", b"a\nc");
+}
@@ -0,0 +1,65 @@
+use vglang_dsl::{
+    attrs::*,
+    dsl::{apply, layer, marker_end, marker_start, with, Graphic},
+    generator::IRGenerator,
+};
+use vglang_svg::{SvgDevice, VGLProgram};
+
+#[futures_test::test]
+async fn test_marker() {
+    let scene = layer(
+        Layer::from((100, 100)),
+        (
+            apply(
+                (marker_start("arrow"), marker_end("arrow")),
+                Path::default().move_to((10.0, 10.0)).line_to((90.0, 90.0)),
+            ),
+            with(
+                Marker::new("arrow")
+                    .refx(5)
+                    .refy(5)
+                    .width(10)
+                    .height(10)
+                    .orient(MarkerOrient::AutoStartReverse),
+                Path::default()
+                    .move_to((0.0, 0.0))
+                    .line_to((10.0, 5.0))
+                    .line_to((0.0, 10.0))
+                    .close_path(),
+            ),
+        ),
+    );
+
+    let mut generator = IRGenerator::default();
+
+    scene.draw(&mut generator);
+
+    assert!(generator.verify().is_ok());
+
+    // the marker contents are not part of the rendered bounds.
+    assert_eq!(
+        bounds(generator.codes(), &ResolveContext::default()),
+        Some(geometry::Rect::new(10.0, 10.0, 80.0, 80.0))
+    );
+
+    let svg = generator
+        .compile(&mut SvgDevice::default())
+        .await
+        .unwrap()
+        .execute(&Default::default())
+        .await
+        .unwrap();
+
+    // markers are moved into the `defs` of the root viewport, even if they are defined after the references.
+    assert!(
+        svg.contains(r#"<defs><marker id="arrow" markerUnits="strokeWidth" refX="5" refY="5""#),
+        "{}",
+        svg
+    );
+    assert!(svg.contains(r#"orient="auto-start-reverse""#), "{}", svg);
+    assert!(
+        svg.contains(r#"<g marker-end="url(#arrow)"><g marker-start="url(#arrow)"><path"#),
+        "{}",
+        svg
+    );
+}
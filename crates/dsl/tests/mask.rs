@@ -0,0 +1,48 @@
+use vglang_dsl::{
+    attrs::*,
+    dsl::{apply, layer, with, Graphic},
+    generator::IRGenerator,
+};
+use vglang_svg::{SvgDevice, VGLProgram};
+
+#[futures_test::test]
+async fn test_mask() {
+    let scene = layer(
+        Layer::from((100, 100)),
+        (
+            with(
+                Mask::new("fade").mode(MaskType::Alpha),
+                apply(Fill::from(Color::white), Rect::from((0, 0, 100, 50))),
+            ),
+            apply(MaskRef::from("fade"), Rect::from((0, 0, 100, 100))),
+        ),
+    );
+
+    let mut generator = IRGenerator::default();
+
+    scene.draw(&mut generator);
+
+    assert!(generator.verify().is_ok());
+
+    // the mask contents are not part of the rendered bounds.
+    assert_eq!(
+        bounds(generator.codes(), &ResolveContext::default()),
+        Some(geometry::Rect::new(0.0, 0.0, 100.0, 100.0))
+    );
+
+    let svg = generator
+        .compile(&mut SvgDevice::default())
+        .await
+        .unwrap()
+        .execute(&Default::default())
+        .await
+        .unwrap();
+
+    // masks are moved into the `defs` of the root viewport.
+    assert!(
+        svg.contains(r#"<defs><mask id="fade" mask-type="alpha""#),
+        "{}",
+        svg
+    );
+    assert!(svg.contains(r#"<g mask="url(#fade)"><rect"#), "{}", svg);
+}
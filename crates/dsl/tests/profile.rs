@@ -0,0 +1,51 @@
+use vglang_dsl::{attrs::*, dsl::layer, profile::*};
+use vglang_svg::SvgDevice;
+
+#[futures_test::test]
+async fn test_profile() {
+    let mut profiler = Profiler::default();
+
+    let generator = profiler.generate(layer(
+        Layer::from((100, 100)),
+        (Rect::from((0, 0, 10, 10)), Rect::from((10, 0, 10, 10))),
+    ));
+
+    profiler.verify(&generator).unwrap();
+
+    let codes = profiler.optimize(generator.into_codes(), hoist_scopes);
+
+    let program = profiler
+        .compile(&SvgDevice::default(), codes)
+        .await
+        .unwrap();
+
+    profiler
+        .execute(&program, &Default::default())
+        .await
+        .unwrap();
+
+    let profile = profiler.into_profile();
+
+    assert_eq!(
+        profile
+            .phases
+            .iter()
+            .map(|timing| timing.phase)
+            .collect::<Vec<_>>(),
+        vec![
+            Phase::Generation,
+            Phase::Validation,
+            Phase::Optimization,
+            Phase::Compile,
+            Phase::Execute
+        ]
+    );
+
+    assert_eq!(profile.elements.get("rect"), Some(&2));
+    assert_eq!(profile.elements.get("layer"), Some(&1));
+
+    let json = profile.to_trace_json();
+
+    assert!(json.starts_with(r#"[{"name":"generation","#));
+    assert!(json.contains(r#""args":{"layer":1,"pop":1,"rect":2}"#));
+}
@@ -0,0 +1,32 @@
+use vglang_dsl::{
+    attrs::*,
+    dsl::{apply, layer, Graphic},
+    generator::IRGenerator,
+};
+
+#[test]
+fn test_source_map() {
+    let mut generator = IRGenerator::default();
+
+    // the setters reject negative widths in debug builds.
+    let stroke = Stroke {
+        width: Some(Animatable::Constant(Measurement::from(-1))),
+        ..Default::default()
+    };
+
+    let (graphic, line) = (apply(stroke, Rect::from((0, 0, 10, 10))), line!());
+
+    layer(Layer::from((100, 100)), graphic).draw(&mut generator);
+
+    let violations = generator.verify().unwrap_err();
+
+    assert_eq!(violations.len(), 1);
+
+    let (violation, location) = &violations[0];
+
+    assert_eq!(violation.index, 1);
+    assert_eq!(location.unwrap().file(), file!());
+    assert_eq!(location.unwrap().line(), line);
+
+    assert_eq!(generator.location(0).unwrap().line(), line + 2);
+}
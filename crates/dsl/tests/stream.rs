@@ -0,0 +1,46 @@
+use vglang_dsl::{
+    attrs::*,
+    dsl::{apply, layer, Graphic},
+    generator::{Generator, IRGenerator, StreamGenerator},
+};
+use vglang_svg::{SvgDevice, VGLProgram};
+
+fn scene<G: Generator>() -> impl Graphic<G> {
+    layer(
+        Layer::from((100, 100)),
+        apply(
+            Fill::from(Color::black),
+            (Rect::from((0, 0, 10, 10)), Rect::from((10, 0, 10, 10))),
+        ),
+    )
+}
+
+#[futures_test::test]
+async fn test_stream_generator() {
+    let device = SvgDevice::default();
+
+    let mut generator = StreamGenerator::new(&device);
+
+    scene().draw(&mut generator);
+
+    let streamed = generator
+        .finish()
+        .unwrap()
+        .execute(&Default::default())
+        .await
+        .unwrap();
+
+    let mut generator = IRGenerator::default();
+
+    scene().draw(&mut generator);
+
+    let buffered = generator
+        .compile(&mut SvgDevice::default())
+        .await
+        .unwrap()
+        .execute(&Default::default())
+        .await
+        .unwrap();
+
+    assert_eq!(streamed, buffered);
+}
@@ -0,0 +1,90 @@
+use vglang_dsl::{
+    attrs::*,
+    dsl::{layer, with, Graphic},
+    generator::IRGenerator,
+};
+use vglang_svg::{SvgDevice, VGLProgram};
+
+#[futures_test::test]
+async fn test_symbol() {
+    let dot = || with(SymbolDef::new("dot"), Rect::from((0, 0, 10, 10)));
+
+    let scene = layer(
+        Layer::from((100, 100)),
+        (
+            dot(),
+            Use::new("dot").x(10).y(10),
+            // redefinitions are skipped by the generator.
+            dot(),
+            Use::new("dot").x(50).y(50).width(20).height(20),
+        ),
+    );
+
+    let mut generator = IRGenerator::default();
+
+    scene.draw(&mut generator);
+
+    assert!(generator.verify().is_ok());
+
+    assert_eq!(generator.definition("dot"), Some(1));
+    assert_eq!(
+        generator
+            .codes()
+            .iter()
+            .filter(|ir| matches!(ir, IR::Symbol(_)))
+            .count(),
+        1
+    );
+
+    // the symbol contents are measured at the instances, the symbol has no viewbox to stretch.
+    assert_eq!(
+        bounds(generator.codes(), &ResolveContext::default()),
+        Some(geometry::Rect::new(10.0, 10.0, 50.0, 50.0))
+    );
+
+    let svg = generator
+        .compile(&mut SvgDevice::default())
+        .await
+        .unwrap()
+        .execute(&Default::default())
+        .await
+        .unwrap();
+
+    assert!(
+        svg.contains(r#"<defs><symbol id="dot"><rect x="0" y="0""#),
+        "{}",
+        svg
+    );
+    assert!(svg.contains(r##"<use href="#dot" x="10" y="10"/>"##), "{}", svg);
+    assert!(
+        svg.contains(r##"<use href="#dot" x="50" y="50" width="20" height="20"/>"##),
+        "{}",
+        svg
+    );
+}
+
+#[test]
+fn test_undefined_symbol() {
+    let scene = layer(
+        Layer::from((100, 100)),
+        (
+            // a symbol may be used before its definition.
+            Use::new("dot"),
+            with(SymbolDef::new("dot"), Rect::from((0, 0, 10, 10))),
+            Use::new("square"),
+        ),
+    );
+
+    let mut generator = IRGenerator::default();
+
+    scene.draw(&mut generator);
+
+    let violations = generator
+        .verify()
+        .unwrap_err()
+        .into_iter()
+        .map(|(v, _)| v.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(violations, vec!["[5].href: undefined id `square`"]);
+}
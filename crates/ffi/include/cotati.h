@@ -0,0 +1,95 @@
+#ifndef COTATI_H
+#define COTATI_H
+
+/* This file is generated by cbindgen, do not edit it manually. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// The status code returned by the fallible functions.
+//
+// On failure, the error message can be read by [`cotati_last_error`].
+typedef enum {
+  COTATI_STATUS_OK = 0,
+  // A required pointer argument is null.
+  COTATI_STATUS_NULL_POINTER,
+  // A string argument is not valid utf-8.
+  COTATI_STATUS_INVALID_UTF8,
+  // The serialized ir codes are malformed, have an unsupported version or don't pass the validation.
+  COTATI_STATUS_INVALID_IR,
+  // The ir codes failed to compile or execute.
+  COTATI_STATUS_RENDER,
+  // The output format is not supported by this build.
+  COTATI_STATUS_UNSUPPORTED,
+  // The library panicked, the arguments of the call should not be used anymore.
+  COTATI_STATUS_PANIC,
+} CotatiStatus;
+
+// An ir codes stream.
+typedef struct CotatiGenerator CotatiGenerator;
+
+// A byte buffer owned by this library, which must be released with [`cotati_buffer_free`].
+typedef struct {
+  uint8_t *data;
+  size_t len;
+} CotatiBuffer;
+
+// Returns the error message of the last failed call on the current thread, or null.
+//
+// The returned string is owned by this library, and is valid until the next failed call on the same thread.
+const char *cotati_last_error(void);
+
+// Creates an empty generator, which must be released with [`cotati_generator_free`].
+//
+// Returns null if the generator can't be created.
+CotatiGenerator *cotati_generator_new(void);
+
+// Releases a generator created by [`cotati_generator_new`], null is ignored.
+//
+// # Safety
+//
+// `generator` must be null or returned by [`cotati_generator_new`], and must not be used after this call.
+void cotati_generator_free(CotatiGenerator *generator);
+
+// Appends the ir codes of a nul-terminated json envelope to `generator`.
+//
+// # Safety
+//
+// `generator` must be returned by [`cotati_generator_new`], `json` must be a nul-terminated string.
+CotatiStatus cotati_generator_push_json(CotatiGenerator *generator, const char *json);
+
+// Appends the ir codes of a cbor envelope of `len` bytes to `generator`.
+//
+// # Safety
+//
+// `generator` must be returned by [`cotati_generator_new`], `data` must point to `len` readable bytes.
+CotatiStatus cotati_generator_push_cbor(CotatiGenerator *generator,
+                                        const uint8_t *data,
+                                        size_t len);
+
+// Compiles and executes the ir codes of `generator`, and writes the svg document into `output`.
+//
+// # Safety
+//
+// `generator` must be returned by [`cotati_generator_new`], `output` must be a writable [`CotatiBuffer`].
+CotatiStatus cotati_generator_render_svg(const CotatiGenerator *generator,
+                                         CotatiBuffer *output);
+
+// Renders `generator` as a png image.
+//
+// There is no raster backend yet, so this always returns [`CotatiStatus::Unsupported`].
+//
+// # Safety
+//
+// `generator` must be returned by [`cotati_generator_new`], `output` must be a writable [`CotatiBuffer`].
+CotatiStatus cotati_generator_render_png(const CotatiGenerator *generator,
+                                         CotatiBuffer *output);
+
+// Releases a buffer written by this library, an empty buffer is ignored.
+//
+// # Safety
+//
+// `buffer` must be written by this library, and must not be used after this call.
+void cotati_buffer_free(CotatiBuffer buffer);
+
+#endif  /* COTATI_H */
@@ -0,0 +1,324 @@
+//! The C bindings of vglang.
+//!
+//! The C declarations of this crate are in `include/cotati.h`, which is generated from this file by
+//! [cbindgen](https://github.com/mozilla/cbindgen):
+//!
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/cotati.h
+//! ```
+//!
+//! A host application creates one generator, pushes serialized ir codes into it and then renders it as many
+//! times as needed:
+//!
+//! ```c
+//! CotatiGenerator *generator = cotati_generator_new();
+//!
+//! if (cotati_generator_push_json(generator, json) != COTATI_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", cotati_last_error());
+//! }
+//!
+//! CotatiBuffer svg;
+//!
+//! if (cotati_generator_render_svg(generator, &svg) == COTATI_STATUS_OK) {
+//!     fwrite(svg.data, 1, svg.len, stdout);
+//!     cotati_buffer_free(svg);
+//! }
+//!
+//! cotati_generator_free(generator);
+//! ```
+//!
+//! Animatable registers are not supported yet, because no register value can be bound, the programs that
+//! reference a register are rejected by the push functions. A panic never crosses the C boundary, it is
+//! reported as [`CotatiStatus::Panic`].
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    fmt::Display,
+    panic::{self, UnwindSafe},
+    ptr, slice,
+};
+
+use futures::executor::block_on;
+use vglang_ir::{from_cbor_validated, from_json_validated, registers, IR};
+use vglang_svg::{Device, SvgDevice, VGLProgram};
+
+/// The status code returned by the fallible functions.
+///
+/// On failure, the error message can be read by [`cotati_last_error`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CotatiStatus {
+    Ok = 0,
+    /// A required pointer argument is null.
+    NullPointer,
+    /// A string argument is not valid utf-8.
+    InvalidUtf8,
+    /// The serialized ir codes are malformed, have an unsupported version or don't pass the validation.
+    InvalidIr,
+    /// The ir codes failed to compile or execute.
+    Render,
+    /// The output format is not supported by this build.
+    Unsupported,
+    /// The library panicked, the arguments of the call should not be used anymore.
+    Panic,
+}
+
+/// An ir codes stream.
+pub struct CotatiGenerator {
+    codes: Vec<IR>,
+}
+
+/// A byte buffer owned by this library, which must be released with [`cotati_buffer_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct CotatiBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl From<Vec<u8>> for CotatiBuffer {
+    fn from(value: Vec<u8>) -> Self {
+        let len = value.len();
+        let data = Box::into_raw(value.into_boxed_slice()) as *mut u8;
+
+        Self { data, len }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Saves the error message for [`cotati_last_error`] and returns `status`.
+fn fail(status: CotatiStatus, err: impl Display) -> CotatiStatus {
+    // the interior nul bytes can't be represented by a C string.
+    let message = err.to_string().replace('\0', "");
+
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = CString::new(message).ok();
+    });
+
+    status
+}
+
+/// Returns the message of a panic payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Calls `f`, a panic is caught and reported as [`CotatiStatus::Panic`] instead of unwinding into the C caller.
+fn guard<F>(f: F) -> CotatiStatus
+where
+    F: FnOnce() -> CotatiStatus + UnwindSafe,
+{
+    panic::catch_unwind(f).unwrap_or_else(|payload| {
+        fail(
+            CotatiStatus::Panic,
+            format!("panicked: {}", panic_message(payload)),
+        )
+    })
+}
+
+/// Appends the decoded ir codes to `generator`, the codes referencing animatable registers are rejected.
+fn push(generator: &mut CotatiGenerator, codes: vglang_ir::Result<Vec<IR>>) -> CotatiStatus {
+    let codes = match codes {
+        Ok(codes) => codes,
+        Err(err) => return fail(CotatiStatus::InvalidIr, err),
+    };
+
+    if let Some(name) = registers(&codes).first() {
+        return fail(
+            CotatiStatus::InvalidIr,
+            format!(
+                "register `{}` is not bound, registers are not supported",
+                name
+            ),
+        );
+    }
+
+    generator.codes.extend(codes);
+
+    CotatiStatus::Ok
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Result<&'a str, CotatiStatus> {
+    if s.is_null() {
+        return Err(fail(CotatiStatus::NullPointer, "string argument is null"));
+    }
+
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|err| fail(CotatiStatus::InvalidUtf8, err))
+}
+
+/// Returns the error message of the last failed call on the current thread, or null.
+///
+/// The returned string is owned by this library, and is valid until the next failed call on the same thread.
+#[no_mangle]
+pub extern "C" fn cotati_last_error() -> *const c_char {
+    panic::catch_unwind(|| {
+        LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map(|message| message.as_ptr())
+                .unwrap_or(ptr::null())
+        })
+    })
+    .unwrap_or(ptr::null())
+}
+
+/// Creates an empty generator, which must be released with [`cotati_generator_free`].
+///
+/// Returns null if the generator can't be created.
+#[no_mangle]
+pub extern "C" fn cotati_generator_new() -> *mut CotatiGenerator {
+    panic::catch_unwind(|| Box::into_raw(Box::new(CotatiGenerator { codes: vec![] })))
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Releases a generator created by [`cotati_generator_new`], null is ignored.
+///
+/// # Safety
+///
+/// `generator` must be null or returned by [`cotati_generator_new`], and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn cotati_generator_free(generator: *mut CotatiGenerator) {
+    if !generator.is_null() {
+        let generator = Box::from_raw(generator);
+
+        // a panic in the destructor is not recoverable, the generator is leaked then.
+        let _ = panic::catch_unwind(move || drop(generator));
+    }
+}
+
+/// Appends the ir codes of a nul-terminated json envelope to `generator`.
+///
+/// # Safety
+///
+/// `generator` must be returned by [`cotati_generator_new`], `json` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn cotati_generator_push_json(
+    generator: *mut CotatiGenerator,
+    json: *const c_char,
+) -> CotatiStatus {
+    guard(panic::AssertUnwindSafe(|| {
+        let Some(generator) = generator.as_mut() else {
+            return fail(CotatiStatus::NullPointer, "generator is null");
+        };
+
+        let json = match to_str(json) {
+            Ok(json) => json,
+            Err(status) => return status,
+        };
+
+        push(generator, from_json_validated(json))
+    }))
+}
+
+/// Appends the ir codes of a cbor envelope of `len` bytes to `generator`.
+///
+/// # Safety
+///
+/// `generator` must be returned by [`cotati_generator_new`], `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn cotati_generator_push_cbor(
+    generator: *mut CotatiGenerator,
+    data: *const u8,
+    len: usize,
+) -> CotatiStatus {
+    guard(panic::AssertUnwindSafe(|| {
+        let Some(generator) = generator.as_mut() else {
+            return fail(CotatiStatus::NullPointer, "generator is null");
+        };
+
+        if data.is_null() {
+            return fail(CotatiStatus::NullPointer, "data is null");
+        }
+
+        push(
+            generator,
+            from_cbor_validated(slice::from_raw_parts(data, len)),
+        )
+    }))
+}
+
+/// Compiles and executes the ir codes of `generator`, and writes the svg document into `output`.
+///
+/// # Safety
+///
+/// `generator` must be returned by [`cotati_generator_new`], `output` must be a writable [`CotatiBuffer`].
+#[no_mangle]
+pub unsafe extern "C" fn cotati_generator_render_svg(
+    generator: *const CotatiGenerator,
+    output: *mut CotatiBuffer,
+) -> CotatiStatus {
+    guard(panic::AssertUnwindSafe(|| {
+        let Some(generator) = generator.as_ref() else {
+            return fail(CotatiStatus::NullPointer, "generator is null");
+        };
+
+        if output.is_null() {
+            return fail(CotatiStatus::NullPointer, "output is null");
+        }
+
+        // the svg device never suspends, the futures are ready on the first poll.
+        let svg = block_on(async {
+            let program = SvgDevice::default()
+                .compile(generator.codes.clone())
+                .await?;
+
+            program.execute(&Default::default()).await
+        });
+
+        match svg {
+            Ok(svg) => {
+                output.write(svg.into_bytes().into());
+                CotatiStatus::Ok
+            }
+            Err(err) => fail(CotatiStatus::Render, err),
+        }
+    }))
+}
+
+/// Renders `generator` as a png image.
+///
+/// There is no raster backend yet, so this always returns [`CotatiStatus::Unsupported`].
+///
+/// # Safety
+///
+/// `generator` must be returned by [`cotati_generator_new`], `output` must be a writable [`CotatiBuffer`].
+#[no_mangle]
+pub unsafe extern "C" fn cotati_generator_render_png(
+    generator: *const CotatiGenerator,
+    output: *mut CotatiBuffer,
+) -> CotatiStatus {
+    guard(|| {
+        if generator.is_null() || output.is_null() {
+            return fail(CotatiStatus::NullPointer, "generator or output is null");
+        }
+
+        fail(CotatiStatus::Unsupported, "png output is not supported")
+    })
+}
+
+/// Releases a buffer written by this library, an empty buffer is ignored.
+///
+/// # Safety
+///
+/// `buffer` must be written by this library, and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn cotati_buffer_free(buffer: CotatiBuffer) {
+    if !buffer.data.is_null() {
+        let data = Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len));
+
+        let _ = panic::catch_unwind(move || drop(data));
+    }
+}
@@ -0,0 +1,98 @@
+use std::ffi::CStr;
+
+use cotati_ffi::*;
+
+#[test]
+fn test_ffi_errors() {
+    unsafe {
+        let generator = cotati_generator_new();
+
+        assert_eq!(
+            cotati_generator_push_json(generator, c"{\"version\":0,\"body\":[]}".as_ptr()),
+            CotatiStatus::InvalidIr
+        );
+
+        assert_eq!(
+            CStr::from_ptr(cotati_last_error()).to_str().unwrap(),
+            "unsupported ir version: 0"
+        );
+
+        let mut output = CotatiBuffer {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+
+        assert_eq!(
+            cotati_generator_render_svg(generator, &mut output),
+            CotatiStatus::Render
+        );
+
+        assert_eq!(
+            cotati_generator_render_png(generator, &mut output),
+            CotatiStatus::Unsupported
+        );
+
+        assert_eq!(
+            cotati_generator_push_json(std::ptr::null_mut(), c"{}".as_ptr()),
+            CotatiStatus::NullPointer
+        );
+
+        assert!(output.data.is_null());
+
+        cotati_generator_free(generator);
+    }
+}
+
+#[test]
+fn test_ffi_render_svg() {
+    unsafe {
+        let generator = cotati_generator_new();
+
+        assert_eq!(
+            cotati_generator_push_json(
+                generator,
+                c"{\"version\":1,\"body\":[{\"Layer\":{\"width\":{\"Constant\":[10,null]},\"height\":{\"Constant\":[10,null]}}},{\"Pop\":1}]}".as_ptr()
+            ),
+            CotatiStatus::Ok
+        );
+
+        let mut output = CotatiBuffer {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+
+        assert_eq!(
+            cotati_generator_render_svg(generator, &mut output),
+            CotatiStatus::Ok
+        );
+
+        let svg = std::str::from_utf8(std::slice::from_raw_parts(output.data, output.len)).unwrap();
+
+        assert!(svg.contains("<svg"), "{}", svg);
+
+        cotati_buffer_free(output);
+        cotati_generator_free(generator);
+    }
+}
+
+#[test]
+fn test_ffi_unbound_register() {
+    unsafe {
+        let generator = cotati_generator_new();
+
+        assert_eq!(
+            cotati_generator_push_json(
+                generator,
+                c"{\"version\":1,\"body\":[{\"Layer\":{\"width\":{\"Animated\":\"w\"},\"height\":{\"Constant\":[10,null]}}},{\"Pop\":1}]}".as_ptr()
+            ),
+            CotatiStatus::InvalidIr
+        );
+
+        assert_eq!(
+            CStr::from_ptr(cotati_last_error()).to_str().unwrap(),
+            "register `w` is not bound, registers are not supported"
+        );
+
+        cotati_generator_free(generator);
+    }
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+/// `include/cotati.h` must be the cbindgen output of the current exports.
+#[test]
+fn test_header_is_generated() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap();
+
+    let mut header = vec![];
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(dir.join("src/lib.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut header);
+
+    let expected = std::fs::read_to_string(dir.join("include/cotati.h")).unwrap();
+
+    assert!(
+        expected == String::from_utf8(header).unwrap(),
+        "include/cotati.h is out of date, run `cbindgen --config cbindgen.toml --output include/cotati.h` in crates/ffi"
+    );
+}
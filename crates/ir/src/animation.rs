@@ -1,3 +1,8 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 use crate::errors::{Error, Result};
@@ -14,6 +19,8 @@ impl<T> FrameVariable for Vec<T> where T: FrameVariable {}
 /// An variable container, indicates that this variable can be used as animation frame variable.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "Animatable_{T}"))]
 pub enum Animatable<T>
 where
     T: FrameVariable,
@@ -42,16 +49,19 @@ where
     }
 
     /// Get animatable variable from constant storage or registers.
+    ///
+    /// Returns the register name as error if the register is not bound. No register value can be bound yet,
+    /// because [`AnimatableValue`] has no variants, so every animated variable is unbound.
+    #[cfg(feature = "std")]
     pub fn get(
         &self,
         animatable: &HashMap<String, AnimatableValue>,
-    ) -> std::result::Result<&T, &str> {
+    ) -> core::result::Result<&T, &str> {
         match self {
-            Animatable::Animated(name) => {
-                animatable.get(name);
-
-                todo!("cast AnimatableValue to `T`")
-            }
+            Animatable::Animated(name) => match animatable.get(name) {
+                Some(value) => match *value {},
+                None => Err(name),
+            },
             Animatable::Constant(v) => Ok(v),
         }
     }
@@ -96,4 +106,5 @@ where
 /// An variant that referenced by one animatable register.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AnimatableValue {}
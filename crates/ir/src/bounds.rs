@@ -0,0 +1,501 @@
+//! Bounding-box computation over ir codes.
+
+use crate::{
+    geometry::{Matrix, Rect, SubPath},
+    resolve::constant,
+    viewbox_matrix, Animatable, Axis, Fill, FillRule, Layer, Paint, ResolveContext, Rgba, Stroke,
+    StrokeStyle, SymbolDef, Transform, Use, ViewBox, DEFAULT_TOLERANCE, IR,
+};
+
+/// Options of [`bounds_with`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct BoundsOptions {
+    /// Include the half stroke widths of stroked elements, the default is the geometry bounds only.
+    pub stroke: bool,
+}
+
+/// Returns the tight bounding box of the ir codes in the root user space, or `None` if nothing is drawn.
+///
+/// See [`bounds_with`] for more information.
+pub fn bounds(codes: &[IR], ctx: &ResolveContext) -> Option<Rect> {
+    bounds_with(codes, ctx, &BoundsOptions::default())
+}
+
+/// Returns the bounding box of the ir codes in the root user space, or `None` if nothing is drawn.
+///
+/// Units are resolved by `ctx`, and the viewbox transformations of nested layers are applied.
+/// The contents of a symbol are measured at every [`Use`] instance of it.
+/// Elements with animated geometry, empty rectangles and text elements are skipped, the latter
+/// needs font metrics to be measured.
+pub fn bounds_with(codes: &[IR], ctx: &ResolveContext, options: &BoundsOptions) -> Option<Rect> {
+    let mut walker = Walker::new(*ctx);
+    let symbols = Symbols::new(codes);
+
+    let mut bounds: Option<Rect> = None;
+
+    for ir in codes {
+        let rect = match ir {
+            IR::Use(value) => walker.instance(value, &symbols, options, &mut |_, _| {}),
+            ir => walker.next(ir, options),
+        };
+
+        if let Some(rect) = rect {
+            bounds = Some(match bounds {
+                Some(bounds) => bounds.union(&rect),
+                None => rect,
+            });
+        }
+    }
+
+    bounds
+}
+
+impl Layer {
+    /// Set the viewbox of this layer to the bounds of `content`, including stroke widths, inflated by `padding`.
+    ///
+    /// `content` is the ir codes in the scope of this layer, resolved by `ctx` with the current viewport of this layer.
+    /// The aspect ratio setting of the current viewbox is kept. Returns the new viewbox, which can also be used to
+    /// resize the layer, or `None` if nothing is drawn or the layer is animated, which leaves this layer unchanged.
+    pub fn fit_content(
+        &mut self,
+        content: &[IR],
+        ctx: &ResolveContext,
+        padding: f32,
+    ) -> Option<Rect> {
+        let mut inner = *ctx;
+
+        let aspect = match self.viewbox.as_ref() {
+            Some(viewbox) => {
+                let viewbox = constant(viewbox)?;
+                let (rect, _) = ctx.viewbox(viewbox)?;
+
+                inner.viewport_width = rect.width;
+                inner.viewport_height = rect.height;
+
+                viewbox.aspect.clone()
+            }
+            None => {
+                inner.viewport_width = ctx.animatable_length(&self.width, Axis::Horizontal)?;
+                inner.viewport_height = ctx.animatable_length(&self.height, Axis::Vertical)?;
+
+                None
+            }
+        };
+
+        let rect = bounds_with(content, &inner, &BoundsOptions { stroke: true })?.inflate(padding);
+
+        let viewbox = ViewBox {
+            aspect,
+            ..ViewBox::from((rect.x, rect.y, rect.width, rect.height))
+        };
+
+        self.viewbox = Some(Animatable::Constant(viewbox));
+
+        Some(rect)
+    }
+}
+
+/// The contents of the symbols in ir codes, by id.
+pub(crate) struct Symbols<'a>(Vec<(&'a SymbolDef, &'a [IR])>);
+
+impl<'a> Symbols<'a> {
+    pub(crate) fn new(codes: &'a [IR]) -> Self {
+        let mut symbols = vec![];
+
+        for (index, ir) in codes.iter().enumerate() {
+            let IR::Symbol(symbol) = ir else {
+                continue;
+            };
+
+            let contents = &codes[index + 1..];
+
+            // the contents end at the pop of the symbol scope.
+            let mut depth = 1usize;
+
+            let end = contents
+                .iter()
+                .position(|ir| match ir {
+                    IR::Pop(n) => {
+                        depth = depth.saturating_sub(*n);
+                        depth == 0
+                    }
+                    ir if ir.is_scoped() => {
+                        depth += 1;
+                        false
+                    }
+                    _ => false,
+                })
+                .unwrap_or(contents.len());
+
+            symbols.push((symbol.as_ref(), &contents[..end]));
+        }
+
+        Self(symbols)
+    }
+
+    /// Returns the first symbol with `id` and its contents.
+    fn get(&self, id: &str) -> Option<(&'a SymbolDef, &'a [IR])> {
+        self.0.iter().find(|(symbol, _)| symbol.id == id).copied()
+    }
+}
+
+/// The inherited state of a scope.
+#[derive(Clone)]
+pub(crate) struct State {
+    pub(crate) ctx: ResolveContext,
+    /// Maps the current user space to the root user space.
+    pub(crate) matrix: Matrix,
+    pub(crate) fill_rule: FillRule,
+    /// False if the fill is `none`.
+    pub(crate) fill_paint: bool,
+    /// The fill color, `None` if the fill is `none` or a paint server.
+    pub(crate) fill_color: Option<Rgba>,
+    pub(crate) stroke_paint: bool,
+    /// The stroke color, `None` if the stroke is a paint server.
+    pub(crate) stroke_color: Option<Rgba>,
+    pub(crate) stroke: StrokeStyle,
+    /// Elements in an empty viewbox, a mask or a marker are not rendered,
+    /// elements under an animated transform are skipped.
+    pub(crate) disabled: bool,
+}
+
+/// Tracks the inherited states while walking the ir codes.
+pub(crate) struct Walker {
+    current: State,
+    stack: Vec<State>,
+}
+
+impl Walker {
+    pub(crate) fn new(ctx: ResolveContext) -> Self {
+        Self {
+            current: State {
+                ctx,
+                matrix: Matrix::identity(),
+                fill_rule: FillRule::Nonzero,
+                // the initial value of `fill` is black.
+                fill_paint: true,
+                fill_color: Some(Rgba(0.0, 0.0, 0.0, 1.0)),
+                stroke_paint: false,
+                stroke_color: None,
+                stroke: StrokeStyle::default(),
+                disabled: false,
+            },
+            stack: vec![],
+        }
+    }
+
+    pub(crate) fn state(&self) -> &State {
+        &self.current
+    }
+
+    /// Update the states by `ir`, returns the resolved bounds in the root user space if `ir` is a drawn element.
+    pub(crate) fn next(&mut self, ir: &IR, options: &BoundsOptions) -> Option<Rect> {
+        if let IR::Pop(n) = ir {
+            for _ in 0..*n {
+                match self.stack.pop() {
+                    Some(state) => self.current = state,
+                    None => break,
+                }
+            }
+
+            return None;
+        }
+
+        let bounds = match ir {
+            IR::Rect(rect) if !self.current.disabled => self.rect(rect, options),
+            IR::Path(path) if !self.current.disabled => self.path(path, options),
+            IR::Image(image) if !self.current.disabled => self.image(image),
+            _ => None,
+        };
+
+        if ir.is_scoped() {
+            self.stack.push(self.current.clone());
+
+            match ir {
+                IR::Layer(layer) => self.layer(layer),
+                IR::Fill(fill) => self.fill(fill),
+                IR::Stroke(stroke) => self.stroke(stroke),
+                IR::Transform(transform) => self.transform(transform),
+                // the mask, marker, symbol and filter contents are never rendered directly.
+                IR::Mask(_) | IR::Marker(_) | IR::Symbol(_) | IR::Filter(_) => {
+                    self.current.disabled = true
+                }
+                IR::Font(font) => {
+                    if let Some(size) = font.size.as_ref().and_then(constant) {
+                        self.current.ctx.font_size = self.current.ctx.length(*size, Axis::Other);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        bounds
+    }
+
+    /// Walks the contents of the symbol instanced by `value`, calls `f` with the walker of the contents before
+    /// every ir code. Returns the bounds of the contents in the root user space.
+    ///
+    /// The contents inherit the states of the `use` element, placed at its `x` and `y`, and the symbol viewbox
+    /// is stretched to its `width` and `height`. Nested instances are walked too, except the recursive ones.
+    pub(crate) fn instance<'a, F>(
+        &self,
+        value: &'a Use,
+        symbols: &Symbols<'a>,
+        options: &BoundsOptions,
+        f: &mut F,
+    ) -> Option<Rect>
+    where
+        F: FnMut(&Walker, &IR),
+    {
+        self.instance_in(value, symbols, options, &mut vec![], f)
+    }
+
+    fn instance_in<'a, F>(
+        &self,
+        value: &'a Use,
+        symbols: &Symbols<'a>,
+        options: &BoundsOptions,
+        instances: &mut Vec<&'a str>,
+        f: &mut F,
+    ) -> Option<Rect>
+    where
+        F: FnMut(&Walker, &IR),
+    {
+        if self.current.disabled || instances.contains(&value.href.as_str()) {
+            return None;
+        }
+
+        let (symbol, contents) = symbols.get(&value.href)?;
+        let mut walker = self.instance_walker(value, symbol)?;
+
+        instances.push(&value.href);
+
+        let mut bounds: Option<Rect> = None;
+
+        for ir in contents {
+            f(&walker, ir);
+
+            let rect = match ir {
+                IR::Use(value) => walker.instance_in(value, symbols, options, instances, f),
+                ir => walker.next(ir, options),
+            };
+
+            if let Some(rect) = rect {
+                bounds = Some(match bounds {
+                    Some(bounds) => bounds.union(&rect),
+                    None => rect,
+                });
+            }
+        }
+
+        instances.pop();
+
+        bounds
+    }
+
+    /// Returns the walker of the contents of `symbol` instanced by `value`.
+    fn instance_walker(&self, value: &Use, symbol: &SymbolDef) -> Option<Walker> {
+        let ctx = self.current.ctx;
+
+        let x = ctx.animatable_length(&value.x, Axis::Horizontal)?;
+        let y = ctx.animatable_length(&value.y, Axis::Vertical)?;
+
+        let mut current = self.current.clone();
+        current.matrix = current.matrix.multiply(&Matrix::translate(x, y));
+
+        if let Some(viewbox) = &symbol.viewbox {
+            let (viewbox, aspect) = ctx.viewbox(constant(viewbox)?)?;
+
+            // the viewport defaults to `100%`.
+            let width = match &value.width {
+                Some(width) => ctx.animatable_length(width, Axis::Horizontal)?,
+                None => ctx.viewport_width,
+            };
+
+            let height = match &value.height {
+                Some(height) => ctx.animatable_length(height, Axis::Vertical)?,
+                None => ctx.viewport_height,
+            };
+
+            current.matrix = current
+                .matrix
+                .multiply(&viewbox_matrix(&viewbox, aspect, width, height)?);
+            current.ctx.viewport_width = viewbox.width;
+            current.ctx.viewport_height = viewbox.height;
+        }
+
+        Some(Walker {
+            current,
+            stack: vec![],
+        })
+    }
+
+    /// Returns the resolved rectangle in the current user space.
+    pub(crate) fn resolve_rect(&self, rect: &crate::Rect) -> Option<Rect> {
+        let ctx = &self.current.ctx;
+
+        let rect = Rect::new(
+            ctx.animatable_length(&rect.x, Axis::Horizontal)?,
+            ctx.animatable_length(&rect.y, Axis::Vertical)?,
+            ctx.animatable_length(&rect.width, Axis::Horizontal)?,
+            ctx.animatable_length(&rect.height, Axis::Vertical)?,
+        );
+
+        // a value of zero disables the rendering of the element.
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return None;
+        }
+
+        Some(rect)
+    }
+
+    /// Returns the resolved outline of the rectangle in the current user space.
+    pub(crate) fn rect_outline(&self, rect: &crate::Rect) -> Option<SubPath> {
+        let ctx = &self.current.ctx;
+
+        let bounds = self.resolve_rect(rect)?;
+
+        let rx = ctx.animatable_length(&rect.rx, Axis::Horizontal)?;
+
+        // `ry` defaults to `rx` if it is not specified.
+        let ry = match &rect.ry {
+            Some(ry) => ctx.animatable_length(ry, Axis::Vertical)?,
+            None => rx,
+        };
+
+        Some(SubPath::rect(&bounds, rx, ry))
+    }
+
+    fn rect(&self, rect: &crate::Rect, options: &BoundsOptions) -> Option<Rect> {
+        let mut rect = self.resolve_rect(rect)?;
+
+        if options.stroke && self.current.stroke_paint {
+            rect = rect.inflate(self.current.stroke.width / 2.0);
+        }
+
+        Some(self.current.matrix.transform_rect(&rect))
+    }
+
+    /// Returns the resolved sub-paths of the path in the current user space.
+    pub(crate) fn path_outline(&self, path: &crate::Path) -> Option<Vec<SubPath>> {
+        self.current.ctx.path(path)
+    }
+
+    fn path(&self, path: &crate::Path, options: &BoundsOptions) -> Option<Rect> {
+        let subpaths = self.path_outline(path)?;
+
+        let mut rect = Rect::from_points(
+            subpaths
+                .iter()
+                .flat_map(|subpath| subpath.flatten(DEFAULT_TOLERANCE).points),
+        )?;
+
+        if options.stroke && self.current.stroke_paint {
+            rect = rect.inflate(self.current.stroke.width / 2.0);
+        }
+
+        Some(self.current.matrix.transform_rect(&rect))
+    }
+
+    /// Images are never stroked, the bounds are the placement rectangle whatever the aspect ratio is.
+    fn image(&self, image: &crate::Image) -> Option<Rect> {
+        let ctx = &self.current.ctx;
+
+        let rect = Rect::new(
+            ctx.animatable_length(&image.x, Axis::Horizontal)?,
+            ctx.animatable_length(&image.y, Axis::Vertical)?,
+            ctx.animatable_length(&image.width, Axis::Horizontal)?,
+            ctx.animatable_length(&image.height, Axis::Vertical)?,
+        );
+
+        // a value of zero disables the rendering of the element.
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return None;
+        }
+
+        Some(self.current.matrix.transform_rect(&rect))
+    }
+
+    fn layer(&mut self, layer: &Layer) {
+        let ctx = self.current.ctx;
+
+        let (Some(width), Some(height)) = (
+            ctx.animatable_length(&layer.width, Axis::Horizontal),
+            ctx.animatable_length(&layer.height, Axis::Vertical),
+        ) else {
+            return;
+        };
+
+        self.current.ctx.viewport_width = width;
+        self.current.ctx.viewport_height = height;
+
+        let Some((viewbox, aspect)) = layer
+            .viewbox
+            .as_ref()
+            .and_then(constant)
+            .and_then(|viewbox| ctx.viewbox(viewbox))
+        else {
+            return;
+        };
+
+        match viewbox_matrix(&viewbox, aspect, width, height) {
+            Some(matrix) => {
+                self.current.matrix = self.current.matrix.multiply(&matrix);
+                self.current.ctx.viewport_width = viewbox.width;
+                self.current.ctx.viewport_height = viewbox.height;
+            }
+            None => self.current.disabled = true,
+        }
+    }
+
+    fn transform(&mut self, transform: &Animatable<Transform>) {
+        match constant(transform) {
+            Some(transform) => {
+                self.current.matrix = self.current.matrix.multiply(&Matrix::from(*transform));
+            }
+            // the elements under an animated transform can't be located.
+            None => self.current.disabled = true,
+        }
+    }
+
+    fn fill(&mut self, fill: &Fill) {
+        // a fill without paint is rendered as `fill="none"`.
+        match &fill.paint {
+            Some(paint) => {
+                self.current.fill_paint = true;
+
+                if let Some(paint) = constant(paint) {
+                    self.current.fill_color = color(paint);
+                }
+            }
+            None => {
+                self.current.fill_paint = false;
+                self.current.fill_color = None;
+            }
+        }
+
+        if let Some(rule) = fill.rule.as_ref().and_then(constant) {
+            self.current.fill_rule = *rule;
+        }
+    }
+
+    fn stroke(&mut self, stroke: &Stroke) {
+        if let Some(paint) = &stroke.paint {
+            self.current.stroke_paint = true;
+
+            if let Some(paint) = constant(paint) {
+                self.current.stroke_color = color(paint);
+            }
+        }
+
+        // animated properties keep the inherited values.
+        _ = self.current.stroke.inherit(stroke, &self.current.ctx);
+    }
+}
+
+fn color(paint: &Paint) -> Option<Rgba> {
+    match paint {
+        Paint::Color(rgba) => Some(*rgba),
+        Paint::Gradient(_) | Paint::Pattern(_) => None,
+    }
+}
@@ -0,0 +1,253 @@
+//! Clipping of geometry to clip paths on the cpu, for devices without native clipping support.
+
+use crate::{
+    geometry::{line_intersections, winding_number, Point, Polyline, SubPath},
+    FillRule,
+};
+
+/// Returns true if `point` is in the interior of `polylines` under the fill `rule`.
+fn inside(polylines: &[Polyline], point: Point, rule: FillRule) -> bool {
+    let winding = winding_number(polylines, point);
+
+    match rule {
+        FillRule::Nonzero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// Returns the edges of `polylines`, without the zero-length ones.
+fn edges(polylines: &[Polyline]) -> Vec<(Point, Point)> {
+    polylines
+        .iter()
+        .flat_map(|polyline| polyline.edges())
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+/// Splits `edges` at the intersections with `others`.
+fn split(edges: &[(Point, Point)], others: &[(Point, Point)]) -> Vec<(Point, Point)> {
+    let mut pieces = vec![];
+
+    for (from, to) in edges {
+        let direction = *to - *from;
+        let length = direction.dot(direction);
+
+        let mut ts = vec![0.0, 1.0];
+
+        for (p, q) in others {
+            let mut points = vec![];
+            line_intersections(*from, *to, *p, *q, &mut points);
+
+            ts.extend(
+                points
+                    .into_iter()
+                    .map(|point| ((point - *from).dot(direction) / length).clamp(0.0, 1.0)),
+            );
+        }
+
+        ts.sort_by(f32::total_cmp);
+        ts.dedup();
+
+        // the end points are kept exact, so the pieces of adjacent edges stay connected.
+        let point = |t: f32| match t {
+            0.0 => *from,
+            1.0 => *to,
+            t => from.lerp(*to, t),
+        };
+
+        pieces.extend(
+            ts.windows(2)
+                .map(|t| (point(t[0]), point(t[1])))
+                .filter(|(from, to)| from != to),
+        );
+    }
+
+    pieces
+}
+
+/// Returns the distance from `point` to the line segment from `from` to `to`.
+fn distance_to_edge(point: Point, from: Point, to: Point) -> f32 {
+    let direction = to - from;
+    let length = direction.dot(direction);
+
+    let t = if length > 0.0 {
+        ((point - from).dot(direction) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    point.distance(from.lerp(to, t))
+}
+
+/// Connects the edges into closed polylines, edges that don't form a loop are dropped.
+fn stitch(mut edges: Vec<(Point, Point)>, tolerance: f32) -> Vec<Polyline> {
+    let mut polylines = vec![];
+
+    while let Some((start, mut current)) = edges.pop() {
+        let mut points = vec![start];
+
+        loop {
+            if current.distance(start) <= tolerance {
+                break;
+            }
+
+            let next = edges
+                .iter()
+                .enumerate()
+                .map(|(index, (from, _))| (index, from.distance(current)))
+                .filter(|(_, distance)| *distance <= tolerance)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(index, _)| index);
+
+            let Some(next) = next else {
+                points.clear();
+                break;
+            };
+
+            points.push(current);
+            current = edges.swap_remove(next).1;
+        }
+
+        if points.len() > 2 {
+            polylines.push(Polyline {
+                points,
+                closed: true,
+            });
+        }
+    }
+
+    polylines
+}
+
+/// Returns the intersection of the area of `subject` filled with `subject_rule` and the area of `clip`
+/// filled with `clip_rule`, the curves are flattened with `tolerance`.
+///
+/// All sub-paths are implicitly closed. The result outlines go clockwise around the filled area and
+/// counterclockwise around the holes in the y-down user space, so they can be filled with either rule.
+pub fn clip_fill(
+    subject: &[SubPath],
+    subject_rule: FillRule,
+    clip: &[SubPath],
+    clip_rule: FillRule,
+    tolerance: f32,
+) -> Vec<Polyline> {
+    let subject = subject
+        .iter()
+        .map(|subpath| subpath.flatten(tolerance))
+        .collect::<Vec<_>>();
+
+    let clip = clip
+        .iter()
+        .map(|subpath| subpath.flatten(tolerance))
+        .collect::<Vec<_>>();
+
+    let (subject_edges, clip_edges) = (edges(&subject), edges(&clip));
+
+    // the offset of the sample points from the edges.
+    let epsilon = tolerance / 10.0;
+
+    let filled =
+        |point: Point| inside(&subject, point, subject_rule) && inside(&clip, point, clip_rule);
+
+    let mut boundary = vec![];
+
+    let pieces = split(&subject_edges, &clip_edges).into_iter().chain(
+        // the clip edges on the subject outline are already covered by the subject edges.
+        split(&clip_edges, &subject_edges)
+            .into_iter()
+            .filter(|(from, to)| {
+                let middle = from.lerp(*to, 0.5);
+
+                subject_edges
+                    .iter()
+                    .all(|(p, q)| distance_to_edge(middle, *p, *q) > epsilon)
+            }),
+    );
+
+    for (from, to) in pieces {
+        let middle = from.lerp(to, 0.5);
+        let direction = (to - from).normalize();
+
+        // the right side of the edge in the y-down user space.
+        let right = Point::new(-direction.y, direction.x) * epsilon;
+
+        match (filled(middle - right), filled(middle + right)) {
+            (false, true) => boundary.push((from, to)),
+            (true, false) => boundary.push((to, from)),
+            _ => {}
+        }
+    }
+
+    stitch(boundary, epsilon * 2.0)
+}
+
+/// Returns the parts of the outlines of `subject` in the area of `clip` filled with `clip_rule`,
+/// the curves are flattened with `tolerance`.
+///
+/// This clips the centerlines of strokes, the closed sub-paths are cut open at the clip boundary.
+pub fn clip_stroke(
+    subject: &[SubPath],
+    clip: &[SubPath],
+    clip_rule: FillRule,
+    tolerance: f32,
+) -> Vec<Polyline> {
+    let clip = clip
+        .iter()
+        .map(|subpath| subpath.flatten(tolerance))
+        .collect::<Vec<_>>();
+
+    let clip_edges = edges(&clip);
+
+    let mut polylines = vec![];
+
+    for subpath in subject {
+        let polyline = subpath.flatten(tolerance);
+
+        let mut runs: Vec<Vec<Point>> = vec![];
+        let mut cut = false;
+        let mut last: Option<Point> = None;
+
+        for (from, to) in split(&edges(std::slice::from_ref(&polyline)), &clip_edges) {
+            if !inside(&clip, from.lerp(to, 0.5), clip_rule) {
+                cut = true;
+                last = None;
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some(run) if last == Some(from) => run.push(to),
+                _ => runs.push(vec![from, to]),
+            }
+
+            last = Some(to);
+        }
+
+        if polyline.closed && !cut {
+            if let Some(mut points) = runs.pop() {
+                // the closed outline is not cut at all.
+                points.pop();
+
+                polylines.push(Polyline {
+                    points,
+                    closed: true,
+                });
+            }
+
+            continue;
+        }
+
+        // the run crossing the start point of a closed outline is split in two.
+        if polyline.closed && runs.len() > 1 && runs[runs.len() - 1].last() == runs[0].first() {
+            let mut tail = runs.pop().expect("more than one run");
+            tail.extend(runs[0].drain(..).skip(1));
+            runs[0] = tail;
+        }
+
+        polylines.extend(runs.into_iter().map(|points| Polyline {
+            points,
+            closed: false,
+        }));
+    }
+
+    polylines
+}
@@ -1,8 +1,14 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::str::FromStr;
+
+use crate::Error;
+
 use super::FrameVariable;
 
 /// A color structure repesents as RGBA, the storage value is normalized.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Rgba(pub f32, pub f32, pub f32, pub f32);
 
 /// Rgba can be used as context variant type.
@@ -83,6 +89,7 @@ impl Rgba {
 #[allow(non_camel_case_types)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Color {
     aliceblue,
     antiquewhite,
@@ -386,3 +393,217 @@ impl From<Color> for Rgba {
         }
     }
 }
+
+impl FromStr for Color {
+    type Err = Error;
+
+    /// Parse a color keyword name, the match is case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "aliceblue" => Ok(Color::aliceblue),
+            "antiquewhite" => Ok(Color::antiquewhite),
+            "aqua" => Ok(Color::aqua),
+            "aquamarine" => Ok(Color::aquamarine),
+            "azure" => Ok(Color::azure),
+            "beige" => Ok(Color::beige),
+            "bisque" => Ok(Color::bisque),
+            "black" => Ok(Color::black),
+            "blanchedalmond" => Ok(Color::blanchedalmond),
+            "blue" => Ok(Color::blue),
+            "blueviolet" => Ok(Color::blueviolet),
+            "brown" => Ok(Color::brown),
+            "burlywood" => Ok(Color::burlywood),
+            "cadetblue" => Ok(Color::cadetblue),
+            "chartreuse" => Ok(Color::chartreuse),
+            "chocolate" => Ok(Color::chocolate),
+            "coral" => Ok(Color::coral),
+            "cornflowerblue" => Ok(Color::cornflowerblue),
+            "cornsilk" => Ok(Color::cornsilk),
+            "crimson" => Ok(Color::crimson),
+            "cyan" => Ok(Color::cyan),
+            "darkblue" => Ok(Color::darkblue),
+            "darkcyan" => Ok(Color::darkcyan),
+            "darkgoldenrod" => Ok(Color::darkgoldenrod),
+            "darkgray" => Ok(Color::darkgray),
+            "darkgreen" => Ok(Color::darkgreen),
+            "darkgrey" => Ok(Color::darkgrey),
+            "darkkhaki" => Ok(Color::darkkhaki),
+            "darkmagenta" => Ok(Color::darkmagenta),
+            "darkolivegreen" => Ok(Color::darkolivegreen),
+            "darkorange" => Ok(Color::darkorange),
+            "darkorchid" => Ok(Color::darkorchid),
+            "darkred" => Ok(Color::darkred),
+            "darksalmon" => Ok(Color::darksalmon),
+            "darkseagreen" => Ok(Color::darkseagreen),
+            "darkslateblue" => Ok(Color::darkslateblue),
+            "darkslategray" => Ok(Color::darkslategray),
+            "darkslategrey" => Ok(Color::darkslategrey),
+            "darkturquoise" => Ok(Color::darkturquoise),
+            "darkviolet" => Ok(Color::darkviolet),
+            "deeppink" => Ok(Color::deeppink),
+            "deepskyblue" => Ok(Color::deepskyblue),
+            "dimgray" => Ok(Color::dimgray),
+            "dimgrey" => Ok(Color::dimgrey),
+            "dodgerblue" => Ok(Color::dodgerblue),
+            "firebrick" => Ok(Color::firebrick),
+            "floralwhite" => Ok(Color::floralwhite),
+            "forestgreen" => Ok(Color::forestgreen),
+            "fuchsia" => Ok(Color::fuchsia),
+            "gainsboro" => Ok(Color::gainsboro),
+            "ghostwhite" => Ok(Color::ghostwhite),
+            "gold" => Ok(Color::gold),
+            "goldenrod" => Ok(Color::goldenrod),
+            "gray" => Ok(Color::gray),
+            "grey" => Ok(Color::grey),
+            "green" => Ok(Color::green),
+            "greenyellow" => Ok(Color::greenyellow),
+            "honeydew" => Ok(Color::honeydew),
+            "hotpink" => Ok(Color::hotpink),
+            "indianred" => Ok(Color::indianred),
+            "indigo" => Ok(Color::indigo),
+            "ivory" => Ok(Color::ivory),
+            "khaki" => Ok(Color::khaki),
+            "lavender" => Ok(Color::lavender),
+            "lavenderblush" => Ok(Color::lavenderblush),
+            "lawngreen" => Ok(Color::lawngreen),
+            "lemonchiffon" => Ok(Color::lemonchiffon),
+            "lightblue" => Ok(Color::lightblue),
+            "lightcoral" => Ok(Color::lightcoral),
+            "lightcyan" => Ok(Color::lightcyan),
+            "lightgoldenrodyellow" => Ok(Color::lightgoldenrodyellow),
+            "lightgray" => Ok(Color::lightgray),
+            "lightgreen" => Ok(Color::lightgreen),
+            "lightgrey" => Ok(Color::lightgrey),
+            "lightpink" => Ok(Color::lightpink),
+            "lightsalmon" => Ok(Color::lightsalmon),
+            "lightseagreen" => Ok(Color::lightseagreen),
+            "lightskyblue" => Ok(Color::lightskyblue),
+            "lightslategray" => Ok(Color::lightslategray),
+            "lightslategrey" => Ok(Color::lightslategrey),
+            "lightsteelblue" => Ok(Color::lightsteelblue),
+            "lightyellow" => Ok(Color::lightyellow),
+            "lime" => Ok(Color::lime),
+            "limegreen" => Ok(Color::limegreen),
+            "linen" => Ok(Color::linen),
+            "magenta" => Ok(Color::magenta),
+            "maroon" => Ok(Color::maroon),
+            "mediumaquamarine" => Ok(Color::mediumaquamarine),
+            "mediumblue" => Ok(Color::mediumblue),
+            "mediumorchid" => Ok(Color::mediumorchid),
+            "mediumpurple" => Ok(Color::mediumpurple),
+            "mediumseagreen" => Ok(Color::mediumseagreen),
+            "mediumslateblue" => Ok(Color::mediumslateblue),
+            "mediumspringgreen" => Ok(Color::mediumspringgreen),
+            "mediumturquoise" => Ok(Color::mediumturquoise),
+            "mediumvioletred" => Ok(Color::mediumvioletred),
+            "midnightblue" => Ok(Color::midnightblue),
+            "mintcream" => Ok(Color::mintcream),
+            "mistyrose" => Ok(Color::mistyrose),
+            "moccasin" => Ok(Color::moccasin),
+            "navajowhite" => Ok(Color::navajowhite),
+            "navy" => Ok(Color::navy),
+            "oldlace" => Ok(Color::oldlace),
+            "olive" => Ok(Color::olive),
+            "olivedrab" => Ok(Color::olivedrab),
+            "orange" => Ok(Color::orange),
+            "orangered" => Ok(Color::orangered),
+            "orchid" => Ok(Color::orchid),
+            "palegoldenrod" => Ok(Color::palegoldenrod),
+            "palegreen" => Ok(Color::palegreen),
+            "paleturquoise" => Ok(Color::paleturquoise),
+            "palevioletred" => Ok(Color::palevioletred),
+            "papayawhip" => Ok(Color::papayawhip),
+            "peachpuff" => Ok(Color::peachpuff),
+            "peru" => Ok(Color::peru),
+            "pink" => Ok(Color::pink),
+            "plum" => Ok(Color::plum),
+            "powderblue" => Ok(Color::powderblue),
+            "purple" => Ok(Color::purple),
+            "red" => Ok(Color::red),
+            "rosybrown" => Ok(Color::rosybrown),
+            "royalblue" => Ok(Color::royalblue),
+            "saddlebrown" => Ok(Color::saddlebrown),
+            "salmon" => Ok(Color::salmon),
+            "sandybrown" => Ok(Color::sandybrown),
+            "seagreen" => Ok(Color::seagreen),
+            "seashell" => Ok(Color::seashell),
+            "sienna" => Ok(Color::sienna),
+            "silver" => Ok(Color::silver),
+            "skyblue" => Ok(Color::skyblue),
+            "slateblue" => Ok(Color::slateblue),
+            "slategray" => Ok(Color::slategray),
+            "slategrey" => Ok(Color::slategrey),
+            "snow" => Ok(Color::snow),
+            "springgreen" => Ok(Color::springgreen),
+            "steelblue" => Ok(Color::steelblue),
+            "tan" => Ok(Color::tan),
+            "teal" => Ok(Color::teal),
+            "thistle" => Ok(Color::thistle),
+            "tomato" => Ok(Color::tomato),
+            "turquoise" => Ok(Color::turquoise),
+            "violet" => Ok(Color::violet),
+            "wheat" => Ok(Color::wheat),
+            "white" => Ok(Color::white),
+            "whitesmoke" => Ok(Color::whitesmoke),
+            "yellow" => Ok(Color::yellow),
+            "yellowgreen" => Ok(Color::yellowgreen),
+            _ => Err(Error::UnrecognizedColor(s.to_owned())),
+        }
+    }
+}
+
+impl FromStr for Rgba {
+    type Err = Error;
+
+    /// Parse a svg 1.1 color value: `#rgb`, `#rrggbb`, `rgb(r,g,b)`, `rgb(r%,g%,b%)` or a color keyword name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+
+        let unrecognized = || Error::UnrecognizedColor(s.to_owned());
+
+        if let Some(hex) = value.strip_prefix('#') {
+            if !hex.is_ascii() {
+                return Err(unrecognized());
+            }
+
+            let hex = match hex.len() {
+                3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+                6 => hex.to_owned(),
+                _ => return Err(unrecognized()),
+            };
+
+            return u32::from_str_radix(&hex, 16)
+                .map(Rgba::from)
+                .map_err(|_| unrecognized());
+        }
+
+        if let Some(args) = value
+            .strip_prefix("rgb(")
+            .and_then(|value| value.strip_suffix(')'))
+        {
+            let components = args
+                .split(',')
+                .map(|v| {
+                    let v = v.trim();
+
+                    let value = if let Some(v) = v.strip_suffix('%') {
+                        v.trim().parse::<f32>().ok()? / 100.0
+                    } else {
+                        v.parse::<f32>().ok()? / 255.0
+                    };
+
+                    Some(value.clamp(0.0, 1.0))
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(unrecognized)?;
+
+            if let [r, g, b] = components.as_slice() {
+                return Ok(Rgba::rgbf(*r, *g, *b));
+            }
+
+            return Err(unrecognized());
+        }
+
+        value.parse::<Color>().map(Rgba::from)
+    }
+}
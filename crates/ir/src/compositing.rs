@@ -1,3 +1,4 @@
+use alloc::string::String;
 use vglang_derive::Dsl;
 
 use super::{Animatable, FrameVariable, Measurement, Rect, Units};
@@ -7,6 +8,7 @@ use super::{Animatable, FrameVariable, Measurement, Rect, Units};
 /// See [`overflow`](https://www.w3.org/TR/SVG11/masking.html#OverflowAndClipProperties)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Overflow {
     Visible,
     Hidden,
@@ -21,6 +23,7 @@ impl FrameVariable for Overflow {}
 /// The value of auto defines a clipping path along the bounds of the viewport created by the given element.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Clip {
     Shape(Rect),
     Auto,
@@ -38,6 +41,7 @@ impl FrameVariable for Clip {}
 /// A clipping path is used/referenced using the ‘clip-path’ property.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClipPath(
     /// Defines the coordinate system for the contents of the ‘clipPath’.
     ///
@@ -62,15 +66,45 @@ impl Default for ClipPath {
 /// The  property only applies to graphics elements that are contained within a [`ClipPath`] element.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ClipRule {
     Nonzero,
     EvenOdd,
 }
 
+/// Indicates which values of the mask contents are used as the mask values.
+///
+/// See [`mask-type`](https://www.w3.org/TR/css-masking-1/#the-mask-type)
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum MaskType {
+    /// The luminance values of the mask contents, multiplied by their alpha values.
+    #[default]
+    Luminance,
+    /// The alpha values of the mask contents.
+    Alpha,
+}
+
+impl FrameVariable for MaskType {}
+
 /// used as an alpha mask for compositing the current object into the background.
+///
+/// A mask is a scoped element, the elements in its scope are the mask contents, which are never rendered directly.
+/// Graphic elements reference a mask by id with [`MaskRef`].
 #[derive(Dsl, Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Mask {
+    /// The unique name of this mask, referenced by [`MaskRef`].
+    #[dsl(required)]
+    pub id: String,
+
+    /// Whether the luminance or the alpha values of the mask contents are used as the mask values.
+    ///
+    /// If attribute ‘mask-type’ is not specified, then the effect is as if a value of 'luminance' were specified.
+    pub mode: Animatable<MaskType>,
+
     /// Defines the coordinate system for attributes ‘x’, ‘y’, ‘width’ and ‘height’.
     ///
     /// If maskUnits="userSpaceOnUse", ‘x’, ‘y’, ‘width’ and ‘height’ represent values in the current user coordinate system
@@ -127,6 +161,8 @@ pub struct Mask {
 impl Default for Mask {
     fn default() -> Self {
         Self {
+            id: String::new(),
+            mode: MaskType::Luminance.into(),
             units: Units::ObjectBoundingBox.into(),
             content_units: Units::UserSpaceOnUse.into(),
             x: Measurement::percentage(-10.0).into(),
@@ -137,6 +173,20 @@ impl Default for Mask {
     }
 }
 
+/// Applies the mask with the id to the elements in scope, the same as the svg `mask` property.
+///
+/// Like other scoped instructions, a mask reference must be closed by a paired `pop` instruction.
+#[derive(Dsl, Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MaskRef(pub String);
+
+impl From<&str> for MaskRef {
+    fn from(value: &str) -> Self {
+        Self(value.into())
+    }
+}
+
 /// Except for object/group opacity (described just below), all other opacity properties are involved in intermediate
 /// rendering operations. Object/group opacity can be thought of conceptually as a postprocessing operation. Conceptually,
 /// after the object/group is rendered into an RGBA offscreen image, the object/group opacity setting specifies how
@@ -145,6 +195,7 @@ impl Default for Mask {
 /// See [`opacity`](https://www.w3.org/TR/SVG11/masking.html#OpacityProperty)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Opacity(f32);
 
 impl Default for Opacity {
@@ -0,0 +1,89 @@
+//! Dash pattern expansion, for backends without native dash support.
+
+use crate::{geometry::SubPath, Path, PathMeasure, ResolveContext, DEFAULT_TOLERANCE};
+
+/// Returns the distance intervals of the dashes on a sub-path with `length`,
+/// or `None` if the dash pattern is not valid and the sub-path is drawn as a solid line.
+fn intervals(length: f32, dasharray: &[f32], dashoffset: f32) -> Option<Vec<(f32, f32)>> {
+    let total: f32 = dasharray.iter().sum();
+
+    // negative values are an error, and a zero sum pattern draws a solid line.
+    if dasharray.iter().any(|v| v.is_nan() || *v < 0.0) || total.is_nan() || total <= 0.0 {
+        return None;
+    }
+
+    let mut pattern = dasharray.to_vec();
+
+    // an odd number of values is repeated to yield an even number of values.
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+
+    let total = total * (pattern.len() / dasharray.len()) as f32;
+
+    // find the dash or gap at the start of the sub-path.
+    let mut index = 0;
+    let mut offset = dashoffset.rem_euclid(total);
+
+    while offset >= pattern[index] {
+        offset -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+
+    let mut intervals = vec![];
+    let mut position = -offset;
+
+    while position < length {
+        let end = position + pattern[index];
+
+        if index.is_multiple_of(2) && end >= 0.0 {
+            intervals.push((position.max(0.0), end.min(length)));
+        }
+
+        position = end;
+        index = (index + 1) % pattern.len();
+    }
+
+    Some(intervals)
+}
+
+/// Splits `subpaths` into dashes, `dasharray` contains the lengths of alternating dashes and gaps, which
+/// start at the distance `dashoffset` into the pattern.
+///
+/// Every sub-path restarts the dash pattern, and the dashes are open sub-paths; the curves are split exactly.
+/// Returns `subpaths` unchanged if the dash pattern is empty, sums to zero or contains negative values.
+pub fn dash(subpaths: &[SubPath], dasharray: &[f32], dashoffset: f32) -> Vec<SubPath> {
+    let mut dashes = vec![];
+
+    for subpath in subpaths {
+        let measure = PathMeasure::new(std::slice::from_ref(subpath), DEFAULT_TOLERANCE);
+
+        let Some(intervals) = intervals(measure.length(), dasharray, dashoffset) else {
+            dashes.push(subpath.clone());
+            continue;
+        };
+
+        for (start, end) in intervals {
+            dashes.push(SubPath {
+                segments: measure.extract(start, end),
+                closed: false,
+            });
+        }
+    }
+
+    dashes
+}
+
+impl Path {
+    /// Splits this path into dashes, see [`dash`] for more information.
+    ///
+    /// Returns `None` if the path data is animated.
+    pub fn dash(
+        &self,
+        ctx: &ResolveContext,
+        dasharray: &[f32],
+        dashoffset: f32,
+    ) -> Option<Vec<SubPath>> {
+        Some(dash(&ctx.path(self)?, dasharray, dashoffset))
+    }
+}
@@ -1,12 +1,14 @@
-use std::{f32::consts::PI, fmt::Display};
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+use core::{f32::consts::PI, fmt::Display, str::FromStr};
 
-use crate::{tuple_map_collect, MapCollect};
+use crate::{tuple_map_collect, Error, MapCollect};
 
 use super::{Animatable, FrameVariable};
 
 /// The unit identifier.
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Unit {
     /// The 'font-size' of the relevant font
     Em,
@@ -45,18 +47,39 @@ impl AsRef<str> for Unit {
 }
 
 impl Display for Unit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.as_ref())
     }
 }
 
+impl FromStr for Unit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "em" => Ok(Unit::Em),
+            "ex" => Ok(Unit::Ex),
+            "px" => Ok(Unit::Px),
+            "in" => Ok(Unit::In),
+            "cm" => Ok(Unit::Cm),
+            "mm" => Ok(Unit::Mm),
+            "pt" => Ok(Unit::Pt),
+            "pc" => Ok(Unit::Pc),
+            "%" => Ok(Unit::Percentages),
+            _ => Err(Error::UnrecognizedMeasurement(s.to_owned())),
+        }
+    }
+}
+
 /// measurement, given as a number along with a unit.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Measurement(pub f32, pub Option<Unit>);
 
 impl Display for Measurement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if let Some(unit) = self.1 {
             write!(f, "{}{}", self.0, unit)
         } else {
@@ -129,18 +152,200 @@ impl Measurement {
     }
 }
 
+impl FromStr for Measurement {
+    type Err = Error;
+
+    /// Parse a number followed by an optional unit identifier, e.g. `12`, `1.5em` or `50%`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unrecognized = || Error::UnrecognizedMeasurement(s.to_owned());
+
+        let (value, unit) = split_number(s.trim()).ok_or_else(unrecognized)?;
+
+        if unit.is_empty() {
+            return Ok(Self(value, None));
+        }
+
+        let unit = unit.parse::<Unit>().map_err(|_| unrecognized())?;
+
+        Ok(Self(value, Some(unit)))
+    }
+}
+
+/// Split the leading number off `s`, returns the number and the remaining string.
+///
+/// The exponent part is only consumed if it's followed by digits, so `1em` is parsed as `1` and `em`.
+pub(crate) fn split_number(s: &str) -> Option<(f32, &str)> {
+    let bytes = s.as_bytes();
+
+    let digits = |mut offset: usize| {
+        while offset < bytes.len() && bytes[offset].is_ascii_digit() {
+            offset += 1;
+        }
+
+        offset
+    };
+
+    let mut end = 0;
+
+    if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+        end += 1;
+    }
+
+    let integer = digits(end);
+    let mut fraction = integer;
+
+    if fraction < bytes.len() && bytes[fraction] == b'.' {
+        fraction = digits(fraction + 1);
+
+        if fraction == integer + 1 && integer == end {
+            return None;
+        }
+    } else if integer == end {
+        return None;
+    }
+
+    end = fraction;
+
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        let mut offset = end + 1;
+
+        if offset < bytes.len() && (bytes[offset] == b'+' || bytes[offset] == b'-') {
+            offset += 1;
+        }
+
+        let exponent = digits(offset);
+
+        if exponent > offset {
+            end = exponent;
+        }
+    }
+
+    let value = s[..end].parse::<f32>().ok()?;
+
+    Some((value, &s[end..]))
+}
+
+/// Parse a comma/space separated list of lengths, e.g. `10 5%, 1.5em`.
+pub fn parse_length_list(s: &str) -> Result<Vec<Measurement>, Error> {
+    let unrecognized = || Error::UnrecognizedMeasurement(s.to_owned());
+
+    let mut values = vec![];
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let (value, tail) = split_number(rest).ok_or_else(unrecognized)?;
+
+        let unit_len = tail
+            .find(|c: char| !(c.is_ascii_alphabetic() || c == '%'))
+            .unwrap_or(tail.len());
+
+        let unit = match &tail[..unit_len] {
+            "" => None,
+            unit => Some(unit.parse::<Unit>().map_err(|_| unrecognized())?),
+        };
+
+        values.push(Measurement(value, unit));
+
+        rest = skip_separator(&tail[unit_len..]).ok_or_else(unrecognized)?;
+    }
+
+    Ok(values)
+}
+
+/// Parse the value of the ‘stroke-dasharray’ property.
+///
+/// `none` is parsed as an empty list, a list with an odd number of values is repeated to yield an even number
+/// of values, as required by svg 1.1.
+pub fn parse_dasharray(s: &str) -> Result<Vec<Measurement>, Error> {
+    if s.trim() == "none" {
+        return Ok(vec![]);
+    }
+
+    let mut values = parse_length_list(s)?;
+
+    if values.iter().any(|v| v.0 < 0.0) {
+        return Err(Error::UnrecognizedMeasurement(s.to_owned()));
+    }
+
+    if values.len() % 2 == 1 {
+        values.extend_from_slice(&values.clone());
+    }
+
+    Ok(values)
+}
+
+/// Parse the value of the ‘points’ attribute of ‘polyline’ and ‘polygon’, e.g. `0,0 10,0 10-10`.
+pub fn parse_points(s: &str) -> Result<Vec<Point>, Error> {
+    let unrecognized = || Error::UnrecognizedPoints(s.to_owned());
+
+    let coordinates = split_numbers(s).ok_or_else(unrecognized)?;
+
+    if coordinates.len() % 2 == 1 {
+        return Err(unrecognized());
+    }
+
+    Ok(coordinates
+        .chunks(2)
+        .map(|v| Point {
+            x: v[0].into(),
+            y: v[1].into(),
+        })
+        .collect())
+}
+
+/// Split a comma/space separated list of plain numbers.
+fn split_numbers(s: &str) -> Option<Vec<f32>> {
+    let mut values = vec![];
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let (value, tail) = split_number(rest)?;
+
+        values.push(value);
+
+        rest = skip_separator(tail)?;
+    }
+
+    Some(values)
+}
+
+/// Skip the whitespaces and at most one comma between two list items.
+///
+/// Returns `None` if a comma is not followed by another item, or the items are not separated.
+fn skip_separator(s: &str) -> Option<&str> {
+    let trimmed = s.trim_start();
+
+    if let Some(rest) = trimmed.strip_prefix(',') {
+        let rest = rest.trim_start();
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        return Some(rest);
+    }
+
+    // a sign or a dot may start the next number without any separator(e.g. `10-5`).
+    if trimmed.len() == s.len() && !trimmed.is_empty() && !trimmed.starts_with(['+', '-', '.']) {
+        return None;
+    }
+
+    Some(trimmed)
+}
+
 /// see [`svg`] document for more information.
 ///
 /// [`svg`]: https://www.w3.org/TR/SVG11/coords.html#PreserveAspectRatioAttribute
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum MeetOrSlice {
     Meet,
     Slice,
 }
 
 impl Display for MeetOrSlice {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             MeetOrSlice::Meet => write!(f, "meet"),
             MeetOrSlice::Slice => write!(f, "slice"),
@@ -156,6 +361,7 @@ impl Display for MeetOrSlice {
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum PreserveAspectRatio {
     /// Force uniform scaling
     ///
@@ -213,12 +419,63 @@ impl Default for PreserveAspectRatio {
 /// PreserveAspectRatio can be used as context variant type.
 impl FrameVariable for PreserveAspectRatio {}
 
+impl FromStr for PreserveAspectRatio {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl PreserveAspectRatio {
+    /// Parse the value of the ‘preserveAspectRatio’ attribute, e.g. `xMidYMid slice`.
+    ///
+    /// The optional `defer` keyword is ignored, the `meet` is used if `<meetOrSlice>` is omitted.
+    ///
+    /// `none` is not accepted by this function, it's represented by a [`ViewBox`] without [`aspect`](ViewBox::aspect).
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let unrecognized = || Error::UnrecognizedAspectRatio(s.to_owned());
+
+        let mut tokens = s.split_whitespace().peekable();
+
+        if tokens.peek() == Some(&"defer") {
+            tokens.next();
+        }
+
+        let align = tokens.next().ok_or_else(unrecognized)?;
+
+        let meet_or_slice = match tokens.next() {
+            None | Some("meet") => MeetOrSlice::Meet,
+            Some("slice") => MeetOrSlice::Slice,
+            Some(_) => return Err(unrecognized()),
+        };
+
+        if tokens.next().is_some() {
+            return Err(unrecognized());
+        }
+
+        match align {
+            "xMinYMin" => Ok(Self::xMinYMin(meet_or_slice)),
+            "xMidYMin" => Ok(Self::xMidYMin(meet_or_slice)),
+            "xMaxYMin" => Ok(Self::xMaxYMin(meet_or_slice)),
+            "xMinYMid" => Ok(Self::xMinYMid(meet_or_slice)),
+            "xMidYMid" => Ok(Self::xMidYMid(meet_or_slice)),
+            "xMaxYMid" => Ok(Self::xMaxYMid(meet_or_slice)),
+            "xMinYMax" => Ok(Self::xMinYMax(meet_or_slice)),
+            "xMidYMax" => Ok(Self::xMidYMax(meet_or_slice)),
+            "xMaxYMax" => Ok(Self::xMaxYMax(meet_or_slice)),
+            _ => Err(unrecognized()),
+        }
+    }
+}
+
 /// Angles are specified in one of two ways depending upon
 /// whether they are used in CSS property syntax or SVG
 /// presentation attribute syntax:
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Angle {
     deg(f32),
     grad(f32),
@@ -226,7 +483,7 @@ pub enum Angle {
 }
 
 impl Display for Angle {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Angle::deg(v) => write!(f, "{}deg", v),
             Angle::grad(v) => write!(f, "{}grad", v),
@@ -286,6 +543,7 @@ impl Angle {
 /// A 2d coordinate point.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Point {
     pub x: Measurement,
     pub y: Measurement,
@@ -379,6 +637,7 @@ impl Point {
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ViewBox {
     /// ViewBox left-top x coordinate,
     pub minx: Animatable<Measurement>,
@@ -394,6 +653,31 @@ pub struct ViewBox {
 
 impl FrameVariable for ViewBox {}
 
+impl FromStr for ViewBox {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl ViewBox {
+    /// Parse the value of the ‘viewBox’ attribute: `<min-x> <min-y> <width> <height>`, separated by whitespace
+    /// and/or a comma.
+    ///
+    /// The returned viewbox has no [`aspect`](ViewBox::aspect), a negative `width` or `height` is an error.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let unrecognized = || Error::UnrecognizedViewBox(s.to_owned());
+
+        match split_numbers(s).ok_or_else(unrecognized)?.as_slice() {
+            [minx, miny, width, height] if *width >= 0.0 && *height >= 0.0 => {
+                Ok(Self::from((*minx, *miny, *width, *height)))
+            }
+            _ => Err(unrecognized()),
+        }
+    }
+}
+
 impl<X, Y, W, H> From<(X, Y, W, H)> for ViewBox
 where
     Measurement: From<X> + From<Y> + From<W> + From<H>,
@@ -1,5 +1,7 @@
 //! `errors`,`result` types used by this crate.
 
+use alloc::string::String;
+
 /// Error variant used by crate.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -8,7 +10,61 @@ pub enum Error {
 
     #[error("unrecognized color: {0}")]
     UnrecognizedColor(String),
+
+    #[error("unrecognized measurement: {0}")]
+    UnrecognizedMeasurement(String),
+
+    #[error("unrecognized points: {0}")]
+    UnrecognizedPoints(String),
+
+    #[error("unrecognized viewbox: {0}")]
+    UnrecognizedViewBox(String),
+
+    #[error("unrecognized preserve aspect ratio: {0}")]
+    UnrecognizedAspectRatio(String),
+
+    #[error("invalid stylesheet: {0}")]
+    Stylesheet(String),
+
+    #[error("invalid markup: {0}")]
+    Markup(String),
+
+    #[error("unsupported ir version: {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("required field `{ty}.{field}` is not set")]
+    MissingField {
+        ty: &'static str,
+        field: &'static str,
+    },
+
+    #[error("invalid value at `{path}`: {message}")]
+    Validation { path: String, message: String },
+
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    Cbor(#[from] ciborium::de::Error<std::io::Error>),
+
+    #[cfg(feature = "postcard")]
+    #[error(transparent)]
+    Postcard(#[from] postcard::Error),
+
+    #[cfg(feature = "hyphenation")]
+    #[error("invalid hyphenation dictionary: {0}")]
+    Hyphenation(String),
+
+    #[cfg(feature = "font")]
+    #[error("invalid font data: {0}")]
+    FontData(String),
+
+    #[cfg(feature = "font")]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
 }
 
 /// Result type used by this crate.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,1170 @@
+//! Font files loading and font metrics, backed by [`ttf_parser`].
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ttf_parser::{colr, name_id, Face, GlyphId, OutlineBuilder, RasterImageFormat, RgbaColor};
+
+use crate::{
+    geometry::{Matrix, Point, Rect, Segment, SubPath},
+    resolve::constant,
+    AlignmentBaseline, Axis, BaselineShift, DominantBaseline, Error, Font, FontFamily, FontStretch,
+    FontStyle, FontWeight, GlyphOrientationVertical, Measurement, ResolveContext, Result, Rgba,
+    TextAnchor, TextLayout, Unit, WhiteSpaceProcessor, WritingMode, DEFAULT_TAB_SIZE,
+};
+
+/// Font-wide metrics, scaled to one font size.
+///
+/// All values are in user units, the `descent` is negative if it's below the baseline.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct FontMetrics {
+    /// The font size these metrics were scaled to.
+    pub size: f32,
+    /// The distance from the baseline to the top of the em box.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of the em box.
+    pub descent: f32,
+    /// The recommended additional space between lines.
+    pub line_gap: f32,
+    /// The height of lowercase letters, used to resolve the `ex` unit.
+    ///
+    /// Falls back to `0.5em` if the font doesn't provide this value.
+    pub x_height: f32,
+    /// The height of capital letters.
+    ///
+    /// Falls back to the `ascent` if the font doesn't provide this value.
+    pub cap_height: f32,
+    /// The distance the baseline is lowered by for subscripts.
+    ///
+    /// Falls back to `0.2em` if the font doesn't provide this value.
+    pub subscript_offset: f32,
+    /// The distance the baseline is raised by for superscripts.
+    ///
+    /// Falls back to `0.35em` if the font doesn't provide this value.
+    pub superscript_offset: f32,
+}
+
+impl FontMetrics {
+    /// Returns the distance between two consecutive baselines.
+    pub fn line_height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+
+    /// Returns the height of the `dominant-baseline` above the alphabetic baseline in the horizontal writing modes.
+    ///
+    /// The fonts don't carry the baseline tables, so the baselines are derived from the font metrics: the
+    /// `hanging` baseline is at `0.8` of the ascent and the `mathematical` baseline at the half of it.
+    pub fn dominant_baseline(&self, baseline: &DominantBaseline) -> f32 {
+        match baseline {
+            DominantBaseline::Auto
+            | DominantBaseline::UseScript
+            | DominantBaseline::NoChange
+            | DominantBaseline::ResetSize
+            | DominantBaseline::Alphabetic => 0.0,
+            DominantBaseline::Ideographic | DominantBaseline::TextAfterEdge => self.descent,
+            DominantBaseline::Hanging => self.ascent * 0.8,
+            DominantBaseline::Mathematical => self.ascent * 0.5,
+            DominantBaseline::Central => (self.ascent + self.descent) / 2.0,
+            DominantBaseline::Middle => self.x_height / 2.0,
+            DominantBaseline::TextBeforeEdge => self.ascent,
+        }
+    }
+
+    /// Returns the height of the `alignment-baseline` above the alphabetic baseline, `None` for the `auto`
+    /// value, which uses the dominant baseline.
+    ///
+    /// See [`dominant_baseline`](Self::dominant_baseline).
+    pub fn alignment_baseline(&self, baseline: &AlignmentBaseline) -> Option<f32> {
+        let height = match baseline {
+            AlignmentBaseline::Auto => return None,
+            AlignmentBaseline::Baseline | AlignmentBaseline::Alphabetic => 0.0,
+            AlignmentBaseline::BeforeEdge | AlignmentBaseline::TextBeforeEdge => self.ascent,
+            AlignmentBaseline::AfterEdge
+            | AlignmentBaseline::TextAfterEdge
+            | AlignmentBaseline::Ideographic => self.descent,
+            AlignmentBaseline::Middle => self.x_height / 2.0,
+            AlignmentBaseline::Central => (self.ascent + self.descent) / 2.0,
+            AlignmentBaseline::Hanging => self.ascent * 0.8,
+            AlignmentBaseline::Mathematical => self.ascent * 0.5,
+        };
+
+        Some(height)
+    }
+
+    /// Returns the distance the baseline is raised by with the `baseline-shift` value, negative values lower it.
+    ///
+    /// Percentages refer to the line height, other lengths are resolved by `ctx`.
+    pub fn baseline_shift(&self, shift: &BaselineShift, ctx: &ResolveContext) -> f32 {
+        match shift {
+            BaselineShift::Baseline => 0.0,
+            BaselineShift::Sub => -self.subscript_offset,
+            BaselineShift::Super => self.superscript_offset,
+            BaselineShift::Value(Measurement(value, Some(Unit::Percentages))) => {
+                value * self.line_height() / 100.0
+            }
+            BaselineShift::Value(value) => ctx.length(*value, Axis::Vertical),
+        }
+    }
+}
+
+/// A glyph positioned by [`FontFace::shape`].
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct ShapedGlyph {
+    /// The glyph id in the face.
+    pub id: u16,
+    /// The byte offset in the shaped text of the first character mapped to this glyph.
+    pub cluster: usize,
+    /// The horizontal advance of the current text position.
+    pub advance: f32,
+    /// The offset of the glyph from the current text position.
+    pub offset: Point,
+}
+
+/// The measured position of a glyph, see [`FontDatabase::measure_text`].
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct GlyphMetrics {
+    /// The first character mapped to the glyph.
+    pub c: char,
+    /// The offset of the glyph from the current text position along the inline axis.
+    pub x: f32,
+    /// The advance of the glyph along the inline axis.
+    pub advance: f32,
+}
+
+/// The metrics of a single-line text, see [`FontDatabase::measure_text`].
+///
+/// All values are in user units, the `descent` is negative if it's below the baseline.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TextMetrics {
+    /// The sum of the glyph advances, the height of the text in the vertical writing modes.
+    pub width: f32,
+    /// The ascent of the font.
+    pub ascent: f32,
+    /// The descent of the font.
+    pub descent: f32,
+    /// The distance between two consecutive baselines.
+    pub line_height: f32,
+    /// The glyphs in the visual order.
+    pub glyphs: Vec<GlyphMetrics>,
+}
+
+/// A layer of a color glyph, filled with one color by the nonzero rule.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct ColorLayer {
+    pub subpaths: Vec<SubPath>,
+    pub color: Rgba,
+}
+
+/// An embedded bitmap of a glyph, the `data` is a png image.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct GlyphImage {
+    pub data: Vec<u8>,
+    /// The placement of the image, in the same space as the glyph outlines.
+    pub rect: Rect,
+}
+
+/// The colored representation of a glyph, e.g. an emoji.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub enum ColorGlyph {
+    /// Vector layers from the `COLR`/`CPAL` tables.
+    Layers(Vec<ColorLayer>),
+    /// A bitmap from the `CBDT` or `sbix` tables.
+    Image(GlyphImage),
+}
+
+/// One face loaded from a TrueType/OpenType font file or collection.
+#[derive(Debug, Clone)]
+pub struct FontFace {
+    data: Arc<[u8]>,
+    index: u32,
+    units_per_em: f32,
+    family: String,
+    style: FontStyle,
+    weight: u16,
+    stretch: u16,
+    monospaced: bool,
+}
+
+impl FontFace {
+    /// Load the face at `index` from font file data.
+    ///
+    /// The `index` is only meaningful for font collections(`.ttc`), use `0` otherwise.
+    pub fn from_data<D>(data: D, index: u32) -> Result<Self>
+    where
+        Arc<[u8]>: From<D>,
+    {
+        let data: Arc<[u8]> = data.into();
+
+        let face = Face::parse(&data, index).map_err(|err| Error::FontData(err.to_string()))?;
+
+        let family = face
+            .names()
+            .into_iter()
+            .filter(|name| name.name_id == name_id::TYPOGRAPHIC_FAMILY)
+            .chain(
+                face.names()
+                    .into_iter()
+                    .filter(|name| name.name_id == name_id::FAMILY),
+            )
+            .find_map(|name| name.to_string())
+            .ok_or_else(|| Error::FontData("font family name is missing".to_owned()))?;
+
+        let style = match face.style() {
+            ttf_parser::Style::Normal => FontStyle::Normal,
+            ttf_parser::Style::Italic => FontStyle::Italic,
+            ttf_parser::Style::Oblique => FontStyle::Oblique,
+        };
+
+        let units_per_em = face.units_per_em() as f32;
+        let weight = face.weight().to_number();
+        let stretch = face.width().to_number();
+        let monospaced = face.is_monospaced();
+
+        Ok(Self {
+            data,
+            index,
+            units_per_em,
+            family,
+            style,
+            weight,
+            stretch,
+            monospaced,
+        })
+    }
+
+    /// Load the first face of a font file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_data(std::fs::read(path)?, 0)
+    }
+
+    /// Parses the font data, the returned view answers a run of queries without parsing it again.
+    ///
+    /// The methods of [`FontFace`] parse the data on every call, measure or lay out more than one glyph through
+    /// the parsed view instead.
+    pub fn parse(&self) -> ParsedFace<'_> {
+        ParsedFace {
+            face: self,
+            parsed: Face::parse(&self.data, self.index).expect("checked by from_data"),
+        }
+    }
+
+    /// The family name of this face.
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    /// The style of this face.
+    pub fn style(&self) -> FontStyle {
+        self.style
+    }
+
+    /// The numeric weight of this face, in the range `100..=900`.
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// The numeric width class of this face, in the range `1..=9`.
+    pub fn stretch(&self) -> u16 {
+        self.stretch
+    }
+
+    /// Returns true if all glyphs of this face have the same advance width.
+    pub fn is_monospaced(&self) -> bool {
+        self.monospaced
+    }
+
+    /// The number of font design units per em.
+    pub fn units_per_em(&self) -> f32 {
+        self.units_per_em
+    }
+
+    /// Returns the font-wide metrics scaled to font `size`.
+    pub fn metrics(&self, size: f32) -> FontMetrics {
+        self.parse().metrics(size)
+    }
+
+    /// Returns the horizontal advance of `c` at font `size`.
+    ///
+    /// Characters without glyph in this face use the advance of the `.notdef` glyph.
+    pub fn advance(&self, c: char, size: f32) -> f32 {
+        self.parse().advance(c, size)
+    }
+
+    /// Returns the sum of the horizontal advances of `text` at font `size`.
+    pub fn advance_width(&self, text: &str, size: f32) -> f32 {
+        self.parse().advance_width(text, size)
+    }
+
+    /// Returns the outline of the glyph of `c` at font `size`.
+    ///
+    /// The outline is in the y-down user space with the origin at the start of the glyph on the baseline;
+    /// characters without glyph in this face use the outline of the `.notdef` glyph.
+    pub fn glyph_outline(&self, c: char, size: f32) -> Vec<SubPath> {
+        self.parse().glyph_outline(c, size)
+    }
+
+    /// Returns the id of the glyph of `c`, or the `.notdef` glyph if this face has no glyph for it.
+    pub fn glyph_id(&self, c: char) -> u16 {
+        self.parse().glyph_id(c)
+    }
+
+    /// Returns the outline of the glyph with `id` at font `size`, see [`glyph_outline`](Self::glyph_outline).
+    pub fn outline(&self, id: u16, size: f32) -> Vec<SubPath> {
+        self.parse().outline(id, size)
+    }
+
+    /// Returns true if this face has color glyphs, e.g. an emoji font.
+    pub fn has_color_glyphs(&self) -> bool {
+        self.parse().has_color_glyphs()
+    }
+
+    /// Returns the colored representation of the glyph with `id` at font `size`, or `None` if the glyph
+    /// has only an outline.
+    ///
+    /// `COLR` layers are preferred over bitmaps. Both are in the space of [`glyph_outline`](Self::glyph_outline);
+    /// gradient paints are approximated by the average color of their stops, the foreground color is black.
+    /// Only png bitmaps are supported.
+    pub fn color_glyph(&self, id: u16, size: f32) -> Option<ColorGlyph> {
+        self.parse().color_glyph(id, size)
+    }
+
+    /// Maps `text` to glyphs positioned at font `size`, in the visual order.
+    ///
+    /// With the `shaping` feature, the text is shaped by [`rustybuzz`], which applies ligatures, kerning
+    /// and the rules of complex scripts. Otherwise, every character is mapped to its own glyph, advanced
+    /// by the horizontal metrics.
+    pub fn shape(&self, text: &str, size: f32) -> Vec<ShapedGlyph> {
+        self.parse().shape(text, size)
+    }
+
+    /// Returns the vertical advance of `c` at font `size`, used by the vertical writing modes.
+    ///
+    /// Falls back to the height of the em box if this face has no vertical metrics.
+    pub fn vertical_advance(&self, c: char, size: f32) -> f32 {
+        self.parse().vertical_advance(c, size)
+    }
+}
+
+/// A [`FontFace`] with its data parsed, see [`FontFace::parse`].
+///
+/// The methods are the ones of [`FontFace`], which parse the data on every call.
+#[derive(Debug, Clone)]
+pub struct ParsedFace<'a> {
+    face: &'a FontFace,
+    parsed: Face<'a>,
+}
+
+impl<'a> ParsedFace<'a> {
+    /// The face this view was parsed from.
+    pub fn face(&self) -> &'a FontFace {
+        self.face
+    }
+
+    /// See [`FontFace::metrics`].
+    pub fn metrics(&self, size: f32) -> FontMetrics {
+        let face = &self.parsed;
+        let scale = size / self.face.units_per_em;
+
+        let ascent = face.ascender() as f32 * scale;
+
+        FontMetrics {
+            size,
+            ascent,
+            descent: face.descender() as f32 * scale,
+            line_gap: face.line_gap() as f32 * scale,
+            x_height: face
+                .x_height()
+                .map(|v| v as f32 * scale)
+                .unwrap_or(size * 0.5),
+            cap_height: face
+                .capital_height()
+                .map(|v| v as f32 * scale)
+                .unwrap_or(ascent),
+            subscript_offset: face
+                .subscript_metrics()
+                .filter(|metrics| metrics.y_offset > 0)
+                .map(|metrics| metrics.y_offset as f32 * scale)
+                .unwrap_or(size * 0.2),
+            superscript_offset: face
+                .superscript_metrics()
+                .filter(|metrics| metrics.y_offset > 0)
+                .map(|metrics| metrics.y_offset as f32 * scale)
+                .unwrap_or(size * 0.35),
+        }
+    }
+
+    /// See [`FontFace::advance`].
+    pub fn advance(&self, c: char, size: f32) -> f32 {
+        self.glyph_advance(c) * size / self.face.units_per_em
+    }
+
+    /// See [`FontFace::advance_width`].
+    pub fn advance_width(&self, text: &str, size: f32) -> f32 {
+        text.chars().map(|c| self.glyph_advance(c)).sum::<f32>() * size / self.face.units_per_em
+    }
+
+    /// See [`FontFace::glyph_outline`].
+    pub fn glyph_outline(&self, c: char, size: f32) -> Vec<SubPath> {
+        self.outline(self.glyph_id(c), size)
+    }
+
+    /// See [`FontFace::glyph_id`].
+    pub fn glyph_id(&self, c: char) -> u16 {
+        self.parsed.glyph_index(c).unwrap_or(GlyphId(0)).0
+    }
+
+    /// See [`FontFace::outline`].
+    pub fn outline(&self, id: u16, size: f32) -> Vec<SubPath> {
+        let scale = size / self.face.units_per_em;
+        let mut builder = GlyphOutlineBuilder::new(Matrix::scale(scale, -scale));
+
+        self.parsed.outline_glyph(GlyphId(id), &mut builder);
+
+        builder.finish()
+    }
+
+    /// See [`FontFace::has_color_glyphs`].
+    pub fn has_color_glyphs(&self) -> bool {
+        let tables = self.parsed.tables();
+
+        tables.colr.is_some() || tables.cbdt.is_some() || tables.sbix.is_some()
+    }
+
+    /// See [`FontFace::color_glyph`].
+    pub fn color_glyph(&self, id: u16, size: f32) -> Option<ColorGlyph> {
+        let face = &self.parsed;
+        let glyph = GlyphId(id);
+
+        if face.is_color_glyph(glyph) {
+            let scale = size / self.face.units_per_em;
+
+            let mut painter = ColorGlyphPainter {
+                face,
+                matrix: Matrix::scale(scale, -scale),
+                transforms: vec![],
+                outline: vec![],
+                clips: vec![],
+                layers: vec![],
+            };
+
+            face.paint_color_glyph(glyph, 0, RgbaColor::new(0, 0, 0, 255), &mut painter)?;
+
+            if !painter.layers.is_empty() {
+                return Some(ColorGlyph::Layers(painter.layers));
+            }
+        }
+
+        let ppem = size.ceil().clamp(1.0, u16::MAX as f32) as u16;
+        let image = face.glyph_raster_image(glyph, ppem)?;
+
+        if image.format != RasterImageFormat::PNG {
+            return None;
+        }
+
+        // the offsets are from the glyph origin to the bottom left corner in the y-up pixel space.
+        let scale = size / image.pixels_per_em as f32;
+
+        Some(ColorGlyph::Image(GlyphImage {
+            data: image.data.to_vec(),
+            rect: Rect::new(
+                image.x as f32 * scale,
+                -(image.y as f32 + image.height as f32) * scale,
+                image.width as f32 * scale,
+                image.height as f32 * scale,
+            ),
+        }))
+    }
+
+    /// See [`FontFace::shape`].
+    #[cfg(not(feature = "shaping"))]
+    pub fn shape(&self, text: &str, size: f32) -> Vec<ShapedGlyph> {
+        let scale = size / self.face.units_per_em;
+
+        text.char_indices()
+            .map(|(cluster, c)| {
+                let glyph = self.parsed.glyph_index(c).unwrap_or(GlyphId(0));
+
+                ShapedGlyph {
+                    id: glyph.0,
+                    cluster,
+                    advance: self.parsed.glyph_hor_advance(glyph).unwrap_or(0) as f32 * scale,
+                    offset: Point::default(),
+                }
+            })
+            .collect()
+    }
+
+    /// See [`FontFace::shape`].
+    #[cfg(feature = "shaping")]
+    pub fn shape(&self, text: &str, size: f32) -> Vec<ShapedGlyph> {
+        let face = rustybuzz::Face::from_face(self.parsed.clone());
+        let scale = size / self.face.units_per_em;
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+
+        let output = rustybuzz::shape(&face, &[], buffer);
+
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .map(|(info, position)| ShapedGlyph {
+                id: info.glyph_id as u16,
+                cluster: info.cluster as usize,
+                advance: position.x_advance as f32 * scale,
+                // the font design space is y-up.
+                offset: Point::new(
+                    position.x_offset as f32 * scale,
+                    -position.y_offset as f32 * scale,
+                ),
+            })
+            .collect()
+    }
+
+    /// See [`FontFace::vertical_advance`].
+    pub fn vertical_advance(&self, c: char, size: f32) -> f32 {
+        let glyph = self.parsed.glyph_index(c).unwrap_or(GlyphId(0));
+
+        match self.parsed.glyph_ver_advance(glyph) {
+            Some(advance) => advance as f32 * size / self.face.units_per_em,
+            None => {
+                let metrics = self.metrics(size);
+                metrics.ascent - metrics.descent
+            }
+        }
+    }
+
+    /// Returns the advance of `c` along the vertical inline axis, with the glyph rotated by `angle` degrees.
+    pub(crate) fn inline_advance(&self, c: char, size: f32, angle: f32) -> f32 {
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        cos.abs() * self.vertical_advance(c, size) + sin.abs() * self.advance(c, size)
+    }
+
+    fn glyph_advance(&self, c: char) -> f32 {
+        let glyph = self.parsed.glyph_index(c).unwrap_or(GlyphId(0));
+
+        self.parsed.glyph_hor_advance(glyph).unwrap_or(0) as f32
+    }
+}
+
+/// Collects the glyph outline into sub-paths, mapped from the y-up font design space by `matrix`.
+struct GlyphOutlineBuilder {
+    matrix: Matrix,
+    subpaths: Vec<SubPath>,
+    current: SubPath,
+    start: Point,
+    point: Point,
+}
+
+impl GlyphOutlineBuilder {
+    fn new(matrix: Matrix) -> Self {
+        Self {
+            matrix,
+            subpaths: vec![],
+            current: SubPath::default(),
+            start: Point::default(),
+            point: Point::default(),
+        }
+    }
+
+    fn map(&self, x: f32, y: f32) -> Point {
+        self.matrix.transform_point(Point::new(x, y))
+    }
+
+    fn push(&mut self, segment: Segment) {
+        self.point = segment.to();
+        self.current.segments.push(segment);
+    }
+
+    fn finish(mut self) -> Vec<SubPath> {
+        if !self.current.segments.is_empty() {
+            self.subpaths.push(self.current);
+        }
+
+        self.subpaths
+    }
+}
+
+impl OutlineBuilder for GlyphOutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        if !self.current.segments.is_empty() {
+            self.subpaths.push(std::mem::take(&mut self.current));
+        }
+
+        self.start = self.map(x, y);
+        self.point = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (from, to) = (self.point, self.map(x, y));
+
+        self.push(Segment::Line { from, to });
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (from, ctrl, to) = (self.point, self.map(x1, y1), self.map(x, y));
+
+        self.push(Segment::Quadratic { from, ctrl, to });
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (from, ctrl1, ctrl2, to) = (
+            self.point,
+            self.map(x1, y1),
+            self.map(x2, y2),
+            self.map(x, y),
+        );
+
+        self.push(Segment::Cubic {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        });
+    }
+
+    fn close(&mut self) {
+        if self.point != self.start {
+            let (from, to) = (self.point, self.start);
+            self.push(Segment::Line { from, to });
+        }
+
+        if !self.current.segments.is_empty() {
+            self.current.closed = true;
+            self.subpaths.push(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+/// Flattens the paint graph of a `COLR` glyph into [`ColorLayer`]s.
+///
+/// Only the innermost clip is applied, composite modes are ignored.
+struct ColorGlyphPainter<'f, 'a> {
+    face: &'f Face<'a>,
+    /// Maps the font design space to the glyph space.
+    matrix: Matrix,
+    transforms: Vec<Matrix>,
+    /// The outline of the last `outline_glyph` call.
+    outline: Vec<SubPath>,
+    clips: Vec<Vec<SubPath>>,
+    layers: Vec<ColorLayer>,
+}
+
+impl ColorGlyphPainter<'_, '_> {
+    fn current(&self) -> Matrix {
+        self.transforms.last().copied().unwrap_or_default()
+    }
+
+    fn color(color: RgbaColor) -> Rgba {
+        Rgba(
+            color.red as f32 / 255.0,
+            color.green as f32 / 255.0,
+            color.blue as f32 / 255.0,
+            color.alpha as f32 / 255.0,
+        )
+    }
+
+    fn average<I>(stops: I) -> Option<Rgba>
+    where
+        I: Iterator<Item = colr::ColorStop>,
+    {
+        let mut count = 0;
+        let mut sum = [0.0; 4];
+
+        for stop in stops {
+            let color = Self::color(stop.color);
+            count += 1;
+            sum[0] += color.0;
+            sum[1] += color.1;
+            sum[2] += color.2;
+            sum[3] += color.3;
+        }
+
+        let count = count as f32;
+
+        (count > 0.0).then(|| {
+            Rgba(
+                sum[0] / count,
+                sum[1] / count,
+                sum[2] / count,
+                sum[3] / count,
+            )
+        })
+    }
+}
+
+impl<'a> colr::Painter<'a> for ColorGlyphPainter<'_, 'a> {
+    fn outline_glyph(&mut self, glyph_id: GlyphId) {
+        let mut builder = GlyphOutlineBuilder::new(self.matrix.multiply(&self.current()));
+
+        self.face.outline_glyph(glyph_id, &mut builder);
+        self.outline = builder.finish();
+    }
+
+    fn paint(&mut self, paint: colr::Paint<'a>) {
+        // the variable fonts are painted with the default instance.
+        let color = match paint {
+            colr::Paint::Solid(color) => Some(Self::color(color)),
+            colr::Paint::LinearGradient(gradient) => Self::average(gradient.stops(0, &[])),
+            colr::Paint::RadialGradient(gradient) => Self::average(gradient.stops(0, &[])),
+            colr::Paint::SweepGradient(gradient) => Self::average(gradient.stops(0, &[])),
+        };
+
+        let Some(color) = color else {
+            return;
+        };
+
+        let subpaths = self.clips.last().unwrap_or(&self.outline).clone();
+
+        if !subpaths.is_empty() {
+            self.layers.push(ColorLayer { subpaths, color });
+        }
+    }
+
+    fn push_clip(&mut self) {
+        self.clips.push(std::mem::take(&mut self.outline));
+    }
+
+    fn push_clip_box(&mut self, clipbox: colr::ClipBox) {
+        let matrix = self.matrix.multiply(&self.current());
+
+        let corners = [
+            (clipbox.x_min, clipbox.y_min),
+            (clipbox.x_max, clipbox.y_min),
+            (clipbox.x_max, clipbox.y_max),
+            (clipbox.x_min, clipbox.y_max),
+        ]
+        .map(|(x, y)| matrix.transform_point(Point::new(x, y)));
+
+        let segments = (0..4)
+            .map(|i| Segment::Line {
+                from: corners[i],
+                to: corners[(i + 1) % 4],
+            })
+            .collect();
+
+        self.clips.push(vec![SubPath {
+            segments,
+            closed: true,
+        }]);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    fn push_layer(&mut self, _mode: colr::CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, transform: ttf_parser::Transform) {
+        let transform = Matrix {
+            a: transform.a,
+            b: transform.b,
+            c: transform.c,
+            d: transform.d,
+            e: transform.e,
+            f: transform.f,
+        };
+
+        self.transforms.push(self.current().multiply(&transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+}
+
+/// A collection of [`FontFace`]s, queried by the ir [`Font`] attributes.
+#[derive(Debug, Default, Clone)]
+pub struct FontDatabase {
+    faces: Vec<FontFace>,
+    generic_families: Vec<(FontFamily, String)>,
+}
+
+impl FontDatabase {
+    /// Load all faces from font file data, returns the number of loaded faces.
+    pub fn load_font_data(&mut self, data: Vec<u8>) -> Result<usize> {
+        let data: Arc<[u8]> = data.into();
+
+        let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+
+        for index in 0..count {
+            self.faces.push(FontFace::from_data(data.clone(), index)?);
+        }
+
+        Ok(count as usize)
+    }
+
+    /// Load all faces from a font file, returns the number of loaded faces.
+    pub fn load_font_file<P: AsRef<Path>>(&mut self, path: P) -> Result<usize> {
+        self.load_font_data(std::fs::read(path)?)
+    }
+
+    /// Load the font files(`.ttf`, `.otf`, `.ttc` and `.otc`) in the directory `dir` and its sub-directories,
+    /// returns the number of loaded faces.
+    ///
+    /// The files are loaded in the order of their paths, so the matching result doesn't depend on the directory
+    /// listing order of the file system. Invalid font files are skipped with a warning.
+    pub fn load_font_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<usize> {
+        let mut files = vec![];
+        let mut dirs = vec![dir.as_ref().to_path_buf()];
+        let mut root = true;
+
+        while let Some(dir) = dirs.pop() {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(err) if root => return Err(err.into()),
+                Err(err) => {
+                    log::warn!("skip font directory {:?}: {}", dir, err);
+                    continue;
+                }
+            };
+
+            root = false;
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if is_font_file(&path) {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+
+        let mut count = 0;
+
+        for path in files {
+            match self.load_font_file(&path) {
+                Ok(n) => count += n,
+                Err(err) => log::warn!("skip font file {:?}: {}", path, err),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Load the fonts installed in the system font directories, returns the number of loaded faces.
+    ///
+    /// The installed fonts differ from machine to machine, programs that must render identically everywhere
+    /// (e.g. in CI and locally) should load their own font files instead.
+    pub fn load_system_fonts(&mut self) -> usize {
+        system_font_dirs()
+            .into_iter()
+            .filter(|dir| dir.is_dir())
+            .filter_map(|dir| self.load_font_dir(dir).ok())
+            .sum()
+    }
+
+    /// Add a loaded face into this database.
+    pub fn push(&mut self, face: FontFace) {
+        self.faces.push(face);
+    }
+
+    /// Returns all faces in loading order.
+    pub fn faces(&self) -> &[FontFace] {
+        &self.faces
+    }
+
+    /// Returns the family names of the loaded faces, sorted and deduplicated.
+    pub fn families(&self) -> Vec<&str> {
+        let mut families = self.faces.iter().map(FontFace::family).collect::<Vec<_>>();
+
+        families.sort();
+        families.dedup();
+
+        families
+    }
+
+    /// Map a generic family(e.g. [`FontFamily::Serif`]) to a concrete family name.
+    pub fn set_generic_family<S: Into<String>>(&mut self, generic: FontFamily, family: S) {
+        self.generic_families.retain(|(v, _)| *v != generic);
+        self.generic_families.push((generic, family.into()));
+    }
+
+    /// Find the face that best matches the `font` attributes, following the css font matching rules.
+    ///
+    /// If no face has the requested family, all faces are candidates. The animated attributes are
+    /// treated as unspecified, and ties are broken by loading order, so the result is deterministic.
+    pub fn query(&self, font: &Font) -> Option<&FontFace> {
+        let family = font
+            .family
+            .as_ref()
+            .and_then(constant)
+            .cloned()
+            .unwrap_or_default();
+
+        let family = match family {
+            FontFamily::Custom(name) => name,
+            generic => self
+                .generic_families
+                .iter()
+                .find(|(v, _)| *v == generic)
+                .map(|(_, name)| name.clone())
+                .unwrap_or_else(|| generic.to_string()),
+        };
+
+        let style = font
+            .style
+            .as_ref()
+            .and_then(constant)
+            .cloned()
+            .unwrap_or_default();
+
+        let weight = font
+            .weight
+            .as_ref()
+            .and_then(constant)
+            .map(FontWeight::to_number)
+            .unwrap_or(400);
+
+        let stretch = font
+            .stretch
+            .as_ref()
+            .and_then(constant)
+            .map(FontStretch::to_number)
+            .unwrap_or(5);
+
+        let mut candidates = self
+            .faces
+            .iter()
+            .filter(|face| face.family.eq_ignore_ascii_case(&family))
+            .collect::<Vec<_>>();
+
+        if candidates.is_empty() {
+            candidates = self.faces.iter().collect();
+        }
+
+        candidates
+            .into_iter()
+            .enumerate()
+            .min_by_key(|(order, face)| {
+                (
+                    stretch_distance(stretch, face.stretch),
+                    style_distance(style, face.style),
+                    weight_distance(weight, face.weight),
+                    *order,
+                )
+            })
+            .map(|(_, face)| face)
+    }
+
+    /// Measure the single-line `text` set in the face matching `font`, returns `None` if no face is loaded.
+    ///
+    /// The font size is resolved by `ctx`, falling back to the `ctx.font_size` if it is animated. The glyph positions
+    /// are relative to the current text position aligned by the `anchor` of `layout`, the horizontal text is shaped
+    /// by [`FontFace::shape`].
+    ///
+    /// In the vertical writing modes, the width and glyph positions are measured along the vertical inline axis.
+    /// The white space characters are processed by the [`WhiteSpace`](crate::WhiteSpace) mode of `layout` first.
+    pub fn measure_text(
+        &self,
+        font: &Font,
+        layout: &TextLayout,
+        text: &str,
+        ctx: &ResolveContext,
+    ) -> Option<TextMetrics> {
+        let face = self.query(font)?.parse();
+
+        let size = font
+            .size
+            .as_ref()
+            .and_then(|size| ctx.animatable_length(size, Axis::Other))
+            .unwrap_or(ctx.font_size);
+
+        let vertical = vertical_orientation(layout);
+
+        let text = WhiteSpaceProcessor::default().process(
+            text,
+            layout.white_space.unwrap_or_default(),
+            layout.tab_size.unwrap_or(DEFAULT_TAB_SIZE),
+        );
+        let text = text.as_str();
+
+        let mut x = 0.0;
+
+        let mut glyphs = match &vertical {
+            Some(orientation) => text
+                .chars()
+                .map(|c| {
+                    let advance = face.inline_advance(c, size, vertical_angle(c, orientation));
+                    let glyph = GlyphMetrics { c, x, advance };
+
+                    x += advance;
+
+                    glyph
+                })
+                .collect::<Vec<_>>(),
+            None => face
+                .shape(text, size)
+                .into_iter()
+                .map(|shaped| {
+                    let c = text[shaped.cluster..].chars().next().unwrap_or_default();
+
+                    let glyph = GlyphMetrics {
+                        c,
+                        x: x + shaped.offset.x,
+                        advance: shaped.advance,
+                    };
+
+                    x += shaped.advance;
+
+                    glyph
+                })
+                .collect::<Vec<_>>(),
+        };
+
+        let width = x;
+
+        let shift = match layout.anchor.as_ref().and_then(constant) {
+            Some(TextAnchor::Middle) => -width / 2.0,
+            Some(TextAnchor::End) => -width,
+            _ => 0.0,
+        };
+
+        for glyph in &mut glyphs {
+            glyph.x += shift;
+        }
+
+        let metrics = face.metrics(size);
+
+        Some(TextMetrics {
+            width,
+            ascent: metrics.ascent,
+            descent: metrics.descent,
+            line_height: metrics.line_height(),
+            glyphs,
+        })
+    }
+}
+
+/// Returns the glyph orientation if `layout` sets a vertical writing mode.
+pub(crate) fn vertical_orientation(layout: &TextLayout) -> Option<GlyphOrientationVertical> {
+    match layout.write_mode {
+        Some(WritingMode::Tb) | Some(WritingMode::TbRl) => {
+            Some(layout.vertical.clone().unwrap_or_default())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the rotation of the glyph of `c` in degrees in the vertical writing modes.
+///
+/// With the `auto` orientation, the fullwidth characters(e.g. CJK ideographs) are set upright, and the other
+/// characters(e.g. Latin letters) are rotated 90 degrees clockwise.
+pub(crate) fn vertical_angle(c: char, orientation: &GlyphOrientationVertical) -> f32 {
+    match orientation {
+        GlyphOrientationVertical::Angle(angle) => angle.as_deg(),
+        GlyphOrientationVertical::Auto if is_fullwidth(c) => 0.0,
+        GlyphOrientationVertical::Auto => 90.0,
+    }
+}
+
+/// Returns true if `c` is a wide or fullwidth character of the east asian scripts.
+fn is_fullwidth(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x3FFFD
+    )
+}
+
+/// Returns true if `path` has the extension of a font file or collection.
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|v| ext.eq_ignore_ascii_case(v))
+        })
+}
+
+/// The font directories of the current platform.
+fn system_font_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+
+    let mut dirs = vec![];
+
+    if cfg!(target_os = "windows") {
+        if let Some(windir) = std::env::var_os("WINDIR") {
+            dirs.push(PathBuf::from(windir).join("Fonts"));
+        }
+
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            dirs.push(
+                PathBuf::from(local)
+                    .join("Microsoft")
+                    .join("Windows")
+                    .join("Fonts"),
+            );
+        }
+    } else if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        dirs.extend(home.map(|home| home.join("Library/Fonts")));
+    } else {
+        dirs.push(PathBuf::from("/usr/share/fonts"));
+        dirs.push(PathBuf::from("/usr/local/share/fonts"));
+
+        if let Some(home) = home {
+            dirs.push(home.join(".local/share/fonts"));
+            dirs.push(home.join(".fonts"));
+        }
+    }
+
+    dirs
+}
+
+/// Narrower widths are preferred for condensed requests, wider ones otherwise, then the nearer widths.
+///
+/// Returns `(wrong_direction, distance)`, so any width in the preferred direction wins.
+fn stretch_distance(desired: u16, available: u16) -> (bool, u16) {
+    let prefer_narrower = desired <= 5;
+
+    let wrong_direction = available != desired && (available < desired) != prefer_narrower;
+
+    (wrong_direction, desired.abs_diff(available))
+}
+
+fn style_distance(desired: FontStyle, available: FontStyle) -> u8 {
+    let order = match desired {
+        FontStyle::Normal => [FontStyle::Normal, FontStyle::Oblique, FontStyle::Italic],
+        FontStyle::Italic => [FontStyle::Italic, FontStyle::Oblique, FontStyle::Normal],
+        FontStyle::Oblique => [FontStyle::Oblique, FontStyle::Italic, FontStyle::Normal],
+    };
+
+    order.iter().position(|v| *v == available).unwrap_or(3) as u8
+}
+
+fn weight_distance(desired: u16, available: u16) -> (u8, u16) {
+    match desired {
+        // `400` and `500` prefer each other, then lighter weights, then heavier weights.
+        400 | 500 if (400..=500).contains(&available) => (0, desired.abs_diff(available)),
+        400 | 500 if available < desired => (1, desired - available),
+        400 | 500 => (2, available - desired),
+        _ if desired < 400 && available <= desired => (0, desired - available),
+        _ if desired < 400 => (1, available - desired),
+        _ if available >= desired => (0, available - desired),
+        _ => (1, desired - available),
+    }
+}
@@ -1,13 +1,15 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
 use vglang_derive::Dsl;
 
 use super::{
-    Angle, Animatable, ChannelSelector, FrameVariable, Href, Measurement, NumberOptNumber,
-    PreserveAspectRatio, Color, Rgba,
+    Angle, Animatable, ChannelSelector, Color, FrameVariable, Href, Measurement, NumberOptNumber,
+    PreserveAspectRatio, Rgba,
 };
 
 /// Defines the coordinate system for attributes ‘x’, ‘y’, ‘width’ and ‘height’.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeUnits {
     /// see [`units`](Fe::units) and [`primitive_units`](Fe::primitive_units)
     /// for more informations.
@@ -41,6 +43,7 @@ impl FrameVariable for FeUnits {}
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeRes {
     /// `x-pixels`
     pub x: f32,
@@ -61,6 +64,7 @@ impl FrameVariable for FeRes {}
 /// ‘result’. Forward references to results are an error.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeIn {
     /// This keyword represents the graphics elements that were the original input into the ‘filter’ element.
     /// For raster effects filter primitives, the graphics elements will be rasterized into an initially clear
@@ -103,9 +107,17 @@ impl Default for FeIn {
     }
 }
 
+/// Reference the named result of a previous filter primitive.
+impl From<&str> for FeIn {
+    fn from(value: &str) -> Self {
+        Self::Register(value.to_owned())
+    }
+}
+
 /// Assign output to a named register. otherwise the filter output will only be referenced by next filter primitive.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeOut {
     Position,
     Named(String),
@@ -119,6 +131,12 @@ impl Default for FeOut {
 
 impl FrameVariable for FeOut {}
 
+impl From<&str> for FeOut {
+    fn from(value: &str) -> Self {
+        Self::Named(value.to_owned())
+    }
+}
+
 /// A filter effect consists of a series of graphics operations that are applied to
 /// a given source graphic to produce a modified graphical result. The result of the
 /// filter effect is rendered to the target device instead of the original source
@@ -126,7 +144,12 @@ impl FrameVariable for FeOut {}
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Fe {
+    /// The unique name of this filter, referenced by [`FilterRef`].
+    #[cfg_attr(feature = "dsl", dsl(required))]
+    pub id: String,
+
     /// Defines the coordinate system for attributes ‘x’, ‘y’, ‘width’ and ‘height’.
     ///
     /// If filterUnits="userSpaceOnUse", ‘x’, ‘y’, ‘width’ and ‘height’ represent values
@@ -208,6 +231,7 @@ pub struct Fe {
 impl Default for Fe {
     fn default() -> Self {
         Self {
+            id: String::new(),
             units: FeUnits::ObjectBoundingBox.into(),
             primitive_units: FeUnits::UserSpaceOnUse.into(),
             x: Measurement::percentage(-10.0).into(),
@@ -219,6 +243,20 @@ impl Default for Fe {
     }
 }
 
+/// Applies the filter with the id to the elements in scope, the same as the svg `filter` property.
+///
+/// Like other scoped instructions, a filter reference must be closed by a paired `pop` instruction.
+#[derive(Dsl, Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FilterRef(pub String);
+
+impl From<&str> for FilterRef {
+    fn from(value: &str) -> Self {
+        Self(value.into())
+    }
+}
+
 /// The common attributes are available on all filter primitive elements:
 ///
 ///
@@ -252,6 +290,7 @@ impl Default for Fe {
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FePrimitive {
     /// The minimum x coordinate for the subregion which restricts calculation and rendering of the given filter primitive.
     pub x: Animatable<Measurement>,
@@ -295,6 +334,7 @@ impl Default for FePrimitive {
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeDistantLight {
     /// Direction angle for the light source on the XY plane (clockwise), in degrees from the x axis.
     ///
@@ -312,6 +352,7 @@ pub struct FeDistantLight {
 /// Range 1.0 to 128.0.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeSpecularExponent(pub f32);
 
 impl Default for FeSpecularExponent {
@@ -338,6 +379,7 @@ impl FrameVariable for FeSpecularExponent {}
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeSpotLight {
     /// X location for the light source in the coordinate system established by attribute ‘primitiveUnits’ on the ‘filter’ element.
     ///
@@ -398,6 +440,7 @@ pub struct FeSpotLight {
 /// * cb = Color (RGB) at a given pixel for image B - premultiplied
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeBlendMode {
     /// cr = (1 - qa) * cb + ca
     Normal,
@@ -424,6 +467,7 @@ impl FrameVariable for FeBlendMode {}
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeBlend {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -453,6 +497,7 @@ impl Default for FeBlend {
 /// Values of FeColorMatrix.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeColorMatrixValues {
     /// a list of 20 matrix values.
     Matrix([f32; 20]),
@@ -500,6 +545,7 @@ impl FrameVariable for FeColorMatrixValues {}
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeColorMatrix {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -548,6 +594,7 @@ impl FeColorMatrix {
 /// Defines transfer function of [`feComponentTransfer`](https://www.w3.org/TR/SVG11/filters.html#feComponentTransferTypeAttribute)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeTransferFn {
     Identity,
 
@@ -592,6 +639,7 @@ impl Default for FeTransferFn {
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeComponentTransfer {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -612,6 +660,7 @@ pub struct FeComponentTransfer {
 
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeCompositeArithmetic(pub f32);
 
 impl From<f32> for FeCompositeArithmetic {
@@ -633,6 +682,7 @@ impl FrameVariable for FeCompositeArithmetic {}
 /// ‘operator’ is not specified, then the effect is as if a value of over were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeCompositeOperator {
     Over,
     In,
@@ -660,7 +710,9 @@ impl FrameVariable for FeCompositeOperator {}
 /// [`PORTERDUFF`]: https://www.w3.org/TR/SVG11/refs.html#ref-PORTERDUFF
 /// [`SVG-COMPOSITING`]: https://www.w3.org/TR/SVG11/refs.html#ref-SVG-COMPOSITING
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeComposite {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -702,6 +754,7 @@ pub struct FeComposite {
 /// If the attribute is not specified, the effect is as if a value of 3 were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeConvolveMatrixOrder {
     pub order_x: u32,
 
@@ -723,6 +776,7 @@ impl FrameVariable for FeConvolveMatrixOrder {}
 /// can be applied when the kernel is positioned at or near the edge of the input image.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeConvolveMatrixEdgeMode {
     Duplicate,
     Wrap,
@@ -743,6 +797,7 @@ impl Default for FeConvolveMatrixEdgeMode {
 /// See [`feConvolveMatrix`](https://www.w3.org/TR/SVG11/filters.html#feConvolveMatrixElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeConvolveMatrix {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -804,6 +859,7 @@ pub struct FeConvolveMatrix {
 /// See [`feConvolveMatrix`](https://www.w3.org/TR/SVG11/filters.html#feDiffuseLightingElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeDiffuseLighting {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -840,6 +896,7 @@ pub struct FeDiffuseLighting {
 /// This filter primitive uses the pixels values from the image from ‘in2’ to spatially displace the image from ‘in’.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeDisplacementMap {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -873,13 +930,30 @@ pub struct FeDisplacementMap {
 /// This filter primitive creates a rectangle filled with the color and opacity values from properties ‘flood-color’ and ‘flood-opacity’.
 /// The rectangle is as large as the filter primitive subregion established by the ‘x’, ‘y’, ‘width’ and ‘height’ attributes on the
 /// ‘feFlood’ element.
+///
+/// See [`feFlood`](https://www.w3.org/TR/SVG11/filters.html#feFloodElement)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct FeFlood(pub Rgba);
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FeFlood {
+    /// common properties.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub primitive: FePrimitive,
+
+    /// The color and opacity to fill the filter primitive subregion with, the same as
+    /// the ‘flood-color’ and ‘flood-opacity’ properties.
+    ///
+    /// If the attribute is not specified, the effect is as if opaque black were specified.
+    pub color: Animatable<Rgba>,
+}
 
 impl Default for FeFlood {
     fn default() -> Self {
-        Self(Color::black.into())
+        Self {
+            primitive: Default::default(),
+            color: Rgba::from(Color::black).into(),
+        }
     }
 }
 
@@ -887,7 +961,9 @@ impl Default for FeFlood {
 ///
 /// See [`feGaussianBlur`](https://www.w3.org/TR/SVG11/filters.html#feGaussianBlurElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeGaussianBlur {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -913,6 +989,7 @@ pub struct FeGaussianBlur {
 /// See [`feImage`](https://www.w3.org/TR/SVG11/filters.html#feImageElement)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeImage {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -945,18 +1022,23 @@ impl FeImage {
 ///
 /// See [`feMerge`](https://www.w3.org/TR/SVG11/filters.html#feMergeElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeMerge(pub FePrimitive);
 
 /// See [`FeMerge`]
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeMergeItem(pub Animatable<FeIn>);
 
 /// A keyword indicating whether to erode (i.e., thin) or dilate (fatten) the source graphic.
 /// If attribute `mode` is not specified, then the effect is as if a value of erode were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeMorphologyMode {
     Erode,
     Dilate,
@@ -976,6 +1058,7 @@ impl FrameVariable for FeMorphologyMode {}
 /// See [`feMorphology`](https://www.w3.org/TR/SVG11/filters.html#feMorphologyElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeMorphology {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -1005,7 +1088,9 @@ pub struct FeMorphology {
 ///
 /// See [`feOffset`](https://www.w3.org/TR/SVG11/filters.html#feOffsetElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeOffset {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -1036,6 +1121,7 @@ pub struct FeOffset {
 /// See [`feSpecularLighting`](https://www.w3.org/TR/SVG11/filters.html#feSpecularLightingElement)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeSpecularLighting {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -1094,6 +1180,7 @@ impl Default for FeSpecularLighting {
 /// See [`feTitle`](https://www.w3.org/TR/SVG11/filters.html#feTitleElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeTile {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -1106,6 +1193,7 @@ pub struct FeTile {
 /// See [`stitch_tiles`](FeTurbulence::stitch_tiles)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeStitchTiles {
     /// If stitchTiles="stitch", then the user agent will automatically adjust baseFrequency-x and baseFrequency-y values
     /// such that the feTurbulence node's width and height (i.e., the width and height of the current subregion) contains
@@ -1133,6 +1221,7 @@ impl FrameVariable for FeStitchTiles {}
 // If attribute ‘type’ is not specified, then the effect is as if a value of turbulence were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FeTurbulenceType {
     FractalNoise,
     Turbulence,
@@ -1154,6 +1243,7 @@ impl FrameVariable for FeTurbulenceType {}
 /// See [`feTurbulence`](https://www.w3.org/TR/SVG11/filters.html#feTurbulenceElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FeTurbulence {
     /// common properties.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -1186,3 +1276,30 @@ pub struct FeTurbulence {
     /// See [`FeStitchTiles`]
     pub r#type: Animatable<FeTurbulenceType>,
 }
+
+/// Assigns the filter primitive output to the named register `name`, which can be referenced by the following
+/// primitives of the same filter with [`FeIn::Register`].
+macro_rules! fe_result {
+    ($($ty: ty => $($field: tt).+),+ $(,)?) => {
+        $(
+            impl $ty {
+                /// Assigns the output of this primitive to the named register `name`, see [`FeOut::Named`].
+                pub fn result<S: Into<String>>(mut self, name: S) -> Self {
+                    self.$($field).+.out = FeOut::Named(name.into()).into();
+                    self
+                }
+            }
+        )+
+    };
+}
+
+fe_result!(
+    FeBlend => primitive,
+    FeColorMatrix => primitive,
+    FeComponentTransfer => primitive,
+    FeComposite => primitive,
+    FeFlood => primitive,
+    FeGaussianBlur => primitive,
+    FeMerge => 0,
+    FeOffset => primitive,
+);
@@ -0,0 +1,79 @@
+//! Curve flattening, which converts paths into polylines for backends that only draw straight lines.
+
+use crate::{
+    geometry::{Point, Polyline, Segment, SubPath},
+    Path, ResolveContext,
+};
+
+/// The default maximum distance between a curve and its flattened polyline, in user units.
+pub const DEFAULT_TOLERANCE: f32 = 0.05;
+
+/// The maximum subdivision depth of the adaptive flattening.
+const MAX_DEPTH: usize = 16;
+
+/// Flatten `segment` adaptively, calls `f` with the parameter and the point of every vertex except the start point.
+pub(crate) fn flatten_segment<F>(segment: &Segment, tolerance: f32, mut f: F)
+where
+    F: FnMut(f32, Point),
+{
+    fn subdivide<F>(segment: &Segment, t0: f32, t1: f32, tolerance: f32, depth: usize, f: &mut F)
+    where
+        F: FnMut(f32, Point),
+    {
+        if depth >= MAX_DEPTH || segment.flatness() <= tolerance {
+            f(t1, segment.to());
+            return;
+        }
+
+        let (left, right) = segment.split(0.5);
+        let mid = (t0 + t1) / 2.0;
+
+        subdivide(&left, t0, mid, tolerance, depth + 1, f);
+        subdivide(&right, mid, t1, tolerance, depth + 1, f);
+    }
+
+    subdivide(segment, 0.0, 1.0, tolerance.max(f32::EPSILON), 0, &mut f);
+}
+
+impl SubPath {
+    /// Flatten this sub-path into a polyline, the distance between the curves and the polyline is at most `tolerance`.
+    ///
+    /// The end point of a closed sub-path is not repeated.
+    pub fn flatten(&self, tolerance: f32) -> Polyline {
+        let mut points = vec![];
+
+        if let Some(first) = self.segments.first() {
+            points.push(first.from());
+        }
+
+        for segment in &self.segments {
+            flatten_segment(segment, tolerance, |_, to| points.push(to));
+        }
+
+        // the closing edge is implied by `closed`.
+        if self.closed && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        Polyline {
+            points,
+            closed: self.closed,
+        }
+    }
+}
+
+impl Path {
+    /// Flatten this path into polylines, one per sub-path, `tolerance` is in user units.
+    ///
+    /// Returns `None` if the path data is animated.
+    pub fn flatten(&self, ctx: &ResolveContext, tolerance: f32) -> Option<Vec<Polyline>> {
+        let subpaths = ctx.path(self)?;
+
+        Some(
+            subpaths
+                .iter()
+                .map(|subpath| subpath.flatten(tolerance))
+                .collect(),
+        )
+    }
+}
@@ -1,10 +1,15 @@
-use std::fmt::Display;
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
+use core::fmt::Display;
 
 use crate::{Animatable, FrameVariable, Measurement};
 
 /// See [`style`](FontFace::style)
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FontStyle {
     Normal,
     Italic,
@@ -27,6 +32,7 @@ impl FrameVariable for FontStyle {}
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FontVariant {
     Normal,
     SmallCaps,
@@ -45,6 +51,7 @@ impl FrameVariable for FontVariant {}
 /// See [`FontFace`]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FontWeight {
     Normal,
     Bold,
@@ -69,9 +76,34 @@ impl Default for FontWeight {
 
 impl FrameVariable for FontWeight {}
 
+impl FontWeight {
+    /// Returns the numeric weight of this value, in the range `100..=900`.
+    ///
+    /// The relative weights [`Bolder`](FontWeight::Bolder) and [`Lighter`](FontWeight::Lighter)
+    /// are resolved against the `normal` weight.
+    pub fn to_number(&self) -> u16 {
+        match self {
+            FontWeight::Normal => 400,
+            FontWeight::Bold => 700,
+            FontWeight::Bolder => 700,
+            FontWeight::Lighter => 100,
+            FontWeight::W100 => 100,
+            FontWeight::W200 => 200,
+            FontWeight::W300 => 300,
+            FontWeight::W400 => 400,
+            FontWeight::W500 => 500,
+            FontWeight::W600 => 600,
+            FontWeight::W700 => 700,
+            FontWeight::W800 => 800,
+            FontWeight::W900 => 900,
+        }
+    }
+}
+
 /// This property specifies a prioritized font family names and/or generic family names.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FontFamily {
     Serif,
     SansSerif,
@@ -94,7 +126,7 @@ impl Default for FontFamily {
 }
 
 impl Display for FontFamily {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             FontFamily::Serif => write!(f, "serif"),
             FontFamily::SansSerif => write!(f, "sans-serif"),
@@ -111,6 +143,7 @@ impl FrameVariable for FontFamily {}
 /// Indication of the condensed or expanded nature of the face relative to others in the same font family.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FontStretch {
     Normal,
     Wider,
@@ -133,10 +166,31 @@ impl Default for FontStretch {
 
 impl FrameVariable for FontStretch {}
 
+impl FontStretch {
+    /// Returns the numeric width class of this value, in the range `1..=9`(`5` is normal).
+    ///
+    /// The relative values [`Wider`](FontStretch::Wider) and [`Narrower`](FontStretch::Narrower)
+    /// are resolved against the `normal` width.
+    pub fn to_number(&self) -> u16 {
+        match self {
+            FontStretch::UltraCondensed => 1,
+            FontStretch::ExtraCondensed => 2,
+            FontStretch::Condensed => 3,
+            FontStretch::SemiCondensed | FontStretch::Narrower => 4,
+            FontStretch::Normal => 5,
+            FontStretch::SemiExpanded | FontStretch::Wider => 6,
+            FontStretch::Expanded => 7,
+            FontStretch::ExtraExpanded => 8,
+            FontStretch::UltraExpanded => 9,
+        }
+    }
+}
+
 /// Shorthand property for setting ‘font-style’, ‘font-variant’, ‘font-weight’, ‘font-size’, ‘line-height’ and ‘font-family’.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Font {
     /// See [`FontFamily`]
     pub family: Option<Animatable<FontFamily>>,
@@ -148,6 +202,7 @@ pub struct Font {
     pub weight: Option<Animatable<FontWeight>>,
     /// This property refers to the size of the font from baseline to baseline when multiple lines of
     /// text are set solid in a multiline layout environment.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub size: Option<Animatable<Measurement>>,
     /// See [`FontStretch`]
     pub stretch: Option<Animatable<FontStretch>>,
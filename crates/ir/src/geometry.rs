@@ -0,0 +1,1377 @@
+//! Resolved geometry in user units, used by the measurement, hit-testing and layout algorithms.
+//!
+//! Unlike the ir elements, the values here are plain numbers: units, percentages and animated
+//! registers have already been resolved, see [`ResolveContext`](crate::ResolveContext).
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{ApproxEq, Tolerance, Transform};
+
+/// A resolved 2d point or vector.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    /// Create a new point.
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the length of this vector.
+    pub fn length(&self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// Returns the distance between two points.
+    pub fn distance(&self, other: Point) -> f32 {
+        (*self - other).length()
+    }
+
+    /// Returns the dot product of two vectors.
+    pub fn dot(&self, other: Point) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z component of the cross product of two vectors.
+    pub fn cross(&self, other: Point) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the unit vector of this vector, or the zero vector if the length is zero.
+    pub fn normalize(&self) -> Point {
+        let length = self.length();
+
+        if length > 0.0 {
+            *self * (1.0 / length)
+        } else {
+            Point::default()
+        }
+    }
+
+    /// Returns the linear interpolation between two points.
+    pub fn lerp(&self, other: Point, t: f32) -> Point {
+        *self + (other - *self) * t
+    }
+}
+
+impl From<(f32, f32)> for Point {
+    fn from(value: (f32, f32)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: f32) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// A resolved axis-aligned rectangle.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    /// Create a new rectangle.
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the smallest rectangle containing all `points`, or `None` if `points` is empty.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut points = points.into_iter();
+
+        let first = points.next()?;
+
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+
+        Some(Self::new(min.x, min.y, max.x - min.x, max.y - min.y))
+    }
+
+    pub fn min_x(&self) -> f32 {
+        self.x
+    }
+
+    pub fn min_y(&self) -> f32 {
+        self.y
+    }
+
+    pub fn max_x(&self) -> f32 {
+        self.x + self.width
+    }
+
+    pub fn max_y(&self) -> f32 {
+        self.y + self.height
+    }
+
+    /// Returns the four corners, clockwise from the top-left one.
+    pub fn corners(&self) -> [Point; 4] {
+        [
+            Point::new(self.min_x(), self.min_y()),
+            Point::new(self.max_x(), self.min_y()),
+            Point::new(self.max_x(), self.max_y()),
+            Point::new(self.min_x(), self.max_y()),
+        ]
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.min_x().min(other.min_x());
+        let y = self.min_y().min(other.min_y());
+
+        Rect::new(
+            x,
+            y,
+            self.max_x().max(other.max_x()) - x,
+            self.max_y().max(other.max_y()) - y,
+        )
+    }
+
+    /// Returns this rectangle grown by `delta` in every direction.
+    pub fn inflate(&self, delta: f32) -> Rect {
+        Rect::new(
+            self.x - delta,
+            self.y - delta,
+            self.width + delta * 2.0,
+            self.height + delta * 2.0,
+        )
+    }
+
+    /// Returns true if `point` is inside this rectangle, edges included.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.min_x()
+            && point.x <= self.max_x()
+            && point.y >= self.min_y()
+            && point.y <= self.max_y()
+    }
+
+    /// Returns true if the two rectangles overlap, touching edges included.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min_x() <= other.max_x()
+            && other.min_x() <= self.max_x()
+            && self.min_y() <= other.max_y()
+            && other.min_y() <= self.max_y()
+    }
+
+    /// Returns the distance from `point` to this rectangle, zero if `point` is inside.
+    pub fn distance(&self, point: Point) -> f32 {
+        let dx = (self.min_x() - point.x)
+            .max(point.x - self.max_x())
+            .max(0.0);
+        let dy = (self.min_y() - point.y)
+            .max(point.y - self.max_y())
+            .max(0.0);
+
+        Point::new(dx, dy).length()
+    }
+}
+
+/// A resolved 2d affine transformation matrix.
+///
+/// Maps `(x, y)` to `(a * x + c * y + e, b * x + d * y + f)`, the same as the svg `matrix(a b c d e f)`.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Matrix {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+/// The 3x2 affine matrix, an alias of [`Matrix`].
+pub type Matrix3x2 = Matrix;
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Matrix {
+    /// Create the identity matrix.
+    pub const fn identity() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    pub const fn translate(tx: f32, ty: f32) -> Self {
+        Self {
+            e: tx,
+            f: ty,
+            ..Self::identity()
+        }
+    }
+
+    pub const fn scale(sx: f32, sy: f32) -> Self {
+        Self {
+            a: sx,
+            d: sy,
+            ..Self::identity()
+        }
+    }
+
+    /// Create a rotation of `angle` degrees around the origin.
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// Create a skew of `angle` degrees along the x axis.
+    pub fn skew_x(angle: f32) -> Self {
+        Self {
+            c: angle.to_radians().tan(),
+            ..Self::identity()
+        }
+    }
+
+    /// Create a skew of `angle` degrees along the y axis.
+    pub fn skew_y(angle: f32) -> Self {
+        Self {
+            b: angle.to_radians().tan(),
+            ..Self::identity()
+        }
+    }
+
+    /// Returns the matrix that applies `other` first, then `self`.
+    ///
+    /// The transformation of a nested element is `parent.multiply(&child)`.
+    pub fn multiply(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            e: self.a * other.e + self.c * other.f + self.e,
+            f: self.b * other.e + self.d * other.f + self.f,
+        }
+    }
+
+    /// Returns the matrix that applies `self` first, then `other`, the reverse order of [`multiply`](Matrix::multiply).
+    pub fn concat(&self, other: &Matrix) -> Matrix {
+        other.multiply(self)
+    }
+
+    /// Returns the inverse matrix, or `None` if this matrix is not invertible.
+    pub fn invert(&self) -> Option<Matrix> {
+        let det = self.a * self.d - self.b * self.c;
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        Some(Matrix {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+
+    /// Returns true if this matrix is the identity matrix within the [default tolerance](Tolerance::DEFAULT),
+    /// e.g. a full-turn rotation.
+    pub fn is_identity(&self) -> bool {
+        self.approx_eq(&Self::identity())
+    }
+
+    /// Map a point by this matrix.
+    pub fn transform_point(&self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    /// Map a vector by this matrix, the translation is ignored.
+    pub fn transform_vector(&self, vector: Point) -> Point {
+        Point::new(
+            self.a * vector.x + self.c * vector.y,
+            self.b * vector.x + self.d * vector.y,
+        )
+    }
+
+    /// Returns the axis-aligned bounding box of the mapped rectangle.
+    pub fn transform_rect(&self, rect: &Rect) -> Rect {
+        Rect::from_points(rect.corners().map(|p| self.transform_point(p))).expect("four corners")
+    }
+
+    /// Returns the mean scale factor of this matrix, used to map lengths like stroke widths.
+    pub fn mean_scale(&self) -> f32 {
+        (self.a * self.d - self.b * self.c).abs().sqrt()
+    }
+}
+
+impl From<Transform> for Matrix {
+    fn from(value: Transform) -> Self {
+        match value {
+            Transform::Translate { tx, ty } => Matrix::translate(tx, ty),
+            Transform::Matrix { a, b, c, d, e, f } => Matrix { a, b, c, d, e, f },
+            Transform::Scale { sx, sy } => Matrix::scale(sx, sy),
+            Transform::Rotate { angle, cx, cy } => Matrix::translate(cx, cy)
+                .multiply(&Matrix::rotate(angle))
+                .multiply(&Matrix::translate(-cx, -cy)),
+            Transform::SkewX(angle) => Matrix::skew_x(angle),
+            Transform::SkewY(angle) => Matrix::skew_y(angle),
+        }
+    }
+}
+
+/// A resolved path segment, elliptical arcs are approximated by cubic béziers.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Segment {
+    Line {
+        from: Point,
+        to: Point,
+    },
+    Quadratic {
+        from: Point,
+        ctrl: Point,
+        to: Point,
+    },
+    Cubic {
+        from: Point,
+        ctrl1: Point,
+        ctrl2: Point,
+        to: Point,
+    },
+}
+
+impl Segment {
+    /// Returns the start point of this segment.
+    pub fn from(&self) -> Point {
+        match self {
+            Segment::Line { from, .. }
+            | Segment::Quadratic { from, .. }
+            | Segment::Cubic { from, .. } => *from,
+        }
+    }
+
+    /// Returns the end point of this segment.
+    pub fn to(&self) -> Point {
+        match self {
+            Segment::Line { to, .. }
+            | Segment::Quadratic { to, .. }
+            | Segment::Cubic { to, .. } => *to,
+        }
+    }
+
+    /// Returns the point at parameter `t` in `[0, 1]`.
+    pub fn eval(&self, t: f32) -> Point {
+        match *self {
+            Segment::Line { from, to } => from.lerp(to, t),
+            Segment::Quadratic { from, ctrl, to } => from.lerp(ctrl, t).lerp(ctrl.lerp(to, t), t),
+            Segment::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let a = from.lerp(ctrl1, t);
+                let b = ctrl1.lerp(ctrl2, t);
+                let c = ctrl2.lerp(to, t);
+
+                a.lerp(b, t).lerp(b.lerp(c, t), t)
+            }
+        }
+    }
+
+    /// Returns the derivative at parameter `t` in `[0, 1]`.
+    pub fn derivative(&self, t: f32) -> Point {
+        match *self {
+            Segment::Line { from, to } => to - from,
+            Segment::Quadratic { from, ctrl, to } => {
+                ((ctrl - from) * (1.0 - t) + (to - ctrl) * t) * 2.0
+            }
+            Segment::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let a = ctrl1 - from;
+                let b = ctrl2 - ctrl1;
+                let c = to - ctrl2;
+
+                (a * ((1.0 - t) * (1.0 - t)) + b * (2.0 * (1.0 - t) * t) + c * (t * t)) * 3.0
+            }
+        }
+    }
+
+    /// Split this segment at parameter `t` in `[0, 1]`.
+    pub fn split(&self, t: f32) -> (Segment, Segment) {
+        match *self {
+            Segment::Line { from, to } => {
+                let mid = from.lerp(to, t);
+
+                (
+                    Segment::Line { from, to: mid },
+                    Segment::Line { from: mid, to },
+                )
+            }
+            Segment::Quadratic { from, ctrl, to } => {
+                let a = from.lerp(ctrl, t);
+                let b = ctrl.lerp(to, t);
+                let mid = a.lerp(b, t);
+
+                (
+                    Segment::Quadratic {
+                        from,
+                        ctrl: a,
+                        to: mid,
+                    },
+                    Segment::Quadratic {
+                        from: mid,
+                        ctrl: b,
+                        to,
+                    },
+                )
+            }
+            Segment::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let a = from.lerp(ctrl1, t);
+                let b = ctrl1.lerp(ctrl2, t);
+                let c = ctrl2.lerp(to, t);
+                let ab = a.lerp(b, t);
+                let bc = b.lerp(c, t);
+                let mid = ab.lerp(bc, t);
+
+                (
+                    Segment::Cubic {
+                        from,
+                        ctrl1: a,
+                        ctrl2: ab,
+                        to: mid,
+                    },
+                    Segment::Cubic {
+                        from: mid,
+                        ctrl1: bc,
+                        ctrl2: c,
+                        to,
+                    },
+                )
+            }
+        }
+    }
+
+    /// Returns the part of this segment between the parameters `t0` and `t1` in `[0, 1]`.
+    pub fn slice(&self, t0: f32, t1: f32) -> Segment {
+        let right = if t0 > 0.0 { self.split(t0).1 } else { *self };
+
+        if t1 >= 1.0 {
+            return right;
+        }
+
+        let t = if t0 < 1.0 {
+            (t1 - t0) / (1.0 - t0)
+        } else {
+            0.0
+        };
+
+        right.split(t).0
+    }
+
+    /// Returns the maximum distance between the control points and the chord of this segment,
+    /// an upper bound of the distance between the curve and the chord.
+    pub fn flatness(&self) -> f32 {
+        let (from, to) = (self.from(), self.to());
+
+        let distance = |p: Point| {
+            let chord = to - from;
+            let length = chord.length();
+
+            if length > 0.0 {
+                (p - from).cross(chord).abs() / length
+            } else {
+                p.distance(from)
+            }
+        };
+
+        match *self {
+            Segment::Line { .. } => 0.0,
+            Segment::Quadratic { ctrl, .. } => distance(ctrl),
+            Segment::Cubic { ctrl1, ctrl2, .. } => distance(ctrl1).max(distance(ctrl2)),
+        }
+    }
+
+    /// Map this segment by `matrix`.
+    pub fn transform(&self, matrix: &Matrix) -> Segment {
+        let map = |p: Point| matrix.transform_point(p);
+
+        match *self {
+            Segment::Line { from, to } => Segment::Line {
+                from: map(from),
+                to: map(to),
+            },
+            Segment::Quadratic { from, ctrl, to } => Segment::Quadratic {
+                from: map(from),
+                ctrl: map(ctrl),
+                to: map(to),
+            },
+            Segment::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Segment::Cubic {
+                from: map(from),
+                ctrl1: map(ctrl1),
+                ctrl2: map(ctrl2),
+                to: map(to),
+            },
+        }
+    }
+
+    /// Approximate an svg elliptical arc by cubic béziers, `x_rotation` is in degrees.
+    ///
+    /// See [`EndpointArc::to_cubics`] for more information.
+    pub fn arc(
+        from: Point,
+        rx: f32,
+        ry: f32,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    ) -> Vec<Segment> {
+        EndpointArc {
+            from,
+            rx,
+            ry,
+            x_rotation,
+            large_arc,
+            sweep,
+            to,
+        }
+        .to_cubics()
+    }
+}
+
+/// An elliptical arc in the svg endpoint parameterization, the form of path `A` commands.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct EndpointArc {
+    pub from: Point,
+    pub rx: f32,
+    pub ry: f32,
+    /// The rotation of the ellipse x axis in degrees.
+    pub x_rotation: f32,
+    pub large_arc: bool,
+    pub sweep: bool,
+    pub to: Point,
+}
+
+impl EndpointArc {
+    /// Convert into the center parameterization, see svg 1.1 appendix F.6.5.
+    ///
+    /// Radii that are too small are scaled up, as required by the svg out-of-range correction.
+    /// Returns `None` if the endpoints are identical or either radius is zero, in which cases the arc
+    /// is omitted or drawn as a straight line.
+    pub fn to_center(&self) -> Option<CenterArc> {
+        let (from, to) = (self.from, self.to);
+
+        let (mut rx, mut ry) = (self.rx.abs(), self.ry.abs());
+
+        if from == to || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+
+        let (sin, cos) = self.x_rotation.to_radians().sin_cos();
+
+        let half = (from - to) * 0.5;
+        let x1 = cos * half.x + sin * half.y;
+        let y1 = -sin * half.x + cos * half.y;
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+
+        let mut coef = (num / den).max(0.0).sqrt();
+
+        if self.large_arc == self.sweep {
+            coef = -coef;
+        }
+
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+
+        let center = Point::new(
+            cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0,
+            sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0,
+        );
+
+        let angle = |u: Point, v: Point| u.cross(v).atan2(u.dot(v));
+
+        let u = Point::new((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let v = Point::new((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+
+        let start_angle = angle(Point::new(1.0, 0.0), u);
+        let mut sweep_angle = angle(u, v);
+
+        if !self.sweep && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * std::f32::consts::PI;
+        } else if self.sweep && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * std::f32::consts::PI;
+        }
+
+        Some(CenterArc {
+            center,
+            rx,
+            ry,
+            x_rotation: self.x_rotation,
+            start_angle: start_angle.to_degrees(),
+            sweep_angle: sweep_angle.to_degrees(),
+        })
+    }
+
+    /// Approximate this arc by cubic béziers, at most one per quarter turn.
+    ///
+    /// Returns a line if either radius is zero, and nothing if the endpoints are identical.
+    pub fn to_cubics(&self) -> Vec<Segment> {
+        if self.from == self.to {
+            return vec![];
+        }
+
+        let Some(arc) = self.to_center() else {
+            return vec![Segment::Line {
+                from: self.from,
+                to: self.to,
+            }];
+        };
+
+        let mut segments = arc.to_cubics();
+
+        // snap the endpoints to avoid gaps caused by rounding errors.
+        if let Some(Segment::Cubic { from, .. }) = segments.first_mut() {
+            *from = self.from;
+        }
+
+        if let Some(Segment::Cubic { to, .. }) = segments.last_mut() {
+            *to = self.to;
+        }
+
+        segments
+    }
+}
+
+/// An elliptical arc in the center parameterization, angles are in degrees.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct CenterArc {
+    pub center: Point,
+    pub rx: f32,
+    pub ry: f32,
+    /// The rotation of the ellipse x axis.
+    pub x_rotation: f32,
+    /// The start angle on the unrotated ellipse.
+    pub start_angle: f32,
+    /// The signed sweep angle, positive values go clockwise in the y-down user space.
+    pub sweep_angle: f32,
+}
+
+impl CenterArc {
+    /// Returns the matrix mapping the unit circle to the ellipse.
+    fn matrix(&self) -> Matrix {
+        Matrix::translate(self.center.x, self.center.y)
+            .multiply(&Matrix::rotate(self.x_rotation))
+            .multiply(&Matrix::scale(self.rx, self.ry))
+    }
+
+    /// Returns the point at `angle` degrees on the unrotated ellipse.
+    pub fn point_at(&self, angle: f32) -> Point {
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        self.matrix().transform_point(Point::new(cos, sin))
+    }
+
+    /// Convert into the svg endpoint parameterization.
+    pub fn to_endpoint(&self) -> EndpointArc {
+        EndpointArc {
+            from: self.point_at(self.start_angle),
+            rx: self.rx,
+            ry: self.ry,
+            x_rotation: self.x_rotation,
+            large_arc: self.sweep_angle.abs() > 180.0,
+            sweep: self.sweep_angle > 0.0,
+            to: self.point_at(self.start_angle + self.sweep_angle),
+        }
+    }
+
+    /// Approximate this arc by cubic béziers, at most one per quarter turn.
+    pub fn to_cubics(&self) -> Vec<Segment> {
+        let sweep_angle = self.sweep_angle.to_radians();
+
+        let count = (sweep_angle.abs() / std::f32::consts::FRAC_PI_2)
+            .ceil()
+            .max(1.0) as usize;
+
+        let delta = sweep_angle / count as f32;
+
+        // the control point distance of a unit circle arc with the angle `delta`.
+        let k = 4.0 / 3.0 * (delta / 4.0).tan();
+
+        let matrix = self.matrix();
+
+        (0..count)
+            .map(|i| {
+                let a0 = self.start_angle.to_radians() + delta * i as f32;
+                let a1 = a0 + delta;
+
+                let (sin0, cos0) = a0.sin_cos();
+                let (sin1, cos1) = a1.sin_cos();
+
+                Segment::Cubic {
+                    from: Point::new(cos0, sin0),
+                    ctrl1: Point::new(cos0 - k * sin0, sin0 + k * cos0),
+                    ctrl2: Point::new(cos1 + k * sin1, sin1 - k * cos1),
+                    to: Point::new(cos1, sin1),
+                }
+                .transform(&matrix)
+            })
+            .collect()
+    }
+}
+
+/// A resolved sub-path, a sequence of connected segments.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SubPath {
+    pub segments: Vec<Segment>,
+    /// True if this sub-path is closed by a `ClosePath` command.
+    pub closed: bool,
+}
+
+impl SubPath {
+    /// Create the closed outline of a rectangle with corner radii `rx` and `ry`,
+    /// which are clamped to half the width and height as in the svg `rect` element.
+    pub fn rect(rect: &Rect, rx: f32, ry: f32) -> Self {
+        let rx = rx.clamp(0.0, rect.width / 2.0);
+        let ry = ry.clamp(0.0, rect.height / 2.0);
+
+        let (x0, y0, x1, y1) = (rect.min_x(), rect.min_y(), rect.max_x(), rect.max_y());
+
+        let mut segments = vec![];
+
+        let line = |segments: &mut Vec<Segment>, from: Point, to: Point| {
+            if from != to {
+                segments.push(Segment::Line { from, to });
+            }
+        };
+
+        if rx == 0.0 || ry == 0.0 {
+            let corners = rect.corners();
+
+            for (from, to) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+                line(&mut segments, *from, *to);
+            }
+        } else {
+            let sides = [
+                (Point::new(x0 + rx, y0), Point::new(x1 - rx, y0)),
+                (Point::new(x1, y0 + ry), Point::new(x1, y1 - ry)),
+                (Point::new(x1 - rx, y1), Point::new(x0 + rx, y1)),
+                (Point::new(x0, y1 - ry), Point::new(x0, y0 + ry)),
+            ];
+
+            // each side is followed by the corner arc to the next side.
+            for ((from, to), (next, _)) in sides.iter().zip(sides.iter().cycle().skip(1)) {
+                line(&mut segments, *from, *to);
+                segments.extend(Segment::arc(*to, rx, ry, 0.0, false, true, *next));
+            }
+        }
+
+        Self {
+            segments,
+            closed: true,
+        }
+    }
+}
+
+/// A flattened sub-path.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Polyline {
+    pub points: Vec<Point>,
+    /// True if the last point connects back to the first one.
+    pub closed: bool,
+}
+
+impl Polyline {
+    /// Returns the edges of this polyline, including the closing edge of a closed polyline.
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let count = match self.points.len() {
+            0 | 1 => 0,
+            len if self.closed => len,
+            len => len - 1,
+        };
+
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .take(count)
+            .map(|(from, to)| (*from, *to))
+    }
+
+    /// Returns the length of this polyline.
+    pub fn length(&self) -> f32 {
+        self.edges().map(|(from, to)| from.distance(to)).sum()
+    }
+
+    /// Returns the signed area of this polyline, which is implicitly closed.
+    ///
+    /// The area is positive if the polyline goes clockwise in the y-down user space.
+    pub fn signed_area(&self) -> f32 {
+        let points = &self.points;
+
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(from, to)| from.cross(*to))
+            .sum::<f32>()
+            / 2.0
+    }
+
+    /// Returns the orientation of this polyline, or `None` if the area is zero.
+    pub fn orientation(&self) -> Option<Orientation> {
+        let area = self.signed_area();
+
+        if area > 0.0 {
+            Some(Orientation::Clockwise)
+        } else if area < 0.0 {
+            Some(Orientation::CounterClockwise)
+        } else {
+            None
+        }
+    }
+}
+
+/// The direction of a closed outline in the y-down user space.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// A resolved circle.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Returns true if `point` is in this circle, within the [default tolerance](Tolerance::DEFAULT)
+    /// for rounding errors.
+    pub fn contains(&self, point: Point) -> bool {
+        let distance = self.center.distance(point);
+
+        distance <= self.radius || Tolerance::DEFAULT.eq(distance, self.radius)
+    }
+
+    /// Returns the smallest circle through two points.
+    fn diameter(a: Point, b: Point) -> Self {
+        Self {
+            center: a.lerp(b, 0.5),
+            radius: a.distance(b) / 2.0,
+        }
+    }
+
+    /// Returns the circumcircle of three points, or the smallest circle containing them if they are collinear.
+    fn circumcircle(a: Point, b: Point, c: Point) -> Self {
+        let (ab, ac) = (b - a, c - a);
+        let d = 2.0 * ab.cross(ac);
+
+        if d.abs() <= f32::EPSILON {
+            return [
+                Self::diameter(a, b),
+                Self::diameter(a, c),
+                Self::diameter(b, c),
+            ]
+            .into_iter()
+            .max_by(|x, y| x.radius.total_cmp(&y.radius))
+            .expect("three circles");
+        }
+
+        let center = Point::new(
+            (ac.y * ab.dot(ab) - ab.y * ac.dot(ac)) / d,
+            (ab.x * ac.dot(ac) - ac.x * ab.dot(ab)) / d,
+        );
+
+        Self {
+            center: a + center,
+            radius: center.length(),
+        }
+    }
+}
+
+/// A resolved rectangle rotated by `angle` degrees around its center.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct OrientedRect {
+    pub center: Point,
+    pub width: f32,
+    pub height: f32,
+    pub angle: f32,
+}
+
+impl OrientedRect {
+    /// Returns the four corners, clockwise from the top-left one before the rotation.
+    pub fn corners(&self) -> [Point; 4] {
+        let matrix =
+            Matrix::translate(self.center.x, self.center.y).multiply(&Matrix::rotate(self.angle));
+
+        Rect::new(
+            -self.width / 2.0,
+            -self.height / 2.0,
+            self.width,
+            self.height,
+        )
+        .corners()
+        .map(|p| matrix.transform_point(p))
+    }
+
+    /// Returns the area of this rectangle.
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+}
+
+/// Returns the convex hull of `points` by the monotone chain algorithm.
+///
+/// The hull vertices are ordered clockwise in the y-down user space, starting from the leftmost one,
+/// collinear points on the hull edges are dropped.
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    // builds half of the hull, the last point is the first point of the other half.
+    let half = |points: &mut dyn Iterator<Item = &Point>| {
+        let mut hull: Vec<Point> = vec![];
+
+        for point in points {
+            while let [.., a, b] = hull[..] {
+                if (b - a).cross(*point - a) > 0.0 {
+                    break;
+                }
+
+                hull.pop();
+            }
+
+            hull.push(*point);
+        }
+
+        hull.pop();
+
+        hull
+    };
+
+    let mut hull = half(&mut points.iter());
+
+    hull.extend(half(&mut points.iter().rev()));
+
+    hull
+}
+
+/// Returns the smallest circle containing all `points`, or `None` if `points` is empty.
+pub fn bounding_circle(points: &[Point]) -> Option<Circle> {
+    // the circle is determined by the hull vertices only.
+    let hull = convex_hull(points);
+
+    let mut circle = Circle {
+        center: *hull.first()?,
+        radius: 0.0,
+    };
+
+    // the incremental algorithm of Welzl.
+    for (i, p) in hull.iter().enumerate() {
+        if circle.contains(*p) {
+            continue;
+        }
+
+        circle = Circle {
+            center: *p,
+            radius: 0.0,
+        };
+
+        for (j, q) in hull[..i].iter().enumerate() {
+            if circle.contains(*q) {
+                continue;
+            }
+
+            circle = Circle::diameter(*p, *q);
+
+            for r in &hull[..j] {
+                if !circle.contains(*r) {
+                    circle = Circle::circumcircle(*p, *q, *r);
+                }
+            }
+        }
+    }
+
+    Some(circle)
+}
+
+/// Returns the minimum area rectangle containing all `points`, or `None` if `points` is empty.
+///
+/// One side of the rectangle is collinear with a convex hull edge, which is tested for every edge.
+pub fn oriented_bbox(points: &[Point]) -> Option<OrientedRect> {
+    let hull = convex_hull(points);
+
+    let first = *hull.first()?;
+
+    let mut best = OrientedRect {
+        center: first,
+        ..Default::default()
+    };
+
+    let mut best_area = f32::INFINITY;
+
+    for (from, to) in hull.iter().zip(hull.iter().cycle().skip(1)) {
+        let axis = (*to - *from).normalize();
+
+        if axis == Point::default() {
+            continue;
+        }
+
+        let normal = Point::new(-axis.y, axis.x);
+
+        let (mut min, mut max) = (
+            Point::new(f32::INFINITY, f32::INFINITY),
+            Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+        );
+
+        for point in &hull {
+            let local = Point::new(point.dot(axis), point.dot(normal));
+
+            min = Point::new(min.x.min(local.x), min.y.min(local.y));
+            max = Point::new(max.x.max(local.x), max.y.max(local.y));
+        }
+
+        let (width, height) = (max.x - min.x, max.y - min.y);
+
+        if width * height < best_area {
+            best_area = width * height;
+
+            let mid = min.lerp(max, 0.5);
+
+            best = OrientedRect {
+                center: axis * mid.x + normal * mid.y,
+                width,
+                height,
+                angle: axis.y.atan2(axis.x).to_degrees(),
+            };
+        }
+    }
+
+    Some(best)
+}
+
+/// Returns the winding number of `polylines` around `point`, which are implicitly closed.
+///
+/// The number is positive if the polylines go clockwise around `point` in the y-down user space.
+pub fn winding_number(polylines: &[Polyline], point: Point) -> i32 {
+    let mut winding = 0;
+
+    for polyline in polylines {
+        let points = &polyline.points;
+
+        for (from, to) in points.iter().zip(points.iter().cycle().skip(1)) {
+            let side = (*to - *from).cross(point - *from);
+
+            if from.y <= point.y {
+                if to.y > point.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if to.y <= point.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+
+    winding
+}
+
+/// Appends the intersection points of two line segments, two points for overlapping collinear segments.
+pub(crate) fn line_intersections(
+    p0: Point,
+    p1: Point,
+    q0: Point,
+    q1: Point,
+    output: &mut Vec<Point>,
+) {
+    let (r, s) = (p1 - p0, q1 - q0);
+
+    let denom = r.cross(s);
+    let offset = q0 - p0;
+
+    if denom.abs() > f32::EPSILON * r.length() * s.length() {
+        let t = offset.cross(s) / denom;
+        let u = offset.cross(r) / denom;
+
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            output.push(p0.lerp(p1, t));
+        }
+
+        return;
+    }
+
+    let length = r.dot(r);
+
+    // parallel but not collinear, or degenerated.
+    if length == 0.0 || offset.cross(r).abs() > f32::EPSILON * length.sqrt() * offset.length() {
+        return;
+    }
+
+    let (t0, t1) = (offset.dot(r) / length, (q1 - p0).dot(r) / length);
+
+    let (start, end) = (t0.min(t1).max(0.0), t0.max(t1).min(1.0));
+
+    if start <= end {
+        output.push(p0.lerp(p1, start));
+
+        if start < end {
+            output.push(p0.lerp(p1, end));
+        }
+    }
+}
+
+/// Returns the intersection points of the outlines of `a` and `b`, the curves are flattened with `tolerance`.
+///
+/// The points closer than `tolerance` to each other are merged.
+pub fn intersection_points(a: &[SubPath], b: &[SubPath], tolerance: f32) -> Vec<Point> {
+    let flatten = |subpaths: &[SubPath]| {
+        subpaths
+            .iter()
+            .map(|subpath| {
+                let polyline = subpath.flatten(tolerance);
+                let bounds = Rect::from_points(polyline.points.iter().copied());
+                (polyline, bounds)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let (a, b) = (flatten(a), flatten(b));
+
+    let mut points = vec![];
+
+    for (pa, ba) in &a {
+        for (pb, bb) in &b {
+            let disjoint = match (ba, bb) {
+                (Some(ba), Some(bb)) => {
+                    ba.max_x() < bb.min_x()
+                        || bb.max_x() < ba.min_x()
+                        || ba.max_y() < bb.min_y()
+                        || bb.max_y() < ba.min_y()
+                }
+                _ => true,
+            };
+
+            if disjoint {
+                continue;
+            }
+
+            for (p0, p1) in pa.edges() {
+                for (q0, q1) in pb.edges() {
+                    line_intersections(p0, p1, q0, q1, &mut points);
+                }
+            }
+        }
+    }
+
+    let mut merged: Vec<Point> = vec![];
+
+    for point in points {
+        if merged.iter().all(|p| p.distance(point) > tolerance) {
+            merged.push(point);
+        }
+    }
+
+    merged
+}
+
+/// Returns true if the areas of `a` and `b` filled with the nonzero rule intersect,
+/// the curves are flattened with `tolerance`.
+pub fn intersects(a: &[SubPath], b: &[SubPath], tolerance: f32) -> bool {
+    // areas are implicitly closed.
+    let close = |subpaths: &[SubPath]| {
+        subpaths
+            .iter()
+            .map(|subpath| SubPath {
+                segments: subpath.segments.clone(),
+                closed: true,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let (a, b) = (close(a), close(b));
+
+    if !intersection_points(&a, &b, tolerance).is_empty() {
+        return true;
+    }
+
+    let flatten = |subpaths: &[SubPath]| {
+        subpaths
+            .iter()
+            .map(|subpath| subpath.flatten(tolerance))
+            .collect::<Vec<_>>()
+    };
+
+    let (a, b) = (flatten(&a), flatten(&b));
+
+    // without crossing outlines, one area intersects the other only if it contains the other's vertices.
+    let inside = |area: &[Polyline], other: &[Polyline]| {
+        other
+            .iter()
+            .flat_map(|polyline| polyline.points.first())
+            .any(|point| winding_number(area, *point) != 0)
+    };
+
+    inside(&a, &b) || inside(&b, &a)
+}
+
+/// Returns the outline of `polygon` with every corner replaced by a circular fillet of `radius`.
+///
+/// The radius of a corner is reduced if the fillet doesn't fit in half of the adjacent edges; the end points
+/// of an open polyline and straight corners are kept unchanged.
+pub fn round_corners(polygon: &Polyline, radius: f32) -> SubPath {
+    let mut points = polygon.points.clone();
+    points.dedup();
+
+    if polygon.closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    let len = points.len();
+    let closed = polygon.closed && len > 2;
+
+    // the fillets as (tangent point on the incoming edge, tangent point on the outgoing edge, radius, sweep).
+    let mut corners = vec![];
+
+    for index in 0..len {
+        let point = points[index];
+
+        let (prev, next) = match (index.checked_sub(1), points.get(index + 1)) {
+            (Some(prev), Some(next)) => (points[prev], *next),
+            (None, Some(next)) if closed => (points[len - 1], *next),
+            (Some(prev), None) if closed => (points[prev], points[0]),
+            _ => {
+                corners.push((point, point, 0.0, false));
+                continue;
+            }
+        };
+
+        let incoming = (prev - point).normalize();
+        let outgoing = (next - point).normalize();
+
+        // the half of the interior angle.
+        let half = incoming.dot(outgoing).clamp(-1.0, 1.0).acos() / 2.0;
+        let tan = half.tan();
+
+        if radius <= 0.0 || tan <= f32::EPSILON || half >= std::f32::consts::FRAC_PI_2 - 1e-4 {
+            corners.push((point, point, 0.0, false));
+            continue;
+        }
+
+        let distance = (radius / tan)
+            .min(point.distance(prev) / 2.0)
+            .min(point.distance(next) / 2.0);
+
+        let sweep = (point - prev).cross(next - point) > 0.0;
+
+        corners.push((
+            point + incoming * distance,
+            point + outgoing * distance,
+            distance * tan,
+            sweep,
+        ));
+    }
+
+    let mut segments = vec![];
+
+    let count = if closed { len } else { len.saturating_sub(1) };
+
+    for (index, (_, from, _, _)) in corners.iter().enumerate().take(count) {
+        let (to, end, radius, sweep) = corners[(index + 1) % len];
+
+        if *from != to {
+            segments.push(Segment::Line { from: *from, to });
+        }
+
+        if radius > 0.0 {
+            segments.extend(Segment::arc(to, radius, radius, 0.0, false, sweep, end));
+        }
+    }
+
+    SubPath { segments, closed }
+}
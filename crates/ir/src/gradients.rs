@@ -1,4 +1,4 @@
-use super::{Animatable, FrameVariable, Measurement, Color, Rgba, Transform};
+use super::{Animatable, Color, FrameVariable, Measurement, Rgba, Transform};
 
 /// Defines the coordinate system for attributes ‘x1’, ‘y1’, ‘x2’ and ‘y2’.
 ///
@@ -7,6 +7,7 @@ use super::{Animatable, FrameVariable, Measurement, Color, Rgba, Transform};
 /// Animatable: yes.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum GradientUnits {
     /// If gradientUnits="userSpaceOnUse", ‘x1’, ‘y1’, ‘x2’ and ‘y2’ represent values in the coordinate system
     /// that results from taking the current user coordinate system in place at the time when the gradient element
@@ -43,6 +44,7 @@ impl FrameVariable for GradientUnits {}
 /// If the attribute is not specified, the effect is as if a value of 'pad' were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SpreadMethod {
     Pad,
     Reflect,
@@ -60,6 +62,7 @@ impl FrameVariable for SpreadMethod {}
 /// Linear gradients are defined by a ‘linearGradient’ element.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LinearGradient {
     /// Defines the coordinate system for attributes ‘x1’, ‘y1’, ‘x2’ and ‘y2’.
     pub unit: Animatable<GradientUnits>,
@@ -114,6 +117,7 @@ impl Default for LinearGradient {
 /// Radial gradients are defined by a ‘radialGradient’ element.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RadialGradient {
     /// Defines the coordinate system for attributes ‘x1’, ‘y1’, ‘x2’ and ‘y2’.
     pub unit: Animatable<GradientUnits>,
@@ -194,6 +198,7 @@ impl Default for RadialGradient {
 /// to either the ‘linearGradient’ element or the ‘radialGradient’ element.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GradientStop {
     /// The ‘offset’ attribute is either a `<number>` (usually ranging from 0 to 1) or a `<percentage>`
     /// (usually ranging from 0% to 100%) which indicates where the gradient stop is placed.
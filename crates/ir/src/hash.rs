@@ -0,0 +1,453 @@
+//! Stable content hashing of ir codes, built on top of the serde data model.
+//!
+//! Unlike [`std::hash::Hash`] with the default hasher, the hash values are stable across processes, platforms
+//! and compiler versions, so they can be used as persistent cache keys or etags.
+
+use std::fmt::Display;
+
+use serde::{ser, Serialize};
+
+/// Options of the stable hashing.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct HashOptions {
+    /// If not `None`, floating-point numbers are rounded to the nearest multiple of this value before hashing,
+    /// so values that differ only by representation noise get the same hash.
+    pub quantum: Option<f64>,
+}
+
+impl HashOptions {
+    /// Create options rounding floating-point numbers to the nearest multiple of `quantum`.
+    pub fn quantized(quantum: f64) -> Self {
+        Self {
+            quantum: Some(quantum),
+        }
+    }
+}
+
+/// A type that can be hashed by [`stable_hash`](StableHash::stable_hash).
+///
+/// It's implemented for all serializable types, e.g. [`IR`](crate::IR) elements and `[IR]` programs.
+pub trait StableHash {
+    /// Returns the stable hash value of this object, floating-point numbers are compared bitwise.
+    fn stable_hash(&self) -> u64 {
+        self.stable_hash_with(&HashOptions::default())
+    }
+
+    /// Returns the stable hash value of this object with `options`.
+    fn stable_hash_with(&self, options: &HashOptions) -> u64;
+}
+
+impl<T> StableHash for T
+where
+    T: Serialize + ?Sized,
+{
+    fn stable_hash_with(&self, options: &HashOptions) -> u64 {
+        let mut hasher = StableHasher::new(*options);
+
+        // `StableHasher` never fails, errors raised by the `Serialize` implementation are ignored,
+        // and the hash of the partially serialized value is returned.
+        _ = self.serialize(&mut hasher);
+
+        hasher.finish()
+    }
+}
+
+/// The FNV-1a hasher feeding on the serde data model.
+struct StableHasher {
+    options: HashOptions,
+    state: u64,
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Type tags, which keep values of different types from colliding.
+mod tag {
+    pub const BOOL: u8 = 1;
+    pub const INT: u8 = 2;
+    pub const UINT: u8 = 3;
+    pub const FLOAT: u8 = 4;
+    pub const QUANTIZED: u8 = 5;
+    pub const NAN: u8 = 6;
+    pub const CHAR: u8 = 7;
+    pub const STR: u8 = 8;
+    pub const BYTES: u8 = 9;
+    pub const NONE: u8 = 10;
+    pub const SOME: u8 = 11;
+    pub const UNIT: u8 = 12;
+    pub const VARIANT: u8 = 13;
+    pub const SEQ: u8 = 14;
+    pub const MAP: u8 = 15;
+    pub const STRUCT: u8 = 16;
+    pub const END: u8 = 17;
+}
+
+impl StableHasher {
+    fn new(options: HashOptions) -> Self {
+        Self {
+            options,
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_tag(&mut self, tag: u8) {
+        self.write(&[tag]);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write(&(value.len() as u64).to_le_bytes());
+        self.write(value.as_bytes());
+    }
+
+    fn write_float(&mut self, value: f64) {
+        if value.is_nan() {
+            self.write_tag(tag::NAN);
+            return;
+        }
+
+        match self.options.quantum {
+            Some(quantum) if quantum > 0.0 => {
+                self.write_tag(tag::QUANTIZED);
+                self.write(&((value / quantum).round() as i64).to_le_bytes());
+            }
+            _ => {
+                // `-0.0` and `0.0` are equal.
+                let value = if value == 0.0 { 0.0 } else { value };
+
+                self.write_tag(tag::FLOAT);
+                self.write(&value.to_bits().to_le_bytes());
+            }
+        }
+    }
+
+    fn write_variant(&mut self, name: &str, variant: &str) {
+        self.write_tag(tag::VARIANT);
+        self.write_str(name);
+        self.write_str(variant);
+    }
+}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+impl ser::Serializer for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.write(&[tag::BOOL, v as u8]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.write_tag(tag::INT);
+        self.write(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.write_tag(tag::UINT);
+        self.write(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.write_float(v as f64);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.write_float(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.write_tag(tag::CHAR);
+        self.write(&(v as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.write_tag(tag::STR);
+        self.write_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.write_tag(tag::BYTES);
+        self.write(&(v.len() as u64).to_le_bytes());
+        self.write(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write_tag(tag::NONE);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        self.write_tag(tag::SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.write_tag(tag::UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<()> {
+        self.write_tag(tag::STRUCT);
+        self.write_str(name);
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.write_variant(name, variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_tag(tag::STRUCT);
+        self.write_str(name);
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_variant(name, variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self> {
+        self.write_tag(tag::SEQ);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        self.write_tag(tag::SEQ);
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, _len: usize) -> Result<Self> {
+        self.write_tag(tag::STRUCT);
+        self.write_str(name);
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_variant(name, variant);
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self> {
+        self.write_tag(tag::MAP);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self> {
+        self.write_tag(tag::STRUCT);
+        self.write_str(name);
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_variant(name, variant);
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut StableHasher {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write_tag(tag::END);
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Point-in-shape tests over ir codes, which map pointer events back to elements.
+
+use crate::{
+    bounds::{Symbols, Walker},
+    geometry::{winding_number, Point, Polyline},
+    BoundsOptions, FillRule, ResolveContext, DEFAULT_TOLERANCE, IR,
+};
+
+/// The location of a hit element in the ir codes.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct ElementPath(
+    /// The indices of the enclosing scoped instructions from the outermost one,
+    /// followed by the index of the hit element.
+    pub Vec<usize>,
+);
+
+impl ElementPath {
+    /// Returns the index of the hit element.
+    pub fn index(&self) -> usize {
+        *self.0.last().expect("element path is not empty")
+    }
+}
+
+/// Returns the elements under `point`, from the topmost one to the bottommost one.
+///
+/// `point` is in the root user space, the inverse transformations of the enclosing layers are applied to it.
+/// An element is hit if `point` is in its interior under the inherited fill rule if it is filled, or within half
+/// the stroke width of its outline if it is stroked; stroke line caps and joins are not considered.
+///
+/// A [`Use`](crate::Use) element is hit if an element in the contents of its symbol is hit.
+///
+/// Elements with animated geometry and text elements are skipped.
+pub fn hit_test(point: Point, codes: &[IR], ctx: &ResolveContext) -> Vec<ElementPath> {
+    let mut walker = Walker::new(*ctx);
+    let symbols = Symbols::new(codes);
+    let mut scopes = vec![];
+    let mut hits = vec![];
+
+    for (index, ir) in codes.iter().enumerate() {
+        let hit = match ir {
+            IR::Use(value) => {
+                let mut hit = false;
+
+                walker.instance(
+                    value,
+                    &symbols,
+                    &BoundsOptions::default(),
+                    &mut |walker, ir| {
+                        hit = hit || is_hit(walker, ir, point);
+                    },
+                );
+
+                hit
+            }
+            ir => is_hit(&walker, ir, point),
+        };
+
+        if hit {
+            let mut path = scopes.clone();
+            path.push(index);
+            hits.push(ElementPath(path));
+        }
+
+        match ir {
+            IR::Pop(n) => scopes.truncate(scopes.len().saturating_sub(*n)),
+            ir if ir.is_scoped() => scopes.push(index),
+            _ => {}
+        }
+
+        walker.next(ir, &BoundsOptions::default());
+    }
+
+    hits.reverse();
+
+    hits
+}
+
+/// Returns true if `ir` is a shape under `point` with the states of `walker`.
+fn is_hit(walker: &Walker, ir: &IR, point: Point) -> bool {
+    let outline = match ir {
+        IR::Rect(rect) if !walker.state().disabled => {
+            walker.rect_outline(rect).map(|outline| vec![outline])
+        }
+        IR::Path(path) if !walker.state().disabled => walker.path_outline(path),
+        _ => None,
+    };
+
+    let Some(outline) = outline else {
+        return false;
+    };
+
+    let state = walker.state();
+
+    state.matrix.invert().is_some_and(|inverse| {
+        let point = inverse.transform_point(point);
+
+        let polylines = outline
+            .iter()
+            .map(|subpath| subpath.flatten(DEFAULT_TOLERANCE))
+            .collect::<Vec<_>>();
+
+        (state.fill_paint && contains(&polylines, point, state.fill_rule))
+            || (state.stroke_paint && on_stroke(&polylines, point, state.stroke.width / 2.0))
+    })
+}
+
+/// Returns true if `point` is in the interior of `polylines` under the fill `rule`.
+fn contains(polylines: &[Polyline], point: Point, rule: FillRule) -> bool {
+    let winding = winding_number(polylines, point);
+
+    match rule {
+        FillRule::Nonzero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// Returns true if the distance between `point` and the edges of `polylines` is not greater than `radius`.
+fn on_stroke(polylines: &[Polyline], point: Point, radius: f32) -> bool {
+    polylines
+        .iter()
+        .flat_map(Polyline::edges)
+        .any(|(from, to)| {
+            let edge = to - from;
+            let length = edge.dot(edge);
+
+            let t = if length > 0.0 {
+                ((point - from).dot(edge) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            from.lerp(to, t).distance(point) <= radius
+        })
+}
@@ -0,0 +1,183 @@
+//! Dictionary-based hyphenation with Liang's algorithm, the one used by TeX.
+//!
+//! The dictionaries are the TeX hyphenation patterns of a language, e.g. the `hyph-en-us.pat.txt` file of the
+//! [hyph-utf8](https://github.com/hyphenation/tex-hyphen) project, and optional exception words.
+
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
+use core::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Finds the hyphenation points of words by the patterns of one language.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Hyphenator {
+    /// The letters of a pattern and the values between them, e.g. `hy3ph` is `hyph => [0, 0, 3, 0, 0]`.
+    patterns: BTreeMap<String, Vec<u8>>,
+    /// The hyphenation points of the exception words, in characters.
+    exceptions: BTreeMap<String, Vec<usize>>,
+    /// The number of letters of the longest pattern.
+    max_pattern: usize,
+    /// The minimum number of characters before the first hyphen, defaults to `2`.
+    pub left_min: usize,
+    /// The minimum number of characters after the last hyphen, defaults to `3`.
+    pub right_min: usize,
+}
+
+impl Default for Hyphenator {
+    fn default() -> Self {
+        Self {
+            patterns: BTreeMap::new(),
+            exceptions: BTreeMap::new(),
+            max_pattern: 0,
+            left_min: 2,
+            right_min: 3,
+        }
+    }
+}
+
+impl FromStr for Hyphenator {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl Hyphenator {
+    /// Parse whitespace separated TeX patterns, e.g. `.ach4 hy3ph 1na`, the `%` comments are skipped.
+    pub fn parse(patterns: &str) -> Result<Self> {
+        let mut hyphenator = Self::default();
+
+        for line in patterns.lines() {
+            let line = line.split('%').next().unwrap_or_default();
+
+            for pattern in line.split_whitespace() {
+                hyphenator.add_pattern(pattern)?;
+            }
+        }
+
+        Ok(hyphenator)
+    }
+
+    fn add_pattern(&mut self, pattern: &str) -> Result<()> {
+        let invalid = || Error::Hyphenation(format!("invalid pattern `{}`", pattern));
+
+        let mut letters = String::new();
+        let mut values = vec![0];
+
+        for c in pattern.chars() {
+            match c.to_digit(10) {
+                Some(value) => {
+                    let last = values.last_mut().expect("at least one value");
+
+                    // two digits in a row.
+                    if *last != 0 {
+                        return Err(invalid());
+                    }
+
+                    *last = value as u8;
+                }
+                None => {
+                    letters.extend(c.to_lowercase());
+                    values.push(0);
+                }
+            }
+        }
+
+        if letters.is_empty() {
+            return Err(invalid());
+        }
+
+        self.max_pattern = self.max_pattern.max(values.len() - 1);
+        self.patterns.insert(letters, values);
+
+        Ok(())
+    }
+
+    /// Adds whitespace separated exception words with explicit hyphens, e.g. `as-so-ciate`, which replace the
+    /// patterns for these words.
+    pub fn add_exceptions(&mut self, exceptions: &str) -> &mut Self {
+        for exception in exceptions.split_whitespace() {
+            let mut word = String::new();
+            let mut points = vec![];
+            let mut len = 0;
+
+            for c in exception.chars() {
+                if c == '-' {
+                    points.push(len);
+                } else {
+                    word.extend(c.to_lowercase());
+                    len += 1;
+                }
+            }
+
+            self.exceptions.insert(word, points);
+        }
+
+        self
+    }
+
+    /// Returns the byte offsets in `word` where a hyphen can be inserted, in ascending order.
+    ///
+    /// `word` should be a single word without punctuations, words shorter than `left_min + right_min` are
+    /// not hyphenated.
+    pub fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let chars = word.chars().collect::<Vec<_>>();
+        let len = chars.len();
+
+        if len < self.left_min + self.right_min {
+            return vec![];
+        }
+
+        let lowercase = word.to_lowercase();
+
+        // the char positions before which a hyphen can be inserted.
+        let points = match self.exceptions.get(&lowercase) {
+            Some(points) => points.clone(),
+            None => {
+                // the word is enclosed in dots, which match the patterns at the word boundaries.
+                let letters = core::iter::once('.')
+                    .chain(chars.iter().flat_map(|c| c.to_lowercase()))
+                    .chain(core::iter::once('.'))
+                    .collect::<Vec<_>>();
+
+                // folding changed the length, the points can't be mapped back to the word.
+                if letters.len() != len + 2 {
+                    return vec![];
+                }
+
+                // `values[i]` is the value between `letters[i - 1]` and `letters[i]`.
+                let mut values = vec![0u8; letters.len() + 1];
+
+                for start in 0..letters.len() {
+                    let end = letters.len().min(start + self.max_pattern);
+
+                    for stop in start + 1..=end {
+                        let key = letters[start..stop].iter().collect::<String>();
+
+                        if let Some(pattern) = self.patterns.get(&key) {
+                            for (offset, value) in pattern.iter().enumerate() {
+                                let slot = &mut values[start + offset];
+                                *slot = (*slot).max(*value);
+                            }
+                        }
+                    }
+                }
+
+                // before the word char `i` is between `letters[i]` and `letters[i + 1]`.
+                (1..len).filter(|i| values[i + 1] % 2 == 1).collect()
+            }
+        };
+
+        let offsets = word
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .collect::<Vec<_>>();
+
+        points
+            .into_iter()
+            .filter(|point| *point >= self.left_min && len - *point >= self.right_min)
+            .filter_map(|point| offsets.get(point).copied())
+            .collect()
+    }
+}
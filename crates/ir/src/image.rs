@@ -0,0 +1,106 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use super::{Animatable, Href, Measurement, PreserveAspectRatio};
+
+/// The source of an [`Image`].
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ImageSource {
+    /// An IRI reference to an external image.
+    Href(Href),
+    /// An embedded image.
+    Data {
+        /// The media type of `data`, e.g. `image/png`.
+        mime: String,
+        /// The encoded image file.
+        data: Vec<u8>,
+    },
+}
+
+impl Default for ImageSource {
+    fn default() -> Self {
+        Self::Href(Href(String::new()))
+    }
+}
+
+impl From<Href> for ImageSource {
+    fn from(value: Href) -> Self {
+        Self::Href(value)
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(value: &str) -> Self {
+        Self::Href(Href(value.to_owned()))
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(value: String) -> Self {
+        Self::Href(Href(value))
+    }
+}
+
+impl ImageSource {
+    /// Create an embedded image source from the encoded image file `data` of media type `mime`.
+    pub fn data<M, D>(mime: M, data: D) -> Self
+    where
+        M: Into<String>,
+        D: Into<Vec<u8>>,
+    {
+        Self::Data {
+            mime: mime.into(),
+            data: data.into(),
+        }
+    }
+}
+
+/// The ‘image’ element indicates that the contents of a complete file are to be rendered into a given rectangle
+/// within the current user coordinate system.
+///
+/// See [`image`](https://www.w3.org/TR/SVG11/struct.html#ImageElement)
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Image {
+    /// The image to render, an external reference or an embedded file.
+    #[cfg_attr(feature = "dsl", dsl(required))]
+    pub source: ImageSource,
+
+    /// The x-axis coordinate of one corner of the rectangular region into which the referenced image is placed.
+    ///
+    /// If the attribute is not specified, the effect is as if a value of "0" were specified.
+    ///
+    /// Animatable: yes.
+    pub x: Animatable<Measurement>,
+
+    /// The y-axis coordinate of one corner of the rectangular region into which the referenced image is placed.
+    ///
+    /// If the attribute is not specified, the effect is as if a value of "0" were specified.
+    ///
+    /// Animatable: yes.
+    pub y: Animatable<Measurement>,
+
+    /// The width of the rectangular region into which the referenced image is placed.
+    ///
+    /// A negative value is an error (see Error processing). A value of zero disables rendering of the element.
+    ///
+    /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
+    pub width: Animatable<Measurement>,
+
+    /// The height of the rectangular region into which the referenced image is placed.
+    ///
+    /// A negative value is an error (see Error processing). A value of zero disables rendering of the element.
+    ///
+    /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
+    pub height: Animatable<Measurement>,
+
+    /// How the image is fitted into the rectangular region, see [`PreserveAspectRatio`].
+    ///
+    /// Animatable: yes.
+    pub aspect: Option<Animatable<PreserveAspectRatio>>,
+}
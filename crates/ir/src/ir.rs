@@ -1,8 +1,15 @@
-use crate::{Fill, Font, Layer, Rect, Stroke, Text, TextLayout, TextSpan};
+use alloc::{boxed::Box, string::String};
+
+use crate::{
+    Animatable, Fe, FeComposite, FeFlood, FeGaussianBlur, FeMerge, FeMergeItem, FeOffset, Fill,
+    FilterRef, Font, Image, Label, Layer, Marker, Markers, Mask, MaskRef, Path, Rect, Stroke,
+    SymbolDef, Text, TextLayout, TextSpan, Transform, Use,
+};
 
 /// A type that representation a cotai script instruction.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum IR {
     /// Pop the top(n) instructions from the `multiline instruction stack`.
     Pop(usize),
@@ -32,6 +39,116 @@ pub enum IR {
     Font(Box<Font>),
     /// text-layout properties.
     TextLayout(Box<TextLayout>),
+    /// Assigns id and class names to the elements in scope.
+    Label(Box<Label>),
+    /// Transforms the user coordinate system of the elements in scope.
+    Transform(Box<Animatable<Transform>>),
+    /// A mask element, the elements in scope are the mask contents.
+    Mask(Box<Mask>),
+    /// Applies a mask to the elements in scope.
+    MaskRef(Box<MaskRef>),
+    /// A marker element, the elements in scope are the marker contents.
+    Marker(Box<Marker>),
+    /// Attaches markers to the elements in scope.
+    Markers(Box<Markers>),
+    /// A symbol element, the elements in scope are the symbol contents.
+    Symbol(Box<SymbolDef>),
+    /// Renders an instance of a symbol.
+    Use(Box<Use>),
+    /// A raster image element.
+    Image(Box<Image>),
+    /// A filter element, the filter primitives in scope are applied in order.
+    Filter(Box<Fe>),
+    /// Applies a filter to the elements in scope.
+    FilterRef(Box<FilterRef>),
+    /// A gaussian blur filter primitive.
+    FeGaussianBlur(Box<FeGaussianBlur>),
+    /// An offset filter primitive.
+    FeOffset(Box<FeOffset>),
+    /// A flood filter primitive.
+    FeFlood(Box<FeFlood>),
+    /// A composite filter primitive.
+    FeComposite(Box<FeComposite>),
+    /// A merge filter primitive, the [`FeMergeItem`]s in scope are the merged layers.
+    FeMerge(Box<FeMerge>),
+    /// A layer of the enclosing merge filter primitive.
+    FeMergeItem(Box<FeMergeItem>),
+    /// Basic path shape.
+    ///
+    /// Appended after the other instructions, so the serialized indexes of the existing ones are unchanged.
+    Path(Box<Path>),
+}
+
+impl IR {
+    /// Returns the name of this instruction, e.g. `rect` or `pop`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            IR::Pop(_) => "pop",
+            IR::String(_) => "string",
+            IR::Animated(_) => "animated",
+            IR::Text(_) => "text",
+            IR::TextSpan(_) => "text_span",
+            IR::Layer(_) => "layer",
+            IR::Rect(_) => "rect",
+            IR::Fill(_) => "fill",
+            IR::Stroke(_) => "stroke",
+            IR::Font(_) => "font",
+            IR::TextLayout(_) => "text_layout",
+            IR::Label(_) => "label",
+            IR::Transform(_) => "transform",
+            IR::Mask(_) => "mask",
+            IR::MaskRef(_) => "mask_ref",
+            IR::Marker(_) => "marker",
+            IR::Markers(_) => "markers",
+            IR::Symbol(_) => "symbol",
+            IR::Use(_) => "use",
+            IR::Image(_) => "image",
+            IR::Filter(_) => "filter",
+            IR::FilterRef(_) => "filter_ref",
+            IR::FeGaussianBlur(_) => "fe_gaussian_blur",
+            IR::FeOffset(_) => "fe_offset",
+            IR::FeFlood(_) => "fe_flood",
+            IR::FeComposite(_) => "fe_composite",
+            IR::FeMerge(_) => "fe_merge",
+            IR::FeMergeItem(_) => "fe_merge_item",
+            IR::Path(_) => "path",
+        }
+    }
+
+    /// Returns true if this instruction must be closed by a paired `pop` instruction.
+    pub fn is_scoped(&self) -> bool {
+        match self {
+            IR::Pop(_)
+            | IR::String(_)
+            | IR::Animated(_)
+            | IR::Rect(_)
+            | IR::Use(_)
+            | IR::Image(_)
+            | IR::FeGaussianBlur(_)
+            | IR::FeOffset(_)
+            | IR::FeFlood(_)
+            | IR::FeComposite(_)
+            | IR::FeMergeItem(_)
+            | IR::Path(_) => false,
+            IR::Text(_)
+            | IR::TextSpan(_)
+            | IR::Layer(_)
+            | IR::Fill(_)
+            | IR::Stroke(_)
+            | IR::Font(_)
+            | IR::TextLayout(_)
+            | IR::Label(_)
+            | IR::Transform(_)
+            | IR::Mask(_)
+            | IR::MaskRef(_)
+            | IR::Marker(_)
+            | IR::Markers(_)
+            | IR::Symbol(_)
+            | IR::Filter(_)
+            | IR::FilterRef(_)
+            | IR::FeMerge(_) => true,
+        }
+    }
 }
 
 impl From<Text> for IR {
@@ -52,6 +169,12 @@ impl From<Rect> for IR {
     }
 }
 
+impl From<Path> for IR {
+    fn from(value: Path) -> Self {
+        IR::Path(Box::new(value))
+    }
+}
+
 impl From<Fill> for IR {
     fn from(value: Fill) -> Self {
         IR::Fill(Box::new(value))
@@ -81,3 +204,111 @@ impl From<TextSpan> for IR {
         IR::TextSpan(Box::new(value))
     }
 }
+
+impl From<Label> for IR {
+    fn from(value: Label) -> Self {
+        IR::Label(Box::new(value))
+    }
+}
+
+impl From<Transform> for IR {
+    fn from(value: Transform) -> Self {
+        IR::Transform(Box::new(Animatable::Constant(value)))
+    }
+}
+
+impl From<Animatable<Transform>> for IR {
+    fn from(value: Animatable<Transform>) -> Self {
+        IR::Transform(Box::new(value))
+    }
+}
+
+impl From<Mask> for IR {
+    fn from(value: Mask) -> Self {
+        IR::Mask(Box::new(value))
+    }
+}
+
+impl From<MaskRef> for IR {
+    fn from(value: MaskRef) -> Self {
+        IR::MaskRef(Box::new(value))
+    }
+}
+
+impl From<Marker> for IR {
+    fn from(value: Marker) -> Self {
+        IR::Marker(Box::new(value))
+    }
+}
+
+impl From<Markers> for IR {
+    fn from(value: Markers) -> Self {
+        IR::Markers(Box::new(value))
+    }
+}
+
+impl From<SymbolDef> for IR {
+    fn from(value: SymbolDef) -> Self {
+        IR::Symbol(Box::new(value))
+    }
+}
+
+impl From<Use> for IR {
+    fn from(value: Use) -> Self {
+        IR::Use(Box::new(value))
+    }
+}
+
+impl From<Image> for IR {
+    fn from(value: Image) -> Self {
+        IR::Image(Box::new(value))
+    }
+}
+
+impl From<Fe> for IR {
+    fn from(value: Fe) -> Self {
+        IR::Filter(Box::new(value))
+    }
+}
+
+impl From<FilterRef> for IR {
+    fn from(value: FilterRef) -> Self {
+        IR::FilterRef(Box::new(value))
+    }
+}
+
+impl From<FeGaussianBlur> for IR {
+    fn from(value: FeGaussianBlur) -> Self {
+        IR::FeGaussianBlur(Box::new(value))
+    }
+}
+
+impl From<FeOffset> for IR {
+    fn from(value: FeOffset) -> Self {
+        IR::FeOffset(Box::new(value))
+    }
+}
+
+impl From<FeFlood> for IR {
+    fn from(value: FeFlood) -> Self {
+        IR::FeFlood(Box::new(value))
+    }
+}
+
+impl From<FeComposite> for IR {
+    fn from(value: FeComposite) -> Self {
+        IR::FeComposite(Box::new(value))
+    }
+}
+
+impl From<FeMerge> for IR {
+    fn from(value: FeMerge) -> Self {
+        IR::FeMerge(Box::new(value))
+    }
+}
+
+impl From<FeMergeItem> for IR {
+    fn from(value: FeMergeItem) -> Self {
+        IR::FeMergeItem(Box::new(value))
+    }
+}
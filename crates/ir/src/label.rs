@@ -0,0 +1,31 @@
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+/// Assigns an `id` and `class` names to a group of graphic elements, see [`Stylesheet`](crate::Stylesheet).
+///
+/// Like other scoped instructions, a label must be closed by a paired `pop` instruction.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Label {
+    /// The unique name of this group.
+    pub id: Option<String>,
+    /// A list of class names, used by the stylesheet `.class` selectors.
+    pub class: Vec<String>,
+}
+
+impl Label {
+    /// Returns true if this label has the class `name`.
+    pub fn has_class(&self, name: &str) -> bool {
+        self.class.iter().any(|v| v == name)
+    }
+}
+
+impl From<&str> for Label {
+    fn from(value: &str) -> Self {
+        Self {
+            id: Some(value.to_owned()),
+            class: vec![],
+        }
+    }
+}
@@ -1,14 +1,18 @@
+
 use super::{Animatable, Measurement, ViewBox};
 use vglang_derive::Dsl;
 
 /// Create a new layer into which the backend render child elements.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
-#[cfg_attr(feature = "dsl", derive(Dsl))]
+#[cfg_attr(feature = "dsl", derive(Dsl), dsl(builder))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Layer {
     /// a number (usually an integer) that represents the width of the rendering layer.
+    #[cfg_attr(feature = "dsl", dsl(required, range(0.0..)))]
     pub width: Animatable<Measurement>,
     /// a number (usually an integer) that represents the height of the rendering layer.
+    #[cfg_attr(feature = "dsl", dsl(required, range(0.0..)))]
     pub height: Animatable<Measurement>,
     /// stretch to fit a particular container element.
     pub viewbox: Option<Animatable<ViewBox>>,
@@ -1,5 +1,12 @@
 //! An intermediate representation of the `VGL` language.
 //!
+//! Without the default `std` feature, the crate is `no_std` and only requires `alloc`: the ir types, their parsers,
+//! [`verify`] and the serde support are available, while the geometry algorithms(bounds, flattening, stroking,
+//! tessellation, etc.), the json/cbor formats, the content hash and the fonts require `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 mod errors;
 pub use errors::*;
@@ -51,3 +58,136 @@ pub use text::*;
 
 mod font;
 pub use font::*;
+
+mod label;
+pub use label::*;
+
+mod symbol;
+pub use symbol::*;
+
+mod image;
+pub use image::*;
+
+mod style;
+pub use style::*;
+
+mod rich_text;
+pub use rich_text::*;
+
+mod white_space;
+pub use white_space::*;
+
+mod verify;
+pub use verify::*;
+
+mod registers;
+pub use registers::*;
+
+mod optimize;
+pub use optimize::*;
+
+#[cfg(feature = "std")]
+pub mod geometry;
+
+#[cfg(feature = "std")]
+mod tolerance;
+#[cfg(feature = "std")]
+pub use tolerance::*;
+
+#[cfg(feature = "std")]
+mod resolve;
+#[cfg(feature = "std")]
+pub use resolve::*;
+
+#[cfg(feature = "std")]
+mod bounds;
+#[cfg(feature = "std")]
+pub use bounds::*;
+
+#[cfg(feature = "std")]
+mod flatten;
+#[cfg(feature = "std")]
+pub use flatten::*;
+
+#[cfg(feature = "std")]
+mod measure;
+#[cfg(feature = "std")]
+pub use measure::*;
+
+#[cfg(feature = "std")]
+mod hit;
+#[cfg(feature = "std")]
+pub use hit::*;
+
+#[cfg(feature = "std")]
+mod dash;
+#[cfg(feature = "std")]
+pub use dash::*;
+
+#[cfg(feature = "std")]
+mod stroker;
+#[cfg(feature = "std")]
+pub use stroker::*;
+
+#[cfg(feature = "std")]
+mod tessellate;
+#[cfg(feature = "std")]
+pub use tessellate::*;
+
+#[cfg(feature = "std")]
+mod snap;
+#[cfg(feature = "std")]
+pub use snap::*;
+
+#[cfg(feature = "std")]
+mod winding;
+
+#[cfg(feature = "std")]
+mod clip;
+#[cfg(feature = "std")]
+pub use clip::*;
+
+#[cfg(feature = "std")]
+mod spatial;
+#[cfg(feature = "std")]
+pub use spatial::*;
+
+#[cfg(feature = "serde")]
+mod loader;
+#[cfg(feature = "serde")]
+pub use loader::*;
+
+#[cfg(all(feature = "serde", feature = "std"))]
+mod hash;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use hash::*;
+
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "schema")]
+pub use schema::*;
+
+#[cfg(feature = "hyphenation")]
+mod hyphenation;
+#[cfg(feature = "hyphenation")]
+pub use hyphenation::*;
+
+#[cfg(feature = "font")]
+mod face;
+#[cfg(feature = "font")]
+pub use face::*;
+
+#[cfg(feature = "font")]
+mod text_path;
+#[cfg(feature = "font")]
+pub use text_path::*;
+
+#[cfg(feature = "font")]
+mod wrap;
+#[cfg(feature = "font")]
+pub use wrap::*;
+
+#[cfg(feature = "font")]
+mod truncate;
+#[cfg(feature = "font")]
+pub use truncate::*;
@@ -0,0 +1,130 @@
+//! A versioned, validated ingestion path for serialized ir codes.
+//!
+//! Serialized programs are wrapped into an [`Envelope`], which carries the [`IR_VERSION`] they were
+//! produced with. The loaders in this module refuse unknown versions and run [`validate`] over the
+//! decoded body, so ir codes received from an untrusted peer can be handed to a device safely.
+
+use crate::{verify, Error, Result, IR};
+use alloc::{format, vec::Vec};
+
+/// The current version of the serialized ir format.
+pub const IR_VERSION: u32 = 1;
+
+/// The serialized form of one ir program.
+#[derive(Debug, PartialEq, PartialOrd, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Envelope {
+    /// The format version of `body`, see [`IR_VERSION`].
+    pub version: u32,
+    /// The ir codes stream.
+    pub body: Vec<IR>,
+}
+
+impl From<Vec<IR>> for Envelope {
+    fn from(body: Vec<IR>) -> Self {
+        Self {
+            version: IR_VERSION,
+            body,
+        }
+    }
+}
+
+/// Only used to check the version before decoding the body.
+#[cfg(any(feature = "json", feature = "cbor"))]
+#[derive(serde::Deserialize)]
+struct Header {
+    version: u32,
+}
+
+#[cfg(any(feature = "json", feature = "cbor", feature = "postcard"))]
+fn check_version(version: u32) -> Result<()> {
+    if version != IR_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    Ok(())
+}
+
+/// Load an [`Envelope`] from a json document and [`validate`] its body.
+#[cfg(feature = "json")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(len = json.len())))]
+pub fn from_json_validated(json: &str) -> Result<Vec<IR>> {
+    let header: Header = serde_json::from_str(json)?;
+
+    check_version(header.version)?;
+
+    let envelope: Envelope = serde_json::from_str(json)?;
+
+    validate(&envelope.body)?;
+
+    Ok(envelope.body)
+}
+
+/// Load an [`Envelope`] from a cbor document and [`validate`] its body.
+#[cfg(feature = "cbor")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(len = cbor.len())))]
+pub fn from_cbor_validated(cbor: &[u8]) -> Result<Vec<IR>> {
+    let header: Header = ciborium::from_reader(cbor)?;
+
+    check_version(header.version)?;
+
+    let envelope: Envelope = ciborium::from_reader(cbor)?;
+
+    validate(&envelope.body)?;
+
+    Ok(envelope.body)
+}
+
+/// Serializes like [`Envelope`] without owning the body.
+#[cfg(feature = "postcard")]
+#[derive(serde::Serialize)]
+struct EnvelopeRef<'a> {
+    version: u32,
+    body: &'a [IR],
+}
+
+/// Encode `codes` into a postcard [`Envelope`], a compact binary encoding to cache the ir codes on disk or ship
+/// them between services.
+#[cfg(feature = "postcard")]
+pub fn to_postcard(codes: &[IR]) -> Result<Vec<u8>> {
+    let envelope = EnvelopeRef {
+        version: IR_VERSION,
+        body: codes,
+    };
+
+    Ok(postcard::to_allocvec(&envelope)?)
+}
+
+/// Load an [`Envelope`] from a postcard document and [`validate`] its body.
+///
+/// The version is the leading field of the document, it is checked before the body is decoded.
+///
+/// The decoded codes own their strings, they are not borrowed from `bytes`: [`IR`] has no lifetime, and a
+/// borrowing copy of every instruction type would double the ir surface, so zero-copy reads are not supported.
+#[cfg(feature = "postcard")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(len = bytes.len())))]
+pub fn from_postcard_validated(bytes: &[u8]) -> Result<Vec<IR>> {
+    let (version, body) = postcard::take_from_bytes::<u32>(bytes)?;
+
+    check_version(version)?;
+
+    let body: Vec<IR> = postcard::from_bytes(body)?;
+
+    validate(&body)?;
+
+    Ok(body)
+}
+
+/// Check the ir codes stream with [`verify`].
+///
+/// Returns [`Error::Validation`] with the path (e.g. `body[3].width`) of the first violation.
+pub fn validate(codes: &[IR]) -> Result<()> {
+    verify(codes).map_err(|violations| {
+        let violation = &violations[0];
+
+        Error::Validation {
+            path: format!("body[{}]{}", violation.index, violation.field),
+            message: violation.message.clone(),
+        }
+    })
+}
@@ -0,0 +1,182 @@
+//! Distance-along-a-path computations, used by text on a path, motion animations and dashing.
+
+use crate::{
+    flatten::flatten_segment,
+    geometry::{Point, Segment, SubPath},
+    Path, ResolveContext, DEFAULT_TOLERANCE,
+};
+
+/// A vertex of the flattened path.
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    /// The index of the segment this vertex belongs to.
+    segment: usize,
+    /// The parameter of this vertex in the segment.
+    t: f32,
+    /// The distance from the start of the path.
+    distance: f32,
+}
+
+/// The precomputed arc length table of resolved sub-paths, for repeated distance queries.
+///
+/// Sub-paths are measured one after another, the gaps between sub-paths don't count.
+#[derive(Debug, Default, Clone)]
+pub struct PathMeasure {
+    segments: Vec<Segment>,
+    vertices: Vec<Vertex>,
+}
+
+impl PathMeasure {
+    /// Measure `subpaths`, curves are flattened with the maximum error `tolerance`.
+    pub fn new(subpaths: &[SubPath], tolerance: f32) -> Self {
+        let mut measure = Self::default();
+        let mut distance = 0.0;
+
+        for subpath in subpaths {
+            let Some(first) = subpath.segments.first() else {
+                continue;
+            };
+
+            let mut point = first.from();
+
+            measure.vertices.push(Vertex {
+                segment: measure.segments.len(),
+                t: 0.0,
+                distance,
+            });
+
+            for segment in &subpath.segments {
+                let index = measure.segments.len();
+
+                flatten_segment(segment, tolerance, |t, to| {
+                    distance += point.distance(to);
+                    point = to;
+
+                    measure.vertices.push(Vertex {
+                        segment: index,
+                        t,
+                        distance,
+                    });
+                });
+
+                measure.segments.push(*segment);
+            }
+        }
+
+        measure
+    }
+
+    /// Returns the total length.
+    pub fn length(&self) -> f32 {
+        self.vertices.last().map_or(0.0, |v| v.distance)
+    }
+
+    /// Returns the segment index and the parameter at `distance`, which is clamped to `[0, length]`.
+    fn locate(&self, distance: f32) -> Option<(usize, f32)> {
+        let first = self.vertices.first()?;
+
+        let end = self
+            .vertices
+            .partition_point(|v| v.distance < distance)
+            .min(self.vertices.len() - 1);
+
+        if end == 0 {
+            return Some((first.segment, first.t));
+        }
+
+        let (from, to) = (&self.vertices[end - 1], &self.vertices[end]);
+
+        let t0 = if from.segment == to.segment {
+            from.t
+        } else {
+            0.0
+        };
+
+        let length = to.distance - from.distance;
+
+        let t = if length > 0.0 {
+            t0 + (to.t - t0) * ((distance - from.distance) / length).clamp(0.0, 1.0)
+        } else {
+            to.t
+        };
+
+        Some((to.segment, t))
+    }
+
+    /// Returns the point at `distance` along the path, or `None` if the path is empty.
+    pub fn point_at(&self, distance: f32) -> Option<Point> {
+        self.locate(distance)
+            .map(|(index, t)| self.segments[index].eval(t))
+    }
+
+    /// Returns the part of the path between the distances `start` and `end`, which are clamped to `[0, length]`.
+    ///
+    /// The segments are connected only if the measured path is a single sub-path.
+    pub fn extract(&self, start: f32, end: f32) -> Vec<Segment> {
+        let (Some((i0, t0)), Some((i1, t1))) = (self.locate(start), self.locate(end)) else {
+            return vec![];
+        };
+
+        if (i0, t0) > (i1, t1) {
+            return vec![];
+        }
+
+        if i0 == i1 {
+            return vec![self.segments[i0].slice(t0, t1)];
+        }
+
+        let mut segments = vec![];
+
+        if t0 < 1.0 {
+            segments.push(self.segments[i0].slice(t0, 1.0));
+        }
+
+        segments.extend_from_slice(&self.segments[i0 + 1..i1]);
+
+        if t1 > 0.0 {
+            segments.push(self.segments[i1].slice(0.0, t1));
+        }
+
+        segments
+    }
+
+    /// Returns the unit tangent vector at `distance` along the path, or `None` if the path is empty.
+    ///
+    /// The chord direction is used where the derivative vanishes, e.g. at a cusp.
+    pub fn tangent_at(&self, distance: f32) -> Option<Point> {
+        let (index, t) = self.locate(distance)?;
+        let segment = &self.segments[index];
+
+        let tangent = segment.derivative(t);
+
+        if tangent.length() > f32::EPSILON {
+            Some(tangent.normalize())
+        } else {
+            Some((segment.to() - segment.from()).normalize())
+        }
+    }
+}
+
+impl Path {
+    /// Measure this path with the [`DEFAULT_TOLERANCE`], returns `None` if the path data is animated.
+    pub fn measure(&self, ctx: &ResolveContext) -> Option<PathMeasure> {
+        Some(PathMeasure::new(&ctx.path(self)?, DEFAULT_TOLERANCE))
+    }
+
+    /// Returns the computed length of this path in user units, the author's `length` field is not used.
+    ///
+    /// Use [`measure`](Path::measure) to make repeated queries on the same path.
+    pub fn length(&self, ctx: &ResolveContext) -> Option<f32> {
+        self.measure(ctx).map(|measure| measure.length())
+    }
+
+    /// Returns the point at `distance` user units along this path.
+    pub fn point_at(&self, ctx: &ResolveContext, distance: f32) -> Option<Point> {
+        self.measure(ctx)?.point_at(distance)
+    }
+
+    /// Returns the unit tangent vector at `distance` user units along this path.
+    pub fn tangent_at(&self, ctx: &ResolveContext, distance: f32) -> Option<Point> {
+        self.measure(ctx)?.tangent_at(distance)
+    }
+}
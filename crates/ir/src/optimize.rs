@@ -0,0 +1,116 @@
+//! Optimization passes over ir codes streams.
+
+use alloc::{vec, vec::Vec};
+
+use crate::IR;
+
+/// A scoped instruction and the instructions in its scope.
+enum Node {
+    Leaf(IR),
+    Scope(IR, Vec<Node>),
+}
+
+/// Returns true if `ir` is an attribute scope, which has no rendering output by itself.
+fn is_attribute(ir: &IR) -> bool {
+    matches!(
+        ir,
+        IR::Fill(_) | IR::Stroke(_) | IR::Font(_) | IR::TextLayout(_)
+    )
+}
+
+/// Hoist identical attribute scopes applied to siblings into a single enclosing scope.
+///
+/// Adjacent sibling [`Fill`](crate::Fill), [`Stroke`](crate::Stroke), [`Font`](crate::Font) and
+/// [`TextLayout`](crate::TextLayout) scopes with equal attributes are merged, and an attribute scope
+/// nested in an identical one is removed. Consecutive `pop` instructions are coalesced.
+///
+/// Streams with unbalanced `pop` instructions are returned unchanged.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(codes = codes.len())))]
+pub fn hoist_scopes(codes: Vec<IR>) -> Vec<IR> {
+    let nodes = match parse(&codes) {
+        Some(nodes) => nodes,
+        None => return codes,
+    };
+
+    let mut output = Vec::with_capacity(codes.len());
+
+    emit(hoist(nodes, None), &mut output);
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(output = output.len(), "hoisted");
+
+    output
+}
+
+fn parse(codes: &[IR]) -> Option<Vec<Node>> {
+    // the opened scopes and their children.
+    let mut stack: Vec<(Option<IR>, Vec<Node>)> = vec![(None, vec![])];
+
+    for ir in codes {
+        match ir {
+            IR::Pop(n) => {
+                for _ in 0..*n {
+                    let (head, children) = stack.pop()?;
+
+                    stack.last_mut()?.1.push(Node::Scope(head?, children));
+                }
+            }
+            ir if ir.is_scoped() => stack.push((Some(ir.clone()), vec![])),
+            ir => stack.last_mut()?.1.push(Node::Leaf(ir.clone())),
+        }
+    }
+
+    // the unclosed scopes are left unclosed.
+    if stack.len() != 1 {
+        return None;
+    }
+
+    stack.pop().map(|(_, nodes)| nodes)
+}
+
+fn hoist(nodes: Vec<Node>, parent: Option<&IR>) -> Vec<Node> {
+    let mut output: Vec<Node> = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        match node {
+            Node::Scope(head, children) if is_attribute(&head) && Some(&head) == parent => {
+                output.extend(hoist(children, parent));
+            }
+            Node::Scope(head, children) => {
+                let children = hoist(children, Some(&head));
+
+                if let Some(Node::Scope(prev, prev_children)) = output.last_mut() {
+                    if is_attribute(&head) && *prev == head {
+                        // the merged children may have new identical siblings.
+                        let mut merged = core::mem::take(prev_children);
+                        merged.extend(children);
+                        *prev_children = hoist(merged, Some(&head));
+                        continue;
+                    }
+                }
+
+                output.push(Node::Scope(head, children));
+            }
+            node => output.push(node),
+        }
+    }
+
+    output
+}
+
+fn emit(nodes: Vec<Node>, output: &mut Vec<IR>) {
+    for node in nodes {
+        match node {
+            Node::Leaf(ir) => output.push(ir),
+            Node::Scope(head, children) => {
+                output.push(head);
+                emit(children, output);
+
+                match output.last_mut() {
+                    Some(IR::Pop(n)) => *n += 1,
+                    _ => output.push(IR::Pop(1)),
+                }
+            }
+        }
+    }
+}
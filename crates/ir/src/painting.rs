@@ -1,9 +1,13 @@
+use alloc::{string::String, vec::Vec};
+
 use super::{Angle, Animatable, Color, FrameVariable, Measurement, Rgba, ViewBox};
 use vglang_derive::Dsl;
 
 /// ‘fill’ and ‘stroke’ take on a value of type [`Paint`], which is specified as follows:
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Paint {
     /// the explicit color to be used to paint the current object
     Color(Rgba),
@@ -21,6 +25,8 @@ impl From<Color> for Paint {
     }
 }
 
+// derived by `Dsl` if the feature is enabled.
+#[cfg(not(feature = "dsl"))]
 impl From<Rgba> for Paint {
     fn from(value: Rgba) -> Self {
         Self::Color(value)
@@ -33,6 +39,7 @@ impl From<Rgba> for Paint {
 //// the interpretation of "inside" is not so obvious.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum FillRule {
     /// This rule determines the "insideness" of a point on the canvas by drawing a ray from that point to infinity in
     /// any direction and then examining the places where a segment of the shape crosses the ray. Starting with a count of zero,
@@ -58,6 +65,7 @@ impl FrameVariable for FillRule {}
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Fill {
     /// paints color.
     ///
@@ -84,6 +92,7 @@ where
 /// Specifies the shape to be used at the end of open subpaths when they are stroked
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum StrokeLineCap {
     Butt,
     Round,
@@ -100,7 +109,8 @@ impl Default for StrokeLineCap {
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct StrokeMiterlimit(Measurement);
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StrokeMiterlimit(pub Measurement);
 
 impl FrameVariable for StrokeMiterlimit {}
 
@@ -113,6 +123,7 @@ impl Default for StrokeMiterlimit {
 /// Specifies the shape to be used at the corners of paths or basic shapes when they are stroked.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum StrokeLineJoin {
     Miter(StrokeMiterlimit),
     Round,
@@ -127,10 +138,28 @@ impl Default for StrokeLineJoin {
     }
 }
 
+/// The order in which the fill and the stroke of an element are painted, see
+/// [`paint-order`](https://www.w3.org/TR/SVG2/painting.html#PaintOrder).
+///
+/// Painting the stroke first keeps the glyphs readable under wide strokes, e.g. the halos of map labels.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PaintOrder {
+    /// The fill is painted first, then the stroke.
+    #[default]
+    FillStroke,
+    /// The stroke is painted first, then the fill.
+    StrokeFill,
+}
+
+impl FrameVariable for PaintOrder {}
+
 /// This property affect how an element is stroked.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Stroke {
     /// paints color paints along the outline of the given graphical element.
     ///
@@ -139,6 +168,7 @@ pub struct Stroke {
     /// This property specifies the width of the stroke on the current object
     ///
     /// `Inherited: yes`
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub width: Option<Animatable<Measurement>>,
 
     /// specifies the shape to be used at the end of open subpaths when they are stroked.
@@ -157,11 +187,16 @@ pub struct Stroke {
     /// Thus, stroke-dasharray: 5,3,2 is equivalent to stroke-dasharray: 5,3,2,5,3,2.
     ///
     /// `Inherited: yes`
+    #[cfg_attr(feature = "dsl", dsl(non_empty, range(0.0..)))]
     pub dasharray: Option<Animatable<Vec<Animatable<Measurement>>>>,
     /// specifies the distance into the dash pattern to start the dash
     ///
     /// `Inherited: yes`
     pub dashoffset: Option<Animatable<Measurement>>,
+    /// specifies whether the stroke is painted before or after the fill.
+    ///
+    /// `Inherited: yes`
+    pub paint_order: Option<Animatable<PaintOrder>>,
 }
 
 impl<P> From<P> for Stroke
@@ -179,6 +214,7 @@ where
 /// Defines the coordinate system for attributes ‘markerWidth’, ‘markerHeight’ and the contents of the ‘marker’.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum MarkerUnits {
     /// If markerUnits="strokeWidth", ‘markerWidth’, ‘markerHeight’ and the contents of the ‘marker’ represent values
     /// in a coordinate system which has a single unit equal the size in user units of the current stroke width (see
@@ -199,13 +235,44 @@ impl Default for MarkerUnits {
     }
 }
 
+/// Indicates how a marker is rotated, see [`Marker::orient`].
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum MarkerOrient {
+    /// The marker is oriented along the direction of the path at the marker position.
+    Auto,
+    /// Same as `Auto`, except that the marker at the start of the path is rotated by 180 degrees,
+    /// so one marker works as both the start and end arrowheads.
+    AutoStartReverse,
+    /// The marker is rotated by a fixed angle.
+    Angle(Angle),
+}
+
+impl FrameVariable for MarkerOrient {}
+
+impl From<Angle> for MarkerOrient {
+    fn from(value: Angle) -> Self {
+        Self::Angle(value)
+    }
+}
+
 /// A marker is a symbol which is attached to one or more vertices of ‘path’, ‘line’, ‘polyline’ and ‘polygon’ elements.
 /// Typically, markers are used to make arrowheads or polymarkers. Arrowheads can be defined by attaching a marker to the
 /// start or end vertices of ‘path’, ‘line’ or ‘polyline’ elements. Polymarkers can be defined by attaching a marker to all
 /// vertices of a ‘path’, ‘line’, ‘polyline’ or ‘polygon’ element.
+///
+/// A marker is a scoped element, the elements in its scope are the marker contents, which are only rendered
+/// at the vertices of the elements referencing it by [`Markers`].
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Marker {
+    /// The unique name of this marker, referenced by [`Markers`].
+    #[cfg_attr(feature = "dsl", dsl(required))]
+    pub id: String,
+
     /// Defines the coordinate system for attributes ‘markerWidth’, ‘markerHeight’ and the contents of the ‘marker’.
     ///
     /// If attribute ‘markerUnits’ is not specified, then the effect is as if a value of 'strokeWidth' were specified.
@@ -227,17 +294,21 @@ pub struct Marker {
     /// Represents the width of the viewport into which the marker is to be fitted when it is rendered.
     /// A negative value is an error (see Error processing). A value of zero disables rendering of the element.
     /// If the attribute is not specified, the effect is as if a value of "3" were specified.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub width: Animatable<Measurement>,
 
     /// Represents the height of the viewport into which the marker is to be fitted when it is rendered.
     /// A negative value is an error (see Error processing). A value of zero disables rendering of the element.
     /// If the attribute is not specified, the effect is as if a value of "3" were specified.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub height: Animatable<Measurement>,
 
     /// Indicates how the marker is rotated. see [`svg`] document for more information.
     ///
+    /// If the attribute is not specified, the effect is as if a value of "0" were specified.
+    ///
     /// [`svg`]: https://www.w3.org/TR/SVG11/painting.html#MarkerElement
-    pub orient: Option<Animatable<Angle>>,
+    pub orient: Option<Animatable<MarkerOrient>>,
 
     /// stretch to fit a particular container element.
     pub viewbox: Option<Animatable<ViewBox>>,
@@ -246,6 +317,7 @@ pub struct Marker {
 impl Default for Marker {
     fn default() -> Self {
         Self {
+            id: String::new(),
             unit: Animatable::Constant(MarkerUnits::StrokeWidth),
             refx: Animatable::Constant(0.0.into()),
             refy: Animatable::Constant(0.0.into()),
@@ -256,3 +328,20 @@ impl Default for Marker {
         }
     }
 }
+
+/// Attaches markers to the vertices of the elements in scope, the same as the svg
+/// ‘marker-start’, ‘marker-mid’ and ‘marker-end’ properties.
+///
+/// Like other scoped instructions, markers must be closed by a paired `pop` instruction.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(Dsl))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Markers {
+    /// The id of the [`Marker`] drawn at the first vertex.
+    pub start: Option<String>,
+    /// The id of the [`Marker`] drawn at every vertex other than the first and last ones.
+    pub mid: Option<String>,
+    /// The id of the [`Marker`] drawn at the last vertex.
+    pub end: Option<String>,
+}
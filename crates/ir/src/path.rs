@@ -1,8 +1,12 @@
+use alloc::vec::Vec;
+
 use super::{Angle, Animatable, FrameVariable, Measurement, Point};
 
 /// A direction that representation a path drawing commander.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum PathEvent {
     // (absolute) Start a new sub-path at the given (x,y) coordinate.
     MoveTo(Point),
@@ -53,7 +57,9 @@ impl FrameVariable for PathEvent {}
 /// Paths represent the outline of a shape which can be filled, stroked, used as a clipping path,
 /// or any combination of the three.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Path {
     /// The definition of the outline of a shape.
     pub data: Animatable<Vec<Animatable<PathEvent>>>,
@@ -67,5 +73,99 @@ pub struct Path {
     /// operations.
     ///
     /// A negative value is an error (see Error processing).
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..), rename = "path_length"))]
     pub length: Animatable<Measurement>,
 }
+
+#[cfg(feature = "dsl")]
+impl Path {
+    /// Append `event` to the path data, an animated path data is replaced by a list of constant commands.
+    fn event(mut self, event: PathEvent) -> Self {
+        match &mut self.data {
+            Animatable::Constant(events) => events.push(Animatable::Constant(event)),
+            data => *data = Animatable::Constant(alloc::vec![Animatable::Constant(event)]),
+        }
+
+        self
+    }
+
+    /// Start a new sub-path at `to`.
+    pub fn move_to<P>(self, to: P) -> Self
+    where
+        Point: From<P>,
+    {
+        self.event(PathEvent::MoveTo(to.into()))
+    }
+
+    /// Draw a line from the current point to `to`.
+    pub fn line_to<P>(self, to: P) -> Self
+    where
+        Point: From<P>,
+    {
+        self.event(PathEvent::LineTo(to.into()))
+    }
+
+    /// Draw a polyline from the current point through `points`.
+    pub fn polyline<I, P>(self, points: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        Point: From<P>,
+    {
+        self.event(PathEvent::Polyline(
+            points.into_iter().map(Point::from).collect(),
+        ))
+    }
+
+    /// Draw a cubic Bézier curve from the current point to `to`.
+    pub fn cubic_bezier<C1, C2, P>(self, ctrl1: C1, ctrl2: C2, to: P) -> Self
+    where
+        Point: From<C1> + From<C2> + From<P>,
+    {
+        self.event(PathEvent::CubicBezier {
+            ctrl1: ctrl1.into(),
+            ctrl2: ctrl2.into(),
+            to: to.into(),
+        })
+    }
+
+    /// Draw a quadratic Bézier curve from the current point to `to`.
+    pub fn quadratic_bezier<C, P>(self, ctrl: C, to: P) -> Self
+    where
+        Point: From<C> + From<P>,
+    {
+        self.event(PathEvent::QuadraticBezier {
+            ctrl: ctrl.into(),
+            to: to.into(),
+        })
+    }
+
+    /// Draw an elliptical arc from the current point to `to`, see [`PathEvent::Arc`].
+    pub fn arc<R, A, P>(
+        self,
+        rx: R,
+        ry: R,
+        x_rotation: A,
+        large_arc: bool,
+        sweep: bool,
+        to: P,
+    ) -> Self
+    where
+        Measurement: From<R>,
+        Angle: From<A>,
+        Point: From<P>,
+    {
+        self.event(PathEvent::Arc {
+            rx: rx.into(),
+            ry: ry.into(),
+            x_rotation: x_rotation.into(),
+            large_arc,
+            sweep,
+            to: to.into(),
+        })
+    }
+
+    /// Close the current sub-path.
+    pub fn close_path(self) -> Self {
+        self.event(PathEvent::ClosePath)
+    }
+}
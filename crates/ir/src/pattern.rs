@@ -3,6 +3,7 @@ use super::{Animatable, FrameVariable, Measurement, PreserveAspectRatio, Transfo
 /// Defines the coordinate system for attributes ‘x’, ‘y’, ‘width’ and ‘height’.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum PatternUnits {
     /// see [`units`](Pattern::units) and [`content_units`](Pattern::content_units) for more informations.
     UserSpaceOnUse,
@@ -29,6 +30,7 @@ impl FrameVariable for PatternUnits {}
 /// starting at (x + m*width, y + n* height) for each possible integer value for m and n.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Pattern {
     /// Defines the coordinate system for attributes ‘x’, ‘y’, ‘width’ and ‘height’.
     ///
@@ -1,8 +1,11 @@
+use alloc::string::String;
+
 use super::FrameVariable;
 
 /// A pair of `number`s, where the second `number` is optional.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NumberOptNumber {
     pub dx: f32,
     pub dy: Option<f32>,
@@ -13,6 +16,7 @@ impl FrameVariable for NumberOptNumber {}
 /// The rgba components selector.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ChannelSelector {
     R,
     G,
@@ -30,7 +34,9 @@ impl FrameVariable for ChannelSelector {}
 
 /// An IRI reference
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl), dsl(map_collect))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Href(pub String);
 
 impl FrameVariable for Href {}
@@ -38,6 +44,7 @@ impl FrameVariable for Href {}
 /// Defines the coordinate system for the contents
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Units {
     UserSpaceOnUse,
     ObjectBoundingBox,
@@ -50,6 +57,7 @@ impl FrameVariable for Units {}
 /// See [`description of ranges of Unicode characters in CSS2`](https://www.w3.org/TR/2008/REC-CSS2-20080411/fonts.html#dataqual)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum UnicodeRange {
     Char(char),
 
@@ -69,6 +77,8 @@ impl Default for UnicodeRange {
 
 #[cfg(feature = "dsl")]
 mod dsl {
+    use alloc::{vec, vec::Vec};
+
     /// Map item via iterator and collect them into vec.
     pub trait MapCollect<Item> {
         fn map_collect(self) -> Vec<Item>;
@@ -82,6 +92,29 @@ mod dsl {
             vec![self.into()]
         }
     }
+
+    /// The numeric value checked by the `#[dsl(range(..))]` field attribute.
+    pub trait DslNumber {
+        fn dsl_number(&self) -> f32;
+    }
+
+    impl DslNumber for f32 {
+        fn dsl_number(&self) -> f32 {
+            *self
+        }
+    }
+
+    impl DslNumber for crate::Measurement {
+        fn dsl_number(&self) -> f32 {
+            self.0
+        }
+    }
+
+    impl DslNumber for crate::Angle {
+        fn dsl_number(&self) -> f32 {
+            self.as_deg()
+        }
+    }
 }
 
 #[cfg(feature = "dsl")]
@@ -0,0 +1,244 @@
+//! Collects the animatable registers referenced by ir codes streams.
+
+use alloc::collections::BTreeSet;
+
+use crate::{
+    Animatable, FePrimitive, Fill, Font, FrameVariable, Stroke, Text, TextLayout, TextSpan,
+    ViewBox, IR,
+};
+
+/// Returns the names of the animatable registers referenced by `codes`, in name order.
+///
+/// Both [`Animatable::Animated`] operands and [`IR::Animated`] text contents are collected.
+pub fn registers(codes: &[IR]) -> BTreeSet<&str> {
+    let mut registers = Registers::default();
+
+    for ir in codes {
+        registers.ir(ir);
+    }
+
+    registers.0
+}
+
+/// Collects the register names of the [`Animatable`] operands of each instruction.
+#[derive(Default)]
+struct Registers<'a>(BTreeSet<&'a str>);
+
+impl<'a> Registers<'a> {
+    fn animatable<T: FrameVariable>(&mut self, value: &'a Animatable<T>) {
+        if let Animatable::Animated(name) = value {
+            self.0.insert(name);
+        }
+    }
+
+    fn optional<T: FrameVariable>(&mut self, value: &'a Option<Animatable<T>>) {
+        if let Some(value) = value {
+            self.animatable(value);
+        }
+    }
+
+    fn ir(&mut self, ir: &'a IR) {
+        match ir {
+            IR::Pop(_) | IR::String(_) => {}
+            IR::Animated(name) => {
+                self.0.insert(name);
+            }
+            IR::Text(value) => self.text(value),
+            IR::TextSpan(value) => self.text_span(value),
+            IR::Layer(value) => {
+                self.animatable(&value.width);
+                self.animatable(&value.height);
+                self.viewbox(&value.viewbox);
+            }
+            IR::Rect(value) => {
+                self.animatable(&value.x);
+                self.animatable(&value.y);
+                self.animatable(&value.width);
+                self.animatable(&value.height);
+                self.animatable(&value.rx);
+                self.optional(&value.ry);
+            }
+            IR::Fill(value) => self.fill(value),
+            IR::Stroke(value) => self.stroke(value),
+            IR::Font(value) => self.font(value),
+            IR::TextLayout(value) => self.text_layout(value),
+            IR::Label(_) | IR::MaskRef(_) | IR::Markers(_) | IR::FilterRef(_) => {}
+            IR::Transform(value) => self.animatable(value),
+            IR::Mask(value) => {
+                self.animatable(&value.mode);
+                self.animatable(&value.units);
+                self.animatable(&value.content_units);
+                self.animatable(&value.x);
+                self.animatable(&value.y);
+                self.animatable(&value.width);
+                self.animatable(&value.height);
+            }
+            IR::Marker(value) => {
+                self.animatable(&value.unit);
+                self.animatable(&value.refx);
+                self.animatable(&value.refy);
+                self.animatable(&value.width);
+                self.animatable(&value.height);
+                self.optional(&value.orient);
+                self.viewbox(&value.viewbox);
+            }
+            IR::Symbol(value) => self.viewbox(&value.viewbox),
+            IR::Use(value) => {
+                self.animatable(&value.x);
+                self.animatable(&value.y);
+                self.optional(&value.width);
+                self.optional(&value.height);
+            }
+            IR::Image(value) => {
+                self.animatable(&value.x);
+                self.animatable(&value.y);
+                self.animatable(&value.width);
+                self.animatable(&value.height);
+                self.optional(&value.aspect);
+            }
+            IR::Filter(value) => {
+                self.animatable(&value.units);
+                self.animatable(&value.primitive_units);
+                self.animatable(&value.x);
+                self.animatable(&value.y);
+                self.animatable(&value.width);
+                self.animatable(&value.height);
+                self.optional(&value.filter_res);
+            }
+            IR::FeGaussianBlur(value) => {
+                self.fe_primitive(&value.primitive);
+                self.animatable(&value.r#in);
+                self.animatable(&value.std_deviation);
+            }
+            IR::FeOffset(value) => {
+                self.fe_primitive(&value.primitive);
+                self.animatable(&value.r#in);
+                self.animatable(&value.dx);
+                self.animatable(&value.dy);
+            }
+            IR::FeFlood(value) => {
+                self.fe_primitive(&value.primitive);
+                self.animatable(&value.color);
+            }
+            IR::FeComposite(value) => {
+                self.fe_primitive(&value.primitive);
+                self.animatable(&value.a);
+                self.animatable(&value.b);
+                self.animatable(&value.operator);
+                self.animatable(&value.k1);
+                self.animatable(&value.k2);
+                self.animatable(&value.k3);
+                self.animatable(&value.k4);
+            }
+            IR::FeMerge(value) => self.fe_primitive(&value.0),
+            IR::FeMergeItem(value) => self.animatable(&value.0),
+            IR::Path(value) => {
+                self.animatable(&value.data);
+
+                if let Animatable::Constant(events) = &value.data {
+                    for event in events {
+                        self.animatable(event);
+                    }
+                }
+
+                self.animatable(&value.length);
+            }
+        }
+    }
+
+    fn viewbox(&mut self, value: &'a Option<Animatable<ViewBox>>) {
+        self.optional(value);
+
+        if let Some(Animatable::Constant(value)) = value {
+            self.animatable(&value.minx);
+            self.animatable(&value.miny);
+            self.animatable(&value.width);
+            self.animatable(&value.height);
+            self.optional(&value.aspect);
+        }
+    }
+
+    fn text(&mut self, value: &'a Text) {
+        self.animatable(&value.x);
+        self.animatable(&value.y);
+        self.animatable(&value.dx);
+        self.animatable(&value.dy);
+        self.animatable(&value.rotate);
+        self.animatable(&value.text_length);
+        self.animatable(&value.length_adjust);
+        self.optional(&value.max_width);
+        self.animatable(&value.overflow);
+    }
+
+    fn text_span(&mut self, value: &'a TextSpan) {
+        self.animatable(&value.x);
+        self.animatable(&value.y);
+        self.animatable(&value.dx);
+        self.animatable(&value.dy);
+        self.animatable(&value.rotate);
+        self.animatable(&value.text_length);
+        self.animatable(&value.length_adjust);
+
+        if let Some(font) = &value.font {
+            self.font(font);
+        }
+
+        if let Some(layout) = &value.layout {
+            self.text_layout(layout);
+        }
+
+        if let Some(fill) = &value.fill {
+            self.fill(fill);
+        }
+
+        if let Some(stroke) = &value.stroke {
+            self.stroke(stroke);
+        }
+    }
+
+    fn fill(&mut self, value: &'a Fill) {
+        self.optional(&value.paint);
+        self.optional(&value.rule);
+    }
+
+    fn stroke(&mut self, value: &'a Stroke) {
+        self.optional(&value.paint);
+        self.optional(&value.width);
+        self.optional(&value.linecap);
+        self.optional(&value.linejoin);
+        self.optional(&value.dasharray);
+
+        if let Some(Animatable::Constant(values)) = &value.dasharray {
+            for value in values {
+                self.animatable(value);
+            }
+        }
+
+        self.optional(&value.dashoffset);
+        self.optional(&value.paint_order);
+    }
+
+    fn font(&mut self, value: &'a Font) {
+        self.optional(&value.family);
+        self.optional(&value.style);
+        self.optional(&value.variant);
+        self.optional(&value.weight);
+        self.optional(&value.size);
+        self.optional(&value.stretch);
+    }
+
+    fn text_layout(&mut self, value: &'a TextLayout) {
+        self.optional(&value.anchor);
+        self.optional(&value.dominant_baseline);
+        self.optional(&value.alignment_baseline);
+        self.optional(&value.baseline_shift);
+    }
+
+    fn fe_primitive(&mut self, value: &'a FePrimitive) {
+        self.animatable(&value.x);
+        self.animatable(&value.y);
+        self.animatable(&value.width);
+        self.animatable(&value.height);
+        self.animatable(&value.out);
+    }
+}
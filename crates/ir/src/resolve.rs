@@ -0,0 +1,247 @@
+//! Resolve measurements, angles and viewboxes into user units.
+
+use crate::{
+    geometry::{self, Matrix, Rect, Segment, SubPath},
+    Animatable, FrameVariable, Measurement, MeetOrSlice, Path, PathEvent, Point,
+    PreserveAspectRatio, Unit, ViewBox,
+};
+
+/// The axis that a percentage measurement refers to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Axis {
+    /// Percentages refer to the viewport width, e.g. `x` and `width`.
+    Horizontal,
+    /// Percentages refer to the viewport height, e.g. `y` and `height`.
+    Vertical,
+    /// Percentages refer to the normalized viewport diagonal, e.g. `stroke-width` and `r`.
+    Other,
+}
+
+/// The environment used to resolve measurements into user units.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ResolveContext {
+    /// The font size used by the `em` unit.
+    pub font_size: f32,
+    /// The font x-height used by the `ex` unit, `0.5em` if `None`.
+    pub x_height: Option<f32>,
+    /// The width of the current viewport, referred by percentages.
+    pub viewport_width: f32,
+    /// The height of the current viewport, referred by percentages.
+    pub viewport_height: f32,
+    /// The number of user units per inch.
+    pub dpi: f32,
+}
+
+impl Default for ResolveContext {
+    fn default() -> Self {
+        Self {
+            font_size: 16.0,
+            x_height: None,
+            viewport_width: 0.0,
+            viewport_height: 0.0,
+            dpi: 96.0,
+        }
+    }
+}
+
+impl ResolveContext {
+    /// Create a context with the viewport size.
+    pub fn new(viewport_width: f32, viewport_height: f32) -> Self {
+        Self {
+            viewport_width,
+            viewport_height,
+            ..Default::default()
+        }
+    }
+
+    /// Resolve a measurement into user units.
+    pub fn length(&self, value: Measurement, axis: Axis) -> f32 {
+        let Measurement(value, unit) = value;
+
+        match unit {
+            None | Some(Unit::Px) => value,
+            Some(Unit::Em) => value * self.font_size,
+            Some(Unit::Ex) => value * self.x_height.unwrap_or(self.font_size * 0.5),
+            Some(Unit::In) => value * self.dpi,
+            Some(Unit::Cm) => value * self.dpi / 2.54,
+            Some(Unit::Mm) => value * self.dpi / 25.4,
+            Some(Unit::Pt) => value * self.dpi / 72.0,
+            Some(Unit::Pc) => value * self.dpi / 6.0,
+            Some(Unit::Percentages) => {
+                let base = match axis {
+                    Axis::Horizontal => self.viewport_width,
+                    Axis::Vertical => self.viewport_height,
+                    Axis::Other => {
+                        ((self.viewport_width.powi(2) + self.viewport_height.powi(2)) / 2.0).sqrt()
+                    }
+                };
+
+                value * base / 100.0
+            }
+        }
+    }
+
+    /// Resolve an animatable measurement, returns `None` for animated registers.
+    pub fn animatable_length(&self, value: &Animatable<Measurement>, axis: Axis) -> Option<f32> {
+        constant(value).map(|value| self.length(*value, axis))
+    }
+
+    /// Resolve a viewbox, returns `None` if any part of it is animated.
+    pub fn viewbox(&self, value: &ViewBox) -> Option<(Rect, PreserveAspectRatio)> {
+        let rect = Rect::new(
+            self.animatable_length(&value.minx, Axis::Horizontal)?,
+            self.animatable_length(&value.miny, Axis::Vertical)?,
+            self.animatable_length(&value.width, Axis::Horizontal)?,
+            self.animatable_length(&value.height, Axis::Vertical)?,
+        );
+
+        let aspect = match &value.aspect {
+            Some(aspect) => *constant(aspect)?,
+            None => PreserveAspectRatio::default(),
+        };
+
+        Some((rect, aspect))
+    }
+
+    /// Resolve a point into user units.
+    pub fn point(&self, value: &Point) -> geometry::Point {
+        geometry::Point::new(
+            self.length(value.x, Axis::Horizontal),
+            self.length(value.y, Axis::Vertical),
+        )
+    }
+
+    /// Resolve the path data into sub-paths, returns `None` if any part of it is animated.
+    ///
+    /// Elliptical arcs are approximated by cubic béziers, and sub-paths without any segment are dropped.
+    pub fn path(&self, value: &Path) -> Option<Vec<SubPath>> {
+        let mut subpaths = vec![];
+        let mut current = SubPath::default();
+        let mut start = geometry::Point::default();
+
+        for event in constant(&value.data)? {
+            let point = current.segments.last().map_or(start, Segment::to);
+
+            match constant(event)? {
+                PathEvent::MoveTo(to) => {
+                    if !current.segments.is_empty() {
+                        subpaths.push(std::mem::take(&mut current));
+                    }
+
+                    start = self.point(to);
+                }
+                PathEvent::ClosePath => {
+                    if point != start {
+                        current.segments.push(Segment::Line {
+                            from: point,
+                            to: start,
+                        });
+                    }
+
+                    if !current.segments.is_empty() {
+                        current.closed = true;
+                        subpaths.push(std::mem::take(&mut current));
+                    }
+                }
+                PathEvent::LineTo(to) => current.segments.push(Segment::Line {
+                    from: point,
+                    to: self.point(to),
+                }),
+                PathEvent::Polyline(points) => {
+                    let mut from = point;
+
+                    for to in points {
+                        let to = self.point(to);
+                        current.segments.push(Segment::Line { from, to });
+                        from = to;
+                    }
+                }
+                PathEvent::CubicBezier { ctrl1, ctrl2, to } => {
+                    current.segments.push(Segment::Cubic {
+                        from: point,
+                        ctrl1: self.point(ctrl1),
+                        ctrl2: self.point(ctrl2),
+                        to: self.point(to),
+                    })
+                }
+                PathEvent::QuadraticBezier { ctrl, to } => {
+                    current.segments.push(Segment::Quadratic {
+                        from: point,
+                        ctrl: self.point(ctrl),
+                        to: self.point(to),
+                    })
+                }
+                PathEvent::Arc {
+                    rx,
+                    ry,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => {
+                    current.segments.extend(Segment::arc(
+                        point,
+                        self.length(*rx, Axis::Horizontal),
+                        self.length(*ry, Axis::Vertical),
+                        x_rotation.as_deg(),
+                        *large_arc,
+                        *sweep,
+                        self.point(to),
+                    ));
+                }
+            }
+        }
+
+        if !current.segments.is_empty() {
+            subpaths.push(current);
+        }
+
+        Some(subpaths)
+    }
+}
+
+/// Returns the matrix that maps the `viewbox` into a `width` x `height` viewport.
+///
+/// Returns `None` if the viewbox is empty, which disables the rendering of the element.
+pub fn viewbox_matrix(
+    viewbox: &Rect,
+    aspect: PreserveAspectRatio,
+    width: f32,
+    height: f32,
+) -> Option<Matrix> {
+    if viewbox.width <= 0.0 || viewbox.height <= 0.0 {
+        return None;
+    }
+
+    let (align_x, align_y, meet_or_slice) = match aspect {
+        PreserveAspectRatio::xMinYMin(v) => (0.0, 0.0, v),
+        PreserveAspectRatio::xMidYMin(v) => (0.5, 0.0, v),
+        PreserveAspectRatio::xMaxYMin(v) => (1.0, 0.0, v),
+        PreserveAspectRatio::xMinYMid(v) => (0.0, 0.5, v),
+        PreserveAspectRatio::xMidYMid(v) => (0.5, 0.5, v),
+        PreserveAspectRatio::xMaxYMid(v) => (1.0, 0.5, v),
+        PreserveAspectRatio::xMinYMax(v) => (0.0, 1.0, v),
+        PreserveAspectRatio::xMidYMax(v) => (0.5, 1.0, v),
+        PreserveAspectRatio::xMaxYMax(v) => (1.0, 1.0, v),
+    };
+
+    let sx = width / viewbox.width;
+    let sy = height / viewbox.height;
+
+    let scale = match meet_or_slice {
+        MeetOrSlice::Meet => sx.min(sy),
+        MeetOrSlice::Slice => sx.max(sy),
+    };
+
+    let tx = (width - viewbox.width * scale) * align_x - viewbox.x * scale;
+    let ty = (height - viewbox.height * scale) * align_y - viewbox.y * scale;
+
+    Some(Matrix::translate(tx, ty).multiply(&Matrix::scale(scale, scale)))
+}
+
+pub(crate) fn constant<T: FrameVariable>(value: &Animatable<T>) -> Option<&T> {
+    match value {
+        Animatable::Animated(_) => None,
+        Animatable::Constant(v) => Some(v),
+    }
+}
@@ -0,0 +1,272 @@
+//! Inline rich text: styled text runs, parsed from a tiny markup language and lowered to `text`/`tspan` codes.
+//!
+//! The markup is a subset of html:
+//!
+//! * `<b>` and `<i>`: bold and italic text;
+//! * `<u>` and `<s>`: underlined and struck through text;
+//! * `<sub>` and `<sup>`: subscripts and superscripts;
+//! * `<span fill="...">`: text filled with a svg 1.1 color value;
+//! * `<a href="...">`: a link;
+//! * the entities `&lt;`, `&gt;`, `&amp;`, `&quot;` and `&apos;`.
+//!
+//! Tags can be nested, attribute values are quoted with `"` or `'`.
+
+use alloc::{borrow::ToOwned, boxed::Box, format, string::String, vec, vec::Vec};
+use core::str::FromStr;
+
+use crate::{
+    Animatable, BaselineShift, Error, Fill, Font, FontStyle, FontWeight, Href, Paint, Result, Rgba,
+    Text, TextLayout, TextSpan, IR,
+};
+
+/// The decoration lines of a text run.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+}
+
+impl TextDecoration {
+    /// Returns true if no line is drawn.
+    pub fn is_none(&self) -> bool {
+        !(self.underline || self.overline || self.line_through)
+    }
+}
+
+/// The style of a text run, the `None` values are inherited from the enclosing `text` element.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TextStyle {
+    pub font: Font,
+    pub fill: Option<Fill>,
+    pub baseline_shift: Option<BaselineShift>,
+    pub decoration: TextDecoration,
+    /// The target of a link.
+    pub href: Option<Href>,
+}
+
+/// A run of text with one style.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TextRun {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+/// A text made of styled runs, see [module level documentation](self) for the markup syntax.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct RichText {
+    pub runs: Vec<TextRun>,
+}
+
+impl FromStr for RichText {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl RichText {
+    /// Appends a run, which is merged into the last one if they have the same style.
+    pub fn push<T>(&mut self, text: T, style: TextStyle) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+
+        if text.is_empty() {
+            return self;
+        }
+
+        match self.runs.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(text),
+            _ => self.runs.push(TextRun {
+                text: text.to_owned(),
+                style,
+            }),
+        }
+
+        self
+    }
+
+    /// Returns the text content without the styles.
+    pub fn plain_text(&self) -> String {
+        self.runs.iter().map(|run| run.text.as_str()).collect()
+    }
+
+    /// Parse a markup text.
+    pub fn parse(markup: &str) -> Result<Self> {
+        let invalid = |message: String| Error::Markup(message);
+
+        let mut rich_text = RichText::default();
+        // the open tags and the styles in them.
+        let mut stack: Vec<(&str, TextStyle)> = vec![];
+        let mut rest = markup;
+
+        let style = |stack: &[(&str, TextStyle)]| {
+            stack
+                .last()
+                .map(|(_, style)| style.clone())
+                .unwrap_or_default()
+        };
+
+        while !rest.is_empty() {
+            let Some(start) = rest.find('<') else {
+                rich_text.push(unescape(rest)?, style(&stack));
+                break;
+            };
+
+            rich_text.push(unescape(&rest[..start])?, style(&stack));
+
+            let end = rest[start..]
+                .find('>')
+                .map(|end| start + end)
+                .ok_or_else(|| invalid(format!("unclosed tag `{}`", &rest[start..])))?;
+
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim();
+
+                match stack.pop() {
+                    Some((open, _)) if open == name => {}
+                    Some((open, _)) => {
+                        return Err(invalid(format!("expect `</{}>`, got `</{}>`", open, name)))
+                    }
+                    None => return Err(invalid(format!("unexpected `</{}>`", name))),
+                }
+
+                continue;
+            }
+
+            let (name, attrs) = tag
+                .trim()
+                .split_once(char::is_whitespace)
+                .unwrap_or((tag.trim(), ""));
+
+            let mut style = style(&stack);
+
+            match name {
+                "b" => style.font.weight = Some(Animatable::Constant(FontWeight::Bold)),
+                "i" => style.font.style = Some(Animatable::Constant(FontStyle::Italic)),
+                "u" => style.decoration.underline = true,
+                "s" => style.decoration.line_through = true,
+                "sub" => style.baseline_shift = Some(BaselineShift::Sub),
+                "sup" => style.baseline_shift = Some(BaselineShift::Super),
+                "span" | "a" => {}
+                _ => return Err(invalid(format!("unsupported tag `<{}>`", name))),
+            }
+
+            for (attr, value) in parse_attrs(attrs)? {
+                match (name, attr) {
+                    ("span", "fill") => {
+                        let paint = Paint::Color(value.parse::<Rgba>()?);
+                        style.fill = Some(Fill::from(paint));
+                    }
+                    ("a", "href") => style.href = Some(Href(value)),
+                    _ => {
+                        return Err(invalid(format!(
+                            "unsupported attribute `{}` of `<{}>`",
+                            attr, name
+                        )))
+                    }
+                }
+            }
+
+            stack.push((name, style));
+        }
+
+        if let Some((name, _)) = stack.last() {
+            return Err(invalid(format!("unclosed tag `<{}>`", name)));
+        }
+
+        Ok(rich_text)
+    }
+
+    /// Lowers the runs to a `text` element with one `tspan` for each run.
+    ///
+    /// `text` provides the position and the layout of the whole text. Decorations and links have no ir
+    /// counterpart yet, they are left to the callers, e.g. the `tspan` of the run `i` is the code `2 + 3 * i`.
+    pub fn lower(&self, text: Text) -> Vec<IR> {
+        let mut codes = vec![IR::Text(Box::new(text))];
+
+        for run in &self.runs {
+            let span = TextSpan {
+                font: (run.style.font != Font::default()).then(|| run.style.font.clone()),
+                fill: run.style.fill.clone(),
+                layout: run.style.baseline_shift.clone().map(TextLayout::from),
+                ..Default::default()
+            };
+
+            codes.push(IR::TextSpan(Box::new(span)));
+            codes.push(IR::String(run.text.clone()));
+            codes.push(IR::Pop(1));
+        }
+
+        codes.push(IR::Pop(1));
+
+        codes
+    }
+}
+
+/// Parses `name="value"` pairs.
+fn parse_attrs(source: &str) -> Result<Vec<(&str, String)>> {
+    let invalid = || Error::Markup(format!("invalid attributes `{}`", source));
+
+    let mut pairs = vec![];
+    let mut attrs = source;
+
+    loop {
+        attrs = attrs.trim_start();
+
+        if attrs.is_empty() {
+            return Ok(pairs);
+        }
+
+        let (name, rest) = attrs.split_once('=').ok_or_else(invalid)?;
+        let rest = rest.trim_start();
+
+        let quote = rest
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(invalid)?;
+
+        let (value, rest) = rest[1..].split_once(quote).ok_or_else(invalid)?;
+
+        pairs.push((name.trim(), unescape(value)?));
+        attrs = rest;
+    }
+}
+
+/// Replaces the entities in `text`.
+fn unescape(text: &str) -> Result<String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| Error::Markup(format!("unclosed entity in `{}`", text)))?;
+
+        let entity = &rest[start + 1..start + end];
+
+        unescaped.push(match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => return Err(Error::Markup(format!("unsupported entity `&{};`", entity))),
+        });
+
+        rest = &rest[start + end + 1..];
+    }
+
+    unescaped.push_str(rest);
+
+    Ok(unescaped)
+}
@@ -0,0 +1,11 @@
+//! A machine-readable description of the serialized ir format, for the tools written in other languages.
+
+use crate::Envelope;
+
+/// Returns the [JSON Schema](https://json-schema.org) of a serialized [`Envelope`], which describes all ir
+/// elements with their fields and enums.
+///
+/// The schema is derived from the same definitions and `serde` attributes as the serde implementations.
+pub fn schema() -> schemars::Schema {
+    schemars::schema_for!(Envelope)
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use super::{Animatable, Measurement, Point};
 
 /// The ‘rect’ element defines a rectangle which is axis-aligned with the current user coordinate system.
@@ -17,6 +19,7 @@ use super::{Animatable, Measurement, Point};
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Rect {
     /// The x-axis coordinate of the side of the rectangle which has the smaller x-axis coordinate value in the current user coordinate system.
     /// If the attribute is not specified, the effect is as if a value of "0" were specified.
@@ -34,24 +37,28 @@ pub struct Rect {
     /// A negative value is an error (see Error processing). A value of zero disables rendering of the element.
     ///
     /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub width: Animatable<Measurement>,
 
     /// The height of the rectangle.
     /// A negative value is an error (see Error processing). A value of zero disables rendering of the element.
     ///
     /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub height: Animatable<Measurement>,
 
     /// For rounded rectangles, the x-axis radius of the ellipse used to round off the corners of the rectangle.
     /// A negative value is an error (see Error processing).
     ///
     /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub rx: Animatable<Measurement>,
 
     /// For rounded rectangles, the y-axis radius of the ellipse used to round off the corners of the rectangle.
     /// A negative value is an error (see Error processing).
     ///
     /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
     pub ry: Option<Animatable<Measurement>>,
 }
 
@@ -73,6 +80,7 @@ where
 /// The ‘circle’ element defines a circle based on a center point and a radius.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Circle {
     /// The x-axis coordinate of the center of the circle.
     /// If the attribute is not specified, the effect is as if a value of "0" were specified.
@@ -97,6 +105,7 @@ pub struct Circle {
 /// system based on a center point and two radii.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Ellipse {
     /// The x-axis coordinate of the center of the ellipse.
     /// If the attribute is not specified, the effect is as if a value of "0" were specified.
@@ -126,6 +135,7 @@ pub struct Ellipse {
 /// The ‘line’ element defines a line segment that starts at one point and ends at another.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Line {
     /// The x-axis coordinate of the start of the line.
     ///
@@ -159,6 +169,7 @@ pub struct Line {
 /// The ‘polyline’ element defines a set of connected straight line segments. Typically, ‘polyline’ elements define open shapes.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Polyline(
     /// The points that make up the polyline. All coordinate values are in the user coordinate system.
     ///
@@ -182,6 +193,7 @@ impl Polyline {
 /// The ‘polygon’ element defines a closed shape consisting of a set of connected straight line segments.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Polygon(
     /// The points that make up the polygon. All coordinate values are in the user coordinate system.
     ///
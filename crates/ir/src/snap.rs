@@ -0,0 +1,81 @@
+//! Pixel grid snapping, which makes axis-aligned edges crisp in raster output.
+
+use crate::{
+    bounds::Walker, geometry::Matrix, Animatable, BoundsOptions, Measurement, ResolveContext, IR,
+};
+
+/// Snap the edges of axis-aligned rectangles to the pixel grid of a raster target with `dpi` pixels per inch.
+///
+/// The edges of filled rectangles are snapped to the pixel boundaries, and the edges of rectangles stroked
+/// with an odd number of pixels, e.g. `1px` strokes, are snapped to the pixel centers, so that the stroke
+/// covers whole pixels. Snapped rectangles are at least one pixel wide and high.
+///
+/// `ctx` should have the same `dpi` as the user agent, which is `96` for css pixels. Rotated, skewed and
+/// animated rectangles are left unchanged, the snapped coordinates are written in user units.
+pub fn snap_to_pixels(mut codes: Vec<IR>, ctx: &ResolveContext, dpi: f32) -> Vec<IR> {
+    let mut walker = Walker::new(*ctx);
+
+    // maps the root user space to device pixels.
+    let device = Matrix::scale(dpi / ctx.dpi, dpi / ctx.dpi);
+
+    for ir in codes.iter_mut() {
+        if let IR::Rect(rect) = ir {
+            let state = walker.state();
+
+            let matrix = device.multiply(&state.matrix);
+
+            let axis_aligned =
+                matrix.b == 0.0 && matrix.c == 0.0 && matrix.a != 0.0 && matrix.d != 0.0;
+
+            let resolved = match walker.resolve_rect(rect) {
+                Some(resolved) if axis_aligned && !state.disabled => resolved,
+                _ => {
+                    walker.next(ir, &BoundsOptions::default());
+                    continue;
+                }
+            };
+
+            // the stroke width in device pixels of each axis.
+            let (stroke_x, stroke_y) = if state.stroke_paint {
+                (
+                    (state.stroke.width * matrix.a).abs().round(),
+                    (state.stroke.width * matrix.d).abs().round(),
+                )
+            } else {
+                (0.0, 0.0)
+            };
+
+            let (x, width) = snap(resolved.x, resolved.width, matrix.a, matrix.e, stroke_x);
+            let (y, height) = snap(resolved.y, resolved.height, matrix.d, matrix.f, stroke_y);
+
+            rect.x = Animatable::Constant(Measurement::from(x));
+            rect.y = Animatable::Constant(Measurement::from(y));
+            rect.width = Animatable::Constant(Measurement::from(width));
+            rect.height = Animatable::Constant(Measurement::from(height));
+        }
+
+        walker.next(ir, &BoundsOptions::default());
+    }
+
+    codes
+}
+
+/// Snap an interval `[start, start + length]` along an axis mapped by `v * scale + translate` to device pixels.
+fn snap(start: f32, length: f32, scale: f32, translate: f32, stroke: f32) -> (f32, f32) {
+    let (a, b) = (
+        start * scale + translate,
+        (start + length) * scale + translate,
+    );
+
+    let (min, max) = (a.min(b), a.max(b));
+
+    // odd stroke widths are centered on the pixel centers.
+    let bias = if stroke % 2.0 == 1.0 { 0.5 } else { 0.0 };
+
+    let min = (min - bias).round() + bias;
+    let max = ((max - bias).round() + bias).max(min + 1.0);
+
+    let (a, b) = ((min - translate) / scale, (max - translate) / scale);
+
+    (a.min(b), (b - a).abs())
+}
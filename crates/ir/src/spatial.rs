@@ -0,0 +1,236 @@
+//! A spatial index over the bounds of drawn elements, which keeps region and nearest queries fast in large scenes.
+
+use crate::{
+    bounds::Walker,
+    geometry::{Point, Rect},
+    BoundsOptions, ElementPath, ResolveContext, IR,
+};
+
+/// The maximum number of children of an index node.
+const NODE_CAPACITY: usize = 16;
+
+/// A node of the index tree.
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Rect,
+    /// The children in the lower level, or the entries for leaf nodes.
+    children: std::ops::Range<usize>,
+}
+
+/// A static R-tree over the bounds of drawn elements, bulk loaded with the sort-tile-recursive algorithm.
+///
+/// Elements with animated geometry are not indexed; rebuild the index after the ir codes change.
+#[derive(Debug, Default, Clone)]
+pub struct SpatialIndex {
+    entries: Vec<(Rect, ElementPath)>,
+    /// The levels of the tree from the leaf nodes to the root node.
+    levels: Vec<Vec<Node>>,
+}
+
+impl SpatialIndex {
+    /// Build the index over the bounds in the root user space, including stroke widths, of the elements drawn by `codes`.
+    pub fn new(codes: &[IR], ctx: &ResolveContext) -> Self {
+        let mut walker = Walker::new(*ctx);
+        let mut scopes = vec![];
+        let mut entries = vec![];
+
+        for (index, ir) in codes.iter().enumerate() {
+            if let Some(bounds) = walker.next(ir, &BoundsOptions { stroke: true }) {
+                let mut path = scopes.clone();
+                path.push(index);
+                entries.push((bounds, ElementPath(path)));
+            }
+
+            match ir {
+                IR::Pop(n) => scopes.truncate(scopes.len().saturating_sub(*n)),
+                ir if ir.is_scoped() => scopes.push(index),
+                _ => {}
+            }
+        }
+
+        Self::from_entries(entries)
+    }
+
+    /// Build the index over custom entries, e.g. the bounds of elements in other coordinate systems.
+    pub fn from_entries(mut entries: Vec<(Rect, ElementPath)>) -> Self {
+        let mut levels = vec![];
+
+        if !entries.is_empty() {
+            let mut nodes = pack(&mut entries, |(bounds, _)| *bounds);
+
+            while nodes.len() > 1 {
+                let parents = pack(&mut nodes, |node| node.bounds);
+                levels.push(nodes);
+                nodes = parents;
+            }
+
+            levels.push(nodes);
+        }
+
+        Self { entries, levels }
+    }
+
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no element is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the elements whose bounds intersect `region`, in the stream order.
+    pub fn query(&self, region: &Rect) -> Vec<&ElementPath> {
+        let mut found = vec![];
+
+        self.visit(&mut |bounds| bounds.intersects(region), &mut |(_, path)| {
+            found.push(path)
+        });
+
+        found.sort_by_key(|path| path.index());
+
+        found
+    }
+
+    /// Returns the elements whose bounds contain `point`, from the topmost one to the bottommost one.
+    ///
+    /// This is a cheap pre-filter for [`hit_test`](crate::hit_test), which tests the exact geometry.
+    pub fn query_point(&self, point: Point) -> Vec<&ElementPath> {
+        let mut found = self.query(&Rect::new(point.x, point.y, 0.0, 0.0));
+        found.reverse();
+        found
+    }
+
+    /// Returns the element whose bounds are the nearest to `point`, and the distance to the bounds.
+    ///
+    /// Ties are broken by the stream order, the topmost element wins.
+    pub fn nearest(&self, point: Point) -> Option<(&ElementPath, f32)> {
+        let root = self.levels.last()?;
+
+        let mut best: Option<(&ElementPath, f32)> = None;
+
+        // the nodes to visit as (level, index), the levels count from the leaf nodes.
+        let mut stack = (0..root.len())
+            .map(|index| (self.levels.len() - 1, index))
+            .collect::<Vec<_>>();
+
+        while let Some((level, index)) = stack.pop() {
+            let node = &self.levels[level][index];
+
+            if best.is_some_and(|(_, distance)| node.bounds.distance(point) > distance) {
+                continue;
+            }
+
+            if level > 0 {
+                let mut children = node.children.clone().collect::<Vec<_>>();
+
+                // visit the nearest child first.
+                children.sort_by(|a, b| {
+                    let a = self.levels[level - 1][*a].bounds.distance(point);
+                    let b = self.levels[level - 1][*b].bounds.distance(point);
+                    b.total_cmp(&a)
+                });
+
+                stack.extend(children.into_iter().map(|index| (level - 1, index)));
+
+                continue;
+            }
+
+            for (bounds, path) in &self.entries[node.children.clone()] {
+                let distance = bounds.distance(point);
+
+                let better = match best {
+                    None => true,
+                    Some((best, best_distance)) => {
+                        distance < best_distance
+                            || (distance == best_distance && path.index() > best.index())
+                    }
+                };
+
+                if better {
+                    best = Some((path, distance));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Calls `f` with the entries in the nodes accepted by `filter`.
+    fn visit<'a, F, V>(&'a self, filter: &mut F, f: &mut V)
+    where
+        F: FnMut(&Rect) -> bool,
+        V: FnMut(&'a (Rect, ElementPath)),
+    {
+        let Some(root) = self.levels.last() else {
+            return;
+        };
+
+        let mut stack = (0..root.len())
+            .map(|index| (self.levels.len() - 1, index))
+            .collect::<Vec<_>>();
+
+        while let Some((level, index)) = stack.pop() {
+            let node = &self.levels[level][index];
+
+            if !filter(&node.bounds) {
+                continue;
+            }
+
+            if level > 0 {
+                stack.extend(node.children.clone().map(|index| (level - 1, index)));
+                continue;
+            }
+
+            for entry in &self.entries[node.children.clone()] {
+                if filter(&entry.0) {
+                    f(entry);
+                }
+            }
+        }
+    }
+}
+
+/// Sorts `items` in the sort-tile-recursive order and returns the parent nodes of each run of items.
+fn pack<T, F>(items: &mut [T], bounds: F) -> Vec<Node>
+where
+    F: Fn(&T) -> Rect,
+{
+    let center = |item: &T| {
+        let rect = bounds(item);
+        Point::new(rect.x + rect.width / 2.0, rect.y + rect.height / 2.0)
+    };
+
+    let nodes = items.len().div_ceil(NODE_CAPACITY);
+    let slices = (nodes as f32).sqrt().ceil() as usize;
+    let slice_len = slices * NODE_CAPACITY;
+
+    items.sort_by(|a, b| center(a).x.total_cmp(&center(b).x));
+
+    for slice in items.chunks_mut(slice_len) {
+        slice.sort_by(|a, b| center(a).y.total_cmp(&center(b).y));
+    }
+
+    let mut parents = vec![];
+    let mut start = 0;
+
+    for slice in items.chunks(slice_len) {
+        for chunk in slice.chunks(NODE_CAPACITY) {
+            let bounds = chunk
+                .iter()
+                .map(&bounds)
+                .reduce(|a, b| a.union(&b))
+                .expect("chunks are not empty");
+
+            parents.push(Node {
+                bounds,
+                children: start..start + chunk.len(),
+            });
+
+            start += chunk.len();
+        }
+    }
+
+    parents
+}
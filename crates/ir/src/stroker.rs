@@ -0,0 +1,302 @@
+//! Converts strokes into fill outlines, for backends that can only fill shapes and for exporting outlined strokes.
+
+use std::f32::consts::PI;
+
+use crate::{
+    dash,
+    geometry::{Point, Polyline, SubPath},
+    resolve::constant,
+    Axis, PaintOrder, Path, ResolveContext, Stroke, StrokeLineCap, StrokeLineJoin,
+};
+
+/// The resolved stroke properties in user units.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: StrokeLineCap,
+    pub join: StrokeLineJoin,
+    /// The lengths of alternating dashes and gaps, an empty list draws a solid line.
+    pub dasharray: Vec<f32>,
+    pub dashoffset: f32,
+    /// Painting order of the stroke and the fill, which doesn't change the outline.
+    pub order: PaintOrder,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            cap: StrokeLineCap::default(),
+            join: StrokeLineJoin::default(),
+            dasharray: vec![],
+            dashoffset: 0.0,
+            order: PaintOrder::default(),
+        }
+    }
+}
+
+impl StrokeStyle {
+    /// Resolve the properties of `stroke`, unspecified properties take their initial values.
+    ///
+    /// Returns `None` if any property is animated.
+    pub fn resolve(stroke: &Stroke, ctx: &ResolveContext) -> Option<Self> {
+        let mut style = Self::default();
+
+        style.inherit(stroke, ctx)?;
+
+        Some(style)
+    }
+
+    /// Override the properties specified by `stroke`, the others are inherited from this style.
+    ///
+    /// Returns `None` if any property is animated, and these properties are left unchanged.
+    pub fn inherit(&mut self, stroke: &Stroke, ctx: &ResolveContext) -> Option<()> {
+        let mut animated = false;
+
+        if let Some(width) = &stroke.width {
+            match ctx.animatable_length(width, Axis::Other) {
+                Some(width) => self.width = width,
+                None => animated = true,
+            }
+        }
+
+        if let Some(dasharray) = &stroke.dasharray {
+            let values = constant(dasharray).and_then(|values| {
+                values
+                    .iter()
+                    .map(|value| ctx.animatable_length(value, Axis::Other))
+                    .collect::<Option<Vec<_>>>()
+            });
+
+            match values {
+                Some(values) => self.dasharray = values,
+                None => animated = true,
+            }
+        }
+
+        if let Some(dashoffset) = &stroke.dashoffset {
+            match ctx.animatable_length(dashoffset, Axis::Other) {
+                Some(dashoffset) => self.dashoffset = dashoffset,
+                None => animated = true,
+            }
+        }
+
+        if let Some(cap) = &stroke.linecap {
+            match constant(cap) {
+                Some(cap) => self.cap = *cap,
+                None => animated = true,
+            }
+        }
+
+        if let Some(join) = &stroke.linejoin {
+            match constant(join) {
+                Some(join) => self.join = *join,
+                None => animated = true,
+            }
+        }
+
+        if let Some(order) = &stroke.paint_order {
+            match constant(order) {
+                Some(order) => self.order = *order,
+                None => animated = true,
+            }
+        }
+
+        (!animated).then_some(())
+    }
+}
+
+/// Converts the stroke of `subpaths` into closed polygons, the distance between the curves and the outline
+/// is at most `tolerance`.
+///
+/// The polygons have the same orientation and overlap each other, so they must be filled with the
+/// [`Nonzero`](crate::FillRule::Nonzero) rule to get the stroke area.
+pub fn stroke_to_fill(subpaths: &[SubPath], style: &StrokeStyle, tolerance: f32) -> Vec<Polyline> {
+    let mut stroker = Stroker {
+        half: style.width / 2.0,
+        style,
+        tolerance: tolerance.max(f32::EPSILON),
+        polygons: vec![],
+    };
+
+    if stroker.half.is_nan() || stroker.half <= 0.0 {
+        return vec![];
+    }
+
+    for subpath in dash(subpaths, &style.dasharray, style.dashoffset) {
+        let mut polyline = subpath.flatten(tolerance);
+
+        // zero length dashes are drawn as caps only.
+        polyline.points.dedup();
+
+        stroker.polyline(&polyline);
+    }
+
+    stroker.polygons
+}
+
+impl Path {
+    /// Converts the stroke of this path into closed polygons, see [`stroke_to_fill`] for more information.
+    ///
+    /// Returns `None` if the path data or any stroke property is animated.
+    pub fn stroke_to_fill(
+        &self,
+        ctx: &ResolveContext,
+        stroke: &Stroke,
+        tolerance: f32,
+    ) -> Option<Vec<Polyline>> {
+        let style = StrokeStyle::resolve(stroke, ctx)?;
+
+        Some(stroke_to_fill(&ctx.path(self)?, &style, tolerance))
+    }
+}
+
+struct Stroker<'a> {
+    style: &'a StrokeStyle,
+    half: f32,
+    tolerance: f32,
+    polygons: Vec<Polyline>,
+}
+
+impl<'a> Stroker<'a> {
+    fn push(&mut self, mut points: Vec<Point>) {
+        // keep all polygons counterclockwise in the y-down user space, so the nonzero rule fills the union.
+        let area: f32 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(from, to)| from.cross(*to))
+            .sum();
+
+        if area > 0.0 {
+            points.reverse();
+        }
+
+        self.polygons.push(Polyline {
+            points,
+            closed: true,
+        });
+    }
+
+    fn polyline(&mut self, polyline: &Polyline) {
+        let points = &polyline.points;
+
+        match points.len() {
+            0 => return,
+            // a zero length sub-path is drawn with its caps only.
+            1 => {
+                self.cap(points[0], Point::new(1.0, 0.0));
+                self.cap(points[0], Point::new(-1.0, 0.0));
+                return;
+            }
+            _ => {}
+        }
+
+        for (from, to) in polyline.edges() {
+            let normal = normal(to - from) * self.half;
+
+            self.push(vec![from + normal, to + normal, to - normal, from - normal]);
+        }
+
+        let len = points.len();
+
+        if polyline.closed {
+            for i in 0..len {
+                let prev = points[(i + len - 1) % len];
+                let next = points[(i + 1) % len];
+                self.join(points[i], points[i] - prev, next - points[i]);
+            }
+        } else {
+            for window in points.windows(3) {
+                self.join(window[1], window[1] - window[0], window[2] - window[1]);
+            }
+
+            self.cap(points[0], points[0] - points[1]);
+            self.cap(points[len - 1], points[len - 1] - points[len - 2]);
+        }
+    }
+
+    /// Adds the join at `point` between the incoming direction `d0` and the outgoing direction `d1`.
+    fn join(&mut self, point: Point, d0: Point, d1: Point) {
+        let (d0, d1) = (d0.normalize(), d1.normalize());
+
+        let cross = d0.cross(d1);
+
+        // no join for collinear segments.
+        if cross.abs() <= f32::EPSILON && d0.dot(d1) > 0.0 {
+            return;
+        }
+
+        // the outer side of the corner is opposite to the turn.
+        let sign = if cross > 0.0 { -1.0 } else { 1.0 };
+
+        let n0 = normal(d0) * (sign * self.half);
+        let n1 = normal(d1) * (sign * self.half);
+
+        match self.style.join {
+            StrokeLineJoin::Bevel => self.push(vec![point, point + n0, point + n1]),
+            StrokeLineJoin::Round => {
+                let mut points = vec![point];
+                self.arc(&mut points, point, n0, n1);
+                self.push(points);
+            }
+            StrokeLineJoin::Miter(limit) => {
+                let bisector = (n0 + n1).normalize();
+                let cos = bisector.dot(n0.normalize());
+
+                // the ratio of the miter length to the stroke width is `1 / sin(θ / 2)`,
+                // where θ is the angle between the segments, and `sin(θ / 2)` equals `cos`.
+                if cos > f32::EPSILON && 1.0 / cos <= limit.0 .0 {
+                    let miter = point + bisector * (self.half / cos);
+                    self.push(vec![point, point + n0, miter, point + n1]);
+                } else {
+                    self.push(vec![point, point + n0, point + n1]);
+                }
+            }
+        }
+    }
+
+    /// Adds the cap at the end `point` of an open sub-path, `direction` points out of the sub-path.
+    fn cap(&mut self, point: Point, direction: Point) {
+        let direction = direction.normalize() * self.half;
+        let normal = normal(direction);
+
+        match self.style.cap {
+            StrokeLineCap::Butt => {}
+            StrokeLineCap::Square => self.push(vec![
+                point + normal,
+                point + normal + direction,
+                point - normal + direction,
+                point - normal,
+            ]),
+            StrokeLineCap::Round => {
+                let mut points = vec![];
+                self.arc(&mut points, point, normal, direction);
+                self.arc(&mut points, point, direction, -normal);
+                self.push(points);
+            }
+        }
+    }
+
+    /// Appends the points of the shorter circular arc around `center` from `center + from` to `center + to`.
+    fn arc(&self, points: &mut Vec<Point>, center: Point, from: Point, to: Point) {
+        let angle = from.cross(to).atan2(from.dot(to));
+
+        // the maximum step angle keeping the chords within the tolerance.
+        let step = 2.0 * (1.0 - self.tolerance / self.half).clamp(-1.0, 1.0).acos();
+        let count = (angle.abs() / step.max(PI / 180.0)).ceil().max(1.0) as usize;
+
+        let start = from.y.atan2(from.x);
+
+        for i in 0..=count {
+            let (sin, cos) = (start + angle * i as f32 / count as f32).sin_cos();
+            points.push(center + Point::new(cos, sin) * self.half);
+        }
+    }
+}
+
+/// Returns the unit normal of `direction`, rotated 90 degrees clockwise in the y-down user space.
+fn normal(direction: Point) -> Point {
+    let direction = direction.normalize();
+
+    Point::new(-direction.y, direction.x)
+}
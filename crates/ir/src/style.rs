@@ -0,0 +1,567 @@
+//! A tiny css engine, used to restyle ir codes without editing the program that generated them.
+//!
+//! Only a subset of css is supported:
+//!
+//! * simple selectors: type(`rect`), class(`.name`), id(`#name`), universal(`*`) and the compound of them
+//!   (e.g. `g.name`), grouped by commas;
+//! * the presentation properties that have an ir counterpart: `fill`, `fill-rule`, `stroke`, `stroke-width`,
+//!   `stroke-linecap`, `stroke-linejoin`, `stroke-dasharray`, `stroke-dashoffset`, `paint-order`, `font-family`,
+//!   `font-size`, `font-style`, `font-variant`, `font-weight` and `font-stretch`.
+//!
+//! The selectors are matched against the elements as they are rendered to svg: [`Layer`](crate::Layer) is an `svg`
+//! element, [`Label`] is a `g` element carrying the `id` and `class` attributes, [`Rect`](crate::Rect), [`Text`](crate::Text)
+//! and [`TextSpan`] are `rect`, `text` and `tspan` elements.
+
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+use core::str::FromStr;
+
+use crate::{
+    parse_dasharray, Animatable, Error, Fill, FillRule, Font, FontFamily, FontStretch, FontStyle,
+    FontVariant, FontWeight, Label, Measurement, Paint, PaintOrder, Result, Rgba, Stroke,
+    StrokeLineCap, StrokeLineJoin, TextSpan, IR,
+};
+
+/// A compound selector, e.g. `g#title.primary`.
+#[derive(Debug, Default, PartialEq, Clone)]
+struct Selector {
+    tag: Option<String>,
+    id: Option<String>,
+    class: Vec<String>,
+}
+
+impl Selector {
+    fn parse(s: &str) -> Result<Self> {
+        let invalid = || Error::Stylesheet(format!("unsupported selector `{}`", s));
+
+        if s.is_empty() || s.contains(|c: char| c.is_whitespace() || "[]:>+~".contains(c)) {
+            return Err(invalid());
+        }
+
+        let mut selector = Selector::default();
+
+        let mut rest = s;
+
+        let tag_end = rest.find(['#', '.']).unwrap_or(rest.len());
+
+        match &rest[..tag_end] {
+            "" | "*" => {}
+            tag => selector.tag = Some(tag.to_owned()),
+        }
+
+        rest = &rest[tag_end..];
+
+        while let Some(prefix) = rest.chars().next() {
+            let end = rest[1..]
+                .find(['#', '.'])
+                .map(|v| v + 1)
+                .unwrap_or(rest.len());
+
+            let name = &rest[1..end];
+
+            if name.is_empty() {
+                return Err(invalid());
+            }
+
+            if prefix == '#' {
+                selector.id = Some(name.to_owned());
+            } else {
+                selector.class.push(name.to_owned());
+            }
+
+            rest = &rest[end..];
+        }
+
+        Ok(selector)
+    }
+
+    /// The css specificity: (ids, classes, types).
+    fn specificity(&self) -> (usize, usize, usize) {
+        (
+            self.id.is_some() as usize,
+            self.class.len(),
+            self.tag.is_some() as usize,
+        )
+    }
+
+    fn matches(&self, tag: &str, label: Option<&Label>) -> bool {
+        if let Some(expected) = &self.tag {
+            if expected != tag {
+                return false;
+            }
+        }
+
+        if let Some(id) = &self.id {
+            if label.and_then(|label| label.id.as_ref()) != Some(id) {
+                return false;
+            }
+        }
+
+        self.class
+            .iter()
+            .all(|name| label.map(|label| label.has_class(name)).unwrap_or(false))
+    }
+}
+
+/// A parsed presentation property.
+#[derive(Debug, PartialEq, Clone)]
+enum Declaration {
+    Fill(Option<Paint>),
+    FillRule(FillRule),
+    Stroke(Option<Paint>),
+    StrokeWidth(Measurement),
+    StrokeLineCap(StrokeLineCap),
+    StrokeLineJoin(StrokeLineJoin),
+    StrokeDashArray(Vec<Measurement>),
+    StrokeDashOffset(Measurement),
+    PaintOrder(PaintOrder),
+    FontFamily(FontFamily),
+    FontSize(Measurement),
+    FontStyle(FontStyle),
+    FontVariant(FontVariant),
+    FontWeight(FontWeight),
+    FontStretch(FontStretch),
+}
+
+impl Declaration {
+    /// Parse one `name: value` pair, returns `None` for unsupported properties.
+    fn parse(name: &str, value: &str) -> Result<Option<Self>> {
+        let invalid = || Error::Stylesheet(format!("invalid value `{}` of `{}`", value, name));
+
+        let keyword = value.to_ascii_lowercase();
+
+        let declaration = match name {
+            "fill" => Declaration::Fill(parse_paint(value)?),
+            "fill-rule" => Declaration::FillRule(match keyword.as_str() {
+                "nonzero" => FillRule::Nonzero,
+                "evenodd" => FillRule::EvenOdd,
+                _ => return Err(invalid()),
+            }),
+            "stroke" => Declaration::Stroke(parse_paint(value)?),
+            "stroke-width" => Declaration::StrokeWidth(value.parse()?),
+            "stroke-linecap" => Declaration::StrokeLineCap(match keyword.as_str() {
+                "butt" => StrokeLineCap::Butt,
+                "round" => StrokeLineCap::Round,
+                "square" => StrokeLineCap::Square,
+                _ => return Err(invalid()),
+            }),
+            "stroke-linejoin" => Declaration::StrokeLineJoin(match keyword.as_str() {
+                "miter" => StrokeLineJoin::Miter(Default::default()),
+                "round" => StrokeLineJoin::Round,
+                "bevel" => StrokeLineJoin::Bevel,
+                _ => return Err(invalid()),
+            }),
+            "stroke-dasharray" => Declaration::StrokeDashArray(parse_dasharray(&keyword)?),
+            "stroke-dashoffset" => Declaration::StrokeDashOffset(value.parse()?),
+            "paint-order" => {
+                Declaration::PaintOrder(parse_paint_order(&keyword).ok_or_else(invalid)?)
+            }
+            "font-family" => {
+                let family = value
+                    .split(',')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .trim_matches(['"', '\'']);
+
+                Declaration::FontFamily(match family {
+                    "serif" => FontFamily::Serif,
+                    "sans-serif" => FontFamily::SansSerif,
+                    "cursive" => FontFamily::Cursive,
+                    "fantasy" => FontFamily::Fantasy,
+                    "monospace" => FontFamily::Monospace,
+                    "" => return Err(invalid()),
+                    family => FontFamily::Custom(family.to_owned()),
+                })
+            }
+            "font-size" => Declaration::FontSize(value.parse()?),
+            "font-style" => Declaration::FontStyle(match keyword.as_str() {
+                "normal" => FontStyle::Normal,
+                "italic" => FontStyle::Italic,
+                "oblique" => FontStyle::Oblique,
+                _ => return Err(invalid()),
+            }),
+            "font-variant" => Declaration::FontVariant(match keyword.as_str() {
+                "normal" => FontVariant::Normal,
+                "small-caps" => FontVariant::SmallCaps,
+                _ => return Err(invalid()),
+            }),
+            "font-weight" => Declaration::FontWeight(match keyword.as_str() {
+                "normal" => FontWeight::Normal,
+                "bold" => FontWeight::Bold,
+                "bolder" => FontWeight::Bolder,
+                "lighter" => FontWeight::Lighter,
+                "100" => FontWeight::W100,
+                "200" => FontWeight::W200,
+                "300" => FontWeight::W300,
+                "400" => FontWeight::W400,
+                "500" => FontWeight::W500,
+                "600" => FontWeight::W600,
+                "700" => FontWeight::W700,
+                "800" => FontWeight::W800,
+                "900" => FontWeight::W900,
+                _ => return Err(invalid()),
+            }),
+            "font-stretch" => Declaration::FontStretch(match keyword.as_str() {
+                "normal" => FontStretch::Normal,
+                "wider" => FontStretch::Wider,
+                "narrower" => FontStretch::Narrower,
+                "ultra-condensed" => FontStretch::UltraCondensed,
+                "extra-condensed" => FontStretch::ExtraCondensed,
+                "condensed" => FontStretch::Condensed,
+                "semi-condensed" => FontStretch::SemiCondensed,
+                "semi-expanded" => FontStretch::SemiExpanded,
+                "expanded" => FontStretch::Expanded,
+                "extra-expanded" => FontStretch::ExtraExpanded,
+                "ultra-expanded" => FontStretch::UltraExpanded,
+                _ => return Err(invalid()),
+            }),
+            _ => {
+                log::warn!("stylesheet: unsupported property `{}`, skipped", name);
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(declaration))
+    }
+
+    fn apply(&self, target: &mut StyleTarget<'_>) {
+        match self.clone() {
+            Declaration::Fill(paint) => fill(target).paint = paint.map(Animatable::Constant),
+            Declaration::FillRule(value) => fill(target).rule = Some(Animatable::Constant(value)),
+            Declaration::Stroke(paint) => stroke(target).paint = paint.map(Animatable::Constant),
+            Declaration::StrokeWidth(value) => {
+                stroke(target).width = Some(Animatable::Constant(value))
+            }
+            Declaration::StrokeLineCap(value) => {
+                stroke(target).linecap = Some(Animatable::Constant(value))
+            }
+            Declaration::StrokeLineJoin(value) => {
+                stroke(target).linejoin = Some(Animatable::Constant(value))
+            }
+            Declaration::StrokeDashArray(values) => {
+                stroke(target).dasharray = Some(Animatable::Constant(
+                    values.into_iter().map(Animatable::Constant).collect(),
+                ))
+            }
+            Declaration::StrokeDashOffset(value) => {
+                stroke(target).dashoffset = Some(Animatable::Constant(value))
+            }
+            Declaration::PaintOrder(value) => {
+                stroke(target).paint_order = Some(Animatable::Constant(value))
+            }
+            Declaration::FontFamily(value) => {
+                font(target).family = Some(Animatable::Constant(value))
+            }
+            Declaration::FontSize(value) => font(target).size = Some(Animatable::Constant(value)),
+            Declaration::FontStyle(value) => font(target).style = Some(Animatable::Constant(value)),
+            Declaration::FontVariant(value) => {
+                font(target).variant = Some(Animatable::Constant(value))
+            }
+            Declaration::FontWeight(value) => {
+                font(target).weight = Some(Animatable::Constant(value))
+            }
+            Declaration::FontStretch(value) => {
+                font(target).stretch = Some(Animatable::Constant(value))
+            }
+        }
+    }
+}
+
+fn parse_paint(value: &str) -> Result<Option<Paint>> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+
+    Ok(Some(Paint::Color(value.parse::<Rgba>()?)))
+}
+
+/// The properties an element gets from the stylesheet.
+struct StyleTarget<'a> {
+    fill: &'a mut Option<Fill>,
+    stroke: &'a mut Option<Stroke>,
+    font: &'a mut Option<Font>,
+}
+
+fn fill<'a>(target: &'a mut StyleTarget<'_>) -> &'a mut Fill {
+    target.fill.get_or_insert_with(Default::default)
+}
+
+/// Parses `normal` or a list of `fill`, `stroke` and `markers`, the omitted ones are painted after the listed
+/// ones in their normal order. Markers have no ir counterpart.
+fn parse_paint_order(value: &str) -> Option<PaintOrder> {
+    if value.trim() == "normal" {
+        return Some(PaintOrder::FillStroke);
+    }
+
+    value.split_whitespace().next()?;
+
+    let mut order = None;
+
+    for keyword in value.split_whitespace() {
+        match keyword {
+            "fill" => {
+                order.get_or_insert(PaintOrder::FillStroke);
+            }
+            "stroke" => {
+                order.get_or_insert(PaintOrder::StrokeFill);
+            }
+            "markers" => {}
+            _ => return None,
+        }
+    }
+
+    Some(order.unwrap_or_default())
+}
+
+fn stroke<'a>(target: &'a mut StyleTarget<'_>) -> &'a mut Stroke {
+    target.stroke.get_or_insert_with(Default::default)
+}
+
+fn font<'a>(target: &'a mut StyleTarget<'_>) -> &'a mut Font {
+    target.font.get_or_insert_with(Default::default)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Rule {
+    selectors: Vec<Selector>,
+    declarations: Vec<Declaration>,
+}
+
+/// A parsed stylesheet, see [module level documentation](self) for the supported css subset.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Stylesheet {
+    rules: Vec<Rule>,
+}
+
+impl FromStr for Stylesheet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl Stylesheet {
+    /// Parse a stylesheet from css source, unsupported properties are skipped.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut source = strip_comments(source);
+        let mut rules = vec![];
+
+        loop {
+            let trimmed = source.trim_start();
+
+            if trimmed.is_empty() {
+                break;
+            }
+
+            let open = trimmed.find('{').ok_or_else(|| {
+                Error::Stylesheet(format!("expect `{{` after `{}`", trimmed.trim()))
+            })?;
+
+            let close = trimmed[open..]
+                .find('}')
+                .map(|v| v + open)
+                .ok_or_else(|| Error::Stylesheet("unclosed rule block".to_owned()))?;
+
+            let selectors = trimmed[..open]
+                .split(',')
+                .map(|v| Selector::parse(v.trim()))
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut declarations = vec![];
+
+            for declaration in trimmed[open + 1..close].split(';') {
+                let declaration = declaration.trim();
+
+                if declaration.is_empty() {
+                    continue;
+                }
+
+                let (name, value) = declaration.split_once(':').ok_or_else(|| {
+                    Error::Stylesheet(format!("expect `name: value`, got `{}`", declaration))
+                })?;
+
+                let value = value.trim();
+                let value = value.strip_suffix("!important").unwrap_or(value).trim();
+
+                if let Some(declaration) =
+                    Declaration::parse(&name.trim().to_ascii_lowercase(), value)?
+                {
+                    declarations.push(declaration);
+                }
+            }
+
+            rules.push(Rule {
+                selectors,
+                declarations,
+            });
+
+            source = trimmed[close + 1..].to_owned();
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Returns true if this stylesheet has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Apply this stylesheet to the ir codes stream.
+    ///
+    /// The matched properties are injected as [`Fill`], [`Stroke`] and [`Font`] scopes around `rect` and `text`
+    /// elements, and right inside `svg` and `g` elements, the enclosing `pop` instructions are adjusted accordingly.
+    /// `tspan` elements have their own properties overwritten. Declarations from rules with higher specificity win,
+    /// the later rule wins if the specificity is equal.
+    pub fn apply(&self, codes: Vec<IR>) -> Vec<IR> {
+        if self.is_empty() {
+            return codes;
+        }
+
+        let mut output = Vec::with_capacity(codes.len());
+        // the extra `pop`s of the opened scopes.
+        let mut scopes: Vec<usize> = vec![];
+
+        for ir in codes {
+            match ir {
+                IR::Pop(n) => {
+                    let start = scopes.len().saturating_sub(n);
+                    let extra = scopes.drain(start..).sum::<usize>();
+
+                    output.push(IR::Pop(n + extra));
+                }
+                ir @ (IR::Rect(_) | IR::Path(_)) => {
+                    let styles = self.styles(ir.name(), None);
+                    let count = styles.len();
+
+                    output.extend(styles);
+                    output.push(ir);
+
+                    if count > 0 {
+                        output.push(IR::Pop(count));
+                    }
+                }
+                IR::Text(text) => {
+                    let styles = self.styles("text", None);
+
+                    scopes.push(styles.len());
+                    output.extend(styles);
+                    output.push(IR::Text(text));
+                }
+                IR::Layer(layer) => {
+                    let styles = self.styles("svg", None);
+
+                    scopes.push(styles.len());
+                    output.push(IR::Layer(layer));
+                    output.extend(styles);
+                }
+                IR::Label(label) => {
+                    let styles = self.styles("g", Some(&label));
+
+                    scopes.push(styles.len());
+                    output.push(IR::Label(label));
+                    output.extend(styles);
+                }
+                IR::TextSpan(mut span) => {
+                    self.apply_to_text_span(&mut span);
+
+                    scopes.push(0);
+                    output.push(IR::TextSpan(span));
+                }
+                ir => {
+                    if ir.is_scoped() {
+                        scopes.push(0);
+                    }
+
+                    output.push(ir);
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Returns the declarations that apply to an element, in cascade order.
+    fn cascade(&self, tag: &str, label: Option<&Label>) -> Vec<&Declaration> {
+        let mut matched = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(order, rule)| {
+                rule.selectors
+                    .iter()
+                    .filter(|selector| selector.matches(tag, label))
+                    .map(|selector| selector.specificity())
+                    .max()
+                    .map(|specificity| (specificity, order, rule))
+            })
+            .collect::<Vec<_>>();
+
+        matched.sort_by_key(|(specificity, order, _)| (*specificity, *order));
+
+        matched
+            .into_iter()
+            .flat_map(|(_, _, rule)| rule.declarations.iter())
+            .collect()
+    }
+
+    fn styles(&self, tag: &str, label: Option<&Label>) -> Vec<IR> {
+        let (mut fill, mut stroke, mut font) = (None, None, None);
+
+        let mut target = StyleTarget {
+            fill: &mut fill,
+            stroke: &mut stroke,
+            font: &mut font,
+        };
+
+        for declaration in self.cascade(tag, label) {
+            declaration.apply(&mut target);
+        }
+
+        let mut styles = vec![];
+
+        if let Some(fill) = fill {
+            styles.push(fill.into());
+        }
+
+        if let Some(stroke) = stroke {
+            styles.push(stroke.into());
+        }
+
+        if let Some(font) = font {
+            styles.push(font.into());
+        }
+
+        styles
+    }
+
+    fn apply_to_text_span(&self, span: &mut TextSpan) {
+        let mut target = StyleTarget {
+            fill: &mut span.fill,
+            stroke: &mut span.stroke,
+            font: &mut span.font,
+        };
+
+        for declaration in self.cascade("tspan", None) {
+            declaration.apply(&mut target);
+        }
+    }
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+
+    output.push_str(rest);
+
+    output
+}
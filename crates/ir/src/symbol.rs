@@ -0,0 +1,62 @@
+use alloc::string::String;
+
+use super::{Animatable, Measurement, ViewBox};
+
+/// The ‘symbol’ element.
+///
+/// It is used to define graphical template objects which can be instantiated by a [`Use`] element.
+///
+/// A symbol is a scoped element, the elements in its scope are the template contents, which are only rendered
+/// by the [`Use`] elements referencing it. Instancing a symbol instead of repeating its contents keeps the output
+/// small, the contents are emitted once no matter how many times they are used.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SymbolDef {
+    /// The unique name of this symbol, referenced by [`Use::href`].
+    #[cfg_attr(feature = "dsl", dsl(required))]
+    pub id: String,
+
+    /// stretch the contents to fit the viewport established by the referencing [`Use`] element.
+    pub viewbox: Option<Animatable<ViewBox>>,
+}
+
+/// The ‘use’ element references a [`SymbolDef`], and renders its contents at the given position.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Use {
+    /// The id of the referenced symbol.
+    #[cfg_attr(feature = "dsl", dsl(required))]
+    pub href: String,
+
+    /// The x-axis coordinate of one corner of the rectangular region into which the referenced element is placed.
+    ///
+    /// If the attribute is not specified, the effect is as if a value of "0" were specified.
+    ///
+    /// Animatable: yes.
+    pub x: Animatable<Measurement>,
+
+    /// The y-axis coordinate of one corner of the rectangular region into which the referenced element is placed.
+    ///
+    /// If the attribute is not specified, the effect is as if a value of "0" were specified.
+    ///
+    /// Animatable: yes.
+    pub y: Animatable<Measurement>,
+
+    /// The width of the viewport of the referenced symbol, `100%` if not specified.
+    /// A negative value is an error (see Error processing).
+    ///
+    /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
+    pub width: Option<Animatable<Measurement>>,
+
+    /// The height of the viewport of the referenced symbol, `100%` if not specified.
+    /// A negative value is an error (see Error processing).
+    ///
+    /// Animatable: yes.
+    #[cfg_attr(feature = "dsl", dsl(range(0.0..)))]
+    pub height: Option<Animatable<Measurement>>,
+}
@@ -0,0 +1,249 @@
+//! Triangulates ir codes into a colored triangle mesh, the bridge between the ir and gpu renderers.
+
+use crate::{
+    bounds::{Symbols, Walker},
+    geometry::{line_intersections, Matrix, Point, Polyline},
+    stroke_to_fill, BoundsOptions, FillRule, PaintOrder, ResolveContext, Rgba, DEFAULT_TOLERANCE,
+    IR,
+};
+
+/// A triangle mesh in the root user space.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Mesh {
+    /// The vertex positions.
+    pub vertices: Vec<[f32; 2]>,
+    /// Every three indices form a triangle.
+    pub indices: Vec<u32>,
+    /// The normalized rgba color of every vertex.
+    pub colors: Vec<[f32; 4]>,
+}
+
+impl Mesh {
+    /// Returns the number of triangles.
+    pub fn triangles(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Appends a convex polygon as a triangle fan.
+    fn convex_polygon(&mut self, points: &[Point], matrix: &Matrix, color: Rgba) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let base = self.vertices.len() as u32;
+
+        for point in points {
+            let point = matrix.transform_point(*point);
+            self.vertices.push([point.x, point.y]);
+            self.colors.push(color.into());
+        }
+
+        for i in 1..points.len() as u32 - 1 {
+            self.indices.extend([base, base + i, base + i + 1]);
+        }
+    }
+
+    /// Appends the interior of `polylines` under the fill `rule`, the polylines are closed implicitly.
+    ///
+    /// The interior is cut into horizontal slabs at every vertex and edge crossing, so the edges don't cross
+    /// inside a slab, and every span of a slab between two edges is appended as a trapezoid.
+    fn polygons(&mut self, polylines: &[Polyline], rule: FillRule, matrix: &Matrix, color: Rgba) {
+        // the edges from top to bottom, with their winding directions.
+        let mut edges = vec![];
+
+        for polyline in polylines {
+            let points = &polyline.points;
+
+            for (index, from) in points.iter().enumerate() {
+                let to = points[(index + 1) % points.len()];
+
+                if from.y < to.y {
+                    edges.push((*from, to, 1));
+                } else if from.y > to.y {
+                    edges.push((to, *from, -1));
+                }
+            }
+        }
+
+        let mut ys = edges
+            .iter()
+            .flat_map(|(top, bottom, _)| [top.y, bottom.y])
+            .collect::<Vec<_>>();
+
+        let mut crossings = vec![];
+
+        for (index, (p0, p1, _)) in edges.iter().enumerate() {
+            for (q0, q1, _) in &edges[index + 1..] {
+                line_intersections(*p0, *p1, *q0, *q1, &mut crossings);
+            }
+        }
+
+        ys.extend(crossings.iter().map(|point| point.y));
+        ys.sort_by(f32::total_cmp);
+        ys.dedup();
+
+        let x_at = |(top, bottom): (Point, Point), y: f32| {
+            top.x + (y - top.y) * (bottom.x - top.x) / (bottom.y - top.y)
+        };
+
+        for slab in ys.windows(2) {
+            let (y0, y1) = (slab[0], slab[1]);
+            let middle = (y0 + y1) / 2.0;
+
+            let mut active = edges
+                .iter()
+                .filter(|(top, bottom, _)| top.y <= y0 && bottom.y >= y1)
+                .map(|(top, bottom, winding)| ((*top, *bottom), *winding))
+                .collect::<Vec<_>>();
+
+            active.sort_by(|(a, _), (b, _)| x_at(*a, middle).total_cmp(&x_at(*b, middle)));
+
+            let mut winding = 0;
+            let mut start = None;
+
+            for (edge, direction) in active {
+                winding += direction;
+
+                let inside = match rule {
+                    FillRule::Nonzero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+
+                match (inside, start) {
+                    (true, None) => start = Some(edge),
+                    (false, Some(left)) => {
+                        start = None;
+
+                        let mut points = vec![
+                            Point::new(x_at(left, y0), y0),
+                            Point::new(x_at(edge, y0), y0),
+                            Point::new(x_at(edge, y1), y1),
+                            Point::new(x_at(left, y1), y1),
+                        ];
+
+                        // the trapezoids of the slabs at the vertices and crossings are triangles.
+                        points.dedup();
+
+                        self.convex_polygon(&points, matrix, color);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Options of [`tessellate_with`].
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct TessellateOptions {
+    /// The maximum distance between the curves and the triangles, in the root user space.
+    pub tolerance: f32,
+}
+
+impl Default for TessellateOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+}
+
+/// Triangulates the fills and strokes of the ir codes with the default options.
+///
+/// See [`tessellate_with`] for more information.
+pub fn tessellate(codes: &[IR], ctx: &ResolveContext) -> Mesh {
+    tessellate_with(codes, ctx, &TessellateOptions::default())
+}
+
+/// Triangulates the fills and strokes of the ir codes in painting order, the fill and the stroke of an element
+/// are ordered by the [`PaintOrder`] of the stroke.
+///
+/// Rects and paths are triangulated, the contents of the symbol of a [`Use`](crate::Use) element are triangulated
+/// at the instance. Images and text elements are skipped, so are elements with animated geometry.
+///
+/// Only solid color paints are supported, fills and strokes with paint servers are skipped. Stroke outlines
+/// consist of overlapping triangles, which should be drawn with a stencil or depth test if the color is translucent.
+pub fn tessellate_with(codes: &[IR], ctx: &ResolveContext, options: &TessellateOptions) -> Mesh {
+    let mut walker = Walker::new(*ctx);
+    let symbols = Symbols::new(codes);
+    let mut mesh = Mesh::default();
+
+    for ir in codes {
+        match ir {
+            IR::Use(value) => {
+                walker.instance(
+                    value,
+                    &symbols,
+                    &BoundsOptions::default(),
+                    &mut |walker, ir| element(walker, ir, &mut mesh, options),
+                );
+            }
+            ir => element(&walker, ir, &mut mesh, options),
+        }
+
+        walker.next(ir, &BoundsOptions::default());
+    }
+
+    mesh
+}
+
+/// Appends the fill and the stroke of `ir` to `mesh` with the states of `walker`, if it is a shape.
+fn element(walker: &Walker, ir: &IR, mesh: &mut Mesh, options: &TessellateOptions) {
+    let state = walker.state();
+
+    if state.disabled {
+        return;
+    }
+
+    // the outlines of rects are convex, the paths are cut into trapezoids.
+    let (outline, convex) = match ir {
+        IR::Rect(rect) => match walker.rect_outline(rect) {
+            Some(outline) => (vec![outline], true),
+            None => return,
+        },
+        IR::Path(path) => match walker.path_outline(path) {
+            Some(outline) => (outline, false),
+            None => return,
+        },
+        _ => return,
+    };
+
+    // the tolerance in the current user space.
+    let tolerance = options.tolerance / state.matrix.mean_scale().max(f32::EPSILON);
+
+    let fill = |mesh: &mut Mesh| {
+        let Some(color) = state.fill_color else {
+            return;
+        };
+
+        let polylines = outline
+            .iter()
+            .map(|subpath| subpath.flatten(tolerance))
+            .collect::<Vec<_>>();
+
+        if convex {
+            mesh.convex_polygon(&polylines[0].points, &state.matrix, color);
+        } else {
+            mesh.polygons(&polylines, state.fill_rule, &state.matrix, color);
+        }
+    };
+
+    let stroke = |mesh: &mut Mesh| {
+        if let (true, Some(color)) = (state.stroke_paint, state.stroke_color) {
+            for polygon in stroke_to_fill(&outline, &state.stroke, tolerance) {
+                mesh.convex_polygon(&polygon.points, &state.matrix, color);
+            }
+        }
+    };
+
+    match state.stroke.order {
+        PaintOrder::FillStroke => {
+            fill(mesh);
+            stroke(mesh);
+        }
+        PaintOrder::StrokeFill => {
+            stroke(mesh);
+            fill(mesh);
+        }
+    }
+}
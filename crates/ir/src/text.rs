@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use vglang_derive::Dsl;
 
 use crate::{Fill, Font, MapCollect, Stroke};
@@ -7,6 +8,7 @@ use super::{Angle, Animatable, FrameVariable, Href, Measurement};
 /// See [`length_adjust`](Text::length_adjust)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextLengthAdjust {
     /// 'spacing' indicates that only the advance values are adjusted. The glyphs themselves are not stretched or compressed.
     Spacing,
@@ -24,12 +26,30 @@ impl Default for TextLengthAdjust {
 
 impl FrameVariable for TextLengthAdjust {}
 
+/// See [`overflow`](Text::overflow)
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TextOverflow {
+    /// The characters that don't fit in the maximum width are removed.
+    #[default]
+    Clip,
+    /// The characters that don't fit in the maximum width, together with the room of an ellipsis("…"), are
+    /// replaced by the ellipsis.
+    Ellipsis,
+    /// Like [`Clip`](TextOverflow::Clip), and the last characters fade out with decreasing fill opacity.
+    Fade,
+}
+
+impl FrameVariable for TextOverflow {}
+
 /// The ‘text’ element defines a graphics element consisting of text.
 ///
 /// See [`text`](https://www.w3.org/TR/SVG11/text.html#TextElement)
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Text {
     /// If a single `coordinate` is provided, then the value represents the new absolute X coordinate for
     /// the current text position for rendering the glyphs that correspond to the first character within
@@ -104,6 +124,16 @@ pub struct Text {
     ///
     /// If the attribute is not specified, the effect is as a value of 'spacing' were specified.
     pub length_adjust: Animatable<TextLengthAdjust>,
+
+    /// The maximum advance of the whole text along the inline axis, the content beyond it is handled by
+    /// [`overflow`](Text::overflow).
+    ///
+    /// It has no svg counterpart, the codes are rewritten by `truncate_text`(the `font` feature) before
+    /// rendering. If the attribute is not specified, the text is not truncated.
+    pub max_width: Option<Animatable<Measurement>>,
+
+    /// See [`TextOverflow`]
+    pub overflow: Animatable<TextOverflow>,
 }
 
 impl<X, Y> From<(X, Y)> for Text
@@ -127,6 +157,7 @@ where
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TextSpan {
     /// If a single coordinate is provided, then the value represents the new absolute X coordinate for the current
     /// text position for rendering the glyphs that correspond to the first character within this element or any of its descendants.
@@ -266,6 +297,7 @@ pub struct TextSpan {
 /// For more on bidirectional text, see Relationship with bidirectionality.)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum WritingMode {
     /// Sets the initial inline-progression-direction to left-to-right, as is common in most Latin-based documents.
     /// For most characters, the current text position is advanced from left to right after each glyph is rendered.
@@ -302,6 +334,7 @@ impl Default for WritingMode {
 /// See [`GlyphOrientation`](https://www.w3.org/TR/SVG11/text.html#GlyphOrientation)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum GlyphOrientationVertical {
     Auto,
     Angle(Angle),
@@ -323,6 +356,7 @@ impl Default for GlyphOrientationVertical {
 /// according to the vertical metrics of the glyph.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GlyphOrientationHorizontal(pub Angle);
 
 /// Within text content elements, the alignment of text with regards to the ‘text-anchor’ property is determined by
@@ -337,6 +371,7 @@ pub struct GlyphOrientationHorizontal(pub Angle);
 /// See [`direction`](https://www.w3.org/TR/SVG11/text.html#DirectionProperty)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextDirection {
     Ltr,
     Rtl,
@@ -354,6 +389,7 @@ impl Default for TextDirection {
 /// [`unicode-bidi`]: https://www.w3.org/TR/2008/REC-CSS2-20080411/visuren.html#propdef-unicode-bidi
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum UnicodeBidi {
     Normal,
     Embed,
@@ -366,6 +402,25 @@ impl Default for UnicodeBidi {
     }
 }
 
+/// The handling of the white space characters in the text content, the ‘white-space’ property counterpart of the
+/// ‘xml:space’ attribute.
+///
+/// See [`xml:space`](https://www.w3.org/TR/SVG11/text.html#WhiteSpace)
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum WhiteSpace {
+    /// The ‘xml:space="default"’ processing: newline characters are removed, tab characters are converted into
+    /// space characters, then the leading space characters are stripped and the consecutive space characters are
+    /// collapsed into one.
+    #[default]
+    Normal,
+    /// The ‘xml:space="preserve"’ processing: newline characters are converted into space characters, and tab
+    /// characters are expanded to the next tab stop. The other space characters are kept, which renders
+    /// preformatted text(e.g. code snippets) as written.
+    Pre,
+}
+
 /// The ‘text-anchor’ property is used to align (start-, middle- or end-alignment) a string of text relative to a given point.
 ///
 /// The ‘text-anchor’ property is applied to each individual text chunk within a given ‘text’ element. Each text chunk has an
@@ -375,6 +430,7 @@ impl Default for UnicodeBidi {
 /// text position for a ‘textPath’ element.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextAnchor {
     /// The rendered characters are aligned such that the start of the resulting rendered text is at the initial current text position.
     /// For an element with a ‘direction’ property value of "ltr" (typical for most European languages), the left side of the text is
@@ -404,6 +460,7 @@ impl FrameVariable for TextAnchor {}
 /// See [`baseline`](https://www.w3.org/TR/SVG11/text.html#BaselineAlignmentProperties)
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum DominantBaseline {
     /// If this property occurs on a ‘text’ element, then the computed value depends on the value of the ‘writing-mode’ property.
     /// If the 'writing-mode' is horizontal, then the value of the dominant-baseline component is 'alphabetic', else if the
@@ -482,6 +539,7 @@ impl FrameVariable for DominantBaseline {}
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AlignmentBaseline {
     /// The value is the dominant-baseline of the script to which the character belongs - i.e.,
     /// use the dominant-baseline of the parent.
@@ -528,6 +586,7 @@ impl FrameVariable for AlignmentBaseline {}
 /// element, percent of the "line-height" of the parent text content element or an absolute value.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BaselineShift {
     /// There is no baseline shift; the dominant-baseline remains in its original position.
     Baseline,
@@ -579,6 +638,7 @@ impl FrameVariable for BaselineShift {}
 /// If the attribute is not specified, the effect is as if a value of align were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextPathMethod {
     Align,
     Stretch,
@@ -604,6 +664,7 @@ impl FrameVariable for TextPathMethod {}
 /// If the attribute is not specified, the effect is as if a value of exact were specified.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextPathSpacing {
     Auto,
     Exact,
@@ -619,6 +680,7 @@ impl FrameVariable for TextPathSpacing {}
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TextPath {
     /// An offset from the start of the ‘path’ for the initial current text position,
     /// calculated using the user agent's [`distance along the path`] algorithm.
@@ -652,6 +714,7 @@ pub struct TextPath {
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 #[cfg_attr(feature = "dsl", derive(vglang_derive::Dsl))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TextLayout {
     /// See [`WritingMode`]
     pub write_mode: Option<WritingMode>,
@@ -679,6 +742,14 @@ pub struct TextLayout {
 
     /// See [`BaselineShift`]
     pub baseline_shift: Option<Animatable<BaselineShift>>,
+
+    /// See [`WhiteSpace`]
+    pub white_space: Option<WhiteSpace>,
+
+    /// The distance between two tab stops in the [`WhiteSpace::Pre`] mode, in number of characters.
+    ///
+    /// If the property is not specified, the effect is as if a value of `8` were specified.
+    pub tab_size: Option<u32>,
 }
 
 impl From<WritingMode> for TextLayout {
@@ -726,6 +797,15 @@ impl From<UnicodeBidi> for TextLayout {
     }
 }
 
+impl From<WhiteSpace> for TextLayout {
+    fn from(value: WhiteSpace) -> Self {
+        Self {
+            white_space: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
 impl From<TextAnchor> for TextLayout {
     fn from(value: TextAnchor) -> Self {
         Self {
@@ -0,0 +1,753 @@
+//! Conversion of text elements into glyph outlines, which makes the output independent of installed fonts.
+
+use crate::{
+    bounds::Walker,
+    face::{vertical_angle, vertical_orientation},
+    geometry::{Matrix, Point, SubPath},
+    resolve::constant,
+    AlignmentBaseline, Angle, Animatable, Axis, BoundsOptions, ColorGlyph, ColorLayer,
+    DominantBaseline, ElementPath, Font, FontDatabase, FontFace, GlyphImage,
+    GlyphOrientationVertical, Measurement, ParsedFace, ResolveContext, TextAnchor, TextLayout,
+    TextLengthAdjust, WhiteSpace, WhiteSpaceProcessor, DEFAULT_TAB_SIZE, IR,
+};
+
+/// The glyph outlines of a text element.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TextOutline {
+    /// The location of the `text` element.
+    pub element: ElementPath,
+    /// The glyph outlines of all characters in the element, including the `tspan` contents,
+    /// in the root user space. Fill them with the nonzero rule.
+    pub subpaths: Vec<SubPath>,
+    /// The layers of the color glyphs(e.g. emoji) in the root user space, painted in order.
+    ///
+    /// The color glyphs have no outline in the `subpaths`.
+    pub layers: Vec<ColorLayer>,
+    /// The bitmaps of the color glyphs.
+    pub images: Vec<TextImage>,
+}
+
+/// A glyph bitmap placed in a text element.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TextImage {
+    pub image: GlyphImage,
+    /// Maps the glyph space of the image rect to the root user space.
+    pub matrix: Matrix,
+}
+
+/// Returns the glyph outlines of all text elements in `codes`, in the stream order.
+///
+/// The glyphs are laid out along the current text position as the svg text layout does, with the `x`, `y`,
+/// `dx`, `dy` and `rotate` values of the enclosing `text` and `tspan` elements, stretched to the `textLength`
+/// values by the `lengthAdjust` method, and every text chunk aligned by the `text-anchor` value. Glyphs sit on
+/// the `dominant-baseline` or the `alignment-baseline`, raised or lowered by the `baseline-shift` values.
+///
+/// In the vertical writing modes, glyphs advance downwards and are centered on the vertical central baseline,
+/// rotated by the `glyph-orientation-vertical` value. Fonts are queried from `fonts` by the inherited [`Font`]
+/// attributes; characters are mapped to glyphs by [`FontFace::shape`], the vertical writing modes are not shaped.
+/// The white space characters are processed by the inherited [`WhiteSpace`] mode before the layout.
+/// Color glyphs(e.g. emoji) are reported as [`ColorLayer`]s or [`TextImage`]s, see [`FontFace::color_glyph`].
+///
+/// Animated text content and positions are skipped, and elements with no glyph are not reported.
+pub fn text_to_outlines(
+    codes: &[IR],
+    ctx: &ResolveContext,
+    fonts: &FontDatabase,
+) -> Vec<TextOutline> {
+    let mut walker = Walker::new(*ctx);
+    let mut scopes: Vec<Scope> = vec![];
+    let mut layout: Option<Layout> = None;
+    let mut outlines = vec![];
+
+    for (index, ir) in codes.iter().enumerate() {
+        match ir {
+            IR::Pop(n) => {
+                for _ in 0..*n {
+                    let Some(scope) = scopes.pop() else {
+                        break;
+                    };
+
+                    if let (Some((start, length, adjust)), Some(layout)) =
+                        (&scope.length, layout.as_mut())
+                    {
+                        layout.adjust(*start, *length, adjust);
+                    }
+
+                    if scope.kind == Kind::Text {
+                        if let Some(layout) = layout.take() {
+                            if let Some(outline) = layout.finish() {
+                                outlines.push(outline);
+                            }
+                        }
+                    }
+                }
+            }
+            IR::String(content) => {
+                if let Some(layout) = layout.as_mut() {
+                    layout.push_str(content, &mut scopes, fonts);
+                }
+            }
+            IR::Text(text) if layout.is_none() && !walker.state().disabled => {
+                let mut path = scopes.iter().map(|scope| scope.index).collect::<Vec<_>>();
+                path.push(index);
+
+                layout = Some(Layout::new(ElementPath(path), walker.state().matrix));
+
+                let mut scope = Scope::new(&scopes, index, &walker.state().ctx);
+                scope.kind = Kind::Text;
+
+                let scope = scope
+                    .position(
+                        &walker.state().ctx,
+                        [&text.x, &text.y, &text.dx, &text.dy],
+                        &text.rotate,
+                    )
+                    .length(
+                        &walker.state().ctx,
+                        &text.text_length,
+                        &text.length_adjust,
+                        0,
+                    );
+
+                scopes.push(scope);
+            }
+            IR::TextSpan(span) => {
+                let mut scope = Scope::new(&scopes, index, &walker.state().ctx);
+
+                // the baseline shifts refer to the font of the parent element.
+                if let Some(text_layout) = &span.layout {
+                    scope = scope.layout(text_layout, fonts, &walker.state().ctx);
+                }
+
+                if let Some(font) = &span.font {
+                    scope = scope.font(font);
+                }
+
+                let ctx = ResolveContext {
+                    font_size: scope.size,
+                    ..walker.state().ctx
+                };
+
+                let start = layout.as_ref().map_or(0, |layout| layout.glyphs.len());
+
+                scopes.push(
+                    scope
+                        .position(&ctx, [&span.x, &span.y, &span.dx, &span.dy], &span.rotate)
+                        .length(&ctx, &span.text_length, &span.length_adjust, start),
+                );
+            }
+            IR::Font(font) => {
+                let scope = Scope::new(&scopes, index, &walker.state().ctx).font(font);
+                scopes.push(scope);
+            }
+            IR::TextLayout(text_layout) => {
+                let scope = Scope::new(&scopes, index, &walker.state().ctx).layout(
+                    text_layout,
+                    fonts,
+                    &walker.state().ctx,
+                );
+                scopes.push(scope);
+            }
+            ir if ir.is_scoped() => scopes.push(Scope::new(&scopes, index, &walker.state().ctx)),
+            _ => {}
+        }
+
+        walker.next(ir, &BoundsOptions::default());
+    }
+
+    outlines
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Kind {
+    Text,
+    Other,
+}
+
+/// The per-character positions of a `text` or `tspan` element.
+#[derive(Debug, Default, Clone)]
+struct Position {
+    /// The resolved `x`, `y`, `dx` and `dy` values.
+    values: [Vec<f32>; 4],
+    rotate: Vec<f32>,
+    /// The number of characters laid out in this element.
+    consumed: usize,
+}
+
+/// The inherited text properties of a scope.
+#[derive(Debug, Clone)]
+struct Scope {
+    index: usize,
+    kind: Kind,
+    font: Font,
+    size: f32,
+    anchor: TextAnchor,
+    /// The glyph orientation in the vertical writing modes, `None` in the horizontal ones.
+    vertical: Option<GlyphOrientationVertical>,
+    dominant: DominantBaseline,
+    alignment: Option<AlignmentBaseline>,
+    /// The distance the baseline is raised by the `baseline-shift` values of this and the enclosing elements.
+    shift: f32,
+    white_space: WhiteSpace,
+    tab_size: u32,
+    position: Option<Position>,
+    /// The first glyph, the resolved `textLength` and the `lengthAdjust` values of a `text` or `tspan` element.
+    length: Option<(usize, f32, TextLengthAdjust)>,
+}
+
+impl Scope {
+    fn new(scopes: &[Scope], index: usize, ctx: &ResolveContext) -> Self {
+        match scopes.last() {
+            Some(parent) => Self {
+                index,
+                kind: Kind::Other,
+                position: None,
+                length: None,
+                ..parent.clone()
+            },
+            None => Self {
+                index,
+                kind: Kind::Other,
+                font: Font::default(),
+                size: ctx.font_size,
+                anchor: TextAnchor::Start,
+                vertical: None,
+                dominant: DominantBaseline::Auto,
+                alignment: None,
+                shift: 0.0,
+                white_space: WhiteSpace::Normal,
+                tab_size: DEFAULT_TAB_SIZE,
+                position: None,
+                length: None,
+            },
+        }
+    }
+
+    fn font(mut self, font: &Font) -> Self {
+        if let Some(size) = font.size.as_ref().and_then(constant) {
+            let ctx = ResolveContext {
+                font_size: self.size,
+                ..Default::default()
+            };
+
+            self.size = ctx.length(*size, Axis::Other);
+        }
+
+        let inherited = std::mem::take(&mut self.font);
+
+        self.font = Font {
+            family: font.family.clone().or(inherited.family),
+            style: font.style.clone().or(inherited.style),
+            variant: font.variant.clone().or(inherited.variant),
+            weight: font.weight.clone().or(inherited.weight),
+            size: font.size.clone().or(inherited.size),
+            stretch: font.stretch.clone().or(inherited.stretch),
+        };
+
+        self
+    }
+
+    fn layout(mut self, layout: &TextLayout, fonts: &FontDatabase, ctx: &ResolveContext) -> Self {
+        if let Some(anchor) = layout.anchor.as_ref().and_then(constant) {
+            self.anchor = anchor.clone();
+        }
+
+        if let Some(white_space) = layout.white_space {
+            self.white_space = white_space;
+        }
+
+        if let Some(tab_size) = layout.tab_size {
+            self.tab_size = tab_size;
+        }
+
+        if let Some(baseline) = layout.dominant_baseline.as_ref().and_then(constant) {
+            self.dominant = baseline.clone();
+        }
+
+        if let Some(baseline) = layout.alignment_baseline.as_ref().and_then(constant) {
+            self.alignment = Some(baseline.clone());
+        }
+
+        if let (Some(shift), Some(face)) = (
+            layout.baseline_shift.as_ref().and_then(constant),
+            fonts.query(&self.font),
+        ) {
+            let ctx = ResolveContext {
+                font_size: self.size,
+                ..*ctx
+            };
+
+            self.shift += face.metrics(self.size).baseline_shift(shift, &ctx);
+        }
+
+        if layout.write_mode.is_some() {
+            self.vertical = vertical_orientation(layout);
+        } else if let (Some(vertical), Some(orientation)) = (&mut self.vertical, &layout.vertical) {
+            *vertical = orientation.clone();
+        }
+
+        self
+    }
+
+    fn position(
+        mut self,
+        ctx: &ResolveContext,
+        values: [&Animatable<Vec<Measurement>>; 4],
+        rotate: &Animatable<Vec<Angle>>,
+    ) -> Self {
+        let axes = [
+            Axis::Horizontal,
+            Axis::Vertical,
+            Axis::Horizontal,
+            Axis::Vertical,
+        ];
+
+        let mut position = Position::default();
+
+        for ((value, axis), resolved) in values.iter().zip(axes).zip(&mut position.values) {
+            if let Some(value) = constant(value) {
+                *resolved = value.iter().map(|v| ctx.length(*v, axis)).collect();
+            }
+        }
+
+        if let Some(rotate) = constant(rotate) {
+            position.rotate = rotate.iter().map(|angle| angle.as_deg()).collect();
+        }
+
+        self.position = Some(position);
+
+        self
+    }
+
+    /// Sets the `textLength` adjustment of the glyphs starting from the glyph `start`, zero lengths are ignored.
+    fn length(
+        mut self,
+        ctx: &ResolveContext,
+        length: &Animatable<Measurement>,
+        adjust: &Animatable<TextLengthAdjust>,
+        start: usize,
+    ) -> Self {
+        let axis = if self.vertical.is_some() {
+            Axis::Vertical
+        } else {
+            Axis::Horizontal
+        };
+
+        let Some(length) = constant(length).map(|length| ctx.length(*length, axis)) else {
+            return self;
+        };
+
+        if length > 0.0 {
+            let adjust = constant(adjust).cloned().unwrap_or_default();
+            self.length = Some((start, length, adjust));
+        }
+
+        self
+    }
+}
+
+/// The text properties of a run of characters.
+struct Style<'a> {
+    face: Option<ParsedFace<'a>>,
+    size: f32,
+    anchor: TextAnchor,
+    vertical: Option<GlyphOrientationVertical>,
+    /// The offset of the glyphs from the current text position, by the baseline alignment and shift.
+    baseline: Point,
+}
+
+/// A laid out glyph.
+struct Glyph {
+    outline: Vec<SubPath>,
+    color: Option<ColorGlyph>,
+    /// The current text position of the glyph.
+    origin: Point,
+    /// Maps the glyph space to the space at the origin.
+    local: Matrix,
+    /// The stretch factor along the inline axis, from the `spacingAndGlyphs` adjustments.
+    scale: f32,
+    /// The index of the glyph's text chunk.
+    chunk: usize,
+    /// The index of the character the glyph starts from.
+    cluster: usize,
+}
+
+/// A text chunk, which is aligned by its anchor as a whole.
+struct Chunk {
+    anchor: TextAnchor,
+    vertical: bool,
+    /// The current text position at the start and the end of the chunk.
+    start: Point,
+    end: Point,
+}
+
+/// The layout state of a `text` element.
+struct Layout {
+    element: ElementPath,
+    /// Maps the text user space to the root user space.
+    matrix: Matrix,
+    /// The current text position.
+    position: Point,
+    glyphs: Vec<Glyph>,
+    chunks: Vec<Chunk>,
+    /// The number of characters laid out.
+    chars: usize,
+    spaces: WhiteSpaceProcessor,
+}
+
+impl Layout {
+    fn new(element: ElementPath, matrix: Matrix) -> Self {
+        Self {
+            element,
+            matrix,
+            position: Point::default(),
+            glyphs: vec![],
+            chunks: vec![],
+            chars: 0,
+            spaces: WhiteSpaceProcessor::default(),
+        }
+    }
+
+    fn push_str(&mut self, content: &str, scopes: &mut [Scope], fonts: &FontDatabase) {
+        let Some(scope) = scopes.last() else {
+            return;
+        };
+
+        let content = self
+            .spaces
+            .process(content, scope.white_space, scope.tab_size);
+        let content = content.as_str();
+
+        let face = fonts.query(&scope.font).map(FontFace::parse);
+
+        // the vertical glyphs are centered on the central baseline, the shift goes along the x axis.
+        let baseline = match (&face, &scope.vertical) {
+            (Some(face), None) => {
+                let metrics = face.metrics(scope.size);
+
+                let height = scope
+                    .alignment
+                    .as_ref()
+                    .and_then(|baseline| metrics.alignment_baseline(baseline))
+                    .unwrap_or_else(|| metrics.dominant_baseline(&scope.dominant));
+
+                Point::new(0.0, height - scope.shift)
+            }
+            _ => Point::new(scope.shift, 0.0),
+        };
+
+        let style = Style {
+            face,
+            size: scope.size,
+            anchor: scope.anchor.clone(),
+            vertical: scope.vertical.clone(),
+            baseline,
+        };
+
+        match (&style.face, &style.vertical) {
+            (Some(face), None) => self.push_shaped(content, face, &style, scopes),
+            _ => {
+                for c in content.chars() {
+                    let rotate = self.next_char(&style, scopes);
+                    self.push_vertical(c, rotate, &style);
+                }
+            }
+        }
+    }
+
+    /// Lays out the horizontal glyph runs shaped by [`FontFace::shape`].
+    ///
+    /// The characters mapped to one glyph(e.g. ligatures) are positioned by the values of the first character.
+    fn push_shaped(
+        &mut self,
+        content: &str,
+        face: &ParsedFace,
+        style: &Style,
+        scopes: &mut [Scope],
+    ) {
+        let glyphs = face.shape(content, style.size);
+
+        let mut clusters = glyphs.iter().map(|glyph| glyph.cluster).collect::<Vec<_>>();
+        clusters.sort();
+        clusters.dedup();
+
+        let mut current = None;
+        let mut rotate = 0.0;
+
+        for glyph in glyphs {
+            if current != Some(glyph.cluster) {
+                current = Some(glyph.cluster);
+
+                let end = clusters
+                    .iter()
+                    .find(|cluster| **cluster > glyph.cluster)
+                    .copied()
+                    .unwrap_or(content.len());
+
+                rotate = self.next_char(style, scopes);
+
+                for _ in content[glyph.cluster..end].chars().skip(1) {
+                    Self::consume(scopes);
+                }
+            }
+
+            let local = Matrix::translate(style.baseline.x, style.baseline.y)
+                .multiply(&Matrix::rotate(rotate))
+                .multiply(&Matrix::translate(glyph.offset.x, glyph.offset.y));
+
+            self.push_glyph(face, glyph.id, style.size, local);
+
+            self.position.x += glyph.advance;
+        }
+    }
+
+    /// Moves the current text position to the next character, returns the rotation of the character.
+    fn next_char(&mut self, style: &Style, scopes: &mut [Scope]) -> f32 {
+        // the values of the nearest element specifying them.
+        let value = |i: usize| {
+            scopes.iter().rev().find_map(|scope| {
+                let position = scope.position.as_ref()?;
+                position.values[i].get(position.consumed).copied()
+            })
+        };
+
+        let (x, y, dx, dy) = (value(0), value(1), value(2), value(3));
+
+        // the last rotation value applies to all the remaining characters.
+        let rotate = scopes
+            .iter()
+            .rev()
+            .filter_map(|scope| scope.position.as_ref())
+            .find(|position| !position.rotate.is_empty())
+            .map(|position| position.rotate[position.consumed.min(position.rotate.len() - 1)])
+            .unwrap_or(0.0);
+
+        // an absolute position starts a new text chunk.
+        let new_chunk = x.is_some() || y.is_some() || self.chunks.is_empty();
+
+        if let (true, Some(chunk)) = (new_chunk, self.chunks.last_mut()) {
+            chunk.end = self.position;
+        }
+
+        self.position.x = x.unwrap_or(self.position.x) + dx.unwrap_or(0.0);
+        self.position.y = y.unwrap_or(self.position.y) + dy.unwrap_or(0.0);
+
+        if new_chunk {
+            self.chunks.push(Chunk {
+                anchor: style.anchor.clone(),
+                vertical: style.vertical.is_some(),
+                start: self.position,
+                end: self.position,
+            });
+        }
+
+        Self::consume(scopes);
+        self.chars += 1;
+
+        rotate
+    }
+
+    /// Consumes the positioning values of one character.
+    fn consume(scopes: &mut [Scope]) {
+        for position in scopes
+            .iter_mut()
+            .filter_map(|scope| scope.position.as_mut())
+        {
+            position.consumed += 1;
+        }
+    }
+
+    /// Lays out the glyph of `c` in the vertical writing modes.
+    fn push_vertical(&mut self, c: char, rotate: f32, style: &Style) {
+        let (Some(face), Some(orientation)) = (&style.face, &style.vertical) else {
+            return;
+        };
+
+        let size = style.size;
+
+        let angle = vertical_angle(c, orientation);
+        let advance = face.inline_advance(c, size, angle);
+        let metrics = face.metrics(size);
+
+        // the glyph is centered on the vertical central baseline.
+        let center = Point::new(
+            face.advance(c, size) / 2.0,
+            -(metrics.ascent + metrics.descent) / 2.0,
+        );
+
+        let local = Matrix::translate(style.baseline.x, style.baseline.y)
+            .multiply(&Matrix::rotate(rotate))
+            .multiply(&Matrix::translate(0.0, advance / 2.0))
+            .multiply(&Matrix::rotate(angle))
+            .multiply(&Matrix::translate(-center.x, -center.y));
+
+        self.push_glyph(face, face.glyph_id(c), size, local);
+
+        self.position.y += advance;
+    }
+
+    /// Places the glyph `id` at the current text position, color glyphs are drawn instead of their outlines.
+    fn push_glyph(&mut self, face: &ParsedFace, id: u16, size: f32, local: Matrix) {
+        let color = face.color_glyph(id, size);
+
+        let outline = match color {
+            Some(_) => vec![],
+            None => face.outline(id, size),
+        };
+
+        self.glyphs.push(Glyph {
+            outline,
+            color,
+            origin: self.position,
+            local,
+            scale: 1.0,
+            chunk: self.chunks.len().saturating_sub(1),
+            cluster: self.chars,
+        });
+    }
+
+    /// Adjusts the glyphs laid out since the glyph `start` to fill `length` along the inline axis, which
+    /// applies the `textLength` value of the element that started at that glyph.
+    ///
+    /// Only the glyphs of the current text chunk are adjusted, the ones before are placed by absolute positions.
+    fn adjust(&mut self, start: usize, length: f32, adjust: &TextLengthAdjust) {
+        let Some(chunk) = self.chunks.len().checked_sub(1) else {
+            return;
+        };
+
+        let Some(glyphs) = self.glyphs.get_mut(start..) else {
+            return;
+        };
+
+        let Some(first) = glyphs.iter().position(|glyph| glyph.chunk == chunk) else {
+            return;
+        };
+
+        let glyphs = &mut glyphs[first..];
+
+        let vertical = self.chunks[chunk].vertical;
+        let inline = |point: Point| if vertical { point.y } else { point.x };
+
+        let from = inline(glyphs[0].origin);
+        let actual = inline(self.position) - from;
+
+        if actual <= 0.0 {
+            return;
+        }
+
+        match adjust {
+            TextLengthAdjust::Spacing => {
+                let first = glyphs[0].cluster;
+                let count = glyphs[glyphs.len() - 1].cluster - first;
+
+                // the extra space goes between the characters.
+                if count == 0 {
+                    return;
+                }
+
+                let spacing = (length - actual) / count as f32;
+
+                for glyph in glyphs.iter_mut() {
+                    let shift = (glyph.cluster - first) as f32 * spacing;
+                    shift_inline(&mut glyph.origin, shift, vertical);
+                }
+            }
+            TextLengthAdjust::SpacingAndGlyphs => {
+                let scale = length / actual;
+
+                for glyph in glyphs.iter_mut() {
+                    let shift = (inline(glyph.origin) - from) * (scale - 1.0);
+                    shift_inline(&mut glyph.origin, shift, vertical);
+                    glyph.scale *= scale;
+                }
+            }
+        }
+
+        shift_inline(&mut self.position, length - actual, vertical);
+    }
+
+    /// Aligns the text chunks by their anchors and transforms the glyphs into the root user space.
+    fn finish(mut self) -> Option<TextOutline> {
+        if let Some(chunk) = self.chunks.last_mut() {
+            chunk.end = self.position;
+        }
+
+        let mut subpaths = vec![];
+        let mut layers = vec![];
+        let mut images = vec![];
+
+        for glyph in self.glyphs {
+            let chunk = &self.chunks[glyph.chunk];
+
+            let length = if chunk.vertical {
+                chunk.end.y - chunk.start.y
+            } else {
+                chunk.end.x - chunk.start.x
+            };
+
+            let mut origin = glyph.origin;
+
+            match chunk.anchor {
+                TextAnchor::Start => {}
+                TextAnchor::Middle => shift_inline(&mut origin, -length / 2.0, chunk.vertical),
+                TextAnchor::End => shift_inline(&mut origin, -length, chunk.vertical),
+            }
+
+            let scale = if chunk.vertical {
+                Matrix::scale(1.0, glyph.scale)
+            } else {
+                Matrix::scale(glyph.scale, 1.0)
+            };
+
+            let matrix = self
+                .matrix
+                .multiply(&Matrix::translate(origin.x, origin.y))
+                .multiply(&scale)
+                .multiply(&glyph.local);
+
+            let transform = |subpaths: Vec<SubPath>| {
+                subpaths.into_iter().map(|subpath| SubPath {
+                    segments: subpath
+                        .segments
+                        .iter()
+                        .map(|segment| segment.transform(&matrix))
+                        .collect(),
+                    closed: subpath.closed,
+                })
+            };
+
+            subpaths.extend(transform(glyph.outline));
+
+            match glyph.color {
+                Some(ColorGlyph::Layers(glyph_layers)) => {
+                    layers.extend(glyph_layers.into_iter().map(|layer| ColorLayer {
+                        subpaths: transform(layer.subpaths).collect(),
+                        color: layer.color,
+                    }))
+                }
+                Some(ColorGlyph::Image(image)) => images.push(TextImage { image, matrix }),
+                None => {}
+            }
+        }
+
+        if subpaths.is_empty() && layers.is_empty() && images.is_empty() {
+            return None;
+        }
+
+        Some(TextOutline {
+            element: self.element,
+            subpaths,
+            layers,
+            images,
+        })
+    }
+}
+
+/// Moves `point` by `shift` along the inline axis.
+fn shift_inline(point: &mut Point, shift: f32, vertical: bool) {
+    if vertical {
+        point.y += shift;
+    } else {
+        point.x += shift;
+    }
+}
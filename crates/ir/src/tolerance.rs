@@ -0,0 +1,111 @@
+//! Tolerant floating-point comparisons, so geometry results are consistent across platforms and testable.
+
+use crate::{
+    geometry::{Matrix, Point, Rect},
+    Measurement,
+};
+
+/// The maximum difference of two numbers which are considered equal.
+///
+/// Two numbers `a` and `b` are equal if `|a - b| <= max(absolute, relative * max(|a|, |b|))`, the absolute
+/// tolerance applies to the numbers near zero, and the relative one to the large numbers.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Tolerance {
+    pub absolute: f32,
+    pub relative: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Tolerance {
+    /// The default tolerance, about a hundred times the precision of `f32` numbers in `0..1`.
+    pub const DEFAULT: Tolerance = Tolerance::new(1e-5, 1e-5);
+
+    /// Create a tolerance with `absolute` and `relative` values.
+    pub const fn new(absolute: f32, relative: f32) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Returns the maximum difference of numbers in the magnitude of `a` and `b`.
+    pub fn epsilon(&self, a: f32, b: f32) -> f32 {
+        self.absolute.max(self.relative * a.abs().max(b.abs()))
+    }
+
+    /// Returns true if `a` and `b` are equal within this tolerance.
+    pub fn eq(&self, a: f32, b: f32) -> bool {
+        a == b || (a - b).abs() <= self.epsilon(a, b)
+    }
+
+    /// Returns true if `value` is zero within the absolute tolerance.
+    pub fn is_zero(&self, value: f32) -> bool {
+        value.abs() <= self.absolute
+    }
+}
+
+/// Types that can be compared with a [`Tolerance`].
+pub trait ApproxEq {
+    /// Returns true if `self` and `other` are equal within `tolerance`.
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool;
+
+    /// Returns true if `self` and `other` are equal within the [default tolerance](Tolerance::DEFAULT).
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.approx_eq_with(other, &Tolerance::DEFAULT)
+    }
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        tolerance.eq(*self, *other)
+    }
+}
+
+/// Measurements are equal if they have the same unit and the values are equal.
+impl ApproxEq for Measurement {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.1 == other.1 && tolerance.eq(self.0, other.0)
+    }
+}
+
+impl ApproxEq for Point {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        tolerance.eq(self.x, other.x) && tolerance.eq(self.y, other.y)
+    }
+}
+
+impl ApproxEq for Rect {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        tolerance.eq(self.x, other.x)
+            && tolerance.eq(self.y, other.y)
+            && tolerance.eq(self.width, other.width)
+            && tolerance.eq(self.height, other.height)
+    }
+}
+
+impl ApproxEq for Matrix {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        [self.a, self.b, self.c, self.d, self.e, self.f]
+            .into_iter()
+            .zip([other.a, other.b, other.c, other.d, other.e, other.f])
+            .all(|(a, b)| tolerance.eq(a, b))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|(a, b)| a.approx_eq_with(b, tolerance))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq_with(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.as_slice().approx_eq_with(other.as_slice(), tolerance)
+    }
+}
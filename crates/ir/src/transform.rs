@@ -1,8 +1,11 @@
+use core::fmt::Display;
+
 use super::FrameVariable;
 
 /// A memory represents of svg element's `transform` attribute.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Transform {
     Translate {
         tx: f32,
@@ -45,4 +48,60 @@ impl Transform {
             f: 0.0,
         }
     }
+
+    /// Create a translation by `tx` and `ty`.
+    pub fn translate(tx: f32, ty: f32) -> Self {
+        Self::Translate { tx, ty }
+    }
+
+    /// Create a scaling by `sx` and `sy`.
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Self::Scale { sx, sy }
+    }
+
+    /// Create a rotation of `angle` degrees around the origin.
+    pub fn rotate(angle: f32) -> Self {
+        Self::rotate_around(angle, 0.0, 0.0)
+    }
+
+    /// Create a rotation of `angle` degrees around the point (`cx`, `cy`).
+    pub fn rotate_around(angle: f32, cx: f32, cy: f32) -> Self {
+        Self::Rotate { angle, cx, cy }
+    }
+
+    /// Create a skew of `angle` degrees along the x axis.
+    pub fn skew_x(angle: f32) -> Self {
+        Self::SkewX(angle)
+    }
+
+    /// Create a skew of `angle` degrees along the y axis.
+    pub fn skew_y(angle: f32) -> Self {
+        Self::SkewY(angle)
+    }
+
+    /// Create a transformation from the matrix `[a c e] [b d f] [0 0 1]`.
+    pub fn matrix(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self::Matrix { a, b, c, d, e, f }
+    }
+}
+
+/// Formats the transform as the svg `transform` attribute value, e.g. `rotate(45 0 0)`.
+impl Display for Transform {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Transform::Translate { tx, ty } => write!(f, "translate({} {})", tx, ty),
+            Transform::Matrix {
+                a,
+                b,
+                c,
+                d,
+                e,
+                f: v,
+            } => write!(f, "matrix({} {} {} {} {} {})", a, b, c, d, e, v),
+            Transform::Scale { sx, sy } => write!(f, "scale({} {})", sx, sy),
+            Transform::Rotate { angle, cx, cy } => write!(f, "rotate({} {} {})", angle, cx, cy),
+            Transform::SkewX(angle) => write!(f, "skewX({})", angle),
+            Transform::SkewY(angle) => write!(f, "skewY({})", angle),
+        }
+    }
 }
@@ -0,0 +1,295 @@
+//! Truncation of texts wider than their maximum width, see [`Text::max_width`].
+
+use crate::{
+    resolve::constant, Axis, Fill, Font, FontDatabase, Paint, ResolveContext, Rgba, Text,
+    TextLayout, TextOverflow, TextSpan, WhiteSpace, WhiteSpaceProcessor, DEFAULT_TAB_SIZE, IR,
+};
+
+/// The number of characters faded out by [`TextOverflow::Fade`].
+const FADE_CHARS: usize = 3;
+
+/// Rewrites the content of the `text` elements with a [`max_width`](Text::max_width), so the text fits in it
+/// with the [`overflow`](Text::overflow) policy applied.
+///
+/// The strings are measured by [`FontDatabase::measure_text`] in the inherited fonts, the strings after the
+/// first overflowing one are removed. [`TextOverflow::Fade`] wraps the last characters into `tspan`s filled
+/// with the inherited fill color(black by default) of decreasing opacity. If the ellipsis of
+/// [`TextOverflow::Ellipsis`] doesn't fit in the rest of the maximum width itself, the text is clipped instead.
+///
+/// Texts with animated maximum widths or overflow policies, or without a loaded face, are kept as is.
+pub fn truncate_text(codes: &[IR], ctx: &ResolveContext, fonts: &FontDatabase) -> Vec<IR> {
+    let mut scopes: Vec<Scope> = vec![];
+    let mut truncation: Option<Truncation> = None;
+    let mut output = Vec::with_capacity(codes.len());
+
+    for ir in codes {
+        match ir {
+            IR::Pop(n) => {
+                for _ in 0..*n {
+                    scopes.pop();
+                }
+
+                if truncation
+                    .as_ref()
+                    .is_some_and(|truncation| truncation.depth > scopes.len())
+                {
+                    truncation = None;
+                }
+            }
+            IR::String(content) => {
+                if let (Some(truncation), Some(scope)) = (truncation.as_mut(), scopes.last()) {
+                    truncation.push(content, scope, ctx, fonts, &mut output);
+                    continue;
+                }
+            }
+            IR::Text(text) => {
+                let scope = Scope::new(&scopes, ctx);
+
+                if truncation.is_none() {
+                    truncation = Truncation::new(text, &scope, scopes.len() + 1, ctx);
+                }
+
+                scopes.push(scope);
+            }
+            IR::TextSpan(span) => {
+                let mut scope = Scope::new(&scopes, ctx);
+
+                if let Some(font) = &span.font {
+                    scope.font(font, ctx);
+                }
+
+                if let Some(layout) = &span.layout {
+                    scope.layout(layout);
+                }
+
+                if let Some(fill) = &span.fill {
+                    scope.fill(fill);
+                }
+
+                scopes.push(scope);
+            }
+            IR::Font(font) => {
+                let mut scope = Scope::new(&scopes, ctx);
+                scope.font(font, ctx);
+                scopes.push(scope);
+            }
+            IR::TextLayout(layout) => {
+                let mut scope = Scope::new(&scopes, ctx);
+                scope.layout(layout);
+                scopes.push(scope);
+            }
+            IR::Fill(fill) => {
+                let mut scope = Scope::new(&scopes, ctx);
+                scope.fill(fill);
+                scopes.push(scope);
+            }
+            ir if ir.is_scoped() => scopes.push(Scope::new(&scopes, ctx)),
+            _ => {}
+        }
+
+        output.push(ir.clone());
+    }
+
+    output
+}
+
+/// The inherited properties used by the measurement.
+#[derive(Debug, Clone)]
+struct Scope {
+    /// The inherited font, without the size.
+    font: Font,
+    size: f32,
+    white_space: WhiteSpace,
+    tab_size: u32,
+    color: Rgba,
+}
+
+impl Scope {
+    fn new(scopes: &[Scope], ctx: &ResolveContext) -> Self {
+        scopes.last().cloned().unwrap_or_else(|| Self {
+            font: Font::default(),
+            size: ctx.font_size,
+            white_space: WhiteSpace::Normal,
+            tab_size: DEFAULT_TAB_SIZE,
+            color: Rgba(0.0, 0.0, 0.0, 1.0),
+        })
+    }
+
+    fn font(&mut self, font: &Font, ctx: &ResolveContext) {
+        if let Some(size) = font.size.as_ref().and_then(constant) {
+            let ctx = ResolveContext {
+                font_size: self.size,
+                ..*ctx
+            };
+
+            self.size = ctx.length(*size, Axis::Other);
+        }
+
+        let inherited = std::mem::take(&mut self.font);
+
+        self.font = Font {
+            family: font.family.clone().or(inherited.family),
+            style: font.style.clone().or(inherited.style),
+            variant: font.variant.clone().or(inherited.variant),
+            weight: font.weight.clone().or(inherited.weight),
+            size: None,
+            stretch: font.stretch.clone().or(inherited.stretch),
+        };
+    }
+
+    fn layout(&mut self, layout: &TextLayout) {
+        if let Some(white_space) = layout.white_space {
+            self.white_space = white_space;
+        }
+
+        if let Some(tab_size) = layout.tab_size {
+            self.tab_size = tab_size;
+        }
+    }
+
+    fn fill(&mut self, fill: &Fill) {
+        if let Some(Paint::Color(color)) = fill.paint.as_ref().and_then(constant) {
+            self.color = *color;
+        }
+    }
+}
+
+/// The truncation state of a `text` element.
+struct Truncation {
+    /// The depth of the `text` scope.
+    depth: usize,
+    max_width: f32,
+    overflow: TextOverflow,
+    /// The width of the strings laid out.
+    width: f32,
+    overflowed: bool,
+    spaces: WhiteSpaceProcessor,
+}
+
+impl Truncation {
+    fn new(text: &Text, scope: &Scope, depth: usize, ctx: &ResolveContext) -> Option<Self> {
+        let max_width = constant(text.max_width.as_ref()?)?;
+        let overflow = constant(&text.overflow)?.clone();
+
+        let ctx = ResolveContext {
+            font_size: scope.size,
+            ..*ctx
+        };
+
+        Some(Self {
+            depth,
+            max_width: ctx.length(*max_width, Axis::Horizontal),
+            overflow,
+            width: 0.0,
+            overflowed: false,
+            spaces: WhiteSpaceProcessor::default(),
+        })
+    }
+
+    fn push(
+        &mut self,
+        content: &str,
+        scope: &Scope,
+        ctx: &ResolveContext,
+        fonts: &FontDatabase,
+        output: &mut Vec<IR>,
+    ) {
+        if self.overflowed {
+            return;
+        }
+
+        let ctx = ResolveContext {
+            font_size: scope.size,
+            ..*ctx
+        };
+
+        // the content is measured after the white space processing, which leaves no tab or newline.
+        let layout = TextLayout::from(WhiteSpace::Pre);
+        let text = self
+            .spaces
+            .process(content, scope.white_space, scope.tab_size);
+
+        let measure = |text: &str| {
+            fonts
+                .measure_text(&scope.font, &layout, text, &ctx)
+                .map(|metrics| metrics.width)
+        };
+
+        let Some(width) = measure(&text) else {
+            output.push(IR::String(content.to_owned()));
+            return;
+        };
+
+        if self.width + width <= self.max_width {
+            self.width += width;
+            output.push(IR::String(content.to_owned()));
+            return;
+        }
+
+        self.overflowed = true;
+
+        let available = self.max_width - self.width;
+
+        // an ellipsis wider than the rest of the maximum width is dropped, and the text is clipped.
+        let overflow = match self.overflow {
+            TextOverflow::Ellipsis if measure("…").unwrap_or(0.0) > available => {
+                TextOverflow::Clip
+            }
+            ref overflow => overflow.clone(),
+        };
+
+        let ellipsis = match overflow {
+            TextOverflow::Ellipsis => "…",
+            TextOverflow::Clip | TextOverflow::Fade => "",
+        };
+
+        let limit = available - measure(ellipsis).unwrap_or(0.0);
+
+        // the longest prefix fitting in the limit.
+        let ends = text
+            .char_indices()
+            .map(|(index, _)| index)
+            .skip(1)
+            .chain([text.len()])
+            .collect::<Vec<_>>();
+
+        let fits = ends.partition_point(|end| measure(&text[..*end]).unwrap_or(0.0) <= limit);
+
+        let prefix = match fits {
+            0 => "",
+            fits => &text[..ends[fits - 1]],
+        };
+
+        match overflow {
+            TextOverflow::Clip => output.push(IR::String(prefix.to_owned())),
+            TextOverflow::Ellipsis => {
+                output.push(IR::String(format!("{}{}", prefix.trim_end(), ellipsis)))
+            }
+            TextOverflow::Fade => self.fade(prefix, scope.color, output),
+        }
+    }
+
+    /// Pushes `text` with the last characters faded out.
+    fn fade(&self, text: &str, color: Rgba, output: &mut Vec<IR>) {
+        let chars = text.chars().collect::<Vec<_>>();
+        let split = chars.len().saturating_sub(FADE_CHARS);
+        let faded = chars.len() - split;
+
+        if split > 0 {
+            output.push(IR::String(chars[..split].iter().collect()));
+        }
+
+        for (i, c) in chars[split..].iter().enumerate() {
+            let alpha = (faded - i) as f32 / (faded + 1) as f32;
+
+            let span = TextSpan {
+                fill: Some(Fill::from(Rgba(color.0, color.1, color.2, color.3 * alpha))),
+                ..Default::default()
+            };
+
+            output.push(span.into());
+            output.push(IR::String(c.to_string()));
+            output.push(IR::Pop(1));
+        }
+    }
+}
@@ -0,0 +1,739 @@
+//! A verifier for ir codes streams, devices can assume the verified streams are well-formed.
+
+use alloc::collections::BTreeSet;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    Angle, Animatable, BaselineShift, Fe, FeComposite, FeFlood, FeGaussianBlur, FeIn, FeOffset,
+    FeOut, FePrimitive, Fill, Font, FrameVariable, Image, ImageSource, Label, Layer, Marker,
+    MarkerOrient, Mask, Measurement, Paint, Path, PathEvent, Point, Rect, Rgba, Stroke, SymbolDef,
+    Text, TextLayout, TextSpan, Transform, Use, ViewBox, IR,
+};
+
+/// A rule violation reported by [`verify`].
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Violation {
+    /// The index of the offending instruction.
+    ///
+    /// Unclosed scopes are reported at `codes.len()`.
+    pub index: usize,
+    /// The path of the offending value in the instruction, e.g. `.width` or `.dasharray[1]`,
+    /// empty if the violation is about the instruction itself.
+    pub field: String,
+    /// The description of this violation.
+    pub message: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{}]{}: {}", self.index, self.field, self.message)
+    }
+}
+
+/// Check the ir codes stream, returns all violations found in the stream order.
+///
+/// The following rules are checked:
+///
+/// * every scoped instruction is closed by a `pop` instruction, and no `pop` instruction pops more scopes than opened;
+/// * `tspan` elements are only used in `text` elements, literal strings and animated references are only used
+///   as text content, and `text`, `layer`, `rect` elements are not used in `text` elements;
+/// * [`Label`], mask, marker, symbol and filter ids are unique;
+/// * mask, marker, filter and `use` references name an id defined in the stream, before or after them;
+/// * filter primitives are only used in filter elements, and only reference the results of the preceding
+///   primitives of the same filter;
+/// * the numeric values are finite, sizes are non-negative and colors are normalized.
+///
+/// Values referenced by [`Animatable::Animated`] registers are not known before execution and are skipped.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(codes = codes.len())))]
+pub fn verify(codes: &[IR]) -> Result<(), Vec<Violation>> {
+    let mut verifier = Verifier::default();
+
+    for (index, ir) in codes.iter().enumerate() {
+        verifier.index = index;
+        verifier.verify(ir);
+    }
+
+    verifier.undefined();
+
+    if !verifier.scopes.is_empty() {
+        verifier.index = codes.len();
+        let message = format!("{} scopes are not closed", verifier.scopes.len());
+        verifier.report("", message);
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(violations = verifier.violations.len(), "verified");
+
+    if verifier.violations.is_empty() {
+        Ok(())
+    } else {
+        Err(verifier.violations)
+    }
+}
+
+/// The kind of opened scopes, used to check the operand types.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Scope {
+    Text,
+    Attribute,
+    Filter,
+    FeMerge,
+    Other,
+}
+
+#[derive(Default)]
+struct Verifier<'a> {
+    index: usize,
+    scopes: Vec<Scope>,
+    ids: BTreeSet<&'a str>,
+    /// The referenced ids, with the index and field of the references.
+    refs: Vec<(usize, &'static str, &'a str)>,
+    /// The named results of the primitives of the current filter.
+    results: BTreeSet<&'a str>,
+    violations: Vec<Violation>,
+}
+
+impl<'a> Verifier<'a> {
+    fn report<F: Display, M: Display>(&mut self, field: F, message: M) {
+        self.violations.push(Violation {
+            index: self.index,
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    /// Returns true if the innermost element scope is a text element.
+    fn in_text(&self) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find(|scope| **scope != Scope::Attribute)
+            .map(|scope| *scope == Scope::Text)
+            .unwrap_or(false)
+    }
+
+    fn verify(&mut self, ir: &'a IR) {
+        match ir {
+            IR::Pop(n) => {
+                if *n > self.scopes.len() {
+                    let message = format!(
+                        "pop {} elements, but only {} are pushed",
+                        n,
+                        self.scopes.len()
+                    );
+                    self.report("", message);
+                    self.scopes.clear();
+                } else {
+                    self.scopes.truncate(self.scopes.len() - n);
+                }
+
+                return;
+            }
+            IR::String(_) | IR::Animated(_) => {
+                if !self.in_text() {
+                    self.report("", "text content is only allowed in text elements");
+                }
+            }
+            IR::Text(value) => {
+                self.not_in_text("text");
+                self.text(value);
+            }
+            IR::TextSpan(value) => {
+                if !self.in_text() {
+                    self.report("", "tspan is only allowed in text elements");
+                }
+
+                self.text_span("", value);
+            }
+            IR::Layer(value) => {
+                self.not_in_text("layer");
+                self.layer(value);
+            }
+            IR::Rect(value) => {
+                self.not_in_text("rect");
+                self.rect(value);
+            }
+            IR::Path(value) => {
+                self.not_in_text("path");
+                self.path(value);
+            }
+            IR::Fill(value) => self.fill("", value),
+            IR::Stroke(value) => self.stroke("", value),
+            IR::Font(value) => self.font("", value),
+            IR::TextLayout(value) => self.text_layout("", value),
+            IR::Label(value) => self.label(value),
+            IR::Transform(value) => {
+                self.not_in_text("transform");
+                self.transform(value);
+            }
+            IR::Mask(value) => {
+                self.not_in_text("mask");
+                self.mask(value);
+            }
+            IR::MaskRef(value) => {
+                self.not_in_text("mask reference");
+                self.reference(".0", &value.0);
+            }
+            IR::Marker(value) => {
+                self.not_in_text("marker");
+                self.marker(value);
+            }
+            IR::Markers(value) => {
+                self.not_in_text("markers");
+
+                for (field, id) in [
+                    (".start", &value.start),
+                    (".mid", &value.mid),
+                    (".end", &value.end),
+                ] {
+                    if let Some(id) = id {
+                        self.reference(field, id);
+                    }
+                }
+            }
+            IR::Symbol(value) => {
+                self.not_in_text("symbol");
+                self.symbol(value);
+            }
+            IR::Use(value) => {
+                self.not_in_text("use");
+                self.use_(value);
+            }
+            IR::Image(value) => {
+                self.not_in_text("image");
+                self.image(value);
+            }
+            IR::Filter(value) => {
+                self.not_in_text("filter");
+                self.filter(value);
+            }
+            IR::FilterRef(value) => {
+                self.not_in_text("filter reference");
+                self.reference(".0", &value.0);
+            }
+            IR::FeGaussianBlur(value) => self.fe_gaussian_blur(value),
+            IR::FeOffset(value) => self.fe_offset(value),
+            IR::FeFlood(value) => self.fe_flood(value),
+            IR::FeComposite(value) => self.fe_composite(value),
+            IR::FeMerge(value) => {
+                self.fe_primitive("feMerge", ".0", &value.0);
+                self.fe_result(".0", &value.0);
+            }
+            IR::FeMergeItem(value) => {
+                if self.scopes.last() != Some(&Scope::FeMerge) {
+                    self.report("", "feMergeNode is only allowed in feMerge elements");
+                }
+
+                self.fe_in(".0", &value.0);
+            }
+        }
+
+        let scope = match ir {
+            IR::Text(_) | IR::TextSpan(_) => Scope::Text,
+            IR::Filter(_) => Scope::Filter,
+            IR::FeMerge(_) => Scope::FeMerge,
+            IR::Fill(_) | IR::Stroke(_) | IR::Font(_) | IR::TextLayout(_) => Scope::Attribute,
+            ir if ir.is_scoped() => Scope::Other,
+            _ => return,
+        };
+
+        self.scopes.push(scope);
+    }
+
+    fn not_in_text(&mut self, element: &str) {
+        if self.in_text() {
+            self.report("", format!("{} is not allowed in text elements", element));
+        }
+    }
+
+    fn finite(&mut self, field: &str, value: f32) -> bool {
+        if !value.is_finite() {
+            self.report(field, format!("expect a finite number, got {}", value));
+            return false;
+        }
+
+        true
+    }
+
+    fn non_negative(&mut self, field: &str, value: f32) {
+        if self.finite(field, value) && value < 0.0 {
+            self.report(
+                field,
+                format!("expect a non-negative number, got {}", value),
+            );
+        }
+    }
+
+    fn measurement(&mut self, field: &str, value: &Animatable<Measurement>) {
+        if let Some(value) = constant(value) {
+            self.finite(field, value.0);
+        }
+    }
+
+    fn length(&mut self, field: &str, value: &Animatable<Measurement>) {
+        if let Some(value) = constant(value) {
+            self.non_negative(field, value.0);
+        }
+    }
+
+    fn measurements(&mut self, field: &str, value: &Animatable<Vec<Measurement>>) {
+        if let Some(values) = constant(value) {
+            for (index, value) in values.iter().enumerate() {
+                self.finite(&format!("{}[{}]", field, index), value.0);
+            }
+        }
+    }
+
+    fn angles(&mut self, field: &str, value: &Animatable<Vec<Angle>>) {
+        if let Some(values) = constant(value) {
+            for (index, value) in values.iter().enumerate() {
+                self.finite(&format!("{}[{}]", field, index), value.as_deg());
+            }
+        }
+    }
+
+    fn paint(&mut self, field: &str, value: &Animatable<Paint>) {
+        match constant(value) {
+            Some(Paint::Color(rgba)) => self.rgba(field, rgba),
+            Some(Paint::Gradient(href)) | Some(Paint::Pattern(href)) if href.is_empty() => {
+                self.report(field, "empty paint server reference");
+            }
+            _ => {}
+        }
+    }
+
+    fn rgba(&mut self, field: &str, value: &Rgba) {
+        for (index, component) in <[f32; 4]>::from(*value).into_iter().enumerate() {
+            let field = format!("{}.{}", field, index);
+
+            if self.finite(&field, component) && !(0.0..=1.0).contains(&component) {
+                let message = format!("expect a normalized color component, got {}", component);
+                self.report(field, message);
+            }
+        }
+    }
+
+    fn rect(&mut self, value: &Rect) {
+        self.measurement(".x", &value.x);
+        self.measurement(".y", &value.y);
+        self.length(".width", &value.width);
+        self.length(".height", &value.height);
+        self.length(".rx", &value.rx);
+
+        if let Some(ry) = &value.ry {
+            self.length(".ry", ry);
+        }
+    }
+
+    fn image(&mut self, value: &Image) {
+        match &value.source {
+            ImageSource::Href(href) if href.0.is_empty() => self.report(".source", "empty href"),
+            ImageSource::Data { mime, .. } if !mime.starts_with("image/") => self.report(
+                ".source.mime",
+                format!("expect an image media type, got `{}`", mime),
+            ),
+            _ => {}
+        }
+
+        self.measurement(".x", &value.x);
+        self.measurement(".y", &value.y);
+        self.length(".width", &value.width);
+        self.length(".height", &value.height);
+    }
+
+    fn point(&mut self, field: &str, value: &Point) {
+        self.finite(&format!("{}.x", field), value.x.0);
+        self.finite(&format!("{}.y", field), value.y.0);
+    }
+
+    fn path(&mut self, value: &Path) {
+        self.length(".length", &value.length);
+
+        let Some(events) = constant(&value.data) else {
+            return;
+        };
+
+        for (index, event) in events.iter().enumerate() {
+            let field = format!(".data[{}]", index);
+
+            let Some(event) = constant(event) else {
+                continue;
+            };
+
+            // path data must begin with a `moveto` command.
+            if index == 0 && !matches!(event, PathEvent::MoveTo(_)) {
+                self.report(
+                    &field,
+                    "expect the path data to begin with a move_to command",
+                );
+            }
+
+            match event {
+                PathEvent::MoveTo(to) | PathEvent::LineTo(to) => self.point(&field, to),
+                PathEvent::ClosePath => {}
+                PathEvent::Polyline(points) => {
+                    for (index, point) in points.iter().enumerate() {
+                        self.point(&format!("{}[{}]", field, index), point);
+                    }
+                }
+                PathEvent::CubicBezier { ctrl1, ctrl2, to } => {
+                    self.point(&format!("{}.ctrl1", field), ctrl1);
+                    self.point(&format!("{}.ctrl2", field), ctrl2);
+                    self.point(&format!("{}.to", field), to);
+                }
+                PathEvent::QuadraticBezier { ctrl, to } => {
+                    self.point(&format!("{}.ctrl", field), ctrl);
+                    self.point(&format!("{}.to", field), to);
+                }
+                PathEvent::Arc {
+                    rx,
+                    ry,
+                    x_rotation,
+                    to,
+                    ..
+                } => {
+                    self.non_negative(&format!("{}.rx", field), rx.0);
+                    self.non_negative(&format!("{}.ry", field), ry.0);
+                    self.finite(&format!("{}.x_rotation", field), x_rotation.as_deg());
+                    self.point(&format!("{}.to", field), to);
+                }
+            }
+        }
+    }
+
+    fn transform(&mut self, value: &Animatable<Transform>) {
+        let Some(value) = constant(value) else {
+            return;
+        };
+
+        let values = match *value {
+            Transform::Translate { tx, ty } => vec![tx, ty],
+            Transform::Matrix { a, b, c, d, e, f } => vec![a, b, c, d, e, f],
+            Transform::Scale { sx, sy } => vec![sx, sy],
+            Transform::Rotate { angle, cx, cy } => vec![angle, cx, cy],
+            Transform::SkewX(angle) | Transform::SkewY(angle) => vec![angle],
+        };
+
+        for (index, value) in values.into_iter().enumerate() {
+            self.finite(&format!("[{}]", index), value);
+        }
+    }
+
+    fn viewbox(&mut self, field: &str, value: &ViewBox) {
+        self.measurement(&format!("{}.minx", field), &value.minx);
+        self.measurement(&format!("{}.miny", field), &value.miny);
+        self.length(&format!("{}.width", field), &value.width);
+        self.length(&format!("{}.height", field), &value.height);
+    }
+
+    fn layer(&mut self, value: &Layer) {
+        self.length(".width", &value.width);
+        self.length(".height", &value.height);
+
+        if let Some(viewbox) = value.viewbox.as_ref().and_then(constant) {
+            self.viewbox(".viewbox", viewbox);
+        }
+    }
+
+    fn text(&mut self, value: &Text) {
+        self.measurements(".x", &value.x);
+        self.measurements(".y", &value.y);
+        self.measurements(".dx", &value.dx);
+        self.measurements(".dy", &value.dy);
+        self.angles(".rotate", &value.rotate);
+        self.length(".text_length", &value.text_length);
+
+        if let Some(max_width) = &value.max_width {
+            self.length(".max_width", max_width);
+        }
+    }
+
+    fn text_span(&mut self, field: &str, value: &TextSpan) {
+        self.measurements(&format!("{}.x", field), &value.x);
+        self.measurements(&format!("{}.y", field), &value.y);
+        self.measurements(&format!("{}.dx", field), &value.dx);
+        self.measurements(&format!("{}.dy", field), &value.dy);
+        self.angles(&format!("{}.rotate", field), &value.rotate);
+        self.length(&format!("{}.text_length", field), &value.text_length);
+
+        if let Some(font) = &value.font {
+            self.font(&format!("{}.font", field), font);
+        }
+
+        if let Some(layout) = &value.layout {
+            self.text_layout(&format!("{}.layout", field), layout);
+        }
+
+        if let Some(fill) = &value.fill {
+            self.fill(&format!("{}.fill", field), fill);
+        }
+
+        if let Some(stroke) = &value.stroke {
+            self.stroke(&format!("{}.stroke", field), stroke);
+        }
+    }
+
+    fn fill(&mut self, field: &str, value: &Fill) {
+        if let Some(value) = &value.paint {
+            self.paint(&format!("{}.paint", field), value);
+        }
+    }
+
+    fn stroke(&mut self, field: &str, value: &Stroke) {
+        if let Some(value) = &value.paint {
+            self.paint(&format!("{}.paint", field), value);
+        }
+
+        if let Some(value) = &value.width {
+            self.length(&format!("{}.width", field), value);
+        }
+
+        if let Some(values) = value.dasharray.as_ref().and_then(constant) {
+            for (index, value) in values.iter().enumerate() {
+                self.length(&format!("{}.dasharray[{}]", field, index), value);
+            }
+        }
+
+        if let Some(value) = &value.dashoffset {
+            self.measurement(&format!("{}.dashoffset", field), value);
+        }
+    }
+
+    fn font(&mut self, field: &str, value: &Font) {
+        if let Some(value) = &value.size {
+            self.length(&format!("{}.size", field), value);
+        }
+    }
+
+    fn text_layout(&mut self, field: &str, value: &TextLayout) {
+        if let Some(BaselineShift::Value(value)) = value.baseline_shift.as_ref().and_then(constant)
+        {
+            self.finite(&format!("{}.baseline_shift", field), value.0);
+        }
+
+        if value.tab_size == Some(0) {
+            self.report(format!("{}.tab_size", field), "zero tab size");
+        }
+    }
+
+    /// Checks the id of a referenced element, ids share one namespace with the label ids.
+    fn id(&mut self, id: &'a str) {
+        if id.is_empty() {
+            self.report(".id", "empty id");
+        } else if !self.ids.insert(id) {
+            self.report(".id", format!("duplicate id `{}`", id));
+        }
+    }
+
+    /// Checks the id of a reference, the referenced element is looked up by [`undefined`](Self::undefined).
+    fn reference(&mut self, field: &'static str, id: &'a str) {
+        if id.is_empty() {
+            self.report(field, "empty id");
+        } else {
+            self.refs.push((self.index, field, id));
+        }
+    }
+
+    /// Reports the references to undefined ids, after all ids of the stream are collected.
+    fn undefined(&mut self) {
+        for (index, field, id) in core::mem::take(&mut self.refs) {
+            if !self.ids.contains(id) {
+                self.violations.push(Violation {
+                    index,
+                    field: field.to_string(),
+                    message: format!("undefined id `{}`", id),
+                });
+            }
+        }
+
+        // keep the violations in the stream order.
+        self.violations.sort_by_key(|violation| violation.index);
+    }
+
+    fn mask(&mut self, value: &'a Mask) {
+        self.id(&value.id);
+
+        self.measurement(".x", &value.x);
+        self.measurement(".y", &value.y);
+        self.length(".width", &value.width);
+        self.length(".height", &value.height);
+    }
+
+    fn marker(&mut self, value: &'a Marker) {
+        self.id(&value.id);
+
+        self.measurement(".refx", &value.refx);
+        self.measurement(".refy", &value.refy);
+        self.length(".width", &value.width);
+        self.length(".height", &value.height);
+
+        if let Some(MarkerOrient::Angle(angle)) = value.orient.as_ref().and_then(constant) {
+            self.finite(".orient", angle.as_deg());
+        }
+
+        if let Some(viewbox) = value.viewbox.as_ref().and_then(constant) {
+            self.viewbox(".viewbox", viewbox);
+        }
+    }
+
+    fn symbol(&mut self, value: &'a SymbolDef) {
+        self.id(&value.id);
+
+        if let Some(viewbox) = value.viewbox.as_ref().and_then(constant) {
+            self.viewbox(".viewbox", viewbox);
+        }
+    }
+
+    fn use_(&mut self, value: &'a Use) {
+        self.reference(".href", &value.href);
+
+        self.measurement(".x", &value.x);
+        self.measurement(".y", &value.y);
+
+        if let Some(width) = &value.width {
+            self.length(".width", width);
+        }
+
+        if let Some(height) = &value.height {
+            self.length(".height", height);
+        }
+    }
+
+    fn filter(&mut self, value: &'a Fe) {
+        self.id(&value.id);
+        self.results.clear();
+
+        self.measurement(".x", &value.x);
+        self.measurement(".y", &value.y);
+        self.length(".width", &value.width);
+        self.length(".height", &value.height);
+
+        if let Some(res) = value.filter_res.as_ref().and_then(constant) {
+            self.non_negative(".filter_res.x", res.x);
+
+            if let Some(y) = res.y {
+                self.non_negative(".filter_res.y", y);
+            }
+        }
+    }
+
+    /// Checks the subregion of a filter primitive, which must be a direct child of a filter element.
+    fn fe_primitive(&mut self, element: &str, field: &str, value: &FePrimitive) {
+        if self.scopes.last() != Some(&Scope::Filter) {
+            self.report(
+                "",
+                format!("{} is only allowed in filter elements", element),
+            );
+        }
+
+        self.measurement(&format!("{}.x", field), &value.x);
+        self.measurement(&format!("{}.y", field), &value.y);
+        self.length(&format!("{}.width", field), &value.width);
+        self.length(&format!("{}.height", field), &value.height);
+    }
+
+    /// Checks that a named input is the result of a preceding primitive, forward references are an error.
+    fn fe_in(&mut self, field: &str, value: &Animatable<FeIn>) {
+        if let Some(FeIn::Register(name)) = constant(value) {
+            if name.is_empty() {
+                self.report(field, "empty result reference");
+            } else if !self.results.contains(name.as_str()) {
+                self.report(field, format!("undefined result `{}`", name));
+            }
+        }
+    }
+
+    /// Records the named result of a primitive, after its inputs are checked.
+    fn fe_result(&mut self, field: &str, value: &'a FePrimitive) {
+        if let Some(FeOut::Named(name)) = constant(&value.out) {
+            if name.is_empty() {
+                self.report(format!("{}.out", field), "empty result name");
+            } else {
+                self.results.insert(name);
+            }
+        }
+    }
+
+    fn fe_gaussian_blur(&mut self, value: &'a FeGaussianBlur) {
+        self.fe_primitive("feGaussianBlur", ".primitive", &value.primitive);
+        self.fe_in(".in", &value.r#in);
+
+        if let Some(std_deviation) = constant(&value.std_deviation) {
+            self.non_negative(".std_deviation.dx", std_deviation.dx);
+
+            if let Some(dy) = std_deviation.dy {
+                self.non_negative(".std_deviation.dy", dy);
+            }
+        }
+
+        self.fe_result(".primitive", &value.primitive);
+    }
+
+    fn fe_offset(&mut self, value: &'a FeOffset) {
+        self.fe_primitive("feOffset", ".primitive", &value.primitive);
+        self.fe_in(".in", &value.r#in);
+
+        if let Some(dx) = constant(&value.dx) {
+            self.finite(".dx", *dx);
+        }
+
+        if let Some(dy) = constant(&value.dy) {
+            self.finite(".dy", *dy);
+        }
+
+        self.fe_result(".primitive", &value.primitive);
+    }
+
+    fn fe_flood(&mut self, value: &'a FeFlood) {
+        self.fe_primitive("feFlood", ".primitive", &value.primitive);
+
+        if let Some(color) = constant(&value.color) {
+            self.rgba(".color", color);
+        }
+
+        self.fe_result(".primitive", &value.primitive);
+    }
+
+    fn fe_composite(&mut self, value: &'a FeComposite) {
+        self.fe_primitive("feComposite", ".primitive", &value.primitive);
+        self.fe_in(".a", &value.a);
+        self.fe_in(".b", &value.b);
+
+        for (field, k) in [
+            (".k1", &value.k1),
+            (".k2", &value.k2),
+            (".k3", &value.k3),
+            (".k4", &value.k4),
+        ] {
+            if let Some(k) = constant(k) {
+                self.finite(field, k.0);
+            }
+        }
+
+        self.fe_result(".primitive", &value.primitive);
+    }
+
+    fn label(&mut self, value: &'a Label) {
+        if let Some(id) = &value.id {
+            if id.is_empty() {
+                self.report(".id", "empty id");
+            } else if !self.ids.insert(id) {
+                self.report(".id", format!("duplicate id `{}`", id));
+            }
+        }
+    }
+}
+
+/// Get the constant value of `value`, the animated variables are skipped.
+fn constant<T: FrameVariable>(value: &Animatable<T>) -> Option<&T> {
+    match value {
+        Animatable::Animated(_) => None,
+        Animatable::Constant(v) => Some(v),
+    }
+}
@@ -0,0 +1,71 @@
+//! The white space processing of the text content, see [`WhiteSpace`].
+
+use alloc::string::String;
+
+use crate::WhiteSpace;
+
+/// The default distance between two tab stops, in number of characters.
+pub const DEFAULT_TAB_SIZE: u32 = 8;
+
+/// Processes the white space of the consecutive strings of one text element.
+///
+/// The state is carried from one string to the next, so the spaces around `tspan` boundaries are collapsed
+/// and tab stops are counted from the start of the text, or from the last newline character.
+#[derive(Debug, Clone, Copy)]
+pub struct WhiteSpaceProcessor {
+    /// The column of the next character.
+    column: usize,
+    /// True if the last character is a space, which also holds at the start of the text.
+    space: bool,
+}
+
+impl Default for WhiteSpaceProcessor {
+    fn default() -> Self {
+        Self {
+            column: 0,
+            space: true,
+        }
+    }
+}
+
+impl WhiteSpaceProcessor {
+    /// Returns `text` processed with the white space `mode`, the `tab_size` is used by [`WhiteSpace::Pre`].
+    ///
+    /// The trailing space characters of a text element are not stripped, since the following strings are unknown.
+    pub fn process(&mut self, text: &str, mode: WhiteSpace, tab_size: u32) -> String {
+        let mut processed = String::with_capacity(text.len());
+
+        for c in text.chars() {
+            match (mode, c) {
+                (WhiteSpace::Normal, '\n' | '\r') => {}
+                (WhiteSpace::Normal, ' ' | '\t') => {
+                    if !self.space {
+                        processed.push(' ');
+                        self.space = true;
+                    }
+                }
+                (WhiteSpace::Normal, c) => {
+                    processed.push(c);
+                    self.space = false;
+                }
+                (WhiteSpace::Pre, '\n' | '\r') => {
+                    processed.push(' ');
+                    self.column = 0;
+                }
+                (WhiteSpace::Pre, '\t') => {
+                    let tab_size = tab_size.max(1) as usize;
+                    let spaces = tab_size - self.column % tab_size;
+
+                    processed.extend(core::iter::repeat_n(' ', spaces));
+                    self.column += spaces;
+                }
+                (WhiteSpace::Pre, c) => {
+                    processed.push(c);
+                    self.column += 1;
+                }
+            }
+        }
+
+        processed
+    }
+}
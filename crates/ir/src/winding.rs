@@ -0,0 +1,271 @@
+//! Orientation and winding utilities of paths, which make fills predictable after imports and boolean operations.
+
+use crate::{
+    geometry::{winding_number, Orientation, Polyline},
+    Animatable, FillRule, Path, PathEvent, Point, ResolveContext, DEFAULT_TOLERANCE,
+};
+
+/// Splits the path data into sub-paths, every sub-path starts with a `MoveTo` command.
+fn subpaths(data: &[PathEvent]) -> Vec<Vec<PathEvent>> {
+    let mut subpaths: Vec<Vec<PathEvent>> = vec![];
+    let mut start = Point::default();
+
+    for event in data {
+        match event {
+            PathEvent::MoveTo(to) => {
+                start = *to;
+                subpaths.push(vec![event.clone()]);
+            }
+            event => {
+                // drawing commands after `ClosePath` start a new sub-path at the same initial point.
+                let closed = matches!(
+                    subpaths.last().and_then(|subpath| subpath.last()),
+                    None | Some(PathEvent::ClosePath)
+                );
+
+                if closed {
+                    subpaths.push(vec![PathEvent::MoveTo(start)]);
+                }
+
+                subpaths.last_mut().expect("sub-path").push(event.clone());
+            }
+        }
+    }
+
+    subpaths
+}
+
+/// Returns the end point of the drawing command `event`.
+fn end_point(event: &PathEvent) -> Option<Point> {
+    match event {
+        PathEvent::MoveTo(to)
+        | PathEvent::LineTo(to)
+        | PathEvent::CubicBezier { to, .. }
+        | PathEvent::QuadraticBezier { to, .. }
+        | PathEvent::Arc { to, .. } => Some(*to),
+        PathEvent::Polyline(points) => points.last().copied(),
+        PathEvent::ClosePath => None,
+    }
+}
+
+/// Reverses the direction of a sub-path returned by [`subpaths`].
+fn reverse(subpath: &[PathEvent]) -> Vec<PathEvent> {
+    let Some((PathEvent::MoveTo(start), events)) = subpath.split_first() else {
+        return subpath.to_vec();
+    };
+
+    let closed = matches!(events.last(), Some(PathEvent::ClosePath));
+
+    let events = if closed {
+        &events[..events.len() - 1]
+    } else {
+        events
+    };
+
+    // the start point of every drawing command.
+    let mut point = *start;
+    let mut commands = vec![];
+
+    for event in events {
+        commands.push((point, event));
+        point = end_point(event).unwrap_or(point);
+    }
+
+    let end = point;
+
+    let mut reversed = vec![];
+
+    if closed {
+        reversed.push(PathEvent::MoveTo(*start));
+
+        // the implicit closing segment is reversed into an explicit one.
+        if end != *start {
+            reversed.push(PathEvent::LineTo(end));
+        }
+    } else {
+        reversed.push(PathEvent::MoveTo(end));
+    }
+
+    for (from, event) in commands.into_iter().rev() {
+        let event = match event {
+            PathEvent::LineTo(_) => PathEvent::LineTo(from),
+            PathEvent::Polyline(points) => {
+                let mut points: Vec<Point> = points.iter().rev().skip(1).copied().collect();
+                points.push(from);
+                PathEvent::Polyline(points)
+            }
+            PathEvent::CubicBezier { ctrl1, ctrl2, .. } => PathEvent::CubicBezier {
+                ctrl1: *ctrl2,
+                ctrl2: *ctrl1,
+                to: from,
+            },
+            PathEvent::QuadraticBezier { ctrl, .. } => PathEvent::QuadraticBezier {
+                ctrl: *ctrl,
+                to: from,
+            },
+            PathEvent::Arc {
+                rx,
+                ry,
+                x_rotation,
+                large_arc,
+                sweep,
+                ..
+            } => PathEvent::Arc {
+                rx: *rx,
+                ry: *ry,
+                x_rotation: *x_rotation,
+                large_arc: *large_arc,
+                sweep: !*sweep,
+                to: from,
+            },
+            PathEvent::MoveTo(_) | PathEvent::ClosePath => continue,
+        };
+
+        reversed.push(event);
+    }
+
+    if closed {
+        // the last line back to the start point is implied by `ClosePath`.
+        if end != *start && reversed.last() == Some(&PathEvent::LineTo(*start)) {
+            reversed.pop();
+        }
+
+        reversed.push(PathEvent::ClosePath);
+    }
+
+    reversed
+}
+
+impl Path {
+    /// Returns the path data, or `None` if any part of it is animated.
+    fn events(&self) -> Option<Vec<PathEvent>> {
+        match &self.data {
+            Animatable::Constant(events) => events
+                .iter()
+                .map(|event| match event {
+                    Animatable::Constant(event) => Some(event.clone()),
+                    Animatable::Animated(_) => None,
+                })
+                .collect(),
+            Animatable::Animated(_) => None,
+        }
+    }
+
+    fn with_events(&self, events: Vec<PathEvent>) -> Path {
+        Path {
+            data: Animatable::Constant(events.into_iter().map(Animatable::Constant).collect()),
+            length: self.length.clone(),
+        }
+    }
+
+    /// Returns the flattened polylines of the sub-paths split by [`subpaths`], `None` for empty sub-paths.
+    fn polylines(subpaths: &[Vec<PathEvent>], ctx: &ResolveContext) -> Vec<Option<Polyline>> {
+        subpaths
+            .iter()
+            .map(|events| {
+                let path = Path::default().with_events(events.clone());
+
+                ctx.path(&path)?
+                    .first()
+                    .map(|subpath| subpath.flatten(DEFAULT_TOLERANCE))
+            })
+            .collect()
+    }
+
+    /// Returns the orientation of the total signed area of this path, `None` if the area is zero or
+    /// the path data is animated.
+    pub fn orientation(&self, ctx: &ResolveContext) -> Option<Orientation> {
+        let area: f32 = ctx
+            .path(self)?
+            .iter()
+            .map(|subpath| subpath.flatten(DEFAULT_TOLERANCE).signed_area())
+            .sum();
+
+        if area > 0.0 {
+            Some(Orientation::Clockwise)
+        } else if area < 0.0 {
+            Some(Orientation::CounterClockwise)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the path with every sub-path reversed, the coordinates keep their units.
+    ///
+    /// Returns `None` if the path data is animated.
+    pub fn reverse(&self) -> Option<Path> {
+        let events = subpaths(&self.events()?)
+            .iter()
+            .flat_map(|subpath| reverse(subpath))
+            .collect();
+
+        Some(self.with_events(events))
+    }
+
+    /// Returns the path with the sub-paths reoriented, so that the nonzero rule fills the same area as
+    /// `rule` does with this path: outer sub-paths go clockwise and holes go counterclockwise.
+    ///
+    /// With [`EvenOdd`](FillRule::EvenOdd), a sub-path nested in an odd number of other sub-paths is a hole;
+    /// with [`Nonzero`](FillRule::Nonzero), a sub-path inside the area of the others is a hole if it goes in
+    /// the opposite direction of the enclosing winding. Nesting is tested at the first point of each sub-path.
+    ///
+    /// Returns `None` if the path data is animated.
+    pub fn ensure_winding(&self, ctx: &ResolveContext, rule: FillRule) -> Option<Path> {
+        let subpaths = subpaths(&self.events()?);
+        let polylines = Self::polylines(&subpaths, ctx);
+
+        let mut events = vec![];
+
+        for (index, (subpath, polyline)) in subpaths.iter().zip(&polylines).enumerate() {
+            let Some((polyline, orientation)) = polyline
+                .as_ref()
+                .and_then(|polyline| Some((polyline, polyline.orientation()?)))
+            else {
+                events.extend_from_slice(subpath);
+                continue;
+            };
+
+            let others = polylines
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != index)
+                .filter_map(|(_, polyline)| polyline.clone())
+                .collect::<Vec<_>>();
+
+            let point = polyline.points[0];
+
+            let hole = match rule {
+                FillRule::EvenOdd => {
+                    let depth = others
+                        .iter()
+                        .filter(|other| winding_number(std::slice::from_ref(*other), point) != 0)
+                        .count();
+
+                    depth % 2 == 1
+                }
+                FillRule::Nonzero => {
+                    let winding = winding_number(&others, point);
+
+                    match orientation {
+                        Orientation::Clockwise => winding < 0,
+                        Orientation::CounterClockwise => winding > 0,
+                    }
+                }
+            };
+
+            let expected = if hole {
+                Orientation::CounterClockwise
+            } else {
+                Orientation::Clockwise
+            };
+
+            if orientation == expected {
+                events.extend_from_slice(subpath);
+            } else {
+                events.extend(reverse(subpath));
+            }
+        }
+
+        Some(self.with_events(events))
+    }
+}
@@ -0,0 +1,196 @@
+//! Line breaking of long texts into a width-bounded box, backed by the text measurement.
+
+#[cfg(feature = "hyphenation")]
+use std::sync::Arc;
+
+#[cfg(feature = "hyphenation")]
+use crate::Hyphenator;
+use crate::{
+    Animatable, Font, FontDatabase, Measurement, ResolveContext, Text, TextLayout, TextSpan,
+    WhiteSpace, IR,
+};
+
+/// A width-bounded text box, whose content is broken into lines by [`layout`](TextBlock::layout).
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TextBlock {
+    /// The x coordinate of the current text position of every line, in user units.
+    pub x: f32,
+    /// The y coordinate of the first baseline, in user units.
+    pub y: f32,
+    /// The maximum width of the lines, in user units.
+    pub max_width: f32,
+    /// The distance between two consecutive baselines, defaults to the line height of the font.
+    pub line_height: Option<f32>,
+    /// Hyphenates the words that don't fit in the rest of a line, the words are not hyphenated if it's `None`.
+    #[cfg(feature = "hyphenation")]
+    pub hyphenator: Option<Arc<Hyphenator>>,
+}
+
+impl TextBlock {
+    /// Create a text box with the first baseline at `(x, y)`.
+    pub fn new(x: f32, y: f32, max_width: f32) -> Self {
+        Self {
+            x,
+            y,
+            max_width,
+            line_height: None,
+            #[cfg(feature = "hyphenation")]
+            hyphenator: None,
+        }
+    }
+
+    /// Breaks `text` into lines no wider than `max_width`, set in the face of `fonts` matching `font`.
+    ///
+    /// Lines are broken greedily at whitespaces, and words wider than the box are broken between characters.
+    /// With a [`hyphenator`](Self::hyphenator), the words that don't fit are first broken at their hyphenation
+    /// points, and a hyphen is appended to the line. Line feeds force a line break, and the other whitespaces
+    /// are collapsed into single spaces.
+    ///
+    /// Returns `None` if no face is loaded.
+    pub fn break_lines(
+        &self,
+        fonts: &FontDatabase,
+        font: &Font,
+        text: &str,
+        ctx: &ResolveContext,
+    ) -> Option<Vec<String>> {
+        // the words and spaces are measured as they are.
+        let layout = TextLayout::from(WhiteSpace::Pre);
+
+        let width = |text: &str| Some(fonts.measure_text(font, &layout, text, ctx)?.width);
+
+        let space = width(" ")?;
+
+        let mut lines = vec![];
+
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            let mut line_width = 0.0;
+
+            // the rest of a hyphenated word is laid out again.
+            #[cfg_attr(not(feature = "hyphenation"), allow(unused_mut))]
+            for mut word in paragraph.split_whitespace() {
+                loop {
+                    let gap = if line.is_empty() { 0.0 } else { space };
+                    let word_width = width(word)?;
+
+                    if line_width + gap + word_width <= self.max_width {
+                        if !line.is_empty() {
+                            line.push(' ');
+                        }
+
+                        line.push_str(word);
+                        line_width += gap + word_width;
+                        break;
+                    }
+
+                    #[cfg(feature = "hyphenation")]
+                    if let Some(at) =
+                        self.hyphen_break(word, self.max_width - line_width - gap, &width)
+                    {
+                        if !line.is_empty() {
+                            line.push(' ');
+                        }
+
+                        line.push_str(&word[..at]);
+                        line.push('-');
+                        lines.push(std::mem::take(&mut line));
+                        line_width = 0.0;
+                        word = &word[at..];
+                        continue;
+                    }
+
+                    // retry at the start of the next line.
+                    if !line.is_empty() {
+                        lines.push(std::mem::take(&mut line));
+                        line_width = 0.0;
+                        continue;
+                    }
+
+                    for c in word.chars() {
+                        let advance = width(c.encode_utf8(&mut [0; 4]))?;
+
+                        if !line.is_empty() && line_width + advance > self.max_width {
+                            lines.push(std::mem::take(&mut line));
+                            line_width = 0.0;
+                        }
+
+                        line.push(c);
+                        line_width += advance;
+                    }
+
+                    break;
+                }
+            }
+
+            lines.push(line);
+        }
+
+        Some(lines)
+    }
+
+    /// Returns the byte offset of the last hyphenation point of `word`, where the head and a hyphen fit
+    /// in `available` width.
+    ///
+    /// The leading and trailing punctuations are not part of the hyphenated word.
+    #[cfg(feature = "hyphenation")]
+    fn hyphen_break<F>(&self, word: &str, available: f32, width: &F) -> Option<usize>
+    where
+        F: Fn(&str) -> Option<f32>,
+    {
+        let hyphenator = self.hyphenator.as_ref()?;
+
+        let start = word.find(char::is_alphabetic)?;
+        let end = word
+            .rfind(char::is_alphabetic)
+            .map(|end| end + word[end..].chars().next().map_or(0, char::len_utf8))?;
+
+        hyphenator
+            .hyphenate(&word[start..end])
+            .into_iter()
+            .rev()
+            .map(|at| start + at)
+            .find(|at| width(&format!("{}-", &word[..*at])).is_some_and(|width| width <= available))
+    }
+
+    /// Returns the ir codes of a `text` element with one `tspan` for each line of `text` broken by
+    /// [`break_lines`](Self::break_lines), or `None` if no face is loaded.
+    ///
+    /// The codes are not wrapped in the `font` and `layout` attributes, the caller should apply them to the
+    /// returned codes, e.g. each line is aligned by the text anchor of `layout`.
+    pub fn layout(
+        &self,
+        fonts: &FontDatabase,
+        font: &Font,
+        layout: &TextLayout,
+        text: &str,
+        ctx: &ResolveContext,
+    ) -> Option<Vec<IR>> {
+        let line_height = match self.line_height {
+            Some(line_height) => line_height,
+            None => fonts.measure_text(font, layout, "", ctx)?.line_height,
+        };
+
+        let lines = self.break_lines(fonts, font, text, ctx)?;
+
+        let mut codes = vec![Text::from((vec![self.x], vec![self.y])).into()];
+
+        for (index, line) in lines.into_iter().enumerate() {
+            let dy = if index == 0 { 0.0 } else { line_height };
+
+            let span = TextSpan {
+                x: Animatable::Constant(vec![Measurement::from(self.x)]),
+                dy: Animatable::Constant(vec![Measurement::from(dy)]),
+                ..Default::default()
+            };
+
+            codes.push(span.into());
+            codes.push(IR::String(line));
+            codes.push(IR::Pop(1));
+        }
+
+        codes.push(IR::Pop(1));
+
+        Some(codes)
+    }
+}
@@ -0,0 +1,65 @@
+use vglang_ir::geometry::{EndpointArc, Point};
+
+fn close(a: Point, b: Point) -> bool {
+    a.distance(b) < 1e-3
+}
+
+#[test]
+fn test_arc_center() {
+    let arc = EndpointArc {
+        from: Point::new(0.0, 0.0),
+        rx: 10.0,
+        ry: 10.0,
+        x_rotation: 0.0,
+        large_arc: true,
+        sweep: true,
+        to: Point::new(10.0, 10.0),
+    };
+
+    let center = arc.to_center().unwrap();
+
+    assert!(close(center.center, Point::new(10.0, 0.0)));
+    assert!((center.sweep_angle - 270.0).abs() < 1e-3);
+
+    let endpoint = center.to_endpoint();
+
+    assert!(close(endpoint.from, arc.from));
+    assert!(close(endpoint.to, arc.to));
+    assert_eq!(
+        (endpoint.large_arc, endpoint.sweep),
+        (arc.large_arc, arc.sweep)
+    );
+
+    let cubics = arc.to_cubics();
+
+    assert_eq!(cubics.len(), 3);
+    assert_eq!(cubics[0].from(), arc.from);
+    assert_eq!(cubics[2].to(), arc.to);
+    assert!(close(cubics[0].to(), Point::new(10.0, -10.0)));
+}
+
+#[test]
+fn test_arc_out_of_range() {
+    // the radii are scaled up to span the endpoints.
+    let arc = EndpointArc {
+        from: Point::new(0.0, 0.0),
+        rx: 1.0,
+        ry: 2.0,
+        x_rotation: 0.0,
+        large_arc: false,
+        sweep: true,
+        to: Point::new(20.0, 0.0),
+    };
+
+    let center = arc.to_center().unwrap();
+
+    assert!(close(center.center, Point::new(10.0, 0.0)));
+    assert!((center.rx - 10.0).abs() < 1e-3);
+    assert!((center.ry - 20.0).abs() < 1e-3);
+
+    // zero radius arcs are lines.
+    let line = EndpointArc { rx: 0.0, ..arc };
+
+    assert_eq!(line.to_center(), None);
+    assert_eq!(line.to_cubics().len(), 1);
+}
@@ -0,0 +1,147 @@
+use vglang_ir::{geometry, *};
+
+#[test]
+fn test_bounds() {
+    let ctx = ResolveContext::default();
+
+    let codes: Vec<IR> = vec![
+        Layer::from((200, 100)).into(),
+        Rect::from((10, 10, 20, 20)).into(),
+        Stroke::from(Color::red).width(4).into(),
+        Rect::from((50, 50, 10, 10)).into(),
+        IR::Pop(1),
+        // an empty rect is not drawn.
+        Rect::from((150, 150, 0, 10)).into(),
+        IR::Pop(1),
+    ];
+
+    assert_eq!(
+        bounds(&codes, &ctx),
+        Some(geometry::Rect::new(10.0, 10.0, 50.0, 50.0))
+    );
+
+    assert_eq!(
+        bounds_with(&codes, &ctx, &BoundsOptions { stroke: true }),
+        Some(geometry::Rect::new(10.0, 10.0, 52.0, 52.0))
+    );
+
+    assert_eq!(bounds(&codes[..1], &ctx), None);
+}
+
+#[test]
+fn test_bounds_viewbox() {
+    let codes: Vec<IR> = vec![
+        Layer::from((200, 100)).into(),
+        Layer::from((100, 100)).viewbox((0, 0, 10, 10)).into(),
+        Rect::from((1, 1, 2, Measurement::percentage(50.0))).into(),
+        IR::Pop(2),
+    ];
+
+    assert_eq!(
+        bounds(&codes, &ResolveContext::default()),
+        Some(geometry::Rect::new(10.0, 10.0, 20.0, 50.0))
+    );
+}
+
+#[test]
+fn test_fit_content() {
+    let mut layer = Layer::from((100, 100));
+
+    let content: Vec<IR> = vec![
+        Rect::from((10, 20, 30, 40)).into(),
+        Stroke::from(Color::red).width(2).into(),
+        Rect::from((50, 50, 10, 10)).into(),
+        IR::Pop(1),
+    ];
+
+    let viewbox = layer
+        .fit_content(&content, &ResolveContext::default(), 5.0)
+        .unwrap();
+
+    assert_eq!(viewbox, geometry::Rect::new(5.0, 15.0, 61.0, 51.0));
+
+    assert_eq!(
+        layer.viewbox,
+        Some(Animatable::Constant(ViewBox::from((5.0, 15.0, 61.0, 51.0))))
+    );
+
+    assert_eq!(
+        layer.fit_content(&[], &ResolveContext::default(), 5.0),
+        None
+    );
+}
+
+#[test]
+fn test_bounds_symbol() {
+    let ctx = ResolveContext::default();
+
+    let codes: Vec<IR> = vec![
+        Layer::from((200, 100)).into(),
+        SymbolDef::new("dot").into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+        Use::new("dot").x(50).y(20).into(),
+        Stroke::from(Color::red).width(4).into(),
+        Use::new("dot").x(100).y(60).into(),
+        IR::Pop(2),
+    ];
+
+    // the symbol contents are measured at every instance, not at their definition.
+    assert_eq!(
+        bounds(&codes, &ctx),
+        Some(geometry::Rect::new(50.0, 20.0, 60.0, 50.0))
+    );
+
+    // the contents inherit the stroke of the instance.
+    assert_eq!(
+        bounds_with(&codes, &ctx, &BoundsOptions { stroke: true }),
+        Some(geometry::Rect::new(50.0, 20.0, 62.0, 52.0))
+    );
+
+    let mut layer = Layer::from((200, 100));
+
+    assert_eq!(
+        layer.fit_content(&codes[1..7], &ctx, 0.0),
+        Some(geometry::Rect::new(50.0, 20.0, 62.0, 52.0))
+    );
+}
+
+#[test]
+fn test_bounds_symbol_viewbox() {
+    let codes: Vec<IR> = vec![
+        Layer::from((200, 100)).into(),
+        SymbolDef::new("dot").viewbox((0, 0, 1, 1)).into(),
+        Rect::from((0, 0, 1, 1)).into(),
+        IR::Pop(1),
+        Use::new("dot").x(10).y(10).width(20).height(20).into(),
+        IR::Pop(1),
+    ];
+
+    assert_eq!(
+        bounds(&codes, &ResolveContext::default()),
+        Some(geometry::Rect::new(10.0, 10.0, 20.0, 20.0))
+    );
+}
+
+#[test]
+fn test_bounds_recursive_symbol() {
+    let codes: Vec<IR> = vec![
+        Layer::from((200, 100)).into(),
+        SymbolDef::new("a").into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        Use::new("b").x(10).into(),
+        IR::Pop(1),
+        SymbolDef::new("b").into(),
+        Rect::from((0, 0, 5, 5)).into(),
+        Use::new("a").into(),
+        IR::Pop(1),
+        Use::new("a").into(),
+        IR::Pop(1),
+    ];
+
+    // `b` is expanded in `a`, the instance of `a` in `b` is skipped.
+    assert_eq!(
+        bounds(&codes, &ResolveContext::default()),
+        Some(geometry::Rect::new(0.0, 0.0, 15.0, 10.0))
+    );
+}
@@ -0,0 +1,72 @@
+use vglang_ir::{
+    clip_fill, clip_stroke,
+    geometry::{Point, Rect, Segment, SubPath},
+    FillRule,
+};
+
+fn area(polylines: &[vglang_ir::geometry::Polyline]) -> f32 {
+    polylines
+        .iter()
+        .map(|polyline| polyline.signed_area())
+        .sum()
+}
+
+#[test]
+fn test_clip_fill() {
+    let subject = [SubPath::rect(&Rect::new(0.0, 0.0, 10.0, 10.0), 0.0, 0.0)];
+    let clip = [SubPath::rect(&Rect::new(5.0, 5.0, 10.0, 10.0), 0.0, 0.0)];
+
+    let clipped = clip_fill(&subject, FillRule::Nonzero, &clip, FillRule::Nonzero, 0.05);
+
+    assert_eq!(clipped.len(), 1);
+    assert!((area(&clipped) - 25.0).abs() < 1e-3);
+
+    let bounds = Rect::from_points(clipped[0].points.iter().copied()).unwrap();
+
+    assert_eq!(bounds, Rect::new(5.0, 5.0, 5.0, 5.0));
+
+    // a hole of the clip area is kept in the result.
+    let clip = [
+        SubPath::rect(&Rect::new(-5.0, -5.0, 20.0, 20.0), 0.0, 0.0),
+        SubPath::rect(&Rect::new(2.0, 2.0, 6.0, 6.0), 0.0, 0.0),
+    ];
+
+    let clipped = clip_fill(&subject, FillRule::Nonzero, &clip, FillRule::EvenOdd, 0.05);
+
+    assert_eq!(clipped.len(), 2);
+    assert!((area(&clipped) - 64.0).abs() < 1e-3);
+
+    // disjoint areas.
+    let clip = [SubPath::rect(&Rect::new(20.0, 20.0, 10.0, 10.0), 0.0, 0.0)];
+
+    assert!(clip_fill(&subject, FillRule::Nonzero, &clip, FillRule::Nonzero, 0.05).is_empty());
+}
+
+#[test]
+fn test_clip_stroke() {
+    let line = [SubPath {
+        segments: vec![Segment::Line {
+            from: Point::new(-5.0, 5.0),
+            to: Point::new(15.0, 5.0),
+        }],
+        closed: false,
+    }];
+
+    let clip = [SubPath::rect(&Rect::new(0.0, 0.0, 10.0, 10.0), 0.0, 0.0)];
+
+    let clipped = clip_stroke(&line, &clip, FillRule::Nonzero, 0.05);
+
+    assert_eq!(clipped.len(), 1);
+    assert_eq!(
+        clipped[0].points,
+        [Point::new(0.0, 5.0), Point::new(10.0, 5.0)]
+    );
+
+    // a closed outline inside the clip area is kept as is.
+    let square = [SubPath::rect(&Rect::new(2.0, 2.0, 2.0, 2.0), 0.0, 0.0)];
+
+    let clipped = clip_stroke(&square, &clip, FillRule::Nonzero, 0.05);
+
+    assert_eq!(clipped.len(), 1);
+    assert!(clipped[0].closed);
+}
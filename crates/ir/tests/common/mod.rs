@@ -0,0 +1,328 @@
+//! In-memory fonts shared by the font and text tests.
+#![allow(dead_code)]
+
+use vglang_ir::geometry::{Rect, SubPath};
+
+/// The units per em of the built fonts.
+pub const UNITS_PER_EM: u16 = 1000;
+
+/// The ascender of the built fonts, in font units.
+pub const ASCENDER: i16 = 800;
+
+/// The descender of the built fonts, in font units.
+pub const DESCENDER: i16 = -200;
+
+/// The advance of the `.notdef` glyph, in font units.
+pub const NOTDEF_ADVANCE: u16 = 500;
+
+/// The top of the glyph boxes, in font units.
+pub const GLYPH_TOP: i16 = 700;
+
+/// The inset of the glyph boxes from the advance, in font units.
+pub const GLYPH_INSET: i16 = 50;
+
+/// The layers of a color glyph, the glyph ids and RGBA colors.
+type Layers = Vec<(u16, [u8; 4])>;
+
+struct Glyph {
+    c: Option<char>,
+    advance: u16,
+    outline: bool,
+}
+
+/// A minimal TrueType font, every glyph outline is a box from `GLYPH_INSET` to `advance - GLYPH_INSET` horizontally
+/// and from the baseline to `GLYPH_TOP` vertically. White space characters have no outline.
+pub struct FontBuilder {
+    family: String,
+    weight: u16,
+    stretch: u16,
+    glyphs: Vec<Glyph>,
+    vertical_advance: Option<u16>,
+    /// The color glyphs, with the glyph ids and the RGBA colors of their layers.
+    colors: Vec<(u16, Layers)>,
+}
+
+impl FontBuilder {
+    /// Create a font with the family name `family` and the `.notdef` glyph only.
+    pub fn new(family: &str) -> Self {
+        Self {
+            family: family.to_owned(),
+            weight: 400,
+            stretch: 5,
+            glyphs: vec![Glyph {
+                c: None,
+                advance: NOTDEF_ADVANCE,
+                outline: true,
+            }],
+            vertical_advance: None,
+            colors: vec![],
+        }
+    }
+
+    /// Set the width class.
+    pub fn stretch(mut self, stretch: u16) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    /// Add a glyph for `c` with the horizontal `advance`.
+    pub fn glyph(mut self, c: char, advance: u16) -> Self {
+        self.glyphs.push(Glyph {
+            c: Some(c),
+            advance,
+            outline: !c.is_whitespace(),
+        });
+        self
+    }
+
+    /// Add glyphs for all characters of `chars` with the same horizontal `advance`.
+    pub fn glyphs(self, chars: &str, advance: u16) -> Self {
+        chars.chars().fold(self, |font, c| font.glyph(c, advance))
+    }
+
+    /// Add vertical metrics, every glyph advances `advance` vertically.
+    pub fn vertical(mut self, advance: u16) -> Self {
+        self.vertical_advance = Some(advance);
+        self
+    }
+
+    /// Make the glyph of `c` a `COLR` color glyph, painted with the outlines of the glyphs of `layers` in order.
+    pub fn color(mut self, c: char, layers: &[(char, [u8; 4])]) -> Self {
+        let layers = layers
+            .iter()
+            .map(|(c, color)| (self.glyph_id(*c), *color))
+            .collect();
+
+        self.colors.push((self.glyph_id(c), layers));
+        self
+    }
+
+    fn glyph_id(&self, c: char) -> u16 {
+        self.glyphs
+            .iter()
+            .position(|glyph| glyph.c == Some(c))
+            .expect("add the glyph first") as u16
+    }
+
+    /// Returns the font file data.
+    pub fn build(&self) -> Vec<u8> {
+        let num_glyphs = self.glyphs.len() as u16;
+
+        let mut head = vec![0u8; 54];
+        head[0..4].copy_from_slice(&0x00010000u32.to_be_bytes());
+        head[12..16].copy_from_slice(&0x5F0F3CF5u32.to_be_bytes());
+        head[18..20].copy_from_slice(&UNITS_PER_EM.to_be_bytes());
+        // long `loca` offsets.
+        head[50..52].copy_from_slice(&1u16.to_be_bytes());
+
+        let mut hhea = hhea_like(num_glyphs);
+        hhea[0..4].copy_from_slice(&0x00010000u32.to_be_bytes());
+
+        let mut maxp = 0x00005000u32.to_be_bytes().to_vec();
+        maxp.extend_from_slice(&num_glyphs.to_be_bytes());
+
+        let mut os2 = vec![0u8; 78];
+        os2[4..6].copy_from_slice(&self.weight.to_be_bytes());
+        os2[6..8].copy_from_slice(&self.stretch.to_be_bytes());
+
+        let mut hmtx = vec![];
+        let mut glyf = vec![];
+        let mut loca = vec![];
+
+        for glyph in &self.glyphs {
+            hmtx.extend(be(&[glyph.advance, GLYPH_INSET as u16]));
+
+            loca.extend((glyf.len() as u32).to_be_bytes());
+
+            if glyph.outline {
+                let (x0, x1) = (GLYPH_INSET, glyph.advance as i16 - GLYPH_INSET);
+
+                // one closed contour of four on-curve points, the coordinates are deltas.
+                for v in [1, x0, 0, x1, GLYPH_TOP, 3, 0] {
+                    glyf.extend(v.to_be_bytes());
+                }
+                glyf.extend([1u8; 4]);
+                for v in [x0, x1 - x0, 0, x0 - x1] {
+                    glyf.extend(v.to_be_bytes());
+                }
+                for v in [0, 0, GLYPH_TOP, 0] {
+                    glyf.extend(v.to_be_bytes());
+                }
+            }
+        }
+
+        loca.extend((glyf.len() as u32).to_be_bytes());
+
+        // one `cmap` format 12 subtable of the windows unicode full repertoire.
+        let mut groups = self
+            .glyphs
+            .iter()
+            .enumerate()
+            .filter_map(|(id, glyph)| glyph.c.map(|c| (c as u32, id as u32)))
+            .collect::<Vec<_>>();
+        groups.sort();
+
+        let mut cmap = be(&[0, 1, 3, 10]);
+        cmap.extend(12u32.to_be_bytes());
+        cmap.extend(be(&[12, 0]));
+        for v in [16 + 12 * groups.len() as u32, 0, groups.len() as u32] {
+            cmap.extend(v.to_be_bytes());
+        }
+        for (c, id) in groups {
+            for v in [c, c, id] {
+                cmap.extend(v.to_be_bytes());
+            }
+        }
+
+        // one windows unicode record of the family name.
+        let family = self
+            .family
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect::<Vec<_>>();
+
+        let mut name = be(&[0, 1, 18, 3, 1, 0x409, 1, family.len() as u16, 0]);
+        name.extend(family);
+
+        let mut tables: Vec<(&[u8; 4], Vec<u8>)> = vec![
+            (b"OS/2", os2),
+            (b"cmap", cmap),
+            (b"glyf", glyf),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"maxp", maxp),
+            (b"name", name),
+        ];
+
+        if let Some(advance) = self.vertical_advance {
+            let mut vhea = hhea_like(num_glyphs);
+            vhea[0..4].copy_from_slice(&0x00011000u32.to_be_bytes());
+
+            let vmtx = (0..num_glyphs).flat_map(|_| be(&[advance, 0])).collect();
+
+            tables.push((b"vhea", vhea));
+            tables.push((b"vmtx", vmtx));
+        }
+
+        if !self.colors.is_empty() {
+            let (colr, cpal) = self.colr();
+
+            tables.push((b"COLR", colr));
+            tables.push((b"CPAL", cpal));
+        }
+
+        tables.sort_by_key(|(tag, _)| **tag);
+
+        let mut data = 0x00010000u32.to_be_bytes().to_vec();
+        data.extend(be(&[tables.len() as u16, 0, 0, 0]));
+
+        let mut offset = 12 + 16 * tables.len();
+
+        for (tag, table) in &tables {
+            data.extend_from_slice(*tag);
+            data.extend(0u32.to_be_bytes());
+            data.extend((offset as u32).to_be_bytes());
+            data.extend((table.len() as u32).to_be_bytes());
+            offset += table.len().next_multiple_of(4);
+        }
+
+        for (_, mut table) in tables {
+            table.resize(table.len().next_multiple_of(4), 0);
+            data.extend(table);
+        }
+
+        data
+    }
+
+    /// Returns the `COLR` version 0 and the `CPAL` tables, every layer has its own palette entry.
+    fn colr(&self) -> (Vec<u8>, Vec<u8>) {
+        let mut colors = self.colors.clone();
+        colors.sort();
+
+        let num_layers = colors.iter().map(|(_, layers)| layers.len()).sum::<usize>() as u16;
+
+        let mut colr = be(&[0, colors.len() as u16]);
+        colr.extend(14u32.to_be_bytes());
+        colr.extend((14 + 6 * colors.len() as u32).to_be_bytes());
+        colr.extend(be(&[num_layers]));
+
+        let mut first = 0;
+        for (id, layers) in &colors {
+            colr.extend(be(&[*id, first, layers.len() as u16]));
+            first += layers.len() as u16;
+        }
+
+        let mut cpal = be(&[0, num_layers, 1, num_layers]);
+        cpal.extend(14u32.to_be_bytes());
+        cpal.extend(be(&[0]));
+
+        let mut index = 0;
+        for (_, layers) in &colors {
+            for (id, [r, g, b, a]) in layers {
+                colr.extend(be(&[*id, index]));
+                cpal.extend([*b, *g, *r, *a]);
+                index += 1;
+            }
+        }
+
+        (colr, cpal)
+    }
+}
+
+/// Returns a `hhea` or `vhea` table without the version.
+fn hhea_like(num_metrics: u16) -> Vec<u8> {
+    let mut table = vec![0u8; 36];
+    table[4..6].copy_from_slice(&ASCENDER.to_be_bytes());
+    table[6..8].copy_from_slice(&DESCENDER.to_be_bytes());
+    table[34..36].copy_from_slice(&num_metrics.to_be_bytes());
+    table
+}
+
+fn be(values: &[u16]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+}
+
+/// A font of the family `Test` with the glyphs of the ascii letters, digits and space, every glyph advances
+/// 500 units, except `i`(250 units) and `m`(750 units).
+pub fn test_font() -> FontBuilder {
+    FontBuilder::new("Test")
+        .glyphs("abcdefghjklnopqrstuvwxyz", 500)
+        .glyphs("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789.…", 500)
+        .glyph('i', 250)
+        .glyph('m', 750)
+        .glyph(' ', 250)
+}
+
+/// Returns a font database with [`test_font`] loaded.
+pub fn test_fonts() -> vglang_ir::FontDatabase {
+    let mut db = vglang_ir::FontDatabase::default();
+    db.load_font_data(test_font().build()).unwrap();
+    db
+}
+
+/// Returns the bounding boxes of `subpaths` as `(min_x, min_y, max_x, max_y)`, rounded to 1/1000.
+pub fn boxes(subpaths: &[SubPath]) -> Vec<(f32, f32, f32, f32)> {
+    let round = |v: f32| (v * 1000.0).round() / 1000.0;
+
+    subpaths
+        .iter()
+        .map(|subpath| {
+            let rect = Rect::from_points(
+                subpath
+                    .segments
+                    .iter()
+                    .flat_map(|segment| [segment.from(), segment.to()]),
+            )
+            .unwrap();
+
+            (
+                round(rect.min_x()),
+                round(rect.min_y()),
+                round(rect.max_x()),
+                round(rect.max_y()),
+            )
+        })
+        .collect()
+}
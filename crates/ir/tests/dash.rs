@@ -0,0 +1,62 @@
+use vglang_ir::{
+    dash,
+    geometry::{Point, Rect, Segment, SubPath},
+    PathMeasure,
+};
+
+fn line(to: f32) -> Vec<SubPath> {
+    vec![SubPath {
+        segments: vec![Segment::Line {
+            from: Point::new(0.0, 0.0),
+            to: Point::new(to, 0.0),
+        }],
+        closed: false,
+    }]
+}
+
+fn extents(subpaths: &[SubPath]) -> Vec<(f32, f32)> {
+    subpaths
+        .iter()
+        .map(|subpath| {
+            (
+                subpath.segments.first().unwrap().from().x,
+                subpath.segments.last().unwrap().to().x,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_dash() {
+    assert_eq!(
+        extents(&dash(&line(10.0), &[2.0, 3.0], 0.0)),
+        vec![(0.0, 2.0), (5.0, 7.0)]
+    );
+
+    // odd number of values are repeated, and the offset shifts the pattern.
+    assert_eq!(
+        extents(&dash(&line(10.0), &[1.0, 2.0, 3.0], 1.0)),
+        vec![(2.0, 5.0), (6.0, 8.0)]
+    );
+
+    // invalid patterns draw solid lines.
+    assert_eq!(dash(&line(10.0), &[], 0.0), line(10.0));
+    assert_eq!(dash(&line(10.0), &[1.0, -1.0], 0.0), line(10.0));
+}
+
+#[test]
+fn test_dash_curve() {
+    let subpaths = vec![SubPath::rect(&Rect::new(0.0, 0.0, 20.0, 20.0), 5.0, 5.0)];
+
+    let dashes = dash(&subpaths, &[4.0, 4.0], 0.0);
+
+    let length = PathMeasure::new(&subpaths, 0.01).length();
+
+    assert_eq!(dashes.len(), (length / 8.0).ceil() as usize);
+
+    for dash in &dashes[..dashes.len() - 1] {
+        let length = PathMeasure::new(std::slice::from_ref(dash), 0.01).length();
+
+        assert!((length - 4.0).abs() < 0.05, "{}", length);
+    }
+}
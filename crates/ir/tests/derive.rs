@@ -0,0 +1,179 @@
+#![cfg(feature = "dsl")]
+
+use vglang_derive::Dsl;
+
+#[derive(Debug, Default, PartialEq, Dsl)]
+struct Shape {
+    #[dsl(rename = "size")]
+    width: f32,
+    #[dsl(skip)]
+    #[allow(unused)]
+    cache: String,
+    height: std::option::Option<f32>,
+}
+
+#[allow(unused)]
+impl Shape {
+    fn width(&self) -> f32 {
+        self.width
+    }
+
+    fn cache(&self) -> &str {
+        &self.cache
+    }
+}
+
+#[test]
+fn test_field_attributes() {
+    let shape = Shape::default().size(1.5);
+
+    assert_eq!(shape.width(), 1.5);
+}
+
+#[test]
+fn test_qualified_paths() {
+    let shape = Shape::default().height(2.0);
+
+    assert_eq!(shape.height, Some(2.0));
+    assert_eq!(shape.clear_height().height, None);
+}
+
+#[test]
+fn test_required_constructor() {
+    use vglang_ir::*;
+
+    assert_eq!(Layer::new(100, 50), Layer::from((100, 50)));
+    assert_eq!(
+        Layer::new(100, 50).viewbox((0, 0, 10, 5)),
+        Layer::from((100, 50)).viewbox((0, 0, 10, 5))
+    );
+}
+
+#[test]
+fn test_clear_and_maybe() {
+    use vglang_ir::*;
+
+    let stroke = Stroke::default().width(2).linecap(StrokeLineCap::Round);
+
+    assert_eq!(stroke.clone().clear_width().width, None);
+    assert_eq!(
+        stroke.clone().maybe_linecap(None::<StrokeLineCap>).linecap,
+        None
+    );
+    assert_eq!(
+        stroke.maybe_width(Some(3)).width,
+        Some(Animatable::Constant(Measurement::from(3)))
+    );
+}
+
+#[test]
+fn test_nested_setters() {
+    use vglang_ir::*;
+
+    assert_eq!(
+        Stroke::default().dasharray((1, 2)).dasharray,
+        Some(Animatable::Constant(vec![
+            Animatable::Constant(Measurement::from(1)),
+            Animatable::Constant(Measurement::from(2)),
+        ]))
+    );
+
+    assert_eq!(
+        Stroke::default().dasharray_animated_items(["a", "b"]).dasharray,
+        Some(Animatable::Constant(vec![
+            Animatable::Animated("a".to_owned()),
+            Animatable::Animated("b".to_owned()),
+        ]))
+    );
+
+    assert_eq!(
+        Stroke::default().dasharray_animated("dash").dasharray,
+        Some(Animatable::Animated("dash".to_owned()))
+    );
+}
+
+#[test]
+fn test_builder() {
+    use vglang_ir::*;
+
+    let mut builder = Layer::builder();
+
+    builder.width(100);
+
+    assert!(matches!(
+        builder.build(),
+        Err(Error::MissingField {
+            ty: "Layer",
+            field: "height"
+        })
+    ));
+
+    builder.height(50).viewbox((0, 0, 10, 5));
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Layer::new(100, 50).viewbox((0, 0, 10, 5))
+    );
+}
+
+#[test]
+fn test_newtype_map_collect() {
+    use vglang_ir::*;
+
+    let hrefs: Vec<Href> = ("#a", "#b".to_owned()).map_collect();
+
+    assert_eq!(hrefs, vec![Href::new("#a"), Href::new("#b")]);
+
+    let hrefs: Vec<Href> = vec!["#a", "#b"].map_collect();
+
+    assert_eq!(hrefs, vec![Href::new("#a"), Href::new("#b")]);
+    assert_eq!(Href::from("#a".to_owned()), Href::new("#a"));
+}
+
+#[test]
+#[should_panic(expected = "`width` is out of range")]
+fn test_range_check() {
+    _ = vglang_ir::Rect::default().width(-1);
+}
+
+#[test]
+#[should_panic(expected = "`dasharray` is empty")]
+fn test_non_empty_check() {
+    _ = vglang_ir::Stroke::default().dasharray(Vec::<f32>::new());
+}
+
+#[test]
+#[should_panic(expected = "`height` is out of range")]
+fn test_range_check_required() {
+    _ = vglang_ir::Layer::new(100, -1);
+}
+
+#[test]
+#[should_panic(expected = "`size` is out of range")]
+fn test_range_check_option() {
+    _ = vglang_ir::Font::default().size(-1);
+}
+
+#[test]
+#[should_panic(expected = "`dasharray` is out of range")]
+fn test_range_check_items() {
+    _ = vglang_ir::Stroke::default().dasharray(vec![1.0, -1.0]);
+}
+
+#[test]
+fn test_range_check_bounds() {
+    use vglang_ir::*;
+
+    // the range includes its start, and registers are checked by `verify` instead.
+    let rect = Rect::default().width(0).height(0).rx(0).ry(0);
+
+    assert_eq!(rect.width, Animatable::Constant(Measurement::from(0)));
+
+    let rect = rect.width_animated("w");
+
+    assert_eq!(rect.width, Animatable::Animated("w".to_owned()));
+
+    let stroke = Stroke::default().dasharray(vec![0.0, 1.0]);
+
+    assert!(stroke.dasharray.is_some());
+}
@@ -0,0 +1,80 @@
+use vglang_ir::*;
+
+#[test]
+fn test_length_list() {
+    assert_eq!(
+        parse_length_list("10 5%,1.5em , -2").unwrap(),
+        vec![
+            Measurement(10.0, None),
+            Measurement::percentage(5.0),
+            Measurement::em(1.5),
+            Measurement(-2.0, None)
+        ]
+    );
+
+    assert_eq!(parse_length_list("").unwrap(), vec![]);
+    assert!(parse_length_list("10,").is_err());
+    assert!(parse_length_list("10px20").is_err());
+}
+
+#[test]
+fn test_dasharray() {
+    assert_eq!(parse_dasharray("none").unwrap(), vec![]);
+    assert_eq!(
+        parse_dasharray("5,10,15").unwrap(),
+        [5.0, 10.0, 15.0, 5.0, 10.0, 15.0]
+            .into_iter()
+            .map(Measurement::from)
+            .collect::<Vec<_>>()
+    );
+    assert!(parse_dasharray("5 -1").is_err());
+}
+
+#[test]
+fn test_points() {
+    assert_eq!(
+        parse_points("0,0 10,0 10-10 .5.5").unwrap(),
+        vec![
+            Point::from((0.0, 0.0)),
+            Point::from((10.0, 0.0)),
+            Point::from((10.0, -10.0)),
+            Point::from((0.5, 0.5))
+        ]
+    );
+
+    assert!(parse_points("0,0 10").is_err());
+}
+
+#[test]
+fn test_viewbox() {
+    assert_eq!(
+        ViewBox::parse("0 0 100 100").unwrap(),
+        ViewBox::from((0, 0, 100, 100))
+    );
+    assert_eq!(
+        "-10,-10, 20.5 20".parse::<ViewBox>().unwrap(),
+        ViewBox::from((-10.0, -10.0, 20.5, 20.0))
+    );
+    assert!(ViewBox::parse("0 0 100").is_err());
+    assert!(ViewBox::parse("0 0 -1 100").is_err());
+
+    assert_eq!(
+        PreserveAspectRatio::parse("xMidYMid slice").unwrap(),
+        PreserveAspectRatio::xMidYMid(MeetOrSlice::Slice)
+    );
+    assert_eq!(
+        PreserveAspectRatio::parse("defer xMinYMax").unwrap(),
+        PreserveAspectRatio::xMinYMax(MeetOrSlice::Meet)
+    );
+    assert!(PreserveAspectRatio::parse("none").is_err());
+    assert!(PreserveAspectRatio::parse("xMidYMid meet slice").is_err());
+}
+
+#[test]
+fn test_tuple_struct_dsl() {
+    assert_eq!(
+        Measurement::new(1.5, Unit::Px),
+        Measurement(1.5, Some(Unit::Px))
+    );
+    assert_eq!(Href::new("#a"), Href("#a".to_owned()));
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "font")]
+
+use vglang_ir::*;
+
+mod common;
+
+use common::{test_font, FontBuilder};
+
+fn query(db: &FontDatabase, stretch: FontStretch) -> u16 {
+    db.query(&Font::from("Test").stretch(stretch))
+        .unwrap()
+        .stretch()
+}
+
+#[test]
+fn test_query_stretch_direction() {
+    let mut db = FontDatabase::default();
+
+    for stretch in [1, 4, 6, 9] {
+        db.load_font_data(FontBuilder::new("Test").stretch(stretch).build())
+            .unwrap();
+    }
+
+    assert_eq!(db.faces().len(), 4);
+
+    // condensed requests prefer any narrower width to a nearer wider one.
+    assert_eq!(query(&db, FontStretch::Condensed), 1);
+    // expanded requests prefer any wider width to a nearer narrower one.
+    assert_eq!(query(&db, FontStretch::Expanded), 9);
+    // exact matches win.
+    assert_eq!(query(&db, FontStretch::SemiCondensed), 4);
+    // normal is treated as a condensed request.
+    assert_eq!(query(&db, FontStretch::Normal), 4);
+}
+
+#[test]
+fn test_load_font_dir() {
+    let dir = std::env::temp_dir().join(format!("vglang-font-dir-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    std::fs::create_dir_all(dir.join("a")).unwrap();
+    std::fs::write(dir.join("b.ttf"), FontBuilder::new("B").build()).unwrap();
+    std::fs::write(dir.join("a/a.otf"), FontBuilder::new("A").build()).unwrap();
+    // invalid fonts are skipped, and files without a font extension are ignored.
+    std::fs::write(dir.join("c.ttf"), b"not a font").unwrap();
+    std::fs::write(dir.join("d.txt"), FontBuilder::new("D").build()).unwrap();
+
+    let mut db = FontDatabase::default();
+    let count = db.load_font_dir(&dir);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(count.unwrap(), 2);
+
+    // the files are loaded in the order of their paths.
+    let families = db.faces().iter().map(FontFace::family).collect::<Vec<_>>();
+    assert_eq!(families, ["A", "B"]);
+    assert_eq!(db.families(), ["A", "B"]);
+
+    assert!(db.load_font_dir(&dir).is_err());
+}
+
+#[test]
+fn test_load_system_fonts() {
+    let mut db = FontDatabase::default();
+    db.load_font_data(FontBuilder::new("Test").build()).unwrap();
+
+    let count = db.load_system_fonts();
+
+    assert_eq!(db.faces().len(), count + 1);
+}
+
+/// A font with the color glyph `☺`, painted with the outlines of `a` and `i`.
+fn color_font() -> FontFace {
+    let data = test_font()
+        .glyph('☺', 1000)
+        .color('☺', &[('a', [255, 0, 0, 255]), ('i', [0, 0, 255, 255])])
+        .build();
+
+    FontFace::from_data(data, 0).unwrap()
+}
+
+#[test]
+fn test_color_glyph() {
+    let face = color_font();
+
+    assert!(face.has_color_glyphs());
+    assert!(!FontFace::from_data(test_font().build(), 0)
+        .unwrap()
+        .has_color_glyphs());
+
+    let Some(ColorGlyph::Layers(layers)) = face.color_glyph(face.glyph_id('☺'), 10.0) else {
+        panic!("expect color layers");
+    };
+
+    // the layers are in the space of the glyph outlines, painted in order.
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].color, Rgba(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(layers[0].subpaths, face.glyph_outline('a', 10.0));
+    assert_eq!(layers[1].color, Rgba(0.0, 0.0, 1.0, 1.0));
+    assert_eq!(layers[1].subpaths, face.glyph_outline('i', 10.0));
+
+    // the other glyphs have only outlines.
+    assert_eq!(face.color_glyph(face.glyph_id('a'), 10.0), None);
+}
+
+#[test]
+fn test_parsed_face() {
+    let face = FontFace::from_data(test_font().build(), 0).unwrap();
+    let parsed = face.parse();
+
+    assert_eq!(parsed.metrics(10.0), face.metrics(10.0));
+    assert_eq!(parsed.advance_width("aim", 10.0), 15.0);
+    assert_eq!(parsed.glyph_id('a'), face.glyph_id('a'));
+    assert_eq!(
+        parsed.glyph_outline('m', 10.0),
+        face.glyph_outline('m', 10.0)
+    );
+    assert_eq!(parsed.shape("ai", 10.0), face.shape("ai", 10.0));
+    assert_eq!(parsed.vertical_advance('a', 10.0), 10.0);
+}
@@ -0,0 +1,56 @@
+use vglang_ir::{geometry, *};
+
+#[test]
+fn test_flatten() {
+    let path = Path {
+        data: Animatable::Constant(
+            vec![
+                PathEvent::MoveTo((0.0, 0.0).into()),
+                PathEvent::LineTo((10.0, 0.0).into()),
+                PathEvent::QuadraticBezier {
+                    ctrl: (20.0, 0.0).into(),
+                    to: (20.0, 10.0).into(),
+                },
+                PathEvent::ClosePath,
+                PathEvent::MoveTo((50.0, 50.0).into()),
+                PathEvent::LineTo((60.0, 50.0).into()),
+            ]
+            .into_iter()
+            .map(Animatable::Constant)
+            .collect(),
+        ),
+        ..Default::default()
+    };
+
+    let ctx = ResolveContext::default();
+
+    let coarse = path.flatten(&ctx, 10.0).unwrap();
+
+    assert_eq!(
+        coarse,
+        vec![
+            geometry::Polyline {
+                points: vec![
+                    geometry::Point::new(0.0, 0.0),
+                    geometry::Point::new(10.0, 0.0),
+                    geometry::Point::new(20.0, 10.0),
+                ],
+                closed: true,
+            },
+            geometry::Polyline {
+                points: vec![
+                    geometry::Point::new(50.0, 50.0),
+                    geometry::Point::new(60.0, 50.0),
+                ],
+                closed: false,
+            },
+        ]
+    );
+
+    let fine = path.flatten(&ctx, 0.01).unwrap();
+
+    assert!(fine[0].points.len() > coarse[0].points.len());
+
+    // the quadratic curve is longer than its chord.
+    assert!(fine[0].length() > coarse[0].length());
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "serde")]
+
+use vglang_ir::*;
+
+#[test]
+fn test_stable_hash() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Fill::from(Color::red).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(2),
+    ];
+
+    assert_eq!(codes.stable_hash(), codes.clone().stable_hash());
+    assert_eq!(codes.stable_hash(), codes.as_slice().stable_hash());
+    assert_ne!(codes.stable_hash(), codes[..3].stable_hash());
+    assert_ne!(
+        Rect::from((0, 0, 10, 10)).stable_hash(),
+        Rect::from((0, 0, 10, 11)).stable_hash()
+    );
+    assert_ne!(
+        IR::Pop(1).stable_hash(),
+        IR::String("".to_owned()).stable_hash()
+    );
+}
+
+#[test]
+fn test_stable_hash_quantized() {
+    let lhs = Rect::from((0.300001, 0.0, 10.0, 10.0));
+    let rhs = Rect::from((0.3, -0.0, 10.0, 10.0));
+
+    assert_ne!(lhs.stable_hash(), rhs.stable_hash());
+
+    let options = HashOptions::quantized(1e-4);
+
+    assert_eq!(
+        lhs.stable_hash_with(&options),
+        rhs.stable_hash_with(&options)
+    );
+    assert_ne!(
+        lhs.stable_hash_with(&options),
+        Rect::from((0.31, 0.0, 10.0, 10.0)).stable_hash_with(&options)
+    );
+}
@@ -0,0 +1,90 @@
+use vglang_ir::{geometry::Point, *};
+
+#[test]
+fn test_hit_test() {
+    let ctx = ResolveContext::default();
+
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Rect::from((0, 0, 50, 50)).into(),
+        Stroke::from(Color::red).width(10).into(),
+        Rect::from((20, 20, 50, 50)).into(),
+        IR::Pop(1),
+        IR::Pop(1),
+    ];
+
+    // topmost first.
+    assert_eq!(
+        hit_test(Point::new(30.0, 30.0), &codes, &ctx),
+        vec![ElementPath(vec![0, 2, 3]), ElementPath(vec![0, 1])]
+    );
+
+    // on the stroke of the second rect, outside of its geometry.
+    assert_eq!(
+        hit_test(Point::new(72.0, 40.0), &codes, &ctx),
+        vec![ElementPath(vec![0, 2, 3])]
+    );
+
+    assert!(hit_test(Point::new(90.0, 90.0), &codes, &ctx).is_empty());
+}
+
+#[test]
+fn test_hit_test_transform() {
+    let codes: Vec<IR> = vec![
+        Layer::from((200, 200)).viewbox((0, 0, 100, 100)).into(),
+        Rect::from((0, 0, 10, 10)).rx(5).into(),
+        IR::Pop(1),
+    ];
+
+    let ctx = ResolveContext::default();
+
+    assert_eq!(hit_test(Point::new(10.0, 10.0), &codes, &ctx)[0].index(), 1);
+
+    // out of the rounded corner.
+    assert!(hit_test(Point::new(1.0, 1.0), &codes, &ctx).is_empty());
+}
+
+#[test]
+fn test_hit_test_fill_none() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Fill::default().into(),
+        Rect::from((0, 0, 50, 50)).into(),
+        Stroke::from(Color::red).width(10).into(),
+        Rect::from((20, 20, 50, 50)).into(),
+        IR::Pop(3),
+    ];
+
+    let ctx = ResolveContext::default();
+
+    // the interiors are not filled.
+    assert!(hit_test(Point::new(30.0, 30.0), &codes, &ctx).is_empty());
+
+    // on the stroke of the second rect.
+    assert_eq!(
+        hit_test(Point::new(22.0, 40.0), &codes, &ctx),
+        vec![ElementPath(vec![0, 1, 3, 4])]
+    );
+}
+
+#[test]
+fn test_hit_test_symbol() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        SymbolDef::new("dot").into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+        Use::new("dot").x(50).y(20).into(),
+        IR::Pop(1),
+    ];
+
+    let ctx = ResolveContext::default();
+
+    // the instance is hit, not the symbol contents.
+    assert_eq!(
+        hit_test(Point::new(55.0, 25.0), &codes, &ctx),
+        vec![ElementPath(vec![0, 4])]
+    );
+
+    assert!(hit_test(Point::new(5.0, 5.0), &codes, &ctx).is_empty());
+}
@@ -0,0 +1,60 @@
+use vglang_ir::geometry::*;
+
+fn points() -> Vec<Point> {
+    vec![
+        Point::new(0.0, 0.0),
+        Point::new(10.0, 0.0),
+        Point::new(5.0, 5.0),
+        Point::new(10.0, 10.0),
+        Point::new(0.0, 10.0),
+        Point::new(5.0, 0.0),
+        Point::new(0.0, 0.0),
+    ]
+}
+
+#[test]
+fn test_convex_hull() {
+    assert_eq!(
+        convex_hull(&points()),
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ]
+    );
+
+    assert_eq!(convex_hull(&[]), vec![]);
+}
+
+#[test]
+fn test_bounding_circle() {
+    let circle = bounding_circle(&points()).unwrap();
+
+    assert!(circle.center.distance(Point::new(5.0, 5.0)) < 1e-4);
+    assert!((circle.radius - 50f32.sqrt()).abs() < 1e-4);
+
+    assert!(points().into_iter().all(|p| circle.contains(p)));
+
+    assert_eq!(bounding_circle(&[]), None);
+}
+
+#[test]
+fn test_oriented_bbox() {
+    // a diamond, which is a square rotated by 45 degrees.
+    let diamond = [
+        Point::new(0.0, 5.0),
+        Point::new(5.0, 0.0),
+        Point::new(10.0, 5.0),
+        Point::new(5.0, 10.0),
+    ];
+
+    let rect = oriented_bbox(&diamond).unwrap();
+
+    assert!((rect.area() - 50.0).abs() < 1e-3);
+    assert!(rect.center.distance(Point::new(5.0, 5.0)) < 1e-4);
+
+    for corner in rect.corners() {
+        assert!(diamond.iter().any(|p| p.distance(corner) < 1e-3));
+    }
+}
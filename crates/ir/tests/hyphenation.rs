@@ -0,0 +1,30 @@
+#![cfg(feature = "hyphenation")]
+
+use vglang_ir::*;
+
+// the patterns of the word `hyphenation` from The TeXbook, appendix H.
+const PATTERNS: &str = "hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n % comment";
+
+#[test]
+fn test_hyphenate() {
+    let hyphenator = Hyphenator::parse(PATTERNS).unwrap();
+
+    assert_eq!(hyphenator.hyphenate("hyphenation"), vec![2, 6]);
+    assert_eq!(hyphenator.hyphenate("Hyphenation"), vec![2, 6]);
+    assert!(hyphenator.hyphenate("hyp").is_empty());
+}
+
+#[test]
+fn test_exceptions() {
+    let mut hyphenator = Hyphenator::parse(PATTERNS).unwrap();
+
+    hyphenator.add_exceptions("hyphen-ation");
+
+    assert_eq!(hyphenator.hyphenate("hyphenation"), vec![6]);
+}
+
+#[test]
+fn test_invalid_patterns() {
+    assert!(Hyphenator::parse("a12b").is_err());
+    assert!(Hyphenator::parse("12").is_err());
+}
@@ -0,0 +1,58 @@
+use vglang_ir::geometry::*;
+
+fn rect(x: f32, y: f32, width: f32, height: f32) -> Vec<SubPath> {
+    vec![SubPath::rect(&Rect::new(x, y, width, height), 0.0, 0.0)]
+}
+
+#[test]
+fn test_intersection_points() {
+    let mut points = intersection_points(
+        &rect(0.0, 0.0, 10.0, 10.0),
+        &rect(5.0, 5.0, 10.0, 10.0),
+        0.01,
+    );
+
+    points.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+    assert_eq!(points, vec![Point::new(5.0, 10.0), Point::new(10.0, 5.0)]);
+
+    // the circle of radius 5 crosses the square four times.
+    let circle = vec![SubPath::rect(&Rect::new(0.0, 0.0, 10.0, 10.0), 5.0, 5.0)];
+
+    assert_eq!(
+        intersection_points(&circle, &rect(2.0, -5.0, 6.0, 20.0), 0.01).len(),
+        4
+    );
+}
+
+#[test]
+fn test_intersects() {
+    assert!(intersects(
+        &rect(0.0, 0.0, 10.0, 10.0),
+        &rect(5.0, 5.0, 10.0, 10.0),
+        0.01
+    ));
+
+    // containment without crossing outlines.
+    assert!(intersects(
+        &rect(0.0, 0.0, 10.0, 10.0),
+        &rect(2.0, 2.0, 1.0, 1.0),
+        0.01
+    ));
+    assert!(intersects(
+        &rect(2.0, 2.0, 1.0, 1.0),
+        &rect(0.0, 0.0, 10.0, 10.0),
+        0.01
+    ));
+
+    assert!(!intersects(
+        &rect(0.0, 0.0, 10.0, 10.0),
+        &rect(20.0, 0.0, 10.0, 10.0),
+        0.01
+    ));
+
+    // the rounded corner misses the square.
+    let rounded = vec![SubPath::rect(&Rect::new(0.0, 0.0, 10.0, 10.0), 5.0, 5.0)];
+
+    assert!(!intersects(&rounded, &rect(-1.0, -1.0, 1.5, 1.5), 0.01));
+}
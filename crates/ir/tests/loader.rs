@@ -0,0 +1,50 @@
+#![cfg(feature = "json")]
+
+use vglang_ir::*;
+
+fn to_json(body: Vec<IR>) -> String {
+    serde_json::to_string(&Envelope::from(body)).unwrap()
+}
+
+#[test]
+fn test_json_validated() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        Fill::from(Color::red).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(2),
+    ];
+
+    assert_eq!(from_json_validated(&to_json(codes.clone())).unwrap(), codes);
+}
+
+#[test]
+fn test_json_version() {
+    let json = to_json(vec![]).replace("\"version\":1", "\"version\":100");
+
+    assert!(matches!(
+        from_json_validated(&json),
+        Err(Error::UnsupportedVersion(100))
+    ));
+}
+
+#[test]
+fn test_json_validation_path() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        Rect::from((0, 0, -10, 10)).into(),
+        IR::Pop(1),
+    ];
+
+    match from_json_validated(&to_json(codes)) {
+        Err(Error::Validation { path, .. }) => assert_eq!(path, "body[3].width"),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    match from_json_validated(&to_json(vec![Layer::from((100, 100)).into(), IR::Pop(2)])) {
+        Err(Error::Validation { path, .. }) => assert_eq!(path, "body[1]"),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
@@ -0,0 +1,82 @@
+use vglang_ir::{geometry, *};
+
+fn path(events: Vec<PathEvent>) -> Path {
+    Path {
+        data: Animatable::Constant(events.into_iter().map(Animatable::Constant).collect()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_polyline_measure() {
+    let ctx = ResolveContext::default();
+
+    let path = path(vec![
+        PathEvent::MoveTo((0.0, 0.0).into()),
+        PathEvent::LineTo((10.0, 0.0).into()),
+        PathEvent::LineTo((10.0, 10.0).into()),
+        PathEvent::ClosePath,
+    ]);
+
+    let length = 20.0 + 200f32.sqrt();
+
+    assert!((path.length(&ctx).unwrap() - length).abs() < 1e-4);
+
+    assert_eq!(
+        path.point_at(&ctx, 15.0),
+        Some(geometry::Point::new(10.0, 5.0))
+    );
+
+    assert_eq!(
+        path.tangent_at(&ctx, 15.0),
+        Some(geometry::Point::new(0.0, 1.0))
+    );
+
+    // distances are clamped.
+    assert_eq!(
+        path.point_at(&ctx, -1.0),
+        Some(geometry::Point::new(0.0, 0.0))
+    );
+}
+
+#[test]
+fn test_arc_measure() {
+    let ctx = ResolveContext::default();
+
+    // a half circle of radius 10.
+    let path = path(vec![
+        PathEvent::MoveTo((0.0, 0.0).into()),
+        PathEvent::Arc {
+            rx: 10.into(),
+            ry: 10.into(),
+            x_rotation: 0.0.into(),
+            large_arc: false,
+            sweep: true,
+            to: (20.0, 0.0).into(),
+        },
+    ]);
+
+    let measure = path.measure(&ctx).unwrap();
+
+    let length = 10.0 * std::f32::consts::PI;
+
+    assert!((measure.length() - length).abs() < 0.05);
+
+    let top = measure.point_at(length / 2.0).unwrap();
+
+    assert!(top.distance(geometry::Point::new(10.0, -10.0)) < 0.05);
+
+    let tangent = measure.tangent_at(length / 2.0).unwrap();
+
+    assert!(tangent.distance(geometry::Point::new(1.0, 0.0)) < 0.01);
+}
+
+#[test]
+fn test_animated_measure() {
+    let path = Path {
+        data: Animatable::Animated("data".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(path.length(&ResolveContext::default()), None);
+}
@@ -0,0 +1,180 @@
+#![cfg(feature = "font")]
+
+use vglang_ir::*;
+
+mod common;
+
+use common::{test_font, test_fonts, FontBuilder};
+
+fn ctx() -> ResolveContext {
+    ResolveContext {
+        font_size: 10.0,
+        ..Default::default()
+    }
+}
+
+fn measure(db: &FontDatabase, layout: TextLayout, text: &str) -> TextMetrics {
+    db.measure_text(&Font::from("Test"), &layout, text, &ctx())
+        .unwrap()
+}
+
+fn positions(metrics: &TextMetrics) -> Vec<(char, f32, f32)> {
+    metrics
+        .glyphs
+        .iter()
+        .map(|glyph| (glyph.c, glyph.x, glyph.advance))
+        .collect()
+}
+
+fn assert_near(lhs: f32, rhs: f32) {
+    assert!((lhs - rhs).abs() < 1e-4, "{} != {}", lhs, rhs);
+}
+
+#[test]
+fn test_measure_without_faces() {
+    let db = FontDatabase::default();
+
+    assert_eq!(
+        db.measure_text(&Font::from("Test"), &TextLayout::default(), "a", &ctx()),
+        None
+    );
+}
+
+#[test]
+fn test_measure_advances() {
+    let metrics = measure(&test_fonts(), TextLayout::default(), "aim b");
+
+    assert_eq!(
+        positions(&metrics),
+        vec![
+            ('a', 0.0, 5.0),
+            ('i', 5.0, 2.5),
+            ('m', 7.5, 7.5),
+            (' ', 15.0, 2.5),
+            ('b', 17.5, 5.0)
+        ]
+    );
+
+    assert_eq!(metrics.width, 22.5);
+    assert_eq!(metrics.ascent, 8.0);
+    assert_eq!(metrics.descent, -2.0);
+    assert_eq!(metrics.line_height, 10.0);
+}
+
+#[test]
+fn test_measure_missing_glyph() {
+    // `ä` has no glyph, it advances by the `.notdef` glyph.
+    let metrics = measure(&test_fonts(), TextLayout::default(), "iäi");
+
+    assert_eq!(
+        positions(&metrics),
+        vec![('i', 0.0, 2.5), ('ä', 2.5, 5.0), ('i', 7.5, 2.5)]
+    );
+}
+
+#[test]
+fn test_measure_font_size() {
+    let db = test_fonts();
+
+    let font = Font {
+        size: Some(Animatable::Constant(Measurement::px(20.0))),
+        ..Font::from("Test")
+    };
+
+    let metrics = db
+        .measure_text(&font, &TextLayout::default(), "am", &ctx())
+        .unwrap();
+
+    assert_eq!(metrics.width, 25.0);
+    assert_eq!(metrics.ascent, 16.0);
+}
+
+#[test]
+fn test_measure_anchor() {
+    let db = test_fonts();
+
+    let start = measure(&db, TextAnchor::Start.into(), "ami");
+    let middle = measure(&db, TextAnchor::Middle.into(), "ami");
+    let end = measure(&db, TextAnchor::End.into(), "ami");
+
+    assert_eq!(start.width, 15.0);
+    assert_eq!(middle.width, 15.0);
+    assert_eq!(end.width, 15.0);
+
+    assert_eq!(
+        positions(&start),
+        vec![('a', 0.0, 5.0), ('m', 5.0, 7.5), ('i', 12.5, 2.5)]
+    );
+
+    assert_eq!(
+        positions(&middle),
+        vec![('a', -7.5, 5.0), ('m', -2.5, 7.5), ('i', 5.0, 2.5)]
+    );
+
+    assert_eq!(
+        positions(&end),
+        vec![('a', -15.0, 5.0), ('m', -10.0, 7.5), ('i', -2.5, 2.5)]
+    );
+}
+
+#[test]
+fn test_measure_white_space() {
+    // the default mode collapses the white space sequences.
+    let metrics = measure(&test_fonts(), TextLayout::default(), "a  \n b");
+
+    assert_eq!(
+        positions(&metrics),
+        vec![('a', 0.0, 5.0), (' ', 5.0, 2.5), ('b', 7.5, 5.0)]
+    );
+}
+
+#[test]
+fn test_measure_vertical() {
+    let mut db = FontDatabase::default();
+    db.load_font_data(test_font().glyph('中', 1000).vertical(1200).build())
+        .unwrap();
+
+    let metrics = measure(&db, WritingMode::Tb.into(), "中ma");
+
+    // the fullwidth characters are upright and advance by the vertical metrics, the latin letters are
+    // rotated and advance by their horizontal advances.
+    let expected = [('中', 0.0, 12.0), ('m', 12.0, 7.5), ('a', 19.5, 5.0)];
+
+    assert_eq!(metrics.glyphs.len(), expected.len());
+
+    for (glyph, (c, x, advance)) in metrics.glyphs.iter().zip(expected) {
+        assert_eq!(glyph.c, c);
+        assert_near(glyph.x, x);
+        assert_near(glyph.advance, advance);
+    }
+
+    assert_near(metrics.width, 24.5);
+}
+
+#[test]
+fn test_measure_vertical_upright() {
+    let layout = TextLayout {
+        write_mode: Some(WritingMode::TbRl),
+        vertical: Some(GlyphOrientationVertical::Angle(Angle::deg(0.0))),
+        ..Default::default()
+    };
+
+    // without vertical metrics, the upright glyphs advance by the height of the em box.
+    let metrics = measure(&test_fonts(), layout.clone(), "ai");
+
+    assert_eq!(
+        positions(&metrics),
+        vec![('a', 0.0, 10.0), ('i', 10.0, 10.0)]
+    );
+
+    let mut db = FontDatabase::default();
+    db.load_font_data(
+        FontBuilder::new("Test")
+            .glyph('a', 500)
+            .vertical(900)
+            .build(),
+    )
+    .unwrap();
+
+    assert_eq!(measure(&db, layout, "aa").width, 18.0);
+}
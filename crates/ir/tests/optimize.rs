@@ -0,0 +1,54 @@
+use vglang_ir::*;
+
+fn rect(x: i32) -> IR {
+    Rect::from((x, 0, 10, 10)).into()
+}
+
+#[test]
+fn test_hoist_scopes() {
+    let red: IR = Fill::from(Color::red).into();
+    let blue: IR = Fill::from(Color::blue).into();
+    let stroke: IR = Stroke::from(Color::black).into();
+
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        red.clone(),
+        stroke.clone(),
+        rect(0),
+        IR::Pop(2),
+        red.clone(),
+        stroke.clone(),
+        rect(1),
+        IR::Pop(1),
+        red.clone(),
+        rect(2),
+        IR::Pop(2),
+        blue.clone(),
+        rect(3),
+        IR::Pop(2),
+    ];
+
+    assert_eq!(
+        hoist_scopes(codes),
+        vec![
+            Layer::from((100, 100)).into(),
+            red.clone(),
+            stroke.clone(),
+            rect(0),
+            rect(1),
+            IR::Pop(1),
+            rect(2),
+            IR::Pop(1),
+            blue,
+            rect(3),
+            IR::Pop(2),
+        ]
+    );
+}
+
+#[test]
+fn test_hoist_unbalanced() {
+    let codes = vec![Layer::from((100, 100)).into(), rect(0)];
+
+    assert_eq!(hoist_scopes(codes.clone()), codes);
+}
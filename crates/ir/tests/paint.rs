@@ -0,0 +1,21 @@
+#![cfg(feature = "dsl")]
+
+use vglang_ir::*;
+
+#[test]
+fn test_paint_dsl() {
+    let paint = Paint::color(Color::red);
+
+    assert!(paint.is_color());
+    assert!(!paint.is_gradient());
+    assert_eq!(paint, Paint::from(Rgba::from(Color::red)));
+
+    let paint = Paint::gradient("#linear");
+
+    assert!(paint.is_gradient());
+    assert_eq!(paint, Paint::Gradient("#linear".to_owned()));
+    assert_eq!(
+        Paint::pattern("#pattern"),
+        Paint::Pattern("#pattern".to_owned())
+    );
+}
@@ -0,0 +1,70 @@
+use vglang_ir::{geometry, geometry::Point, *};
+
+fn triangle() -> Path {
+    Path::default()
+        .move_to((0.0, 0.0))
+        .line_to((40.0, 0.0))
+        .line_to((0.0, 40.0))
+        .close_path()
+}
+
+#[test]
+fn test_path_builder() {
+    let path = triangle().cubic_bezier((1.0, 1.0), (2.0, 2.0), (3.0, 3.0));
+
+    assert!(matches!(&path.data, Animatable::Constant(events) if events.len() == 5));
+
+    // an animated path data is replaced.
+    let path = Path::default().data_animated("data").move_to((1.0, 1.0));
+
+    assert_eq!(
+        path.data,
+        Animatable::Constant(vec![Animatable::Constant(PathEvent::MoveTo(
+            (1.0, 1.0).into()
+        ))])
+    );
+}
+
+#[test]
+fn test_verify_path() {
+    let codes: Vec<IR> = vec![Path::default()
+        .line_to((0.0, 0.0))
+        .arc(-1.0, 1.0, 0.0, false, true, (10.0, 10.0))
+        .into()];
+
+    let violations = verify(&codes).unwrap_err();
+
+    assert_eq!(
+        violations
+            .iter()
+            .map(|violation| violation.field.as_str())
+            .collect::<Vec<_>>(),
+        vec![".data[0]", ".data[1].rx"]
+    );
+
+    assert!(verify(&[triangle().into()]).is_ok());
+}
+
+#[test]
+fn test_path_bounds_and_hit() {
+    let ctx = ResolveContext::default();
+
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        triangle().into(),
+        IR::Pop(1),
+    ];
+
+    assert_eq!(
+        bounds(&codes, &ctx),
+        Some(geometry::Rect::new(0.0, 0.0, 40.0, 40.0))
+    );
+
+    assert_eq!(
+        hit_test(Point::new(5.0, 5.0), &codes, &ctx),
+        vec![ElementPath(vec![0, 1])]
+    );
+
+    // outside of the hypotenuse, but inside of the bounds.
+    assert!(hit_test(Point::new(30.0, 30.0), &codes, &ctx).is_empty());
+}
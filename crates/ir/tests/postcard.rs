@@ -0,0 +1,39 @@
+#![cfg(feature = "postcard")]
+
+use vglang_ir::*;
+#[test]
+fn test_postcard_validated() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        Font::from("Verdana").into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(2),
+    ];
+
+    let bytes = to_postcard(&codes).unwrap();
+
+    assert_eq!(from_postcard_validated(&bytes).unwrap(), codes);
+
+    // the version is a varint, `100` is encoded in one byte.
+    let mut bytes = bytes;
+    bytes[0] = 100;
+
+    assert!(matches!(
+        from_postcard_validated(&bytes),
+        Err(Error::UnsupportedVersion(100))
+    ));
+}
+
+#[test]
+fn test_postcard_variant_indexes() {
+    // the variant index follows the version and the body length, the indexes of released instructions
+    // must not change.
+    let index = |ir: IR| to_postcard(&[ir]).unwrap()[2];
+
+    assert_eq!(index(IR::Pop(1)), 0);
+    assert_eq!(index(Rect::from((0, 0, 10, 10)).into()), 6);
+    assert_eq!(index(Fill::default().into()), 7);
+    assert_eq!(index(Stroke::default().into()), 8);
+    assert_eq!(index(TextLayout::default().into()), 10);
+    assert_eq!(index(Path::default().into()), 28);
+}
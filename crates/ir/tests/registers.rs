@@ -0,0 +1,57 @@
+use vglang_ir::*;
+
+fn animated<T: FrameVariable>(name: &str) -> Animatable<T> {
+    Animatable::Animated(name.to_owned())
+}
+
+#[test]
+fn test_registers() {
+    let codes = vec![
+        Layer {
+            width: animated("width"),
+            ..Layer::from((100, 100))
+        }
+        .into(),
+        Stroke {
+            dasharray: Some(Animatable::Constant(vec![
+                Animatable::Constant(Measurement::from(1)),
+                animated("dash"),
+            ])),
+            ..Default::default()
+        }
+        .into(),
+        Text::default().into(),
+        TextSpan {
+            font: Some(Font {
+                size: Some(animated("size")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .into(),
+        IR::Animated("title".to_owned()),
+        IR::Pop(2),
+        Rect {
+            x: animated("width"),
+            ..Rect::from((0, 0, 10, 10))
+        }
+        .into(),
+        IR::Pop(2),
+    ];
+
+    assert_eq!(
+        registers(&codes).into_iter().collect::<Vec<_>>(),
+        ["dash", "size", "title", "width"]
+    );
+}
+
+#[test]
+fn test_registers_constant() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+    ];
+
+    assert!(registers(&codes).is_empty());
+}
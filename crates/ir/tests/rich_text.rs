@@ -0,0 +1,55 @@
+use vglang_ir::*;
+
+#[test]
+fn test_parse() {
+    let text =
+        RichText::parse(r#"a <b>bold <i>both</i></b> &lt;<span fill="red">c</span><sup>2</sup>"#)
+            .unwrap();
+
+    assert_eq!(text.plain_text(), "a bold both <c2");
+    assert_eq!(text.runs.len(), 6);
+
+    let bold = Some(Animatable::Constant(FontWeight::Bold));
+
+    assert_eq!(text.runs[1].text, "bold ");
+    assert_eq!(text.runs[1].style.font.weight, bold);
+    assert_eq!(text.runs[2].style.font.weight, bold);
+    assert_eq!(
+        text.runs[2].style.font.style,
+        Some(Animatable::Constant(FontStyle::Italic))
+    );
+    assert_eq!(text.runs[3].text, " <");
+    assert_eq!(text.runs[4].style.fill, Some(Fill::from(Color::red)));
+    assert_eq!(
+        text.runs[5].style.baseline_shift,
+        Some(BaselineShift::Super)
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(RichText::parse("<b>a").is_err());
+    assert!(RichText::parse("<b>a</i>").is_err());
+    assert!(RichText::parse("<blink>a</blink>").is_err());
+    assert!(RichText::parse(r#"<span stroke="red">a</span>"#).is_err());
+    assert!(RichText::parse("a &nbsp; b").is_err());
+}
+
+#[test]
+fn test_lower() {
+    let text = r#"<a href="https://example.com"><u>x</u></a>y"#.parse::<RichText>().unwrap();
+
+    assert!(text.runs[0].style.decoration.underline);
+    assert_eq!(
+        text.runs[0].style.href,
+        Some(Href("https://example.com".to_owned()))
+    );
+
+    let codes = text.lower(Text::from((10, 20)));
+
+    assert_eq!(codes.len(), 8);
+    assert_eq!(codes[0], Text::from((10, 20)).into());
+    assert_eq!(codes[2], IR::String("x".to_owned()));
+    assert_eq!(codes[5], IR::String("y".to_owned()));
+    assert_eq!(codes[7], IR::Pop(1));
+}
@@ -0,0 +1,68 @@
+use vglang_ir::geometry::{round_corners, Point, Polyline, Rect, Segment, SubPath};
+
+#[test]
+fn test_round_corners() {
+    let rect = Rect::new(0.0, 0.0, 20.0, 10.0);
+
+    let polygon = Polyline {
+        points: rect.corners().to_vec(),
+        closed: true,
+    };
+
+    // the same outline as a rounded rect.
+    let rounded = round_corners(&polygon, 2.0);
+
+    assert!(rounded.closed);
+
+    for (lhs, rhs) in rounded
+        .segments
+        .iter()
+        .zip(SubPath::rect(&rect, 2.0, 2.0).segments.iter())
+    {
+        assert!(lhs.from().distance(rhs.from()) < 1e-4);
+        assert!(lhs.to().distance(rhs.to()) < 1e-4);
+    }
+
+    // the radius is reduced to fit in the short edges.
+    let rounded = round_corners(&polygon, 100.0);
+
+    assert!(rounded.segments.iter().all(|segment| {
+        let to = segment.to();
+        to.y >= -1e-4 && to.y <= 10.0 + 1e-4
+    }));
+
+    assert_eq!(rounded.segments[0].from(), Point::new(5.0, 0.0));
+}
+
+#[test]
+fn test_round_open_corners() {
+    let polyline = Polyline {
+        points: vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ],
+        closed: false,
+    };
+
+    let rounded = round_corners(&polyline, 2.0);
+
+    assert!(!rounded.closed);
+    assert_eq!(
+        rounded.segments.first().unwrap().from(),
+        Point::new(0.0, 0.0)
+    );
+    assert_eq!(
+        rounded.segments.last().unwrap().to(),
+        Point::new(10.0, 10.0)
+    );
+
+    // the fillet is tangent to both edges.
+    assert_eq!(
+        rounded.segments[0],
+        Segment::Line {
+            from: Point::new(0.0, 0.0),
+            to: Point::new(8.0, 0.0)
+        }
+    );
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "schema")]
+
+use vglang_ir::*;
+
+#[test]
+fn test_schema() {
+    let schema = schema();
+
+    assert_eq!(schema.get("title").unwrap(), "Envelope");
+
+    let defs = schema.get("$defs").unwrap().as_object().unwrap();
+
+    for name in [
+        "IR",
+        "Rect",
+        "Layer",
+        "Fill",
+        "Rgba",
+        "Animatable_Measurement",
+    ] {
+        assert!(defs.contains_key(name), "missing definition `{}`", name);
+    }
+
+    assert!(defs["Rect"]["properties"]
+        .as_object()
+        .unwrap()
+        .contains_key("width"));
+}
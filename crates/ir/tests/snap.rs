@@ -0,0 +1,28 @@
+use vglang_ir::*;
+
+#[test]
+fn test_snap_to_pixels() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Rect::from((0.3, 0.6, 10.2, 9.7)).into(),
+        Stroke::from(Color::black).into(),
+        Rect::from((20.2, 20.2, 10.0, 0.1)).into(),
+        IR::Pop(2),
+    ];
+
+    let codes = snap_to_pixels(codes, &ResolveContext::default(), 96.0);
+
+    assert_eq!(codes[1], Rect::from((0.0, 1.0, 11.0, 9.0)).into());
+
+    // 1px strokes are centered on the pixel centers, and the height is at least one pixel.
+    assert_eq!(codes[3], Rect::from((20.5, 20.5, 10.0, 1.0)).into());
+
+    // device pixels are half the user units.
+    let codes = snap_to_pixels(
+        vec![Rect::from((0.3, 0.3, 10.0, 10.0)).into()],
+        &ResolveContext::default(),
+        48.0,
+    );
+
+    assert_eq!(codes[0], Rect::from((0.0, 0.0, 10.0, 10.0)).into());
+}
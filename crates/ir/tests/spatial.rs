@@ -0,0 +1,49 @@
+use vglang_ir::{
+    geometry::{Point, Rect},
+    ElementPath, SpatialIndex,
+};
+
+fn grid(size: usize) -> SpatialIndex {
+    let entries = (0..size * size)
+        .map(|index| {
+            let (x, y) = ((index % size) as f32 * 10.0, (index / size) as f32 * 10.0);
+            (Rect::new(x, y, 5.0, 5.0), ElementPath(vec![index]))
+        })
+        .collect();
+
+    SpatialIndex::from_entries(entries)
+}
+
+#[test]
+fn test_query() {
+    let index = grid(100);
+
+    assert_eq!(index.len(), 10000);
+
+    let found = index.query(&Rect::new(12.0, 12.0, 10.0, 10.0));
+
+    assert_eq!(
+        found.iter().map(|path| path.index()).collect::<Vec<_>>(),
+        [101, 102, 201, 202]
+    );
+
+    let found = index.query_point(Point::new(993.0, 993.0));
+
+    assert_eq!(found, [&ElementPath(vec![9999])]);
+
+    assert!(index.query_point(Point::new(7.0, 7.0)).is_empty());
+}
+
+#[test]
+fn test_nearest() {
+    let index = grid(100);
+
+    let (path, distance) = index.nearest(Point::new(507.0, 302.0)).unwrap();
+
+    assert_eq!(path.index(), 3050);
+    assert_eq!(distance, 2.0);
+
+    assert!(SpatialIndex::default()
+        .nearest(Point::new(0.0, 0.0))
+        .is_none());
+}
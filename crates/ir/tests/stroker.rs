@@ -0,0 +1,100 @@
+use vglang_ir::{
+    geometry::{Point, Polyline, Rect, Segment, SubPath},
+    stroke_to_fill, StrokeLineCap, StrokeLineJoin, StrokeStyle,
+};
+
+fn line(points: &[(f32, f32)]) -> Vec<SubPath> {
+    let segments = points
+        .iter()
+        .zip(points.iter().skip(1))
+        .map(|(from, to)| Segment::Line {
+            from: (*from).into(),
+            to: (*to).into(),
+        })
+        .collect();
+
+    vec![SubPath {
+        segments,
+        closed: false,
+    }]
+}
+
+fn bounds(polygons: &[Polyline]) -> Rect {
+    Rect::from_points(polygons.iter().flat_map(|p| p.points.iter().copied())).unwrap()
+}
+
+#[test]
+fn test_stroke_caps() {
+    let subpaths = line(&[(0.0, 0.0), (10.0, 0.0)]);
+
+    let mut style = StrokeStyle {
+        width: 2.0,
+        ..Default::default()
+    };
+
+    let butt = stroke_to_fill(&subpaths, &style, 0.01);
+
+    assert_eq!(butt.len(), 1);
+    assert_eq!(bounds(&butt), Rect::new(0.0, -1.0, 10.0, 2.0));
+
+    style.cap = StrokeLineCap::Square;
+
+    assert_eq!(
+        bounds(&stroke_to_fill(&subpaths, &style, 0.01)),
+        Rect::new(-1.0, -1.0, 12.0, 2.0)
+    );
+}
+
+#[test]
+fn test_stroke_joins() {
+    let subpaths = line(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+    let mut style = StrokeStyle {
+        width: 2.0,
+        ..Default::default()
+    };
+
+    // the miter join fills the outer corner.
+    assert_eq!(
+        bounds(&stroke_to_fill(&subpaths, &style, 0.01)),
+        Rect::new(0.0, -1.0, 11.0, 11.0)
+    );
+
+    style.join = StrokeLineJoin::Round;
+
+    let round = stroke_to_fill(&subpaths, &style, 0.01);
+
+    // the outer corner point is not covered by the round join.
+    assert!(!hit(&round, Point::new(10.9, -0.9)));
+    assert!(hit(&round, Point::new(10.5, -0.5)));
+}
+
+#[test]
+fn test_stroke_dashes() {
+    let subpaths = line(&[(0.0, 0.0), (10.0, 0.0)]);
+
+    let style = StrokeStyle {
+        width: 2.0,
+        dasharray: vec![2.0, 3.0],
+        dashoffset: 1.0,
+        ..Default::default()
+    };
+
+    let dashes = stroke_to_fill(&subpaths, &style, 0.01);
+
+    // [0, 1], [4, 6] and [9, 10].
+    assert_eq!(dashes.len(), 3);
+    assert_eq!(bounds(&dashes[1..2]), Rect::new(4.0, -1.0, 2.0, 2.0));
+}
+
+/// Returns true if `point` is in any polygon, which are convex here.
+fn hit(polygons: &[Polyline], point: Point) -> bool {
+    polygons.iter().any(|polygon| {
+        let points = &polygon.points;
+
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .all(|(from, to)| (*to - *from).cross(point - *from) <= 0.0)
+    })
+}
@@ -0,0 +1,83 @@
+use vglang_ir::*;
+
+#[test]
+fn test_parse_values() {
+    assert_eq!("#f00".parse::<Rgba>().unwrap(), Rgba::rgb(255, 0, 0));
+    assert_eq!("#00ff00".parse::<Rgba>().unwrap(), Rgba::rgb(0, 255, 0));
+    assert_eq!("Blue".parse::<Rgba>().unwrap(), Rgba::rgb(0, 0, 255));
+    assert_eq!(
+        "rgb(0, 255, 100%)".parse::<Rgba>().unwrap(),
+        Rgba::rgb(0, 255, 255)
+    );
+    assert!("#ff".parse::<Rgba>().is_err());
+
+    assert_eq!("1em".parse::<Measurement>().unwrap(), Measurement::em(1.0));
+    assert_eq!(
+        "-1.5e2".parse::<Measurement>().unwrap(),
+        Measurement(-150.0, None)
+    );
+    assert_eq!(
+        "50%".parse::<Measurement>().unwrap(),
+        Measurement::percentage(50.0)
+    );
+    assert!("1ems".parse::<Measurement>().is_err());
+}
+
+#[test]
+fn test_apply() {
+    let stylesheet = Stylesheet::parse(
+        r#"
+        /* the default rect style */
+        rect { fill: red; stroke-width: 2 }
+        .primary { fill: #00f !important; }
+        g#title, tspan { font-family: "Helvetica", sans-serif; unknown: 1 }
+        "#,
+    )
+    .unwrap();
+
+    let codes = stylesheet.apply(vec![
+        Layer::from((100, 100)).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        Label::from("title").into(),
+        Text::default().into(),
+        IR::String("hello".to_owned()),
+        IR::Pop(2),
+        Label {
+            id: None,
+            class: vec!["primary".to_owned()],
+        }
+        .into(),
+        IR::Pop(2),
+    ]);
+
+    assert_eq!(
+        codes,
+        vec![
+            Layer::from((100, 100)).into(),
+            Fill::from(Color::red).into(),
+            Stroke::default().width(2).into(),
+            Rect::from((0, 0, 10, 10)).into(),
+            IR::Pop(2),
+            Label::from("title").into(),
+            Font::from(FontFamily::from("Helvetica")).into(),
+            Text::default().into(),
+            IR::String("hello".to_owned()),
+            IR::Pop(3),
+            Label {
+                id: None,
+                class: vec!["primary".to_owned()],
+            }
+            .into(),
+            Fill::from(Color::blue).into(),
+            IR::Pop(3),
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_stylesheet() {
+    assert!(Stylesheet::parse("rect { fill: nocolor }").is_err());
+    assert!(Stylesheet::parse("g rect { fill: red }").is_err());
+    assert!(Stylesheet::parse("rect { fill: red").is_err());
+    assert!(Stylesheet::parse("text { paint-order: halo }").is_err());
+}
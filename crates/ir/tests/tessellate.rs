@@ -0,0 +1,179 @@
+use vglang_ir::*;
+
+fn square(x: f32, y: f32, size: f32) -> Vec<PathEvent> {
+    vec![
+        PathEvent::MoveTo((x, y).into()),
+        PathEvent::LineTo((x + size, y).into()),
+        PathEvent::LineTo((x + size, y + size).into()),
+        PathEvent::LineTo((x, y + size).into()),
+        PathEvent::ClosePath,
+    ]
+}
+
+fn path(events: Vec<PathEvent>) -> IR {
+    Path {
+        data: Animatable::Constant(events.into_iter().map(Animatable::Constant).collect()),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// Returns the sum of the triangle areas of `mesh`.
+fn area(mesh: &Mesh) -> f32 {
+    mesh.indices
+        .chunks(3)
+        .map(|triangle| {
+            let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize]);
+
+            ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.0
+        })
+        .sum()
+}
+
+#[test]
+fn test_tessellate() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Fill::from(Color::red).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+        Stroke::from(Color::blue).into(),
+        Rect::from((20, 20, 10, 10)).into(),
+        IR::Pop(2),
+    ];
+
+    let mesh = tessellate(&codes, &ResolveContext::default());
+
+    assert_eq!(mesh.vertices.len(), mesh.colors.len());
+    assert_eq!(mesh.colors[0], [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(mesh.colors[4], [0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(mesh.colors[8], <[f32; 4]>::from(Rgba::from(Color::blue)));
+
+    // 2 triangles per filled rect, 2 per stroke edge and 2 per miter join.
+    assert_eq!(mesh.triangles(), 2 + 2 + 4 * 2 + 4 * 2);
+
+    assert!(mesh
+        .indices
+        .iter()
+        .all(|index| (*index as usize) < mesh.vertices.len()));
+}
+
+#[test]
+fn test_tessellate_paint_order() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Fill::from(Color::red).into(),
+        Stroke::from(Color::blue)
+            .paint_order(PaintOrder::StrokeFill)
+            .into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(3),
+    ];
+
+    let mesh = tessellate(&codes, &ResolveContext::default());
+
+    // the fill is the last rect in the mesh.
+    let fill = mesh.colors.len() - 4;
+
+    assert_eq!(mesh.colors[0], <[f32; 4]>::from(Rgba::from(Color::blue)));
+    assert_eq!(mesh.colors[fill], [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(
+        mesh.colors[fill - 1],
+        <[f32; 4]>::from(Rgba::from(Color::blue))
+    );
+}
+
+#[test]
+fn test_tessellate_fill_none() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Fill::default().into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(2),
+    ];
+
+    let mesh = tessellate(&codes, &ResolveContext::default());
+
+    assert_eq!(mesh.triangles(), 0);
+
+    // only the stroke is tessellated.
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Fill::default().into(),
+        Stroke::from(Color::blue).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(3),
+    ];
+
+    let mesh = tessellate(&codes, &ResolveContext::default());
+
+    assert_eq!(mesh.triangles(), 4 * 2 + 4 * 2);
+    assert!(mesh
+        .colors
+        .iter()
+        .all(|color| *color == <[f32; 4]>::from(Rgba::from(Color::blue))));
+}
+
+#[test]
+fn test_tessellate_path() {
+    // a square with a square hole of the same orientation.
+    let mut events = square(0.0, 0.0, 10.0);
+    events.extend(square(2.0, 2.0, 6.0));
+
+    let codes = |rule: FillRule| -> Vec<IR> {
+        vec![
+            Layer::from((100, 100)).into(),
+            Fill::from(Color::red).rule(rule).into(),
+            path(events.clone()),
+            IR::Pop(2),
+        ]
+    };
+
+    let ctx = ResolveContext::default();
+
+    assert_eq!(area(&tessellate(&codes(FillRule::Nonzero), &ctx)), 100.0);
+    assert_eq!(area(&tessellate(&codes(FillRule::EvenOdd), &ctx)), 64.0);
+}
+
+#[test]
+fn test_tessellate_concave_path() {
+    // a self-intersecting bow tie of two triangles.
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        path(vec![
+            PathEvent::MoveTo((0.0, 0.0).into()),
+            PathEvent::LineTo((10.0, 10.0).into()),
+            PathEvent::LineTo((10.0, 0.0).into()),
+            PathEvent::LineTo((0.0, 10.0).into()),
+            PathEvent::ClosePath,
+        ]),
+        IR::Pop(1),
+    ];
+
+    let mesh = tessellate(&codes, &ResolveContext::default());
+
+    assert_eq!(area(&mesh), 50.0);
+    assert!(mesh
+        .vertices
+        .iter()
+        .all(|[x, y]| (0.0..=10.0).contains(x) && (0.0..=10.0).contains(y)));
+}
+
+#[test]
+fn test_tessellate_symbol() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        SymbolDef::new("dot").into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+        Use::new("dot").x(50).y(20).into(),
+        IR::Pop(1),
+    ];
+
+    let mesh = tessellate(&codes, &ResolveContext::default());
+
+    // only the instance is tessellated.
+    assert_eq!(mesh.triangles(), 2);
+    assert!(mesh.vertices.contains(&[50.0, 20.0]));
+    assert!(mesh.vertices.contains(&[60.0, 30.0]));
+}
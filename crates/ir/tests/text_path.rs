@@ -0,0 +1,447 @@
+#![cfg(feature = "font")]
+
+use vglang_ir::*;
+
+mod common;
+
+use common::{boxes, test_font, test_fonts};
+
+fn ctx() -> ResolveContext {
+    ResolveContext {
+        font_size: 10.0,
+        ..Default::default()
+    }
+}
+
+fn outlines(codes: &[IR]) -> Vec<TextOutline> {
+    text_to_outlines(codes, &ctx(), &test_fonts())
+}
+
+#[test]
+fn test_glyph_origins() {
+    let codes = vec![
+        Font::from("Test").into(),
+        Text::from((10, 20)).into(),
+        IR::String("ai m".to_owned()),
+        IR::Pop(2),
+    ];
+
+    let outlines = outlines(&codes);
+
+    assert_eq!(outlines.len(), 1);
+    assert_eq!(outlines[0].element, ElementPath(vec![0, 1]));
+    assert!(outlines[0].layers.is_empty());
+
+    // the glyph boxes are inset by 0.5 from the advances, and sit on the baseline at y = 20;
+    // the space has no outline.
+    assert_eq!(
+        boxes(&outlines[0].subpaths),
+        vec![
+            (10.5, 13.0, 14.5, 20.0),
+            (15.5, 13.0, 17.0, 20.0),
+            (20.5, 13.0, 27.0, 20.0),
+        ]
+    );
+}
+
+#[test]
+fn test_glyph_positions() {
+    let codes = vec![
+        Font::from("Test").into(),
+        Text {
+            x: Animatable::Constant(vec![Measurement::px(0.0), Measurement::px(100.0)]),
+            dy: Animatable::Constant(vec![Measurement::px(0.0), Measurement::px(5.0)]),
+            ..Default::default()
+        }
+        .into(),
+        IR::String("aa".to_owned()),
+        IR::Pop(2),
+    ];
+
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![(0.5, -7.0, 4.5, 0.0), (100.5, -2.0, 104.5, 5.0)]
+    );
+}
+
+#[test]
+fn test_font_size() {
+    let codes = vec![
+        Font {
+            size: Some(Animatable::Constant(Measurement::px(20.0))),
+            ..Font::from("Test")
+        }
+        .into(),
+        Text::from((10, 20)).into(),
+        IR::String("ai".to_owned()),
+        IR::Pop(2),
+    ];
+
+    // the outlines are scaled from the 1000 units per em to the font size.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![(11.0, 6.0, 19.0, 20.0), (21.0, 6.0, 24.0, 20.0)]
+    );
+}
+
+#[test]
+fn test_transform() {
+    let codes = vec![
+        Transform::scale(2.0, 2.0).into(),
+        Font::from("Test").into(),
+        Text::from((10, 20)).into(),
+        IR::String("a".to_owned()),
+        IR::Pop(3),
+    ];
+
+    // the outlines are in the root user space.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![(21.0, 26.0, 29.0, 40.0)]
+    );
+}
+
+#[test]
+fn test_missing_glyph() {
+    let codes = vec![
+        Font::from("Test").into(),
+        Text::default().into(),
+        IR::String("iäi".to_owned()),
+        IR::Pop(2),
+    ];
+
+    // `ä` falls back to the `.notdef` glyph, which advances 5 at font size 10.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![
+            (0.5, -7.0, 2.0, 0.0),
+            (3.0, -7.0, 7.0, 0.0),
+            (8.0, -7.0, 9.5, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_no_glyphs() {
+    // no font matches in an empty database, and white space only text has no outline.
+    let codes = vec![
+        Text::default().into(),
+        IR::String("a".to_owned()),
+        IR::Pop(1),
+    ];
+
+    assert!(text_to_outlines(&codes, &ctx(), &FontDatabase::default()).is_empty());
+
+    let codes = vec![
+        Text::default().into(),
+        IR::String("   ".to_owned()),
+        IR::Pop(1),
+    ];
+
+    assert!(outlines(&codes).is_empty());
+}
+
+/// Returns a font database with the test font, extended with the fullwidth `中` and vertical metrics.
+fn vertical_fonts() -> FontDatabase {
+    let mut db = FontDatabase::default();
+    db.load_font_data(test_font().glyph('中', 1000).vertical(1000).build())
+        .unwrap();
+    db
+}
+
+#[test]
+fn test_vertical() {
+    let codes = vec![
+        Font::from("Test").into(),
+        TextLayout::from(WritingMode::Tb).into(),
+        Text::from((10, 20)).into(),
+        IR::String("中a".to_owned()),
+        IR::Pop(3),
+    ];
+
+    let outlines = text_to_outlines(&codes, &ctx(), &vertical_fonts());
+
+    // the glyphs advance downwards centered on x = 10, `中` is upright and advances by the vertical metrics,
+    // `a` is rotated 90 degrees clockwise and advances by its horizontal advance.
+    assert_eq!(
+        boxes(&outlines[0].subpaths),
+        vec![(5.5, 21.0, 14.5, 28.0), (7.0, 30.5, 14.0, 34.5)]
+    );
+}
+
+#[test]
+fn test_vertical_orientation() {
+    let codes = vec![
+        Font::from("Test").into(),
+        TextLayout {
+            write_mode: Some(WritingMode::Tb),
+            vertical: Some(GlyphOrientationVertical::Angle(Angle::deg(0.0))),
+            ..Default::default()
+        }
+        .into(),
+        Text::from((10, 20)).into(),
+        IR::String("a".to_owned()),
+        IR::Pop(3),
+    ];
+
+    let outlines = text_to_outlines(&codes, &ctx(), &vertical_fonts());
+
+    // the upright `a` advances by the vertical metrics.
+    assert_eq!(boxes(&outlines[0].subpaths), vec![(8.0, 21.0, 12.0, 28.0)]);
+}
+
+#[test]
+fn test_vertical_anchor() {
+    let codes = vec![
+        Font::from("Test").into(),
+        TextLayout {
+            write_mode: Some(WritingMode::Tb),
+            anchor: Some(Animatable::Constant(TextAnchor::End)),
+            ..Default::default()
+        }
+        .into(),
+        Text::from((10, 20)).into(),
+        IR::String("中a".to_owned()),
+        IR::Pop(3),
+    ];
+
+    let outlines = text_to_outlines(&codes, &ctx(), &vertical_fonts());
+
+    // the chunk of 15 units ends at y = 20.
+    assert_eq!(
+        boxes(&outlines[0].subpaths),
+        vec![(5.5, 6.0, 14.5, 13.0), (7.0, 15.5, 14.0, 19.5)]
+    );
+}
+
+fn text_length(length: f32, adjust: TextLengthAdjust) -> Text {
+    Text {
+        text_length: Animatable::Constant(Measurement::px(length)),
+        length_adjust: Animatable::Constant(adjust),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_text_length_spacing() {
+    let codes = vec![
+        Font::from("Test").into(),
+        text_length(30.0, TextLengthAdjust::Spacing).into(),
+        IR::String("aaa".to_owned()),
+        IR::Pop(2),
+    ];
+
+    // the extra 15 units go between the three characters.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![
+            (0.5, -7.0, 4.5, 0.0),
+            (13.0, -7.0, 17.0, 0.0),
+            (25.5, -7.0, 29.5, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_text_length_spacing_and_glyphs() {
+    let codes = vec![
+        Font::from("Test").into(),
+        text_length(30.0, TextLengthAdjust::SpacingAndGlyphs).into(),
+        IR::String("aaa".to_owned()),
+        IR::Pop(2),
+    ];
+
+    // the glyphs are stretched horizontally to twice their width.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![
+            (1.0, -7.0, 9.0, 0.0),
+            (11.0, -7.0, 19.0, 0.0),
+            (21.0, -7.0, 29.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_text_length_span() {
+    let codes = vec![
+        Font::from("Test").into(),
+        Text::default().into(),
+        IR::String("a".to_owned()),
+        TextSpan {
+            text_length: Animatable::Constant(Measurement::px(20.0)),
+            ..Default::default()
+        }
+        .into(),
+        IR::String("aa".to_owned()),
+        IR::Pop(1),
+        IR::String("a".to_owned()),
+        IR::Pop(2),
+    ];
+
+    // only the span is spaced out, the text after it follows the adjusted span.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![
+            (0.5, -7.0, 4.5, 0.0),
+            (5.5, -7.0, 9.5, 0.0),
+            (20.5, -7.0, 24.5, 0.0),
+            (25.5, -7.0, 29.5, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_text_length_vertical() {
+    let codes = vec![
+        Font::from("Test").into(),
+        TextLayout::from(WritingMode::Tb).into(),
+        Text {
+            y: Animatable::Constant(vec![Measurement::px(20.0)]),
+            ..text_length(40.0, TextLengthAdjust::Spacing)
+        }
+        .into(),
+        IR::String("中中".to_owned()),
+        IR::Pop(3),
+    ];
+
+    let outlines = text_to_outlines(&codes, &ctx(), &vertical_fonts());
+
+    // the extra 20 units go between the glyphs along the vertical inline axis.
+    assert_eq!(
+        boxes(&outlines[0].subpaths),
+        vec![(-4.5, 21.0, 4.5, 28.0), (-4.5, 51.0, 4.5, 58.0)]
+    );
+}
+
+fn shifted(shift: BaselineShift, content: &str) -> Vec<IR> {
+    vec![
+        TextSpan {
+            layout: Some(shift.into()),
+            ..Default::default()
+        }
+        .into(),
+        IR::String(content.to_owned()),
+        IR::Pop(1),
+    ]
+}
+
+#[test]
+fn test_baseline_shift() {
+    let mut codes = vec![
+        Font::from("Test").into(),
+        Text::from((0, 20)).into(),
+        IR::String("a".to_owned()),
+    ];
+
+    codes.extend(shifted(BaselineShift::Super, "a"));
+    codes.extend(shifted(BaselineShift::Sub, "a"));
+    codes.extend(shifted(BaselineShift::Value(Measurement::px(4.0)), "a"));
+    codes.extend(shifted(
+        BaselineShift::Value(Measurement::percentage(50.0)),
+        "a",
+    ));
+    codes.push(IR::Pop(2));
+
+    // the test font has no sub- and superscript metrics, they default to 0.2em and 0.35em;
+    // percentages refer to the line height of 10.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![
+            (0.5, 13.0, 4.5, 20.0),
+            (5.5, 9.5, 9.5, 16.5),
+            (10.5, 15.0, 14.5, 22.0),
+            (15.5, 9.0, 19.5, 16.0),
+            (20.5, 8.0, 24.5, 15.0),
+        ]
+    );
+}
+
+#[test]
+fn test_baseline_shift_nested() {
+    let codes = vec![
+        Font::from("Test").into(),
+        Text::from((0, 20)).into(),
+        TextSpan {
+            layout: Some(BaselineShift::Super.into()),
+            ..Default::default()
+        }
+        .into(),
+        IR::String("a".to_owned()),
+        TextSpan {
+            layout: Some(BaselineShift::Super.into()),
+            ..Default::default()
+        }
+        .into(),
+        IR::String("a".to_owned()),
+        IR::Pop(4),
+    ];
+
+    // the shifts of the enclosing spans accumulate.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![(0.5, 9.5, 4.5, 16.5), (5.5, 6.0, 9.5, 13.0)]
+    );
+}
+
+#[test]
+fn test_dominant_baseline() {
+    let codes = vec![
+        Font::from("Test").into(),
+        TextLayout {
+            dominant_baseline: Some(Animatable::Constant(DominantBaseline::TextBeforeEdge)),
+            ..Default::default()
+        }
+        .into(),
+        Text::from((0, 20)).into(),
+        IR::String("a".to_owned()),
+        TextSpan {
+            layout: Some(TextLayout {
+                alignment_baseline: Some(Animatable::Constant(AlignmentBaseline::Central)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .into(),
+        IR::String("a".to_owned()),
+        IR::Pop(4),
+    ];
+
+    // the ascent of 8 hangs from y = 20, and the central baseline is 3 above the alphabetic one.
+    assert_eq!(
+        boxes(&outlines(&codes)[0].subpaths),
+        vec![(0.5, 21.0, 4.5, 28.0), (5.5, 16.0, 9.5, 23.0)]
+    );
+}
+
+#[test]
+fn test_color_glyph() {
+    let mut db = FontDatabase::default();
+    db.load_font_data(
+        test_font()
+            .glyph('☺', 1000)
+            .color('☺', &[('a', [255, 0, 0, 255]), ('i', [0, 0, 255, 255])])
+            .build(),
+    )
+    .unwrap();
+
+    let codes = vec![
+        Font::from("Test").into(),
+        Text::from((10, 20)).into(),
+        IR::String("a☺".to_owned()),
+        IR::Pop(2),
+    ];
+
+    let outlines = text_to_outlines(&codes, &ctx(), &db);
+
+    // the color glyph has no outline, its layers are placed at its origin.
+    assert_eq!(boxes(&outlines[0].subpaths), vec![(10.5, 13.0, 14.5, 20.0)]);
+
+    let layers = &outlines[0].layers;
+
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].color, Rgba(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(boxes(&layers[0].subpaths), vec![(15.5, 13.0, 19.5, 20.0)]);
+    assert_eq!(layers[1].color, Rgba(0.0, 0.0, 1.0, 1.0));
+    assert_eq!(boxes(&layers[1].subpaths), vec![(15.5, 13.0, 17.0, 20.0)]);
+}
@@ -0,0 +1,38 @@
+use vglang_ir::{
+    geometry::{Matrix, Point},
+    ApproxEq, Measurement, Tolerance, Unit,
+};
+
+#[test]
+fn test_tolerance() {
+    let tolerance = Tolerance::new(1e-3, 1e-2);
+
+    assert!(tolerance.eq(0.0, 0.0005));
+    assert!(!tolerance.eq(0.0, 0.002));
+
+    // the relative tolerance applies to the large numbers.
+    assert!(tolerance.eq(1000.0, 1005.0));
+    assert!(!tolerance.eq(1000.0, 1020.0));
+
+    assert!(0.1f32.approx_eq(&(0.3 - 0.2)));
+}
+
+#[test]
+fn test_approx_eq() {
+    assert!(Point::new(1.0, 2.0).approx_eq(&Point::new(1.000001, 2.0)));
+    assert!(!Point::new(1.0, 2.0).approx_eq(&Point::new(1.1, 2.0)));
+
+    // units must match.
+    assert!(Measurement(1.0, Some(Unit::Px)).approx_eq(&Measurement(1.000001, Some(Unit::Px))));
+    assert!(!Measurement(1.0, Some(Unit::Px)).approx_eq(&Measurement(1.0, Some(Unit::Em))));
+
+    assert!(Matrix::rotate(360.0).is_identity());
+
+    let matrix = Matrix::rotate(30.0).multiply(&Matrix::translate(10.0, 5.0));
+
+    assert!(matrix
+        .multiply(&matrix.invert().unwrap())
+        .approx_eq(&Matrix::identity()));
+
+    assert!(vec![Point::new(0.0, 0.0)].approx_eq(&vec![Point::new(0.0, 1e-7)]));
+}
@@ -0,0 +1,67 @@
+use vglang_ir::{
+    geometry::{self, Matrix3x2, Point},
+    *,
+};
+
+#[test]
+fn test_matrix_concat() {
+    let matrix = Matrix3x2::translate(10.0, 0.0).concat(&Matrix3x2::scale(2.0, 2.0));
+
+    // translated first, then scaled.
+    assert_eq!(
+        matrix.transform_point(Point::new(1.0, 1.0)),
+        Point::new(22.0, 2.0)
+    );
+
+    let inverse = matrix.invert().unwrap();
+
+    assert_eq!(
+        inverse.transform_point(Point::new(22.0, 2.0)),
+        Point::new(1.0, 1.0)
+    );
+
+    assert!(Matrix3x2::from(Transform::rotate_around(90.0, 10.0, 10.0))
+        .transform_point(Point::new(20.0, 10.0))
+        .approx_eq(&Point::new(10.0, 20.0)));
+}
+
+#[test]
+fn test_transform_bounds() {
+    let ctx = ResolveContext::default();
+
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Transform::translate(10.0, 20.0).into(),
+        Transform::scale(2.0, 2.0).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(3),
+    ];
+
+    assert_eq!(
+        bounds(&codes, &ctx),
+        Some(geometry::Rect::new(10.0, 20.0, 20.0, 20.0))
+    );
+
+    assert_eq!(
+        hit_test(Point::new(25.0, 35.0), &codes, &ctx),
+        vec![ElementPath(vec![0, 1, 2, 3])]
+    );
+
+    assert!(hit_test(Point::new(5.0, 5.0), &codes, &ctx).is_empty());
+
+    // elements under an animated transform are skipped.
+    let codes: Vec<IR> = vec![
+        Animatable::<Transform>::Animated("t".into()).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+    ];
+
+    assert_eq!(bounds(&codes, &ctx), None);
+}
+
+#[test]
+fn test_verify_transform() {
+    let violations = verify(&[Transform::skew_x(f32::NAN).into(), IR::Pop(1)]).unwrap_err();
+
+    assert_eq!(violations[0].field, "[0]");
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "font")]
+
+use vglang_ir::*;
+
+mod common;
+
+use common::test_fonts;
+
+fn ctx() -> ResolveContext {
+    ResolveContext {
+        font_size: 10.0,
+        ..Default::default()
+    }
+}
+
+fn text(max_width: f32, overflow: TextOverflow) -> IR {
+    Text {
+        max_width: Some(Animatable::Constant(Measurement::px(max_width))),
+        overflow: Animatable::Constant(overflow),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// Truncates the `strings` of a text element, and returns the strings of the output.
+fn truncate(max_width: f32, overflow: TextOverflow, strings: &[&str]) -> Vec<String> {
+    let mut codes = vec![Font::from("Test").into(), text(max_width, overflow)];
+    codes.extend(strings.iter().map(|s| IR::String(s.to_string())));
+    codes.push(IR::Pop(2));
+
+    truncate_text(&codes, &ctx(), &test_fonts())
+        .into_iter()
+        .filter_map(|ir| match ir {
+            IR::String(s) => Some(s),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_ellipsis() {
+    // `a` and the ellipsis advance 5, `i` advances 2.5.
+    assert_eq!(truncate(20.0, TextOverflow::Ellipsis, &["aaaa"]), ["aaaa"]);
+    assert_eq!(
+        truncate(20.0, TextOverflow::Ellipsis, &["aaaaaa"]),
+        ["aaa…"]
+    );
+    assert_eq!(truncate(19.0, TextOverflow::Ellipsis, &["aaaaaa"]), ["aa…"]);
+    // the white spaces before the ellipsis are removed.
+    assert_eq!(truncate(17.5, TextOverflow::Ellipsis, &["aa aa"]), ["aa…"]);
+}
+
+#[test]
+fn test_ellipsis_strings() {
+    // the first overflowing string is truncated and the following ones are removed.
+    assert_eq!(
+        truncate(22.0, TextOverflow::Ellipsis, &["aa", "aaaa", "a"]),
+        ["aa", "a…"]
+    );
+}
+
+#[test]
+fn test_ellipsis_too_wide() {
+    // the ellipsis doesn't fit itself, the text is clipped instead.
+    assert_eq!(truncate(4.0, TextOverflow::Ellipsis, &["iaa"]), ["i"]);
+    assert_eq!(truncate(2.0, TextOverflow::Ellipsis, &["aa"]), [""]);
+    assert_eq!(
+        truncate(14.0, TextOverflow::Ellipsis, &["aa", "iaa"]),
+        ["aa", "i"]
+    );
+}
+
+#[test]
+fn test_clip() {
+    assert_eq!(truncate(12.0, TextOverflow::Clip, &["aaaaaa"]), ["aa"]);
+    assert_eq!(truncate(12.5, TextOverflow::Clip, &["aaiaa"]), ["aai"]);
+}
+
+#[test]
+fn test_fade() {
+    let codes = vec![
+        Font::from("Test").into(),
+        text(20.0, TextOverflow::Fade),
+        IR::String("aaaaa".to_owned()),
+        IR::Pop(2),
+    ];
+
+    let faded = |alpha: f32, c: &str| {
+        [
+            TextSpan {
+                fill: Some(Fill::from(Rgba(0.0, 0.0, 0.0, alpha))),
+                ..Default::default()
+            }
+            .into(),
+            IR::String(c.to_owned()),
+            IR::Pop(1),
+        ]
+    };
+
+    let mut expected = vec![
+        Font::from("Test").into(),
+        text(20.0, TextOverflow::Fade),
+        IR::String("a".to_owned()),
+    ];
+    expected.extend(faded(0.75, "a"));
+    expected.extend(faded(0.5, "a"));
+    expected.extend(faded(0.25, "a"));
+    expected.push(IR::Pop(2));
+
+    assert_eq!(truncate_text(&codes, &ctx(), &test_fonts()), expected);
+}
+
+#[test]
+fn test_kept() {
+    let codes = vec![
+        text(5.0, TextOverflow::Ellipsis),
+        IR::String("aaaa".to_owned()),
+        IR::Pop(1),
+    ];
+
+    // no face is loaded.
+    assert_eq!(
+        truncate_text(&codes, &ctx(), &FontDatabase::default()),
+        codes
+    );
+
+    // no maximum width.
+    let codes = vec![
+        Text::default().into(),
+        IR::String("aaaa".to_owned()),
+        IR::Pop(1),
+    ];
+
+    assert_eq!(truncate_text(&codes, &ctx(), &test_fonts()), codes);
+}
@@ -0,0 +1,102 @@
+use vglang_ir::*;
+
+#[test]
+fn test_verify() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        Label::from("title").into(),
+        Text::default().into(),
+        IR::String("hello".to_owned()),
+        TextSpan::default().into(),
+        IR::Animated("name".to_owned()),
+        IR::Pop(3),
+    ];
+
+    assert_eq!(
+        verify(&codes),
+        Err(vec![Violation {
+            index: 7,
+            field: "".to_owned(),
+            message: "1 scopes are not closed".to_owned(),
+        }])
+    );
+}
+
+#[test]
+fn test_verify_violations() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        IR::String("hello".to_owned()),
+        Label::from("title").into(),
+        Label::from("title").into(),
+        // the setters reject negative widths in debug builds.
+        Stroke {
+            width: Some(Animatable::Constant(Measurement::from(-1))),
+            ..Default::default()
+        }
+        .into(),
+        Text::default().into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(5),
+        IR::Pop(1),
+    ];
+
+    let violations = verify(&codes)
+        .unwrap_err()
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        violations,
+        vec![
+            "[1]: text content is only allowed in text elements",
+            "[3].id: duplicate id `title`",
+            "[4].width: expect a non-negative number, got -1",
+            "[6]: rect is not allowed in text elements",
+            "[8]: pop 1 elements, but only 0 are pushed",
+        ]
+    );
+}
+
+#[test]
+fn test_verify_references() {
+    let codes = vec![
+        Layer::from((100, 100)).into(),
+        // references may precede the definitions.
+        Use::new("shape").into(),
+        Mask::new("mask").into(),
+        IR::Pop(1),
+        SymbolDef::new("shape").into(),
+        IR::Pop(1),
+        MaskRef::from("mask").into(),
+        MaskRef::from("none").into(),
+        Markers {
+            start: Some("arrow".to_owned()),
+            mid: None,
+            end: Some("dot".to_owned()),
+        }
+        .into(),
+        Marker::new("dot").into(),
+        IR::Pop(1),
+        FilterRef::from("blur").into(),
+        Use::new("missing").into(),
+        IR::Pop(5),
+    ];
+
+    let violations = verify(&codes)
+        .unwrap_err()
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        violations,
+        vec![
+            "[7].0: undefined id `none`",
+            "[8].start: undefined id `arrow`",
+            "[11].0: undefined id `blur`",
+            "[12].href: undefined id `missing`",
+        ]
+    );
+}
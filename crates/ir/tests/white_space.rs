@@ -0,0 +1,21 @@
+use vglang_ir::*;
+
+#[test]
+fn test_normal() {
+    let mut processor = WhiteSpaceProcessor::default();
+
+    assert_eq!(
+        processor.process("  a \t b\n", WhiteSpace::Normal, 4),
+        "a b"
+    );
+    assert_eq!(processor.process(" c  ", WhiteSpace::Normal, 4), " c ");
+}
+
+#[test]
+fn test_pre() {
+    let mut processor = WhiteSpaceProcessor::default();
+
+    assert_eq!(processor.process("a\tb", WhiteSpace::Pre, 4), "a   b");
+    assert_eq!(processor.process("cd\t", WhiteSpace::Pre, 4), "cd ");
+    assert_eq!(processor.process("\n\tx", WhiteSpace::Pre, 4), "     x");
+}
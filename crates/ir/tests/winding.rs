@@ -0,0 +1,86 @@
+use vglang_ir::{geometry::Orientation, *};
+
+fn square(x: f32, y: f32, size: f32, clockwise: bool) -> Vec<PathEvent> {
+    let mut corners = vec![(x + size, y), (x + size, y + size), (x, y + size)];
+
+    if !clockwise {
+        corners.reverse();
+    }
+
+    let mut events = vec![PathEvent::MoveTo((x, y).into())];
+
+    events.extend(
+        corners
+            .into_iter()
+            .map(|corner| PathEvent::LineTo(corner.into())),
+    );
+
+    events.push(PathEvent::ClosePath);
+
+    events
+}
+
+fn path(events: Vec<PathEvent>) -> Path {
+    Path {
+        data: Animatable::Constant(events.into_iter().map(Animatable::Constant).collect()),
+        ..Default::default()
+    }
+}
+
+/// Returns the orientation of every sub-path.
+fn orientations(path: &Path, ctx: &ResolveContext) -> Vec<Option<Orientation>> {
+    ctx.path(path)
+        .unwrap()
+        .iter()
+        .map(|subpath| subpath.flatten(DEFAULT_TOLERANCE).orientation())
+        .collect()
+}
+
+#[test]
+fn test_reverse() {
+    let ctx = ResolveContext::default();
+
+    let clockwise = path(square(0.0, 0.0, 10.0, true));
+
+    assert_eq!(clockwise.orientation(&ctx), Some(Orientation::Clockwise));
+
+    let reversed = clockwise.reverse().unwrap();
+
+    assert_eq!(
+        reversed.orientation(&ctx),
+        Some(Orientation::CounterClockwise)
+    );
+
+    assert_eq!(reversed.reverse().unwrap(), clockwise);
+}
+
+#[test]
+fn test_ensure_winding() {
+    let ctx = ResolveContext::default();
+
+    // a donut with both contours going counterclockwise.
+    let mut events = square(0.0, 0.0, 10.0, false);
+    events.extend(square(2.0, 2.0, 6.0, false));
+
+    let donut = path(events);
+
+    assert_eq!(
+        orientations(
+            &donut.ensure_winding(&ctx, FillRule::EvenOdd).unwrap(),
+            &ctx
+        ),
+        [
+            Some(Orientation::Clockwise),
+            Some(Orientation::CounterClockwise)
+        ]
+    );
+
+    // with the nonzero rule, the inner contour is not a hole.
+    assert_eq!(
+        orientations(
+            &donut.ensure_winding(&ctx, FillRule::Nonzero).unwrap(),
+            &ctx
+        ),
+        [Some(Orientation::Clockwise), Some(Orientation::Clockwise)]
+    );
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "font")]
+
+use vglang_ir::*;
+
+mod common;
+
+use common::{boxes, test_fonts};
+
+fn ctx() -> ResolveContext {
+    ResolveContext {
+        font_size: 10.0,
+        ..Default::default()
+    }
+}
+
+fn break_lines(max_width: f32, text: &str) -> Vec<String> {
+    TextBlock::new(0.0, 0.0, max_width)
+        .break_lines(&test_fonts(), &Font::from("Test"), text, &ctx())
+        .unwrap()
+}
+
+#[test]
+fn test_break_at_width() {
+    // `a` advances 5 and the space 2.5, so two words of `aa` take 22.5.
+    assert_eq!(break_lines(25.0, "aa aa aa"), ["aa aa", "aa"]);
+    assert_eq!(break_lines(22.5, "aa aa aa"), ["aa aa", "aa"]);
+    assert_eq!(break_lines(22.0, "aa aa aa"), ["aa", "aa", "aa"]);
+    assert_eq!(break_lines(100.0, "aa   aa\taa"), ["aa aa aa"]);
+}
+
+#[test]
+fn test_break_newlines() {
+    assert_eq!(break_lines(100.0, "aa\naa aa"), ["aa", "aa aa"]);
+    assert_eq!(break_lines(100.0, "a\n\nb"), ["a", "", "b"]);
+    assert_eq!(break_lines(10.0, "aa aa\naa"), ["aa", "aa", "aa"]);
+}
+
+#[test]
+fn test_break_long_word() {
+    // `m` advances 7.5, the word is broken between characters and the line goes on after it.
+    assert_eq!(break_lines(20.0, "mmmmm aa"), ["mm", "mm", "m aa"]);
+    // the long word starts a new line first.
+    assert_eq!(break_lines(20.0, "a mmm"), ["a", "mm", "m"]);
+    // at least one character is set on every line.
+    assert_eq!(break_lines(5.0, "mm"), ["m", "m"]);
+}
+
+#[test]
+fn test_break_without_faces() {
+    let block = TextBlock::new(0.0, 0.0, 100.0);
+
+    assert_eq!(
+        block.break_lines(&FontDatabase::default(), &Font::from("Test"), "a", &ctx()),
+        None
+    );
+}
+
+fn span(x: f32, dy: f32) -> IR {
+    TextSpan {
+        x: Animatable::Constant(vec![Measurement::from(x)]),
+        dy: Animatable::Constant(vec![Measurement::from(dy)]),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[test]
+fn test_layout_line_height() {
+    let fonts = test_fonts();
+    let font = Font::from("Test");
+
+    let mut block = TextBlock::new(10.0, 20.0, 25.0);
+
+    // defaults to the line height of the font, the ascent 8 minus the descent -2.
+    let codes = block
+        .layout(&fonts, &font, &TextLayout::default(), "aa aa aa", &ctx())
+        .unwrap();
+
+    assert_eq!(
+        codes,
+        vec![
+            Text::from((vec![10.0], vec![20.0])).into(),
+            span(10.0, 0.0),
+            IR::String("aa aa".to_owned()),
+            IR::Pop(1),
+            span(10.0, 10.0),
+            IR::String("aa".to_owned()),
+            IR::Pop(1),
+            IR::Pop(1),
+        ]
+    );
+
+    block.line_height = Some(12.0);
+
+    let codes = block
+        .layout(&fonts, &font, &TextLayout::default(), "aa aa aa", &ctx())
+        .unwrap();
+
+    assert_eq!(codes[4], span(10.0, 12.0));
+}
+
+#[test]
+fn test_layout_alignment() {
+    let fonts = test_fonts();
+    let font = Font::from("Test");
+    let layout = TextLayout::from(TextAnchor::Middle);
+
+    let lines = TextBlock::new(10.0, 20.0, 25.0)
+        .layout(&fonts, &font, &layout, "aa aa aa", &ctx())
+        .unwrap();
+
+    let mut codes = vec![font.into(), layout.into()];
+    codes.extend(lines);
+    codes.push(IR::Pop(2));
+
+    let outlines = text_to_outlines(&codes, &ctx(), &fonts);
+
+    // every line is a text chunk centered at x = 10, the lines are 22.5 and 10 wide.
+    assert_eq!(
+        boxes(&outlines[0].subpaths),
+        vec![
+            (-0.75, 13.0, 3.25, 20.0),
+            (4.25, 13.0, 8.25, 20.0),
+            (11.75, 13.0, 15.75, 20.0),
+            (16.75, 13.0, 20.75, 20.0),
+            (5.5, 23.0, 9.5, 30.0),
+            (10.5, 23.0, 14.5, 30.0),
+        ]
+    );
+}
@@ -1,10 +1,17 @@
 use std::slice::Iter;
 
 use futures::future::BoxFuture;
-pub use vglang_device::{Device, VGLProgram};
+pub use vglang_device::{
+    CancelToken, Device, IRStream, Interrupt, Interrupted, StreamingDevice, VGLProgram,
+};
 use vglang_ir::{
-    Animatable, Fill, Font, FontStyle, FontVariant, FrameVariable, Layer, PreserveAspectRatio,
-    Rect, Stroke, Text, TextLayout, TextSpan, IR,
+    Animatable, Fe, FeComposite, FeCompositeOperator, FeFlood, FeGaussianBlur, FeIn, FeMerge,
+    FeMergeItem, FeOffset, FeOut, FePrimitive, FeUnits, Fill, FilterRef, Font, FontStyle,
+    FontVariant, FrameVariable, Image, ImageSource, Label, Layer, Marker, MarkerOrient,
+    MarkerUnits, Markers, Mask, MaskRef, MaskType, PaintOrder, Path, PathEvent, Point,
+    PreserveAspectRatio, Rect, Rgba, Stroke, StrokeLineCap, StrokeLineJoin, SymbolDef, Text,
+    TextLayout, TextSpan, Transform, Units, Use, ViewBox, WhiteSpace, WhiteSpaceProcessor,
+    DEFAULT_TAB_SIZE, IR,
 };
 use xml_dom::level2::{
     ext::{DocumentDecl, XmlDecl},
@@ -22,11 +29,177 @@ pub enum Error {
 
     #[error("Animated variable `{0}` not found.")]
     AnimatedNotFound(String),
+
+    #[error("Cancelled after {processed} of {total} instructions.")]
+    Cancelled { processed: usize, total: usize },
+
+    #[error("Timed out after {processed} of {total} instructions.")]
+    Timeout { processed: usize, total: usize },
+}
+
+/// The system color emoji fonts of the major platforms.
+const EMOJI_FONT_FAMILY: &str = "Apple Color Emoji, Segoe UI Emoji, Noto Color Emoji";
+
+/// Returns true if `c` is presented as an emoji when followed by `next`.
+///
+/// The characters with the default emoji presentation, and the characters followed by the emoji variation
+/// selector(U+FE0F) or a keycap are emoji; the text variation selector(U+FE0E) forces the text presentation.
+/// Other symbols(e.g. `★`, `✓` and `➔`) are left to the text fonts.
+fn is_emoji(c: char, next: Option<char>) -> bool {
+    match next {
+        Some('\u{FE0E}') => return false,
+        Some('\u{FE0F}') | Some('\u{20E3}') => return true,
+        _ => {}
+    }
+
+    matches!(
+        c,
+        // pictographs, emoticons, transport and map symbols, flags, skin tones.
+        '\u{1F000}'..='\u{1FAFF}'
+            // the symbols and dingbats with the default emoji presentation.
+            | '\u{231A}'..='\u{231B}'
+            | '\u{23E9}'..='\u{23EC}'
+            | '\u{23F0}'
+            | '\u{23F3}'
+            | '\u{25FD}'..='\u{25FE}'
+            | '\u{2614}'..='\u{2615}'
+            | '\u{2648}'..='\u{2653}'
+            | '\u{267F}'
+            | '\u{2693}'
+            | '\u{26A1}'
+            | '\u{26AA}'..='\u{26AB}'
+            | '\u{26BD}'..='\u{26BE}'
+            | '\u{26C4}'..='\u{26C5}'
+            | '\u{26CE}'
+            | '\u{26D4}'
+            | '\u{26EA}'
+            | '\u{26F2}'..='\u{26F3}'
+            | '\u{26F5}'
+            | '\u{26FA}'
+            | '\u{26FD}'
+            | '\u{2705}'
+            | '\u{270A}'..='\u{270B}'
+            | '\u{2728}'
+            | '\u{274C}'
+            | '\u{274E}'
+            | '\u{2753}'..='\u{2755}'
+            | '\u{2757}'
+            | '\u{2795}'..='\u{2797}'
+            | '\u{27B0}'
+            | '\u{27BF}'
+            | '\u{2B1B}'..='\u{2B1C}'
+            | '\u{2B50}'
+            | '\u{2B55}'
+            // zero width joiner, keycap, variation selector-16 and tag characters.
+            | '\u{200D}'
+            | '\u{20E3}'
+            | '\u{FE0F}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Returns the svg keyword of `units`.
+fn units(units: &Units) -> &'static str {
+    match units {
+        Units::UserSpaceOnUse => "userSpaceOnUse",
+        Units::ObjectBoundingBox => "objectBoundingBox",
+    }
+}
+
+/// Returns the svg keyword of the filter `units`.
+fn fe_units(units: &FeUnits) -> &'static str {
+    match units {
+        FeUnits::UserSpaceOnUse => "userSpaceOnUse",
+        FeUnits::ObjectBoundingBox => "objectBoundingBox",
+    }
+}
+
+/// Returns the `in` attribute value of a filter primitive input.
+fn fe_in(value: &FeIn) -> &str {
+    match value {
+        FeIn::SourceGraphic => "SourceGraphic",
+        FeIn::SourceAlpha => "SourceAlpha",
+        FeIn::BackgroundImage => "BackgroundImage",
+        FeIn::BackgroundAlpha => "BackgroundAlpha",
+        FeIn::FillPaint => "FillPaint",
+        FeIn::StrokePaint => "StrokePaint",
+        FeIn::Register(name) => name,
+    }
+}
+
+/// Returns the svg `rgb()` syntax of `rgba`, the alpha component is written as a separated opacity property.
+fn rgb(rgba: &Rgba) -> String {
+    format!(
+        "rgb({},{},{})",
+        (rgba.0 * 255.0) as u8,
+        (rgba.1 * 255.0) as u8,
+        (rgba.2 * 255.0) as u8
+    )
+}
+
+/// Returns the svg syntax of `aspect`.
+fn aspect_ratio(aspect: &PreserveAspectRatio) -> String {
+    match aspect {
+        PreserveAspectRatio::xMinYMin(v) => format!("xMinYMin {}", v),
+        PreserveAspectRatio::xMidYMin(v) => format!("xMidYMin {}", v),
+        PreserveAspectRatio::xMaxYMin(v) => format!("xMaxYMin {}", v),
+        PreserveAspectRatio::xMinYMid(v) => format!("xMinYMid {}", v),
+        PreserveAspectRatio::xMidYMid(v) => format!("xMidYMid {}", v),
+        PreserveAspectRatio::xMaxYMid(v) => format!("xMaxYMid {}", v),
+        PreserveAspectRatio::xMinYMax(v) => format!("xMinYMax {}", v),
+        PreserveAspectRatio::xMidYMax(v) => format!("xMidYMax {}", v),
+        PreserveAspectRatio::xMaxYMax(v) => format!("xMaxYMax {}", v),
+    }
+}
+
+/// Encodes `data` as base64 with padding, see [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-4).
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - index * 6)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+impl Error {
+    fn interrupted(interrupted: Interrupted, processed: usize, total: usize) -> Self {
+        match interrupted {
+            Interrupted::Cancelled => Error::Cancelled { processed, total },
+            Interrupted::Timeout => Error::Timeout { processed, total },
+        }
+    }
 }
 
 /// A svg rendering target implementation.
 #[derive(Default)]
-pub struct SvgDevice {}
+pub struct SvgDevice {
+    interrupt: Interrupt,
+}
+
+impl SvgDevice {
+    /// Set the cancellation token and the deadline checked by the compiled programs before each instruction.
+    pub fn interrupt(mut self, interrupt: Interrupt) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+}
 
 impl Device for SvgDevice {
     type Program = SvgGenerator;
@@ -39,13 +212,68 @@ impl Device for SvgDevice {
         Self: 'a;
 
     fn compile(&self, codes: Vec<vglang_ir::IR>) -> Self::Compile<'_> {
-        Box::pin(async move { Ok(SvgGenerator(codes)) })
+        Box::pin(async move {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(codes = codes.len(), "svg compile");
+
+            self.interrupt
+                .begin()
+                .check()
+                .map_err(|interrupted| Error::interrupted(interrupted, 0, codes.len()))?;
+
+            Ok(SvgGenerator {
+                codes,
+                interrupt: self.interrupt.clone(),
+            })
+        })
+    }
+}
+
+impl StreamingDevice for SvgDevice {
+    type Stream = SvgStream;
+
+    fn stream(&self) -> Self::Stream {
+        SvgStream {
+            codes: vec![],
+            interrupt: self.interrupt.clone(),
+        }
+    }
+}
+
+/// `IRStream` implementation for svg generator.
+///
+/// This stream gives no memory benefit over [`Device::compile`]: the svg document is regenerated with the
+/// registers of each execution, so every pushed ir code is buffered until [`finish`](IRStream::finish) and kept
+/// by the compiled program, constant operands included.
+pub struct SvgStream {
+    codes: Vec<IR>,
+    interrupt: Interrupt,
+}
+
+impl IRStream for SvgStream {
+    type Program = SvgGenerator;
+
+    type Error = Error;
+
+    fn push(&mut self, ir: IR) -> Result<(), Error> {
+        self.codes.push(ir);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<SvgGenerator, Error> {
+        Ok(SvgGenerator {
+            codes: self.codes,
+            interrupt: self.interrupt,
+        })
     }
 }
 
 /// `VGLProgram` implementation for svg generator.
-#[allow(unused)]
-pub struct SvgGenerator(Vec<IR>);
+pub struct SvgGenerator {
+    codes: Vec<IR>,
+    interrupt: Interrupt,
+}
 
 impl VGLProgram for SvgGenerator {
     type Output = String;
@@ -61,21 +289,45 @@ impl VGLProgram for SvgGenerator {
         &'a self,
         animatable: &'a std::collections::HashMap<String, vglang_ir::AnimatableValue>,
     ) -> Self::Execute<'a> {
-        Box::pin(async move { SvgGenerating::new(self.0.iter(), animatable)?.generate() })
+        Box::pin(async move {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!(
+                "svg execute",
+                codes = self.codes.len(),
+                registers = animatable.len()
+            )
+            .entered();
+
+            let svg = SvgGenerating::new(self.codes.iter(), animatable, self.interrupt.begin())?
+                .generate()?;
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(len = svg.len(), "svg generated");
+
+            Ok(svg)
+        })
     }
 }
 
 struct SvgGenerating<'a> {
     codes: Iter<'a, IR>,
+    /// The number of ir codes, to report the progress when interrupted.
+    total: usize,
+    interrupt: Interrupt,
     animatable: &'a std::collections::HashMap<String, vglang_ir::AnimatableValue>,
     document: RefNode,
     els: Vec<RefNode>,
+    /// The `defs` element of the root viewport, created by the first referenced element.
+    defs: Option<RefNode>,
+    /// The white space modes and tab sizes set by the open elements, with the depth of the element.
+    white_space: Vec<(usize, WhiteSpace, u32)>,
 }
 
 impl<'a> SvgGenerating<'a> {
     fn new(
         codes: Iter<'a, IR>,
         animatable: &'a std::collections::HashMap<String, vglang_ir::AnimatableValue>,
+        interrupt: Interrupt,
     ) -> Result<Self, Error> {
         // let doc_type = get_implementation().create_document_type(
         //     "svg",
@@ -101,7 +353,11 @@ impl<'a> SvgGenerating<'a> {
         Ok(Self {
             document,
             els: vec![root_element],
+            defs: None,
+            white_space: vec![],
+            total: codes.len(),
             codes,
+            interrupt,
             animatable,
         })
     }
@@ -125,7 +381,16 @@ impl<'a> SvgGenerating<'a> {
         self.els.last_mut().unwrap()
     }
 
+    /// Checks the interrupt before the next instruction.
+    fn check_interrupt(&self) -> Result<(), Error> {
+        self.interrupt.check().map_err(|interrupted| {
+            Error::interrupted(interrupted, self.total - self.codes.len(), self.total)
+        })
+    }
+
     fn generate_root_viewport(&mut self) -> Result<(), Error> {
+        self.check_interrupt()?;
+
         match self.codes.next() {
             Some(IR::Layer(layer)) => {
                 self.process_layer(layer, true)?;
@@ -137,14 +402,22 @@ impl<'a> SvgGenerating<'a> {
     }
 
     fn process_next(&mut self) -> Result<Option<usize>, Error> {
+        self.check_interrupt()?;
+
         if let Some(ir) = self.codes.next() {
             match ir {
                 IR::Text(text) => {
                     return self.process_text(text).map(Some);
                 }
                 IR::String(literal) => {
-                    let text_node = self.document.create_text_node(&literal);
-                    self.current_element_mut().append_child(text_node)?;
+                    // the renderers don't agree on `tab-size`, the tabs are expanded here.
+                    let literal = match self.white_space.last() {
+                        Some((_, WhiteSpace::Pre, tab_size)) => WhiteSpaceProcessor::default()
+                            .process(literal, WhiteSpace::Pre, *tab_size),
+                        _ => literal.clone(),
+                    };
+
+                    self.process_string(&literal)?;
                     return Ok(Some(0));
                 }
                 IR::Pop(n) => {
@@ -153,7 +426,9 @@ impl<'a> SvgGenerating<'a> {
                 IR::Stroke(stroke) => {
                     return self.process_stroke(stroke).map(Some);
                 }
+                IR::Layer(layer) => return self.process_layer(layer, false).map(Some),
                 IR::Rect(rect) => return self.process_rect(rect).map(Some),
+                IR::Path(path) => return self.process_path(path).map(Some),
                 IR::Fill(fill) => {
                     return self.process_fill(fill).map(Some);
                 }
@@ -166,31 +441,154 @@ impl<'a> SvgGenerating<'a> {
                 IR::TextSpan(value) => {
                     return self.process_text_span(value).map(Some);
                 }
-                _ => todo!(),
+                IR::Label(value) => {
+                    return self.process_label(value).map(Some);
+                }
+                IR::Transform(value) => {
+                    return self.process_transform(value).map(Some);
+                }
+                IR::Mask(value) => {
+                    return self.process_mask(value).map(Some);
+                }
+                IR::MaskRef(value) => {
+                    return self.process_mask_ref(value).map(Some);
+                }
+                IR::Marker(value) => {
+                    return self.process_marker(value).map(Some);
+                }
+                IR::Markers(value) => {
+                    return self.process_markers(value).map(Some);
+                }
+                IR::Symbol(value) => {
+                    return self.process_symbol(value).map(Some);
+                }
+                IR::Use(value) => return self.process_use(value).map(Some),
+                IR::Image(value) => return self.process_image(value).map(Some),
+                IR::Filter(value) => {
+                    return self.process_filter(value).map(Some);
+                }
+                IR::FilterRef(value) => {
+                    return self.process_filter_ref(value).map(Some);
+                }
+                IR::FeGaussianBlur(value) => {
+                    return self.process_fe_gaussian_blur(value).map(Some);
+                }
+                IR::FeOffset(value) => return self.process_fe_offset(value).map(Some),
+                IR::FeFlood(value) => return self.process_fe_flood(value).map(Some),
+                IR::FeComposite(value) => {
+                    return self.process_fe_composite(value).map(Some);
+                }
+                IR::FeMerge(value) => return self.process_fe_merge(value).map(Some),
+                IR::FeMergeItem(value) => {
+                    return self.process_fe_merge_item(value).map(Some);
+                }
+                IR::Animated(name) => {
+                    // text content bound to a register.
+                    return match self.animatable.get(name) {
+                        Some(value) => match *value {},
+                        None => Err(Error::AnimatedNotFound(name.clone())),
+                    };
+                }
             }
         }
 
         Ok(None)
     }
 
-    fn process_child(&mut self, is_root: bool) -> Result<usize, Error> {
-        let mut pop_n = 0;
+    /// Appends the text `literal`, the emoji are wrapped in `tspan`s that fall back to the system emoji fonts,
+    /// the text fonts usually have no glyph for them.
+    fn process_string(&mut self, literal: &str) -> Result<(), Error> {
+        let chars = literal.char_indices().collect::<Vec<_>>();
 
-        while let Some(n) = self.process_next()? {
-            if n > 0 {
-                pop_n = n - 1;
-                break;
+        let emoji_at = |i: usize| is_emoji(chars[i].1, chars.get(i + 1).map(|(_, next)| *next));
+
+        let mut start = 0;
+
+        while start < chars.len() {
+            let emoji = emoji_at(start);
+
+            let end = (start..chars.len())
+                .find(|i| emoji_at(*i) != emoji)
+                .unwrap_or(chars.len());
+
+            let text = &literal[chars[start].0..chars.get(end).map_or(literal.len(), |(i, _)| *i)];
+
+            let text_node = self.document.create_text_node(text);
+
+            if emoji {
+                let mut el = self.document.create_element("tspan")?;
+                el.set_attribute("font-family", EMOJI_FONT_FAMILY)?;
+                el.append_child(text_node)?;
+                self.current_element_mut().append_child(el)?;
+            } else {
+                self.current_element_mut().append_child(text_node)?;
             }
+
+            start = end;
         }
 
+        Ok(())
+    }
+
+    fn process_child(&mut self, is_root: bool) -> Result<usize, Error> {
+        let pop_n = self.process_content()?;
+
         if !is_root {
-            let el = self.els.pop().unwrap();
+            let el = self.pop_element();
             self.current_element_mut().append_child(el)?;
         }
 
         Ok(pop_n)
     }
 
+    /// Processes the children of the current element, like [`process_child`](Self::process_child),
+    /// but moves the element into the `defs` of the root viewport.
+    fn process_def(&mut self) -> Result<usize, Error> {
+        let pop_n = self.process_content()?;
+
+        let el = self.pop_element();
+        self.defs()?.append_child(el)?;
+
+        Ok(pop_n)
+    }
+
+    /// Processes the instructions until the current element is popped, returns the number of the remaining pops.
+    fn process_content(&mut self) -> Result<usize, Error> {
+        while let Some(n) = self.process_next()? {
+            if n > 0 {
+                return Ok(n - 1);
+            }
+        }
+
+        Ok(0)
+    }
+
+    fn pop_element(&mut self) -> RefNode {
+        let el = self.els.pop().unwrap();
+
+        let depth = self.els.len();
+        self.white_space.retain(|(level, _, _)| *level <= depth);
+
+        el
+    }
+
+    /// Returns the `defs` element, which is inserted as the first child of the root viewport.
+    fn defs(&mut self) -> Result<RefNode, Error> {
+        if let Some(defs) = &self.defs {
+            return Ok(defs.clone());
+        }
+
+        let defs = self.document.create_element("defs")?;
+
+        let root = &mut self.els[0];
+        let first = root.first_child();
+        root.insert_before(defs.clone(), first)?;
+
+        self.defs = Some(defs.clone());
+
+        Ok(defs)
+    }
+
     fn process_rect(&mut self, rect: &Rect) -> Result<usize, Error> {
         let mut node = self.document.create_element("rect")?;
 
@@ -213,6 +611,70 @@ impl<'a> SvgGenerating<'a> {
         return Ok(0);
     }
 
+    fn process_path(&mut self, path: &Path) -> Result<usize, Error> {
+        let mut node = self.document.create_element("path")?;
+
+        let mut data = vec![];
+
+        for event in self.get_value(&path.data)? {
+            // the coordinates of path data are numbers in user units.
+            let point = |point: &Point| format!("{} {}", point.x.0, point.y.0);
+
+            let command = match self.get_value(event)? {
+                PathEvent::MoveTo(to) => format!("M {}", point(to)),
+                PathEvent::ClosePath => "Z".to_string(),
+                PathEvent::LineTo(to) => format!("L {}", point(to)),
+                PathEvent::Polyline(points) => {
+                    if points.is_empty() {
+                        continue;
+                    }
+
+                    format!(
+                        "L {}",
+                        points.iter().map(point).collect::<Vec<_>>().join(" ")
+                    )
+                }
+                PathEvent::CubicBezier { ctrl1, ctrl2, to } => {
+                    format!("C {} {} {}", point(ctrl1), point(ctrl2), point(to))
+                }
+                PathEvent::QuadraticBezier { ctrl, to } => {
+                    format!("Q {} {}", point(ctrl), point(to))
+                }
+                PathEvent::Arc {
+                    rx,
+                    ry,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => format!(
+                    "A {} {} {} {} {} {}",
+                    rx.0,
+                    ry.0,
+                    x_rotation.as_deg(),
+                    *large_arc as u8,
+                    *sweep as u8,
+                    point(to)
+                ),
+            };
+
+            data.push(command);
+        }
+
+        node.set_attribute("d", data.join(" ").as_str())?;
+
+        let length = self.get_value(&path.length)?;
+
+        // zero is the default value, which means the attribute is not specified.
+        if length.0 != 0.0 {
+            node.set_attribute("pathLength", length.0.to_string().as_str())?;
+        }
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
     fn process_layer(&mut self, layer: &Layer, is_root: bool) -> Result<usize, Error> {
         let mut el = if is_root {
             self.current_element_mut().clone()
@@ -229,55 +691,7 @@ impl<'a> SvgGenerating<'a> {
         el.set_attribute("height", height.as_str())?;
 
         if let Some(viewbox) = &layer.viewbox {
-            let viewbox = self.get_value(viewbox)?;
-
-            el.set_attribute(
-                "viewBox",
-                format!(
-                    "{} {} {} {}",
-                    self.get_value(&viewbox.minx)?,
-                    self.get_value(&viewbox.miny)?,
-                    self.get_value(&viewbox.width)?,
-                    self.get_value(&viewbox.height)?
-                )
-                .as_str(),
-            )?;
-
-            if let Some(aspect) = &viewbox.aspect {
-                let v = match self.get_value(aspect)? {
-                    PreserveAspectRatio::xMinYMin(meet_or_slice) => {
-                        format!("xMinYMin {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMidYMin(meet_or_slice) => {
-                        format!("xMidYMin {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMaxYMin(meet_or_slice) => {
-                        format!("xMaxYMin {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMinYMid(meet_or_slice) => {
-                        format!("xMinYMid {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMidYMid(meet_or_slice) => {
-                        format!("xMidYMid {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMaxYMid(meet_or_slice) => {
-                        format!("xMaxYMid {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMinYMax(meet_or_slice) => {
-                        format!("xMinYMax {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMidYMax(meet_or_slice) => {
-                        format!("xMidYMax {}", meet_or_slice)
-                    }
-                    PreserveAspectRatio::xMaxYMax(meet_or_slice) => {
-                        format!("xMaxYMax {}", meet_or_slice)
-                    }
-                };
-
-                el.set_attribute("preserveAspectRatio", v.as_str())?;
-            } else {
-                el.set_attribute("preserveAspectRatio", "none")?;
-            }
+            self.process_viewbox(&mut el, viewbox)?;
         }
 
         if !is_root {
@@ -287,6 +701,37 @@ impl<'a> SvgGenerating<'a> {
         self.process_child(is_root)
     }
 
+    fn process_viewbox(
+        &self,
+        el: &mut RefNode,
+        viewbox: &Animatable<ViewBox>,
+    ) -> Result<(), Error> {
+        let viewbox = self.get_value(viewbox)?;
+
+        el.set_attribute(
+            "viewBox",
+            format!(
+                "{} {} {} {}",
+                self.get_value(&viewbox.minx)?,
+                self.get_value(&viewbox.miny)?,
+                self.get_value(&viewbox.width)?,
+                self.get_value(&viewbox.height)?
+            )
+            .as_str(),
+        )?;
+
+        if let Some(aspect) = &viewbox.aspect {
+            el.set_attribute(
+                "preserveAspectRatio",
+                aspect_ratio(self.get_value(aspect)?).as_str(),
+            )?;
+        } else {
+            el.set_attribute("preserveAspectRatio", "none")?;
+        }
+
+        Ok(())
+    }
+
     fn process_stroke(&mut self, stroke: &Stroke) -> Result<usize, Error> {
         let mut el = self.document.create_element("g")?;
 
@@ -300,16 +745,13 @@ impl<'a> SvgGenerating<'a> {
     fn process_stroke_inner(&self, el: &mut RefNode, value: &Stroke) -> Result<(), Error> {
         if let Some(paint) = &value.paint {
             match self.get_value(paint)? {
-                vglang_ir::Paint::Color(rgba) => el.set_attribute(
-                    "stroke",
-                    format!(
-                        "rgb({},{},{})",
-                        (rgba.0 * 255.0) as u8,
-                        (rgba.1 * 255.0) as u8,
-                        (rgba.2 * 255.0) as u8
-                    )
-                    .as_str(),
-                )?,
+                vglang_ir::Paint::Color(rgba) => {
+                    el.set_attribute("stroke", rgb(rgba).as_str())?;
+
+                    if rgba.3 < 1.0 {
+                        el.set_attribute("stroke-opacity", rgba.3.to_string().as_str())?;
+                    }
+                }
                 vglang_ir::Paint::Gradient(uri) => {
                     el.set_attribute("stroke", format!("url(#{})", uri).as_str())?
                 }
@@ -323,6 +765,38 @@ impl<'a> SvgGenerating<'a> {
             el.set_attribute("stroke-width", self.get_value(value)?.to_string().as_str())?
         }
 
+        if let Some(value) = &value.linecap {
+            let linecap = match self.get_value(value)? {
+                StrokeLineCap::Butt => "butt",
+                StrokeLineCap::Round => "round",
+                StrokeLineCap::Square => "square",
+            };
+
+            el.set_attribute("stroke-linecap", linecap)?;
+        }
+
+        if let Some(value) = &value.linejoin {
+            let linejoin = match self.get_value(value)? {
+                StrokeLineJoin::Miter(limit) => {
+                    el.set_attribute("stroke-miterlimit", limit.0.to_string().as_str())?;
+                    "miter"
+                }
+                StrokeLineJoin::Round => "round",
+                StrokeLineJoin::Bevel => "bevel",
+            };
+
+            el.set_attribute("stroke-linejoin", linejoin)?;
+        }
+
+        if let Some(value) = &value.paint_order {
+            let paint_order = match self.get_value(value)? {
+                PaintOrder::FillStroke => "normal",
+                PaintOrder::StrokeFill => "stroke",
+            };
+
+            el.set_attribute("paint-order", paint_order)?;
+        }
+
         Ok(())
     }
 
@@ -339,16 +813,13 @@ impl<'a> SvgGenerating<'a> {
     fn process_fill_inner(&self, el: &mut RefNode, value: &Fill) -> Result<(), Error> {
         if let Some(paint) = &value.paint {
             match self.get_value(paint)? {
-                vglang_ir::Paint::Color(rgba) => el.set_attribute(
-                    "fill",
-                    format!(
-                        "rgb({},{},{})",
-                        (rgba.0 * 255.0) as u8,
-                        (rgba.1 * 255.0) as u8,
-                        (rgba.2 * 255.0) as u8
-                    )
-                    .as_str(),
-                )?,
+                vglang_ir::Paint::Color(rgba) => {
+                    el.set_attribute("fill", rgb(rgba).as_str())?;
+
+                    if rgba.3 < 1.0 {
+                        el.set_attribute("fill-opacity", rgba.3.to_string().as_str())?;
+                    }
+                }
                 vglang_ir::Paint::Gradient(uri) => {
                     el.set_attribute("fill", format!("url(#{})", uri).as_str())?
                 }
@@ -362,6 +833,394 @@ impl<'a> SvgGenerating<'a> {
 
         Ok(())
     }
+    fn process_label(&mut self, value: &Label) -> Result<usize, Error> {
+        let mut el = self.document.create_element("g")?;
+
+        if let Some(id) = &value.id {
+            el.set_attribute("id", id)?;
+        }
+
+        if !value.class.is_empty() {
+            el.set_attribute("class", value.class.join(" ").as_str())?;
+        }
+
+        self.els.push(el);
+
+        self.process_child(false)
+    }
+
+    fn process_transform(&mut self, value: &Animatable<Transform>) -> Result<usize, Error> {
+        let mut el = self.document.create_element("g")?;
+
+        el.set_attribute("transform", self.get_value(value)?.to_string().as_str())?;
+
+        self.els.push(el);
+
+        self.process_child(false)
+    }
+
+    fn process_mask(&mut self, value: &Mask) -> Result<usize, Error> {
+        let mut el = self.document.create_element("mask")?;
+
+        el.set_attribute("id", &value.id)?;
+
+        let mode = match self.get_value(&value.mode)? {
+            MaskType::Luminance => "luminance",
+            MaskType::Alpha => "alpha",
+        };
+
+        el.set_attribute("mask-type", mode)?;
+
+        el.set_attribute("maskUnits", units(self.get_value(&value.units)?))?;
+
+        el.set_attribute(
+            "maskContentUnits",
+            units(self.get_value(&value.content_units)?),
+        )?;
+
+        el.set_attribute("x", self.get_value(&value.x)?.to_string().as_str())?;
+
+        el.set_attribute("y", self.get_value(&value.y)?.to_string().as_str())?;
+
+        el.set_attribute("width", self.get_value(&value.width)?.to_string().as_str())?;
+
+        el.set_attribute(
+            "height",
+            self.get_value(&value.height)?.to_string().as_str(),
+        )?;
+
+        self.els.push(el);
+
+        self.process_def()
+    }
+
+    fn process_mask_ref(&mut self, value: &MaskRef) -> Result<usize, Error> {
+        let mut el = self.document.create_element("g")?;
+
+        el.set_attribute("mask", format!("url(#{})", value.0).as_str())?;
+
+        self.els.push(el);
+
+        self.process_child(false)
+    }
+
+    fn process_marker(&mut self, value: &Marker) -> Result<usize, Error> {
+        let mut el = self.document.create_element("marker")?;
+
+        el.set_attribute("id", &value.id)?;
+
+        let units = match self.get_value(&value.unit)? {
+            MarkerUnits::StrokeWidth => "strokeWidth",
+            MarkerUnits::UserSpaceOnUse => "userSpaceOnUse",
+        };
+
+        el.set_attribute("markerUnits", units)?;
+
+        el.set_attribute("refX", self.get_value(&value.refx)?.to_string().as_str())?;
+
+        el.set_attribute("refY", self.get_value(&value.refy)?.to_string().as_str())?;
+
+        el.set_attribute(
+            "markerWidth",
+            self.get_value(&value.width)?.to_string().as_str(),
+        )?;
+
+        el.set_attribute(
+            "markerHeight",
+            self.get_value(&value.height)?.to_string().as_str(),
+        )?;
+
+        if let Some(orient) = &value.orient {
+            let orient = match self.get_value(orient)? {
+                MarkerOrient::Auto => "auto".to_string(),
+                MarkerOrient::AutoStartReverse => "auto-start-reverse".to_string(),
+                MarkerOrient::Angle(angle) => angle.to_string(),
+            };
+
+            el.set_attribute("orient", orient.as_str())?;
+        }
+
+        if let Some(viewbox) = &value.viewbox {
+            self.process_viewbox(&mut el, viewbox)?;
+        }
+
+        self.els.push(el);
+
+        self.process_def()
+    }
+
+    fn process_markers(&mut self, value: &Markers) -> Result<usize, Error> {
+        let mut el = self.document.create_element("g")?;
+
+        for (name, id) in [
+            ("marker-start", &value.start),
+            ("marker-mid", &value.mid),
+            ("marker-end", &value.end),
+        ] {
+            if let Some(id) = id {
+                el.set_attribute(name, format!("url(#{})", id).as_str())?;
+            }
+        }
+
+        self.els.push(el);
+
+        self.process_child(false)
+    }
+
+    fn process_symbol(&mut self, value: &SymbolDef) -> Result<usize, Error> {
+        let mut el = self.document.create_element("symbol")?;
+
+        el.set_attribute("id", &value.id)?;
+
+        if let Some(viewbox) = &value.viewbox {
+            self.process_viewbox(&mut el, viewbox)?;
+        }
+
+        self.els.push(el);
+
+        self.process_def()
+    }
+
+    fn process_use(&mut self, value: &Use) -> Result<usize, Error> {
+        let mut node = self.document.create_element("use")?;
+
+        node.set_attribute("href", format!("#{}", value.href).as_str())?;
+
+        node.set_attribute("x", self.get_value(&value.x)?.to_string().as_str())?;
+
+        node.set_attribute("y", self.get_value(&value.y)?.to_string().as_str())?;
+
+        if let Some(width) = &value.width {
+            node.set_attribute("width", self.get_value(width)?.to_string().as_str())?;
+        }
+
+        if let Some(height) = &value.height {
+            node.set_attribute("height", self.get_value(height)?.to_string().as_str())?;
+        }
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
+    fn process_image(&mut self, value: &Image) -> Result<usize, Error> {
+        let mut node = self.document.create_element("image")?;
+
+        let href = match &value.source {
+            ImageSource::Href(href) => href.0.clone(),
+            ImageSource::Data { mime, data } => format!("data:{};base64,{}", mime, base64(data)),
+        };
+
+        node.set_attribute("href", href.as_str())?;
+
+        node.set_attribute("x", self.get_value(&value.x)?.to_string().as_str())?;
+
+        node.set_attribute("y", self.get_value(&value.y)?.to_string().as_str())?;
+
+        node.set_attribute("width", self.get_value(&value.width)?.to_string().as_str())?;
+
+        node.set_attribute(
+            "height",
+            self.get_value(&value.height)?.to_string().as_str(),
+        )?;
+
+        if let Some(aspect) = &value.aspect {
+            node.set_attribute(
+                "preserveAspectRatio",
+                aspect_ratio(self.get_value(aspect)?).as_str(),
+            )?;
+        }
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
+    fn process_filter(&mut self, value: &Fe) -> Result<usize, Error> {
+        let mut el = self.document.create_element("filter")?;
+
+        el.set_attribute("id", &value.id)?;
+
+        el.set_attribute("filterUnits", fe_units(self.get_value(&value.units)?))?;
+
+        el.set_attribute(
+            "primitiveUnits",
+            fe_units(self.get_value(&value.primitive_units)?),
+        )?;
+
+        el.set_attribute("x", self.get_value(&value.x)?.to_string().as_str())?;
+
+        el.set_attribute("y", self.get_value(&value.y)?.to_string().as_str())?;
+
+        el.set_attribute("width", self.get_value(&value.width)?.to_string().as_str())?;
+
+        el.set_attribute(
+            "height",
+            self.get_value(&value.height)?.to_string().as_str(),
+        )?;
+
+        if let Some(res) = &value.filter_res {
+            let res = self.get_value(res)?;
+
+            let res = match res.y {
+                Some(y) => format!("{} {}", res.x, y),
+                None => res.x.to_string(),
+            };
+
+            el.set_attribute("filterRes", res.as_str())?;
+        }
+
+        self.els.push(el);
+
+        self.process_def()
+    }
+
+    fn process_filter_ref(&mut self, value: &FilterRef) -> Result<usize, Error> {
+        let mut el = self.document.create_element("g")?;
+
+        el.set_attribute("filter", format!("url(#{})", value.0).as_str())?;
+
+        self.els.push(el);
+
+        self.process_child(false)
+    }
+
+    /// Sets the subregion attributes that differ from the defaults, and the `result` attribute.
+    fn process_fe_primitive(&self, el: &mut RefNode, value: &FePrimitive) -> Result<(), Error> {
+        let default = FePrimitive::default();
+
+        for (name, attr, default) in [
+            ("x", &value.x, &default.x),
+            ("y", &value.y, &default.y),
+            ("width", &value.width, &default.width),
+            ("height", &value.height, &default.height),
+        ] {
+            if attr != default {
+                el.set_attribute(name, self.get_value(attr)?.to_string().as_str())?;
+            }
+        }
+
+        if let FeOut::Named(name) = self.get_value(&value.out)? {
+            el.set_attribute("result", name)?;
+        }
+
+        Ok(())
+    }
+
+    fn process_fe_gaussian_blur(&mut self, value: &FeGaussianBlur) -> Result<usize, Error> {
+        let mut node = self.document.create_element("feGaussianBlur")?;
+
+        self.process_fe_primitive(&mut node, &value.primitive)?;
+
+        node.set_attribute("in", fe_in(self.get_value(&value.r#in)?))?;
+
+        let std_deviation = self.get_value(&value.std_deviation)?;
+
+        let std_deviation = match std_deviation.dy {
+            Some(dy) => format!("{} {}", std_deviation.dx, dy),
+            None => std_deviation.dx.to_string(),
+        };
+
+        node.set_attribute("stdDeviation", std_deviation.as_str())?;
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
+    fn process_fe_offset(&mut self, value: &FeOffset) -> Result<usize, Error> {
+        let mut node = self.document.create_element("feOffset")?;
+
+        self.process_fe_primitive(&mut node, &value.primitive)?;
+
+        node.set_attribute("in", fe_in(self.get_value(&value.r#in)?))?;
+
+        node.set_attribute("dx", self.get_value(&value.dx)?.to_string().as_str())?;
+
+        node.set_attribute("dy", self.get_value(&value.dy)?.to_string().as_str())?;
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
+    fn process_fe_flood(&mut self, value: &FeFlood) -> Result<usize, Error> {
+        let mut node = self.document.create_element("feFlood")?;
+
+        self.process_fe_primitive(&mut node, &value.primitive)?;
+
+        let color = self.get_value(&value.color)?;
+
+        node.set_attribute("flood-color", rgb(color).as_str())?;
+
+        if color.3 < 1.0 {
+            node.set_attribute("flood-opacity", color.3.to_string().as_str())?;
+        }
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
+    fn process_fe_composite(&mut self, value: &FeComposite) -> Result<usize, Error> {
+        let mut node = self.document.create_element("feComposite")?;
+
+        self.process_fe_primitive(&mut node, &value.primitive)?;
+
+        node.set_attribute("in", fe_in(self.get_value(&value.a)?))?;
+
+        node.set_attribute("in2", fe_in(self.get_value(&value.b)?))?;
+
+        let operator = self.get_value(&value.operator)?;
+
+        let name = match operator {
+            FeCompositeOperator::Over => "over",
+            FeCompositeOperator::In => "in",
+            FeCompositeOperator::Out => "out",
+            FeCompositeOperator::Atop => "atop",
+            FeCompositeOperator::Xor => "xor",
+            FeCompositeOperator::Arithmetic => "arithmetic",
+        };
+
+        node.set_attribute("operator", name)?;
+
+        // the coefficients are only used by the arithmetic operator.
+        if *operator == FeCompositeOperator::Arithmetic {
+            for (name, k) in [
+                ("k1", &value.k1),
+                ("k2", &value.k2),
+                ("k3", &value.k3),
+                ("k4", &value.k4),
+            ] {
+                node.set_attribute(name, self.get_value(k)?.0.to_string().as_str())?;
+            }
+        }
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
+    fn process_fe_merge(&mut self, value: &FeMerge) -> Result<usize, Error> {
+        let mut el = self.document.create_element("feMerge")?;
+
+        self.process_fe_primitive(&mut el, &value.0)?;
+
+        self.els.push(el);
+
+        self.process_child(false)
+    }
+
+    fn process_fe_merge_item(&mut self, value: &FeMergeItem) -> Result<usize, Error> {
+        let mut node = self.document.create_element("feMergeNode")?;
+
+        node.set_attribute("in", fe_in(self.get_value(&value.0)?))?;
+
+        self.current_element_mut().append_child(node)?;
+
+        Ok(0)
+    }
+
     fn process_font(&mut self, value: &Font) -> Result<usize, Error> {
         let mut el = self.document.create_element("g")?;
 
@@ -451,12 +1310,32 @@ impl<'a> SvgGenerating<'a> {
         let mut el = self.document.create_element("g")?;
 
         self.process_text_layout_inner(&mut el, value)?;
+        self.push_white_space(value);
 
         self.els.push(el);
 
         self.process_child(false)
     }
 
+    /// Records the white space mode of the element to be pushed, which applies to the strings in it.
+    fn push_white_space(&mut self, value: &TextLayout) {
+        if value.white_space.is_none() && value.tab_size.is_none() {
+            return;
+        }
+
+        let (white_space, tab_size) = self
+            .white_space
+            .last()
+            .map(|(_, white_space, tab_size)| (*white_space, *tab_size))
+            .unwrap_or((WhiteSpace::Normal, DEFAULT_TAB_SIZE));
+
+        self.white_space.push((
+            self.els.len() + 1,
+            value.white_space.unwrap_or(white_space),
+            value.tab_size.unwrap_or(tab_size),
+        ));
+    }
+
     fn process_text_layout_inner(&self, el: &mut RefNode, value: &TextLayout) -> Result<(), Error> {
         if let Some(property) = &value.write_mode {
             match property {
@@ -505,6 +1384,13 @@ impl<'a> SvgGenerating<'a> {
             }
         }
 
+        if let Some(property) = &value.white_space {
+            match property {
+                WhiteSpace::Normal => el.set_attribute("xml:space", "default")?,
+                WhiteSpace::Pre => el.set_attribute("xml:space", "preserve")?,
+            }
+        }
+
         if let Some(property) = &value.anchor {
             match self.get_value(property)? {
                 vglang_ir::TextAnchor::Start => el.set_attribute("text-anchor", "start")?,
@@ -744,6 +1630,7 @@ impl<'a> SvgGenerating<'a> {
 
         if let Some(value) = &text.layout {
             self.process_text_layout_inner(&mut el, value)?;
+            self.push_white_space(value);
         }
 
         if let Some(value) = &text.fill {
@@ -0,0 +1,48 @@
+use futures::executor::block_on;
+use vglang_ir::{Image, ImageSource, Layer, MeetOrSlice, PreserveAspectRatio, IR};
+use vglang_svg::*;
+
+fn render(codes: Vec<IR>) -> String {
+    let program = block_on(SvgDevice::default().compile(codes)).unwrap();
+
+    block_on(program.execute(&Default::default())).unwrap()
+}
+
+#[test]
+fn test_embedded_image() {
+    let svg = render(vec![
+        Layer::from((100, 100)).into(),
+        Image::new(ImageSource::data("image/png", b"Many".as_slice()))
+            .width(10)
+            .height(20)
+            .into(),
+        IR::Pop(1),
+    ]);
+
+    assert!(
+        svg.contains(
+            r#"<image href="data:image/png;base64,TWFueQ==" x="0" y="0" width="10" height="20"/>"#
+        ),
+        "{}",
+        svg
+    );
+}
+
+#[test]
+fn test_external_image() {
+    let svg = render(vec![
+        Layer::from((100, 100)).into(),
+        Image::new("logo.png")
+            .width(10)
+            .height(10)
+            .aspect(PreserveAspectRatio::xMidYMid(MeetOrSlice::Slice))
+            .into(),
+        IR::Pop(1),
+    ]);
+
+    assert!(
+        svg.contains(r#"<image href="logo.png" x="0" y="0" width="10" height="10" preserveAspectRatio="xMidYMid slice"/>"#),
+        "{}",
+        svg
+    );
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use futures::executor::block_on;
+use vglang_ir::{Layer, Rect, IR};
+use vglang_svg::*;
+
+fn codes() -> Vec<IR> {
+    vec![
+        Layer::from((100, 100)).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(1),
+    ]
+}
+
+#[test]
+fn test_cancel() {
+    let token = CancelToken::default();
+
+    let device = SvgDevice::default().interrupt(Interrupt::default().token(token.clone()));
+
+    let program = block_on(device.compile(codes())).unwrap();
+
+    token.cancel();
+
+    assert!(matches!(
+        block_on(program.execute(&Default::default())),
+        Err(Error::Cancelled {
+            processed: 0,
+            total: 3
+        })
+    ));
+
+    assert!(matches!(
+        block_on(device.compile(codes())),
+        Err(Error::Cancelled { .. })
+    ));
+}
+
+#[test]
+fn test_timeout() {
+    let device = SvgDevice::default().interrupt(Interrupt::default().timeout(Duration::ZERO));
+
+    assert!(matches!(
+        block_on(device.compile(codes())),
+        Err(Error::Timeout {
+            processed: 0,
+            total: 3
+        })
+    ));
+}
@@ -0,0 +1,30 @@
+use futures::executor::block_on;
+use vglang_ir::{Layer, Path, IR};
+use vglang_svg::*;
+
+#[test]
+fn test_path_data() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Path::default()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quadratic_bezier((10.0, 10.0), (0.0, 10.0))
+            .arc(5.0, 5.0, 30.0, true, false, (0.0, 0.0))
+            .close_path()
+            .path_length(100.0)
+            .into(),
+        IR::Pop(1),
+    ];
+
+    let program = block_on(SvgDevice::default().compile(codes)).unwrap();
+
+    let svg = block_on(program.execute(&Default::default())).unwrap();
+
+    assert!(
+        svg.contains(r#"d="M 0 0 L 10 0 Q 10 10 0 10 A 5 5 30 1 0 0 0 Z""#),
+        "{}",
+        svg
+    );
+    assert!(svg.contains(r#"pathLength="100""#), "{}", svg);
+}
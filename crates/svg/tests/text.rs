@@ -0,0 +1,75 @@
+use futures::executor::block_on;
+use vglang_ir::{Layer, Text, IR};
+use vglang_svg::*;
+
+#[test]
+fn test_unbound_text_register() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Text::default().into(),
+        IR::Animated("name".to_owned()),
+        IR::Pop(2),
+    ];
+
+    let program = block_on(SvgDevice::default().compile(codes)).unwrap();
+
+    let err = block_on(program.execute(&Default::default())).unwrap_err();
+
+    assert!(
+        matches!(&err, Error::AnimatedNotFound(name) if name == "name"),
+        "{}",
+        err
+    );
+}
+
+fn render(codes: Vec<IR>) -> String {
+    let program = block_on(SvgDevice::default().compile(codes)).unwrap();
+
+    block_on(program.execute(&Default::default())).unwrap()
+}
+
+fn string(content: &str) -> String {
+    render(vec![
+        Layer::from((100, 100)).into(),
+        Text::default().into(),
+        IR::String(content.to_owned()),
+        IR::Pop(2),
+    ])
+}
+
+#[test]
+fn test_emoji_fallback() {
+    let svg = string("a😀b⌛");
+
+    assert!(
+        svg.contains(r#"a<tspan font-family="Apple Color Emoji, Segoe UI Emoji, Noto Color Emoji">😀</tspan>b<tspan font-family="Apple Color Emoji, Segoe UI Emoji, Noto Color Emoji">⌛</tspan>"#),
+        "{}",
+        svg
+    );
+}
+
+#[test]
+fn test_emoji_symbols() {
+    // the symbols and dingbats without the default emoji presentation are left to the text fonts.
+    let svg = string("★ ✓ ➔ ☀");
+
+    assert!(!svg.contains("<tspan"), "{}", svg);
+    assert!(svg.contains("★ ✓ ➔ ☀"), "{}", svg);
+}
+
+#[test]
+fn test_emoji_variation_selectors() {
+    // the emoji variation selector and keycaps select the emoji presentation.
+    let svg = string("☀\u{FE0F}1\u{FE0F}\u{20E3}");
+
+    assert!(
+        svg.contains("<tspan font-family=\"Apple Color Emoji, Segoe UI Emoji, Noto Color Emoji\">☀\u{FE0F}1\u{FE0F}\u{20E3}</tspan>"),
+        "{}",
+        svg
+    );
+
+    // the text variation selector selects the text presentation.
+    let svg = string("⌛\u{FE0E}");
+
+    assert!(!svg.contains("<tspan"), "{}", svg);
+}
@@ -0,0 +1,23 @@
+use futures::executor::block_on;
+use vglang_ir::{Layer, Rect, Transform, IR};
+use vglang_svg::*;
+
+#[test]
+fn test_transform_attribute() {
+    let codes: Vec<IR> = vec![
+        Layer::from((100, 100)).into(),
+        Transform::rotate(45.0).into(),
+        Rect::from((0, 0, 10, 10)).into(),
+        IR::Pop(2),
+    ];
+
+    let program = block_on(SvgDevice::default().compile(codes)).unwrap();
+
+    let svg = block_on(program.execute(&Default::default())).unwrap();
+
+    assert!(
+        svg.contains(r#"<g transform="rotate(45 0 0)"><rect"#),
+        "{}",
+        svg
+    );
+}
@@ -0,0 +1,86 @@
+//! The javascript bindings of vglang.
+//!
+//! A web front end compiles one program once, and then renders it client-side frame by frame:
+//!
+//! ```js
+//! import { Program } from "cotati-wasm";
+//!
+//! const program = Program.fromJson(envelope);
+//!
+//! const svg = program.execute();
+//! ```
+//!
+//! Animatable registers are not supported yet, because no register value can be bound, the programs that
+//! reference a register are rejected by the loaders.
+//!
+//! The bindings are thin wrappers of [`Program::load_json`], [`Program::load_cbor`] and [`Program::render`],
+//! which can be used and tested natively.
+
+use futures::executor::block_on;
+use vglang_ir::{from_cbor_validated, from_json_validated, registers, IR};
+use vglang_svg::{Device, SvgDevice, SvgGenerator, VGLProgram};
+use wasm_bindgen::prelude::*;
+
+/// Error raised by this crate.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Ir(#[from] vglang_ir::Error),
+
+    #[error(transparent)]
+    Svg(#[from] vglang_svg::Error),
+}
+
+/// One compiled `VGL` program.
+#[wasm_bindgen]
+pub struct Program {
+    program: SvgGenerator,
+}
+
+#[wasm_bindgen]
+impl Program {
+    /// Compile a program from a json [`Envelope`](vglang_ir::Envelope), the ir codes are validated first.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<Program, JsError> {
+        Ok(Self::load_json(json)?)
+    }
+
+    /// Compile a program from a cbor [`Envelope`](vglang_ir::Envelope), the ir codes are validated first.
+    #[wasm_bindgen(js_name = fromCbor)]
+    pub fn from_cbor(cbor: &[u8]) -> Result<Program, JsError> {
+        Ok(Self::load_cbor(cbor)?)
+    }
+
+    /// Execute this program, returns the svg document of this frame.
+    pub fn execute(&self) -> Result<String, JsError> {
+        Ok(self.render()?)
+    }
+}
+
+impl Program {
+    /// Compile a program from a json [`Envelope`](vglang_ir::Envelope), see [`Program::from_json`].
+    pub fn load_json(json: &str) -> Result<Program, Error> {
+        Self::compile(from_json_validated(json)?)
+    }
+
+    /// Compile a program from a cbor [`Envelope`](vglang_ir::Envelope), see [`Program::from_cbor`].
+    pub fn load_cbor(cbor: &[u8]) -> Result<Program, Error> {
+        Self::compile(from_cbor_validated(cbor)?)
+    }
+
+    /// Execute this program, see [`Program::execute`].
+    pub fn render(&self) -> Result<String, Error> {
+        // the svg device never suspends, the futures are ready on the first poll.
+        Ok(block_on(self.program.execute(&Default::default()))?)
+    }
+
+    fn compile(codes: Vec<IR>) -> Result<Program, Error> {
+        if let Some(name) = registers(&codes).first() {
+            return Err(vglang_ir::Error::UnsatisfiedFrameVariable(name.to_string()).into());
+        }
+
+        let program = block_on(SvgDevice::default().compile(codes))?;
+
+        Ok(Program { program })
+    }
+}
@@ -0,0 +1,31 @@
+use cotati_wasm::{Error, Program};
+
+#[test]
+fn test_json_program() {
+    let program = Program::load_json(
+        r#"{"version":1,"body":[{"Layer":{"width":{"Constant":[10,null]},"height":{"Constant":[10,null]}}},{"Pop":1}]}"#,
+    )
+    .unwrap();
+
+    let svg = program.render().unwrap();
+
+    assert!(svg.contains("<svg"), "{}", svg);
+
+    // a program is executed as many times as needed.
+    assert_eq!(program.render().unwrap(), svg);
+}
+
+#[test]
+fn test_unbound_register() {
+    let err = Program::load_json(
+        r#"{"version":1,"body":[{"Layer":{"width":{"Animated":"w"},"height":{"Constant":[10,null]}}},{"Pop":1}]}"#,
+    )
+    .err()
+    .unwrap();
+
+    assert!(
+        matches!(&err, Error::Ir(vglang_ir::Error::UnsatisfiedFrameVariable(name)) if name == "w"),
+        "{}",
+        err
+    );
+}